@@ -0,0 +1,639 @@
+//! Heuristic index advice (`plenum advise`)
+//!
+//! Reads a SELECT's structured plan (as `--explain-format structured` returns it)
+//! together with the introspected tables it scans, and suggests `CREATE INDEX`
+//! statements. Nothing is executed: each suggestion is DDL text for a human to review.
+//!
+//! Two plan shapes produce suggestions:
+//!
+//! - `full_scan_filter`: a full scan of a table with at least `min_rows` estimated rows
+//!   (or no estimate at all) whose filter compares columns with `=`, `IN`, `<`, `>` or
+//!   `BETWEEN`. The index leads with the equality columns, then one range column. Where
+//!   the scan estimate counts rows after the filter (`PostgreSQL`, `DuckDB`), a filter
+//!   keeping more than a fifth of the table is not considered selective enough.
+//! - `sort`: a sort of a single table's rows for an ORDER BY of plain columns, which an
+//!   index on the equality filter columns followed by the ORDER BY columns returns in order.
+//!
+//! `SQLite` plans carry no filter text, so for a plan that scans a single table the
+//! outer query's WHERE clause stands in. `DuckDB` only uses its ART indexes for point
+//! lookups and never for ORDER BY, so it only gets equality filter advice. Suggestions
+//! an existing index or the primary key already serves are dropped, as are relations
+//! introspection cannot resolve (CTEs, aliases in `MySQL` plans).
+
+use serde_json::Value;
+
+use crate::cursor::{outer_query, quote_ident, tokenize, Tok, Token};
+use crate::engine::{
+    ConnectionConfig, DatabaseEngine, DatabaseType, ExplainPlanNode, IndexAdvice, IndexSuggestion,
+    IntrospectOperation, IntrospectResult, PlanOperation, TableFields, TableInfo,
+};
+use crate::error::Result;
+use crate::plans::explain_select;
+
+/// Default `min_rows`: tables with fewer estimated rows are left alone
+pub const DEFAULT_MIN_ROWS: i64 = 10_000;
+
+/// Suggestion reason: a full scan whose filter an index could answer
+pub const REASON_FULL_SCAN_FILTER: &str = "full_scan_filter";
+
+/// Suggestion reason: a sort an index could return rows in order for
+pub const REASON_SORT: &str = "sort";
+
+/// Largest fraction of a table a filter may keep and still be worth indexing
+const MAX_SELECTIVITY: f64 = 0.2;
+
+/// Keywords ending a WHERE condition at the top level
+const CLAUSE_END: &[&str] =
+    &["GROUP", "HAVING", "ORDER", "LIMIT", "OFFSET", "FETCH", "WINDOW", "QUALIFY", "FOR"];
+
+/// Words that may precede a parenthesised condition without making it a function call
+const CONNECTIVES: &[&str] = &["AND", "NOT", "WHERE", "ON", "WHEN", "THEN", "ELSE", "CASE"];
+
+/// Plan `sql` and suggest indexes for it.
+///
+/// `sql` must be a SELECT without `EXPLAIN`; it is only planned, never run.
+/// Tables whose row estimate is below `min_rows` get no suggestions.
+///
+/// # Errors
+///
+/// `INVALID_INPUT` for anything but a SELECT, and whatever `E::execute` returns for the
+/// structured EXPLAIN.
+pub async fn advise<E: DatabaseEngine>(
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[Value],
+    timeout_ms: Option<u64>,
+    min_rows: i64,
+) -> Result<IndexAdvice> {
+    let engine = config.engine;
+    let plan = explain_select::<E>(config, sql, params, timeout_ms).await?;
+    let outer = outer_query(sql, engine);
+
+    let mut scans = Vec::new();
+    collect_scans(&plan, &mut scans);
+    let mut relations: Vec<&str> = Vec::new();
+    for relation in scans.iter().filter_map(|n| n.relation.as_deref()) {
+        if !relations.iter().any(|r| r.eq_ignore_ascii_case(relation)) {
+            relations.push(relation);
+        }
+    }
+    let mut tables = Vec::new();
+    for relation in &relations {
+        if let Some(table) = table_details::<E>(config, relation).await {
+            tables.push(table);
+        }
+    }
+    let find_table = |node: &ExplainPlanNode| {
+        let (_, name) = split_relation(node.relation.as_deref()?);
+        tables.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    };
+
+    let clauses = OuterClauses::parse(&outer.body, engine);
+    let single = relations.len() == 1;
+    let filter_of = |node: &ExplainPlanNode| {
+        node.filter.clone().or_else(|| single.then(|| clauses.condition.clone()).flatten())
+    };
+    let large = |table: &TableInfo| table.row_estimate.is_none_or(|rows| rows >= min_rows);
+
+    let mut suggestions: Vec<IndexSuggestion> = Vec::new();
+    for node in scans.iter().filter(|n| n.operation == PlanOperation::FullScan) {
+        let Some(table) = find_table(node).filter(|t| large(t)) else { continue };
+        let Some(filter) = filter_of(node) else { continue };
+        let (mut columns, range) = sargable_columns(&filter, engine, table);
+        if engine != DatabaseType::DuckDB {
+            columns.extend(range.into_iter().take(1));
+        }
+        if columns.is_empty() {
+            continue;
+        }
+        let post_filter = matches!(engine, DatabaseType::Postgres | DatabaseType::DuckDB);
+        if post_filter
+            && node
+                .estimated_rows
+                .zip(table.row_estimate)
+                .is_some_and(|(est, rows)| !selective(est, rows))
+        {
+            continue;
+        }
+        let rationale = format!(
+            "Plan node \"{}\" reads every row of {} ({}) to keep those matching {}; an index on ({}) finds them directly.",
+            node.node_type,
+            table.name,
+            describe_rows(table.row_estimate),
+            filter.trim(),
+            columns.join(", ")
+        );
+        let keys: Vec<(String, bool)> = columns.into_iter().map(|c| (c, false)).collect();
+        push_suggestion(
+            &mut suggestions,
+            engine,
+            table,
+            &keys,
+            REASON_FULL_SCAN_FILTER,
+            rationale,
+            node.estimated_rows,
+        );
+    }
+
+    if engine != DatabaseType::DuckDB && single {
+        let sort = find_node(&plan, PlanOperation::Sort);
+        let table = scans.first().and_then(|n| find_table(n).map(|t| (*n, t)));
+        if let (Some(sort), Some((scan, table)), Some(order_by)) =
+            (sort, table, clauses.order_by.as_ref())
+        {
+            if let Some(keys) =
+                sort_keys(scan, table, order_by, engine, filter_of(scan)).filter(|_| large(table))
+            {
+                let columns: Vec<String> = keys.iter().map(|(c, _)| c.clone()).collect();
+                let rationale = format!(
+                    "Plan node \"{}\" sorts the rows of {} ({}) for ORDER BY {}; an index on ({}) returns them in that order, so the sort can be skipped and a LIMIT can stop the scan early.",
+                    sort.node_type,
+                    table.name,
+                    describe_rows(table.row_estimate),
+                    order_by
+                        .iter()
+                        .map(|(c, desc)| if *desc { format!("{c} DESC") } else { c.clone() })
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    columns.join(", ")
+                );
+                // The sort index leads with the filter columns, so it serves the filter too.
+                suggestions.retain(|s| {
+                    !(s.table == table.name
+                        && s.columns.len() <= columns.len()
+                        && s.columns.iter().zip(&columns).all(|(a, b)| a == b))
+                });
+                push_suggestion(
+                    &mut suggestions,
+                    engine,
+                    table,
+                    &keys,
+                    REASON_SORT,
+                    rationale,
+                    sort.estimated_rows,
+                );
+            }
+        }
+    }
+
+    Ok(IndexAdvice { suggestions, plan })
+}
+
+/// Index keys serving `order_by` after the equality filter columns of `scan`; `None`
+/// when an ORDER BY key is not a column of `table`.
+fn sort_keys(
+    scan: &ExplainPlanNode,
+    table: &TableInfo,
+    order_by: &[(String, bool)],
+    engine: DatabaseType,
+    filter: Option<String>,
+) -> Option<Vec<(String, bool)>> {
+    if order_by.is_empty() || scan.relation.is_none() {
+        return None;
+    }
+    let order_keys = order_by
+        .iter()
+        .map(|(name, desc)| Some((table_column(table, name)?, *desc)))
+        .collect::<Option<Vec<_>>>()?;
+    let mut keys: Vec<(String, bool)> = filter
+        .map(|f| sargable_columns(&f, engine, table).0)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|c| !order_keys.iter().any(|(k, _)| k == c))
+        .map(|c| (c, false))
+        .collect();
+    keys.extend(order_keys);
+    Some(keys)
+}
+
+/// Add a suggestion unless an existing index already leads with `keys` or the same
+/// index was suggested before.
+fn push_suggestion(
+    suggestions: &mut Vec<IndexSuggestion>,
+    engine: DatabaseType,
+    table: &TableInfo,
+    keys: &[(String, bool)],
+    reason: &str,
+    rationale: String,
+    estimated_rows: Option<f64>,
+) {
+    let columns: Vec<String> = keys.iter().map(|(c, _)| c.clone()).collect();
+    let duplicate = suggestions.iter().any(|s| s.table == table.name && s.columns == columns);
+    if duplicate || covered(table, &columns) {
+        return;
+    }
+    let index_name = index_name(&table.name, &columns);
+    suggestions.push(IndexSuggestion {
+        table: table.name.clone(),
+        schema: table.schema.clone(),
+        statement: create_index_statement(engine, table, &index_name, keys),
+        index_name,
+        columns,
+        reason: reason.to_string(),
+        rationale,
+        table_rows: table.row_estimate,
+        estimated_rows,
+    });
+}
+
+/// Whether the primary key or an existing index starts with `columns`
+fn covered(table: &TableInfo, columns: &[String]) -> bool {
+    table.indexes.iter().map(|idx| &idx.columns).chain(table.primary_key.as_ref()).any(|existing| {
+        existing.len() >= columns.len()
+            && existing.iter().zip(columns).all(|(a, b)| a.eq_ignore_ascii_case(b))
+    })
+}
+
+/// `idx_<table>_<columns>`, lowercased, non-alphanumerics replaced by `_`, at most
+/// 63 characters (the `PostgreSQL` identifier limit)
+fn index_name(table: &str, columns: &[String]) -> String {
+    let mut name: String = format!("idx_{table}_{}", columns.join("_"))
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    name.truncate(63);
+    name
+}
+
+/// Engine-specific DDL creating the index without blocking writes where the engine can:
+/// `CONCURRENTLY` for `PostgreSQL` (must run outside a transaction block) and an
+/// in-place, lock-free `ALTER TABLE` for `MySQL`.
+fn create_index_statement(
+    engine: DatabaseType,
+    table: &TableInfo,
+    name: &str,
+    keys: &[(String, bool)],
+) -> String {
+    let columns = keys
+        .iter()
+        .map(|(column, desc)| {
+            let quoted = quote_ident(engine, column);
+            if *desc {
+                format!("{quoted} DESC")
+            } else {
+                quoted
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let qualified = |ident: &str| match &table.schema {
+        Some(schema) => format!("{}.{}", quote_ident(engine, schema), quote_ident(engine, ident)),
+        None => quote_ident(engine, ident),
+    };
+    match engine {
+        DatabaseType::Postgres => format!(
+            "CREATE INDEX CONCURRENTLY {} ON {} ({columns});",
+            quote_ident(engine, name),
+            qualified(&table.name)
+        ),
+        DatabaseType::MySQL => format!(
+            "ALTER TABLE {} ADD INDEX {} ({columns}), ALGORITHM=INPLACE, LOCK=NONE;",
+            qualified(&table.name),
+            quote_ident(engine, name)
+        ),
+        // SQLite qualifies the index name, not the table
+        DatabaseType::SQLite => format!(
+            "CREATE INDEX IF NOT EXISTS {} ON {} ({columns});",
+            qualified(name),
+            quote_ident(engine, &table.name)
+        ),
+        DatabaseType::DuckDB => format!(
+            "CREATE INDEX {} ON {} ({columns});",
+            quote_ident(engine, name),
+            qualified(&table.name)
+        ),
+    }
+}
+
+/// Whether a scan producing `estimated` of `rows` rows keeps few enough to index
+#[allow(clippy::cast_precision_loss)]
+fn selective(estimated: f64, rows: i64) -> bool {
+    rows <= 0 || estimated <= rows as f64 * MAX_SELECTIVITY
+}
+
+fn describe_rows(rows: Option<i64>) -> String {
+    rows.map_or_else(|| "row estimate unavailable".to_string(), |n| format!("about {n} rows"))
+}
+
+/// The table's spelling of column `name`
+fn table_column(table: &TableInfo, name: &str) -> Option<String> {
+    table.columns.iter().find(|c| c.name.eq_ignore_ascii_case(name)).map(|c| c.name.clone())
+}
+
+/// `(schema, table)` of a plan relation; `DuckDB` names `catalog.schema.table`
+fn split_relation(relation: &str) -> (Option<&str>, &str) {
+    let mut parts = relation.rsplitn(3, '.');
+    let table = parts.next().unwrap_or(relation);
+    (parts.next(), table)
+}
+
+async fn table_details<E: DatabaseEngine>(
+    config: &ConnectionConfig,
+    relation: &str,
+) -> Option<TableInfo> {
+    let (schema, name) = split_relation(relation);
+    let operation = IntrospectOperation::TableDetails {
+        name: name.to_string(),
+        fields: TableFields {
+            columns: true,
+            primary_key: true,
+            foreign_keys: false,
+            indexes: true,
+        },
+    };
+    match E::introspect(config, &operation, None, schema).await {
+        Ok(IntrospectResult::TableDetails { table }) => Some(table),
+        _ => None,
+    }
+}
+
+/// Scan nodes (full, index and index-only) that name a relation, in plan order
+fn collect_scans<'a>(node: &'a ExplainPlanNode, out: &mut Vec<&'a ExplainPlanNode>) {
+    let scan = matches!(
+        node.operation,
+        PlanOperation::FullScan | PlanOperation::IndexScan | PlanOperation::IndexOnlyScan
+    );
+    if scan && node.relation.is_some() {
+        out.push(node);
+    }
+    for child in &node.children {
+        collect_scans(child, out);
+    }
+}
+
+/// First node of `operation`, depth first
+fn find_node(node: &ExplainPlanNode, operation: PlanOperation) -> Option<&ExplainPlanNode> {
+    if node.operation == operation {
+        return Some(node);
+    }
+    node.children.iter().find_map(|child| find_node(child, operation))
+}
+
+/// Columns of `table` that `filter` compares with a value, in order of appearance:
+/// `(equality, range)`. A filter with `OR` yields none, since no single index serves it.
+fn sargable_columns(
+    filter: &str,
+    engine: DatabaseType,
+    table: &TableInfo,
+) -> (Vec<String>, Vec<String>) {
+    let chars: Vec<char> = filter.chars().collect();
+    let tokens = tokenize(&chars, engine);
+    let mut equality: Vec<String> = Vec::new();
+    let mut range: Vec<String> = Vec::new();
+    if tokens.iter().any(|t| t.is_word("OR")) {
+        return (equality, range);
+    }
+    let punct = |k: usize| match tokens.get(k).map(|t| &t.tok) {
+        Some(Tok::Punct(c)) => Some(*c),
+        _ => None,
+    };
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let start = i;
+        let Some(mut name) = tokens[i].ident() else {
+            i += 1;
+            continue;
+        };
+        i += 1;
+        while punct(i) == Some('.') {
+            let Some(part) = tokens.get(i + 1).and_then(Token::ident) else { break };
+            name = part;
+            i += 2;
+        }
+        // A function name, or a column inside a function call: `lower(email) = ...`
+        let in_call = start >= 2
+            && punct(start - 1) == Some('(')
+            && match &tokens[start - 2].tok {
+                Tok::Word(w) => !CONNECTIVES.iter().any(|kw| w.eq_ignore_ascii_case(kw)),
+                Tok::Quoted(_) => true,
+                _ => false,
+            };
+        if punct(i) == Some('(') || in_call {
+            continue;
+        }
+        // Skip closing parentheses and casts: `(status)::text = ...`
+        loop {
+            match (punct(i), punct(i + 1)) {
+                (Some(')'), _) => i += 1,
+                (Some(':'), Some(':')) => i += 3,
+                _ => break,
+            }
+        }
+        let (equal, rhs) = match (punct(i), punct(i + 1)) {
+            (Some('='), _) => (Some(true), i + 1),
+            (Some('<'), Some('>')) => (None, i),
+            (Some('<' | '>'), Some('=')) => (Some(false), i + 2),
+            (Some('<' | '>'), _) => (Some(false), i + 1),
+            _ if tokens.get(i).is_some_and(|t| t.is_word("IN")) => (Some(true), i + 1),
+            _ if tokens.get(i).is_some_and(|t| t.is_word("BETWEEN")) => (Some(false), i + 1),
+            _ => (None, i),
+        };
+        // Column-to-column comparisons are join conditions, not filters
+        let rhs_column = tokens.get(rhs).and_then(Token::ident).is_some()
+            && !matches!(punct(rhs + 1), Some('('));
+        let Some(equal) = equal.filter(|_| !rhs_column) else { continue };
+        let Some(column) = table_column(table, name) else { continue };
+        if !equality.contains(&column) && !range.contains(&column) {
+            if equal {
+                equality.push(column);
+            } else {
+                range.push(column);
+            }
+        }
+    }
+    (equality, range)
+}
+
+/// The outer query's WHERE condition and ORDER BY keys
+#[derive(Debug, Default, PartialEq, Eq)]
+struct OuterClauses {
+    condition: Option<String>,
+    /// `(column, descending)` keys; `None` when a key is not a plain column
+    /// (expressions, positions, `NULLS FIRST`/`LAST`)
+    order_by: Option<Vec<(String, bool)>>,
+}
+
+impl OuterClauses {
+    /// Set operations (`UNION`, ...) yield no clauses: the outer WHERE would only
+    /// cover the first branch.
+    fn parse(sql: &str, engine: DatabaseType) -> Self {
+        let chars: Vec<char> = sql.chars().collect();
+        let tokens = tokenize(&chars, engine);
+        let top = |t: &Token, kw: &str| t.depth == 0 && t.is_word(kw);
+        if tokens.iter().any(|t| ["UNION", "INTERSECT", "EXCEPT"].iter().any(|kw| top(t, kw))) {
+            return Self::default();
+        }
+        let condition = tokens.iter().position(|t| top(t, "WHERE")).and_then(|w| {
+            let body = &tokens[w + 1..];
+            let end = body
+                .iter()
+                .position(|t| t.depth == 0 && CLAUSE_END.iter().any(|kw| t.is_word(kw)))
+                .unwrap_or(body.len());
+            let (first, last) = (body.first()?, body[..end].last()?);
+            Some(chars[first.start..last.end].iter().collect())
+        });
+        let order_by = tokens
+            .windows(2)
+            .position(|w| top(&w[0], "ORDER") && w[1].is_word("BY"))
+            .map_or_else(|| Some(Vec::new()), |o| order_keys(&tokens[o + 2..]));
+        Self { condition, order_by }
+    }
+}
+
+fn order_keys(tokens: &[Token]) -> Option<Vec<(String, bool)>> {
+    let mut keys = Vec::new();
+    let mut i = 0;
+    loop {
+        let mut name = tokens.get(i)?.ident()?;
+        i += 1;
+        while tokens.get(i).is_some_and(|t| t.tok == Tok::Punct('.')) {
+            name = tokens.get(i + 1)?.ident()?;
+            i += 2;
+        }
+        let descending = tokens.get(i).is_some_and(|t| t.is_word("DESC"));
+        if descending || tokens.get(i).is_some_and(|t| t.is_word("ASC")) {
+            i += 1;
+        }
+        keys.push((name.to_string(), descending));
+        match tokens.get(i) {
+            None => return Some(keys),
+            Some(t) if t.tok == Tok::Punct(',') => i += 1,
+            Some(t) if ["LIMIT", "OFFSET", "FETCH", "FOR"].iter().any(|kw| t.is_word(kw)) => {
+                return Some(keys);
+            }
+            Some(_) => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{ColumnInfo, IndexInfo};
+
+    fn table(columns: &[&str], primary_key: &[&str], indexes: &[&[&str]]) -> TableInfo {
+        TableInfo {
+            name: "orders".to_string(),
+            schema: None,
+            columns: columns
+                .iter()
+                .map(|name| ColumnInfo {
+                    name: (*name).to_string(),
+                    data_type: "integer".to_string(),
+                    nullable: true,
+                    default: None,
+                    comment: None,
+                })
+                .collect(),
+            primary_key: Some(primary_key.iter().map(|c| (*c).to_string()).collect()),
+            foreign_keys: Vec::new(),
+            indexes: indexes
+                .iter()
+                .enumerate()
+                .map(|(i, cols)| IndexInfo {
+                    name: format!("idx_{i}"),
+                    columns: cols.iter().map(|c| (*c).to_string()).collect(),
+                    unique: false,
+                })
+                .collect(),
+            comment: None,
+            row_estimate: None,
+        }
+    }
+
+    #[test]
+    fn test_sargable_columns() {
+        let t = table(&["id", "status", "customer_id", "placed_at", "email"], &["id"], &[]);
+        let cols = |filter: &str, engine| sargable_columns(filter, engine, &t);
+
+        // PostgreSQL: parenthesised, cast columns; equality ahead of range
+        assert_eq!(
+            cols(
+                "((placed_at > '2024-01-01'::date) AND ((status)::text = 'shipped'::text))",
+                DatabaseType::Postgres
+            ),
+            (vec!["status".to_string()], vec!["placed_at".to_string()])
+        );
+        assert_eq!(
+            cols("(customer_id = ANY ('{1,2}'::integer[]))", DatabaseType::Postgres).0,
+            vec!["customer_id"]
+        );
+        // MySQL: fully qualified, quoted
+        assert_eq!(
+            cols("(`shop`.`orders`.`customer_id` = 7)", DatabaseType::MySQL).0,
+            vec!["customer_id"]
+        );
+        // DuckDB and SQL text
+        assert_eq!(
+            cols("status IN ('a', 'b') AND placed_at BETWEEN 1 AND 2", DatabaseType::DuckDB),
+            (vec!["status".to_string()], vec!["placed_at".to_string()])
+        );
+        // Not sargable: functions, inequality, OR, column comparisons, unknown columns
+        assert_eq!(cols("lower(email) = 'x'", DatabaseType::SQLite), (vec![], vec![]));
+        assert_eq!(cols("status <> 'x' AND status != 'y'", DatabaseType::SQLite), (vec![], vec![]));
+        assert_eq!(cols("status = 'a' OR id = 1", DatabaseType::SQLite), (vec![], vec![]));
+        assert_eq!(cols("o.customer_id = c.id", DatabaseType::SQLite), (vec![], vec![]));
+        assert_eq!(cols("missing = 1", DatabaseType::SQLite), (vec![], vec![]));
+        // A parenthesised condition after AND is not a function call
+        assert_eq!(cols("id > 0 AND (status = 'a')", DatabaseType::SQLite).0, vec!["status"]);
+    }
+
+    #[test]
+    fn test_outer_clauses() {
+        let parse = |sql: &str| OuterClauses::parse(sql, DatabaseType::Postgres);
+
+        let clauses = parse(
+            "SELECT * FROM orders o WHERE o.status = 'a' ORDER BY o.placed_at DESC, id LIMIT 5",
+        );
+        assert_eq!(clauses.condition.as_deref(), Some("o.status = 'a'"));
+        assert_eq!(
+            clauses.order_by,
+            Some(vec![("placed_at".to_string(), true), ("id".to_string(), false)])
+        );
+
+        let clauses = parse("SELECT * FROM t WHERE id IN (SELECT x FROM u WHERE y = 1)");
+        assert_eq!(clauses.condition.as_deref(), Some("id IN (SELECT x FROM u WHERE y = 1)"));
+        assert_eq!(clauses.order_by, Some(vec![]));
+
+        assert_eq!(parse("SELECT * FROM t ORDER BY lower(name)").order_by, None);
+        assert_eq!(parse("SELECT * FROM t ORDER BY 1").order_by, None);
+        assert_eq!(
+            parse("SELECT a FROM t WHERE a = 1 UNION SELECT a FROM u"),
+            OuterClauses::default()
+        );
+    }
+
+    #[test]
+    fn test_covered_and_statements() {
+        let t = table(
+            &["customer_id", "order_no", "status"],
+            &["customer_id", "order_no"],
+            &[&["status"]],
+        );
+        assert!(covered(&t, &["customer_id".to_string()]));
+        assert!(covered(&t, &["STATUS".to_string()]));
+        assert!(!covered(&t, &["order_no".to_string()]));
+        assert!(!covered(&t, &["status".to_string(), "order_no".to_string()]));
+
+        let keys = vec![("status".to_string(), false), ("placed_at".to_string(), true)];
+        let name = index_name("orders", &["status".to_string(), "placed_at".to_string()]);
+        assert_eq!(name, "idx_orders_status_placed_at");
+        assert_eq!(
+            create_index_statement(DatabaseType::Postgres, &t, &name, &keys),
+            "CREATE INDEX CONCURRENTLY \"idx_orders_status_placed_at\" ON \"orders\" (\"status\", \"placed_at\" DESC);"
+        );
+        assert_eq!(
+            create_index_statement(DatabaseType::MySQL, &t, &name, &keys[..1]),
+            "ALTER TABLE `orders` ADD INDEX `idx_orders_status_placed_at` (`status`), ALGORITHM=INPLACE, LOCK=NONE;"
+        );
+        let mut attached = t;
+        attached.schema = Some("aux".to_string());
+        assert_eq!(
+            create_index_statement(DatabaseType::SQLite, &attached, "idx_x", &keys[..1]),
+            "CREATE INDEX IF NOT EXISTS \"aux\".\"idx_x\" ON \"orders\" (\"status\");"
+        );
+        assert_eq!(index_name("Order Items", &["SKU".to_string()]), "idx_order_items_sku");
+        assert_eq!(index_name(&"t".repeat(80), &[]).len(), 63);
+    }
+}
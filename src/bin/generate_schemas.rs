@@ -6,7 +6,10 @@
 //! Run this whenever output types change to keep the checked-in schemas in sync.
 //! The drift test in `tests/schema_drift.rs` fails if schemas are stale.
 
-use plenum::{ConnectionInfo, ErrorEnvelope, IntrospectResult, QueryResult, SuccessEnvelope};
+use plenum::{
+    ConnectionInfo, DataDiff, ErrorEnvelope, ExportResult, IndexAdvice, InspectResult,
+    IntrospectResult, NdjsonLine, PlanReport, QueryResult, ResultSummary, SuccessEnvelope,
+};
 use schemars::schema_for;
 use std::fs;
 
@@ -15,7 +18,14 @@ fn main() {
         ("schemas/error_envelope.json", schema_for!(ErrorEnvelope)),
         ("schemas/connect_success.json", schema_for!(SuccessEnvelope<ConnectionInfo>)),
         ("schemas/introspect_success.json", schema_for!(SuccessEnvelope<IntrospectResult>)),
+        ("schemas/inspect_success.json", schema_for!(SuccessEnvelope<InspectResult>)),
+        ("schemas/advise_success.json", schema_for!(SuccessEnvelope<IndexAdvice>)),
+        ("schemas/plan_success.json", schema_for!(SuccessEnvelope<PlanReport>)),
         ("schemas/query_success.json", schema_for!(SuccessEnvelope<QueryResult>)),
+        ("schemas/query_ndjson_line.json", schema_for!(NdjsonLine)),
+        ("schemas/query_export_success.json", schema_for!(SuccessEnvelope<ExportResult>)),
+        ("schemas/query_diff_success.json", schema_for!(SuccessEnvelope<DataDiff>)),
+        ("schemas/query_summary_success.json", schema_for!(SuccessEnvelope<ResultSummary>)),
     ];
 
     for (path, schema) in schemas {
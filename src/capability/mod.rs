@@ -212,6 +212,43 @@ pub(crate) fn strip_explain_prefix(sql: &str) -> String {
     rest.to_string()
 }
 
+/// Decide whether a structured `EXPLAIN` runs as `EXPLAIN ANALYZE`.
+///
+/// Returns `true` when the EXPLAIN options ask for ANALYZE (`EXPLAIN ANALYZE ...`,
+/// `EXPLAIN (ANALYZE, BUFFERS) ...`); `ANALYZE false` / `off` / `0` does not count.
+/// ANALYZE executes the explained statement, so it is only allowed for a `SELECT`
+/// (or `WITH ... SELECT`), and `SQLite` has no ANALYZE form at all.
+///
+/// # Errors
+///
+/// `INVALID_INPUT` for ANALYZE of anything but a `SELECT`, or on `SQLite`.
+pub(crate) fn structured_explain_analyze(sql: &str, engine: DatabaseType) -> Result<bool> {
+    let sql = sql.trim();
+    let inner = strip_explain_prefix(sql);
+    let prefix = sql[..sql.len() - inner.len()].to_uppercase();
+
+    let analyze = prefix.split(['(', ')', ',']).any(|option| {
+        let mut words =
+            option.split(|c: char| c.is_whitespace() || c == '=').filter(|w| !w.is_empty());
+        words.any(|w| w == "ANALYZE") && !matches!(words.next(), Some("FALSE" | "OFF" | "0"))
+    });
+    if !analyze {
+        return Ok(false);
+    }
+
+    if engine == DatabaseType::SQLite {
+        return Err(PlenumError::invalid_input(
+            "SQLite has no EXPLAIN ANALYZE; use EXPLAIN QUERY PLAN for the estimated plan",
+        ));
+    }
+    if !crate::cursor::outer_query(&inner, engine).is_select {
+        return Err(PlenumError::invalid_input(
+            "EXPLAIN ANALYZE runs the statement and is only supported for SELECT queries",
+        ));
+    }
+    Ok(true)
+}
+
 /// DML/DDL keywords that must never appear in a read-only query.
 ///
 /// Used by `is_safe_cte_query` to detect writes hidden inside CTE bodies or
@@ -610,6 +647,113 @@ fn is_read_only_duckdb(sql: &str) -> bool {
         || sql.starts_with("RELEASE")
 }
 
+/// Replace string and numeric literals in `sql` with `?`.
+///
+/// Used to redact session query text before it is reported. Identifiers, keywords,
+/// comments and placeholders (`$1`, `?`) are kept, so the statement's shape stays
+/// readable. `"..."` is a string literal on `MySQL` and a quoted identifier elsewhere;
+/// dollar-quoted bodies (`$$...$$`, `$tag$...$tag$`) are literals on `PostgreSQL`.
+#[must_use]
+pub fn redact_literals(sql: &str, engine: DatabaseType) -> String {
+    let chars: Vec<char> = sql.chars().collect();
+    let backslash_escapes = engine == DatabaseType::MySQL;
+    let mut out = String::with_capacity(sql.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let after_identifier =
+            out.chars().next_back().is_some_and(|p| p.is_alphanumeric() || p == '_' || p == '$');
+
+        if c == '\'' || (c == '"' && engine == DatabaseType::MySQL) {
+            i = skip_quoted(&chars, i, backslash_escapes);
+            out.push('?');
+        } else if matches!(c, '"' | '`') {
+            let end = skip_quoted(&chars, i, false);
+            out.extend(&chars[i..end]);
+            i = end;
+        } else if c == '$' && engine == DatabaseType::Postgres && !after_identifier {
+            if let Some(end) = skip_dollar_quoted(&chars, i) {
+                out.push('?');
+                i = end;
+            } else {
+                out.push(c);
+                i += 1;
+            }
+        } else if (c == '-' && next == Some('-')) || (c == '#' && engine == DatabaseType::MySQL) {
+            let end = chars[i..].iter().position(|&ch| ch == '\n').map_or(chars.len(), |p| i + p);
+            out.extend(&chars[i..end]);
+            i = end;
+        } else if c == '/' && next == Some('*') {
+            let end = chars[i + 2..]
+                .windows(2)
+                .position(|w| w == ['*', '/'])
+                .map_or(chars.len(), |p| i + 2 + p + 2);
+            out.extend(&chars[i..end]);
+            i = end;
+        } else if c.is_ascii_digit() && !after_identifier {
+            i += 1;
+            while i < chars.len() {
+                let ch = chars[i];
+                let exponent_sign = matches!(ch, '+' | '-') && matches!(chars[i - 1], 'e' | 'E');
+                if ch.is_ascii_alphanumeric() || ch == '.' || ch == '_' || exponent_sign {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            out.push('?');
+        } else {
+            out.push(c);
+            i += 1;
+        }
+    }
+
+    out
+}
+
+/// Index just past the quoted region opening at `start` (doubled quotes are escapes).
+/// An unterminated region runs to the end of the input.
+pub(crate) fn skip_quoted(chars: &[char], start: usize, backslash_escapes: bool) -> usize {
+    let quote = chars[start];
+    let mut i = start + 1;
+    while i < chars.len() {
+        if backslash_escapes && chars[i] == '\\' {
+            i += 2;
+        } else if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                i += 2;
+            } else {
+                return i + 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    chars.len()
+}
+
+/// Index just past a `PostgreSQL` dollar-quoted string opening at `start`, or `None`
+/// when the `$` does not open one (e.g. a `$1` placeholder).
+pub(crate) fn skip_dollar_quoted(chars: &[char], start: usize) -> Option<usize> {
+    let tag_len = chars[start + 1..].iter().position(|&c| c == '$')?;
+    let tag = &chars[start..=start + 1 + tag_len];
+    let valid_tag = tag[1..tag.len() - 1]
+        .iter()
+        .enumerate()
+        .all(|(n, &c)| c == '_' || c.is_alphabetic() || (n > 0 && c.is_ascii_digit()));
+    if !valid_tag {
+        return None;
+    }
+    let body = start + tag.len();
+    let end = chars[body..]
+        .windows(tag.len())
+        .position(|w| w == tag)
+        .map_or(chars.len(), |p| body + p + tag.len());
+    Some(end)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1745,4 +1889,60 @@ mod tests {
         let result = validate_query("SELECT 1; DROP TABLE users", &caps, DatabaseType::DuckDB);
         assert!(result.is_err());
     }
+
+    // Literal redaction tests
+
+    #[test]
+    fn test_redact_literals_strings_and_numbers() {
+        assert_eq!(
+            redact_literals(
+                "SELECT * FROM t1 WHERE email = 'a@b.c' AND id IN (1, 2.5, 3e-4) LIMIT 10",
+                DatabaseType::Postgres
+            ),
+            "SELECT * FROM t1 WHERE email = ? AND id IN (?, ?, ?) LIMIT ?"
+        );
+        assert_eq!(
+            redact_literals("SELECT 'it''s', col2 FROM \"My Table\"", DatabaseType::SQLite),
+            "SELECT ?, col2 FROM \"My Table\""
+        );
+    }
+
+    #[test]
+    fn test_redact_literals_keeps_placeholders_and_comments() {
+        assert_eq!(
+            redact_literals("SELECT $1 /* id 42 */ FROM t -- 'x'", DatabaseType::Postgres),
+            "SELECT $1 /* id 42 */ FROM t -- 'x'"
+        );
+        assert_eq!(
+            redact_literals("SELECT $$secret$$, $tag$body 'x'$tag$", DatabaseType::Postgres),
+            "SELECT ?, ?"
+        );
+    }
+
+    #[test]
+    fn test_redact_literals_mysql_quoting() {
+        assert_eq!(
+            redact_literals(
+                "SELECT \"pw\", 'a\\'b', `col 1` FROM t WHERE x = 0x1F",
+                DatabaseType::MySQL
+            ),
+            "SELECT ?, ?, `col 1` FROM t WHERE x = ?"
+        );
+    }
+
+    #[test]
+    fn test_structured_explain_analyze_detection() {
+        let pg = DatabaseType::Postgres;
+        assert!(!structured_explain_analyze("EXPLAIN SELECT 1", pg).unwrap());
+        assert!(structured_explain_analyze("EXPLAIN ANALYZE SELECT 1", pg).unwrap());
+        assert!(structured_explain_analyze("explain (buffers, analyze) SELECT 1", pg).unwrap());
+        assert!(!structured_explain_analyze("EXPLAIN (ANALYZE false) SELECT 1", pg).unwrap());
+        assert!(!structured_explain_analyze("EXPLAIN FORMAT=JSON SELECT analyze", pg).unwrap());
+
+        let err = structured_explain_analyze("EXPLAIN ANALYZE SHOW work_mem", pg).unwrap_err();
+        assert_eq!(err.error_code(), "INVALID_INPUT");
+        let err = structured_explain_analyze("EXPLAIN ANALYZE SELECT 1", DatabaseType::SQLite)
+            .unwrap_err();
+        assert_eq!(err.error_code(), "INVALID_INPUT");
+    }
 }
@@ -0,0 +1,1113 @@
+//! Keyset (cursor) pagination for `plenum query`
+//!
+//! `--offset` pagination re-runs the query and discards every earlier row, so each
+//! page is slower than the last and pages drift when the query has no ORDER BY.
+//! A keyset cursor instead records the ordering-key values of the last row
+//! returned; the next page rewrites the query with a predicate such as
+//! `WHERE (<original filter>) AND (k1, k2) > (v1, v2)` and reads only new rows.
+//!
+//! Cursors are issued only when the ordering is guaranteed unique and total:
+//! - The query is a single-table `SELECT ... FROM t [WHERE ...] [ORDER BY ...]`
+//!   (no joins, grouping, DISTINCT, set operations, LIMIT/OFFSET or window functions).
+//! - ORDER BY lists plain columns, all ASC or all DESC, that are NOT NULL and cover
+//!   the table's primary key or a unique index.
+//! - Without ORDER BY, the query is ordered by the primary key, so every page
+//!   (including the first) is deterministic.
+//!
+//! Pagination is opt-in (`--paginate`, implied by `--cursor`): without it the query
+//! runs exactly as written and no table metadata is looked up.
+//!
+//! The token is base64 JSON bound to a fingerprint of the engine, SQL text and
+//! parameters; it is rejected if replayed against a different query. Key values
+//! are bound as extra query parameters and key names are quoted identifiers, so a
+//! hand-edited token cannot inject SQL. On `PostgreSQL` each value is bound as text
+//! and cast to the key column's type, taken from a fixed list of comparable types.
+
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::capability::{skip_dollar_quoted, skip_quoted};
+use crate::engine::{
+    Capabilities, ConnectionConfig, DatabaseEngine, DatabaseType, IntrospectOperation,
+    IntrospectResult, QueryResult, TableFields, TableInfo,
+};
+use crate::error::{PlenumError, Result};
+
+/// Cursor token format version
+const TOKEN_VERSION: u8 = 2;
+
+/// Execute `query` with keyset pagination support.
+///
+/// With `cursor`, the query is resumed after the row the cursor points at. Without
+/// it, when `paginate` is set and the query qualifies, a `Keyset` is returned so the
+/// caller can issue `next_cursor` after any post-processing (such as the byte
+/// budget) has trimmed the rows. Without either, the query runs unchanged.
+///
+/// # Errors
+///
+/// Returns `INVALID_INPUT` for a malformed cursor, a cursor issued for a different
+/// query, or a cursor combined with `offset`; otherwise whatever `E::execute` returns.
+pub async fn execute_with_cursor<E: DatabaseEngine>(
+    config: &ConnectionConfig,
+    query: &str,
+    params: &[Value],
+    caps: &Capabilities,
+    cursor: Option<&str>,
+    paginate: bool,
+) -> Result<(QueryResult, Option<Keyset>)> {
+    let engine = config.engine;
+    let fingerprint = fingerprint(engine, query, params);
+
+    if let Some(token) = cursor {
+        if caps.offset.is_some() {
+            return Err(PlenumError::invalid_input("--cursor cannot be combined with --offset"));
+        }
+        let token = CursorToken::decode(token)?;
+        if token.f != fingerprint {
+            return Err(PlenumError::invalid_input(
+                "Cursor was issued for a different query or parameters; rerun the original \
+                 query with the same SQL and params",
+            ));
+        }
+        let shape = SelectShape::parse(query, engine).ok_or_else(|| {
+            PlenumError::invalid_input("Query is not eligible for cursor pagination")
+        })?;
+        let keyset = shape.keyset_for_token(&token, engine, fingerprint)?;
+        let (sql, after) = shape.rewrite(engine, &keyset, Some(&token.a), params.len())?;
+        crate::validate_query(&sql, caps, engine)?;
+        let params = [params, &after].concat();
+        let result = E::execute(config, &sql, &params, caps).await?;
+        return Ok((result, Some(keyset)));
+    }
+
+    let shape = if paginate && caps.max_rows.is_some() && caps.explain_format.is_none() {
+        SelectShape::parse(query, engine)
+    } else {
+        None
+    };
+    let Some(shape) = shape else {
+        return Ok((E::execute(config, query, params, caps).await?, None));
+    };
+
+    if shape.order_by.is_empty() {
+        // No ORDER BY: order by the primary key so pages are deterministic.
+        let Some((sql, keyset)) = primary_key_order::<E>(config, query, params).await else {
+            return Ok((E::execute(config, query, params, caps).await?, None));
+        };
+        let result = E::execute(config, &sql, params, caps).await?;
+        return Ok((result, Some(keyset)));
+    }
+
+    // Explicit ORDER BY: only look up the table's keys when there is a next page.
+    let result = E::execute(config, query, params, caps).await?;
+    if !result.rows_truncated {
+        return Ok((result, None));
+    }
+    let keyset = match table_details::<E>(config, &shape).await {
+        Some(table) => shape.resolve(&table, engine, fingerprint),
+        None => None,
+    };
+    Ok((result, keyset))
+}
+
+/// `query` rewritten to ORDER BY its table's primary key, with the keyset that pages
+/// it; `None` unless it is a single-table SELECT without ORDER BY over a table with
+/// a usable primary key. Looks up the table's metadata on a separate connection.
+pub(crate) async fn primary_key_order<E: DatabaseEngine>(
+    config: &ConnectionConfig,
+    query: &str,
+    params: &[Value],
+) -> Option<(String, Keyset)> {
+    let engine = config.engine;
+    let shape = SelectShape::parse(query, engine).filter(|s| s.order_by.is_empty())?;
+    let table = table_details::<E>(config, &shape).await?;
+    let keyset = shape.resolve(&table, engine, fingerprint(engine, query, params))?;
+    let (sql, _) = shape.rewrite(engine, &keyset, None, params.len()).ok()?;
+    Some((sql, keyset))
+}
+
+/// Look up the queried table's columns, primary key and indexes; `None` when the
+/// relation cannot be introspected (e.g. it is a view).
+async fn table_details<E: DatabaseEngine>(
+    config: &ConnectionConfig,
+    shape: &SelectShape,
+) -> Option<TableInfo> {
+    let operation = IntrospectOperation::TableDetails {
+        name: shape.table.clone(),
+        fields: TableFields {
+            columns: true,
+            primary_key: true,
+            foreign_keys: false,
+            indexes: true,
+        },
+    };
+    match E::introspect(config, &operation, None, shape.schema.as_deref()).await {
+        Ok(IntrospectResult::TableDetails { table }) => Some(table),
+        _ => None,
+    }
+}
+
+/// Ordering keys of a paginated query; produces the cursor for the next page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keyset {
+    keys: Vec<SortKey>,
+    descending: bool,
+    fingerprint: String,
+    /// `PostgreSQL` cast type per key (see [`PG_KEY_TYPES`]); empty elsewhere
+    types: Vec<String>,
+}
+
+impl Keyset {
+    /// Cursor pointing after the last row of `result`, when more rows remain.
+    ///
+    /// `None` when the result was not truncated, or when a key column is missing
+    /// from the select list or NULL in the last row.
+    #[must_use]
+    pub fn next_cursor(&self, result: &QueryResult) -> Option<String> {
+        if !result.rows_truncated {
+            return None;
+        }
+        let last = result.rows.last()?;
+        let mut values = Vec::with_capacity(self.keys.len());
+        for key in &self.keys {
+            let idx = result.columns.iter().position(|c| *c == key.column).or_else(|| {
+                result.columns.iter().position(|c| c.eq_ignore_ascii_case(&key.column))
+            })?;
+            match last.get(idx)? {
+                Value::Null => return None,
+                value => values.push(value.clone()),
+            }
+        }
+        let token = CursorToken {
+            v: TOKEN_VERSION,
+            f: self.fingerprint.clone(),
+            k: self.keys.iter().map(|k| k.column.clone()).collect(),
+            d: self.descending,
+            a: values,
+            t: self.types.clone(),
+        };
+        Some(token.encode())
+    }
+}
+
+/// One ordering key: the SQL expression as written (or a quoted identifier) and
+/// the bare column name it reads in the result set
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SortKey {
+    expr: String,
+    column: String,
+}
+
+/// Decoded cursor token (field names are kept short; the token is opaque)
+#[derive(Debug, Serialize, Deserialize)]
+struct CursorToken {
+    /// Token format version
+    v: u8,
+    /// Query fingerprint
+    f: String,
+    /// Key column names
+    k: Vec<String>,
+    /// Descending order
+    d: bool,
+    /// Key values of the last row returned
+    a: Vec<Value>,
+    /// `PostgreSQL` cast type per key
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    t: Vec<String>,
+}
+
+impl CursorToken {
+    fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(json)
+    }
+
+    fn decode(token: &str) -> Result<Self> {
+        let invalid = || PlenumError::invalid_input("Invalid cursor: not a token issued by plenum");
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(token.trim())
+            .map_err(|_| invalid())?;
+        let token: Self = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+        if token.v != TOKEN_VERSION {
+            return Err(PlenumError::invalid_input(format!(
+                "Invalid cursor: unsupported token version {}",
+                token.v
+            )));
+        }
+        if token.k.is_empty() || token.k.len() != token.a.len() {
+            return Err(invalid());
+        }
+        if !token.t.is_empty()
+            && (token.t.len() != token.k.len()
+                || !token.t.iter().all(|t| PG_KEY_TYPES.iter().any(|(_, cast)| cast == t)))
+        {
+            return Err(invalid());
+        }
+        Ok(token)
+    }
+}
+
+/// Stable 64-bit FNV-1a fingerprint of the engine, SQL text and parameters
+fn fingerprint(engine: DatabaseType, query: &str, params: &[Value]) -> String {
+    let params = serde_json::to_string(params).unwrap_or_default();
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in [engine.as_str(), query.trim(), &params] {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+// ============================================================================
+// Query shape analysis
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Tok {
+    /// Unquoted identifier or keyword, as written
+    Word(String),
+    /// Quoted identifier, unescaped
+    Quoted(String),
+    /// String or numeric literal, placeholder
+    Literal,
+    Punct(char),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Token {
+    pub tok: Tok,
+    /// Char index of the first character
+    pub start: usize,
+    /// Char index just past the last character
+    pub end: usize,
+    /// Parenthesis depth (0 = top level)
+    pub depth: usize,
+}
+
+impl Token {
+    pub(crate) fn is_word(&self, kw: &str) -> bool {
+        matches!(&self.tok, Tok::Word(w) if w.eq_ignore_ascii_case(kw))
+    }
+
+    pub(crate) fn ident(&self) -> Option<&str> {
+        match &self.tok {
+            Tok::Word(w) if !is_reserved(w) => Some(w),
+            Tok::Quoted(q) => Some(q),
+            _ => None,
+        }
+    }
+}
+
+/// Keywords that end a table reference or cannot start one
+const RESERVED: &[&str] = &[
+    "AS",
+    "ALL",
+    "BY",
+    "CROSS",
+    "DISTINCT",
+    "EXCEPT",
+    "FETCH",
+    "FOR",
+    "FROM",
+    "FULL",
+    "GROUP",
+    "HAVING",
+    "INNER",
+    "INTERSECT",
+    "INTO",
+    "JOIN",
+    "LATERAL",
+    "LEFT",
+    "LIMIT",
+    "NATURAL",
+    "OFFSET",
+    "ON",
+    "ONLY",
+    "ORDER",
+    "OUTER",
+    "QUALIFY",
+    "RIGHT",
+    "SAMPLE",
+    "SELECT",
+    "STRAIGHT_JOIN",
+    "TABLESAMPLE",
+    "UNION",
+    "USING",
+    "WHERE",
+    "WINDOW",
+];
+
+fn is_reserved(word: &str) -> bool {
+    RESERVED.iter().any(|kw| word.eq_ignore_ascii_case(kw))
+}
+
+/// Split `chars` into tokens, skipping whitespace and comments
+pub(crate) fn tokenize(chars: &[char], engine: DatabaseType) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let start = i;
+        let tok = if c.is_whitespace() {
+            i += 1;
+            continue;
+        } else if (c == '-' && next == Some('-')) || (c == '#' && engine == DatabaseType::MySQL) {
+            i = chars[i..].iter().position(|&ch| ch == '\n').map_or(chars.len(), |p| i + p);
+            continue;
+        } else if c == '/' && next == Some('*') {
+            i = chars[i + 2..]
+                .windows(2)
+                .position(|w| w == ['*', '/'])
+                .map_or(chars.len(), |p| i + 2 + p + 2);
+            continue;
+        } else if c == '\'' || (c == '"' && engine == DatabaseType::MySQL) {
+            i = skip_quoted(chars, i, engine == DatabaseType::MySQL);
+            Tok::Literal
+        } else if c == '"' || c == '`' {
+            i = skip_quoted(chars, i, false);
+            let inner: String =
+                chars[start + 1..i.saturating_sub(1).max(start + 1)].iter().collect();
+            let quote = c.to_string();
+            Tok::Quoted(inner.replace(&quote.repeat(2), &quote))
+        } else if c == '[' && engine == DatabaseType::SQLite {
+            i = chars[i..].iter().position(|&ch| ch == ']').map_or(chars.len(), |p| i + p + 1);
+            Tok::Quoted(chars[start + 1..i.saturating_sub(1).max(start + 1)].iter().collect())
+        } else if c == '$' && engine == DatabaseType::Postgres {
+            if let Some(end) = skip_dollar_quoted(chars, i) {
+                i = end;
+            } else {
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            Tok::Literal
+        } else if c == '?' {
+            i += 1;
+            Tok::Literal
+        } else if c.is_ascii_digit() {
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            Tok::Literal
+        } else if c.is_alphabetic() || c == '_' {
+            i += 1;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+            {
+                i += 1;
+            }
+            Tok::Word(chars[start..i].iter().collect())
+        } else {
+            i += 1;
+            Tok::Punct(c)
+        };
+        if tok == Tok::Punct(')') {
+            depth = depth.saturating_sub(1);
+        }
+        tokens.push(Token { tok: tok.clone(), start, end: i, depth });
+        if tok == Tok::Punct('(') {
+            depth += 1;
+        }
+    }
+    tokens
+}
+
+/// A single-table `SELECT ... FROM t [WHERE ...] [ORDER BY ...]` eligible for keysets
+#[derive(Debug, Clone)]
+struct SelectShape {
+    chars: Vec<char>,
+    schema: Option<String>,
+    table: String,
+    /// End of the table reference (including any alias)
+    from_end: usize,
+    /// Span of the WHERE condition
+    condition: Option<(usize, usize)>,
+    /// Start of the ORDER BY clause
+    order_start: Option<usize>,
+    order_by: Vec<SortKey>,
+    descending: bool,
+    /// End of the last token of the statement (excluding a trailing `;`)
+    end: usize,
+}
+
+impl SelectShape {
+    #[allow(clippy::too_many_lines)]
+    fn parse(sql: &str, engine: DatabaseType) -> Option<Self> {
+        let chars: Vec<char> = sql.chars().collect();
+        let mut tokens = tokenize(&chars, engine);
+        if tokens.last().is_some_and(|t| t.tok == Tok::Punct(';')) {
+            tokens.pop();
+        }
+        let end = tokens.last()?.end;
+        let mut pos = 0;
+
+        if !tokens.first()?.is_word("SELECT") || tokens.get(1)?.is_word("DISTINCT") {
+            return None;
+        }
+        // Select list: anything up to the top-level FROM, but no window functions
+        // (a keyset predicate would change their input) and no SELECT ... INTO.
+        pos += 1;
+        while !(tokens.get(pos)?.depth == 0 && tokens[pos].is_word("FROM")) {
+            if tokens[pos].is_word("OVER")
+                || (tokens[pos].depth == 0 && tokens[pos].is_word("INTO"))
+            {
+                return None;
+            }
+            pos += 1;
+        }
+        pos += 1;
+
+        // Table reference: [catalog.][schema.]table, not a function or subquery
+        let mut parts = vec![tokens.get(pos)?.ident()?.to_string()];
+        pos += 1;
+        while tokens.get(pos).is_some_and(|t| t.tok == Tok::Punct('.')) {
+            parts.push(tokens.get(pos + 1)?.ident()?.to_string());
+            pos += 2;
+        }
+        if parts.len() > 3 || tokens.get(pos).is_some_and(|t| t.tok == Tok::Punct('(')) {
+            return None;
+        }
+        let table = parts.pop()?;
+        let schema = parts.pop();
+        let mut from_end = tokens[pos - 1].end;
+
+        // Optional alias
+        if tokens.get(pos).is_some_and(|t| t.is_word("AS")) {
+            pos += 1;
+            tokens.get(pos)?.ident()?;
+        }
+        if tokens.get(pos).is_some_and(|t| t.ident().is_some()) {
+            from_end = tokens[pos].end;
+            pos += 1;
+        }
+
+        let mut condition = None;
+        if tokens.get(pos).is_some_and(|t| t.is_word("WHERE")) {
+            pos += 1;
+            let cond_start = tokens.get(pos)?.start;
+            let mut cond_end = cond_start;
+            while let Some(t) = tokens.get(pos) {
+                if t.depth == 0 && t.is_word("ORDER") {
+                    break;
+                }
+                if t.depth == 0
+                    && [
+                        "GROUP",
+                        "HAVING",
+                        "LIMIT",
+                        "OFFSET",
+                        "FETCH",
+                        "UNION",
+                        "INTERSECT",
+                        "EXCEPT",
+                        "WINDOW",
+                        "QUALIFY",
+                        "FOR",
+                    ]
+                    .iter()
+                    .any(|kw| t.is_word(kw))
+                {
+                    return None;
+                }
+                if t.tok == Tok::Punct(';') {
+                    return None;
+                }
+                cond_end = t.end;
+                pos += 1;
+            }
+            condition = Some((cond_start, cond_end));
+        }
+
+        let mut order_start = None;
+        let mut order_by = Vec::new();
+        let mut direction: Option<bool> = None;
+        if tokens.get(pos).is_some_and(|t| t.is_word("ORDER")) {
+            order_start = Some(tokens[pos].start);
+            if !tokens.get(pos + 1)?.is_word("BY") {
+                return None;
+            }
+            pos += 2;
+            loop {
+                let key_start = tokens.get(pos)?.start;
+                let mut column = tokens.get(pos)?.ident()?.to_string();
+                pos += 1;
+                while tokens.get(pos).is_some_and(|t| t.tok == Tok::Punct('.')) {
+                    column = tokens.get(pos + 1)?.ident()?.to_string();
+                    pos += 2;
+                }
+                let expr: String = chars[key_start..tokens[pos - 1].end].iter().collect();
+                let mut descending = false;
+                if tokens.get(pos).is_some_and(|t| t.is_word("DESC")) {
+                    descending = true;
+                    pos += 1;
+                } else if tokens.get(pos).is_some_and(|t| t.is_word("ASC")) {
+                    pos += 1;
+                }
+                if *direction.get_or_insert(descending) != descending {
+                    return None;
+                }
+                order_by.push(SortKey { expr, column });
+                match tokens.get(pos) {
+                    None => break,
+                    Some(t) if t.tok == Tok::Punct(',') => pos += 1,
+                    Some(_) => return None,
+                }
+            }
+        }
+        if pos != tokens.len() {
+            return None;
+        }
+
+        Some(Self {
+            chars,
+            schema,
+            table,
+            from_end,
+            condition,
+            order_start,
+            order_by,
+            descending: direction.unwrap_or(false),
+            end,
+        })
+    }
+
+    /// Pick unique, non-null ordering keys using the table's primary key and indexes.
+    ///
+    /// On `PostgreSQL` every key column must also have a type in [`PG_KEY_TYPES`].
+    fn resolve(
+        &self,
+        table: &TableInfo,
+        engine: DatabaseType,
+        fingerprint: String,
+    ) -> Option<Keyset> {
+        let primary_key = table.primary_key.clone().unwrap_or_default();
+        if self.order_by.is_empty() {
+            if primary_key.is_empty() {
+                return None;
+            }
+            let keys: Vec<SortKey> = primary_key
+                .iter()
+                .map(|c| SortKey { expr: quote_ident(engine, c), column: c.clone() })
+                .collect();
+            let types = key_types(table, &keys, engine)?;
+            return Some(Keyset { keys, descending: false, fingerprint, types });
+        }
+
+        let in_keys =
+            |col: &String| self.order_by.iter().any(|k| k.column.eq_ignore_ascii_case(col));
+        let non_null = self.order_by.iter().all(|key| {
+            primary_key.iter().any(|pk| pk.eq_ignore_ascii_case(&key.column))
+                || table
+                    .columns
+                    .iter()
+                    .any(|c| c.name.eq_ignore_ascii_case(&key.column) && !c.nullable)
+        });
+        let unique = (!primary_key.is_empty() && primary_key.iter().all(in_keys))
+            || table.indexes.iter().any(|idx| {
+                idx.unique && !idx.columns.is_empty() && idx.columns.iter().all(in_keys)
+            });
+        if !(non_null && unique) {
+            return None;
+        }
+        let types = key_types(table, &self.order_by, engine)?;
+        Some(Keyset {
+            keys: self.order_by.clone(),
+            descending: self.descending,
+            fingerprint,
+            types,
+        })
+    }
+
+    /// Rebuild the keyset recorded in a cursor token against this query.
+    fn keyset_for_token(
+        &self,
+        token: &CursorToken,
+        engine: DatabaseType,
+        fingerprint: String,
+    ) -> Result<Keyset> {
+        // PostgreSQL keys are always typed; other engines bind values as they are
+        if (engine == DatabaseType::Postgres) == token.t.is_empty() {
+            return Err(PlenumError::invalid_input("Invalid cursor: not a token issued by plenum"));
+        }
+        if self.order_by.is_empty() {
+            let keys = token
+                .k
+                .iter()
+                .map(|c| SortKey { expr: quote_ident(engine, c), column: c.clone() })
+                .collect();
+            return Ok(Keyset { keys, descending: token.d, fingerprint, types: token.t.clone() });
+        }
+        let matches = self.descending == token.d
+            && self.order_by.len() == token.k.len()
+            && self.order_by.iter().zip(&token.k).all(|(key, col)| key.column == *col);
+        if !matches {
+            return Err(PlenumError::invalid_input("Cursor does not match the query's ORDER BY"));
+        }
+        Ok(Keyset {
+            keys: self.order_by.clone(),
+            descending: self.descending,
+            fingerprint,
+            types: token.t.clone(),
+        })
+    }
+
+    /// The query with the keyset predicate (when resuming `after` a row) and the
+    /// keyset ORDER BY (when the original query had none), plus the key values to
+    /// bind after the query's own `bound` parameters.
+    fn rewrite(
+        &self,
+        engine: DatabaseType,
+        keyset: &Keyset,
+        after: Option<&[Value]>,
+        bound: usize,
+    ) -> Result<(String, Vec<Value>)> {
+        let text = |from: usize, to: usize| -> String { self.chars[from..to].iter().collect() };
+        let order = if let Some(start) = self.order_start {
+            text(start, self.end)
+        } else {
+            let keys: Vec<String> = keyset
+                .keys
+                .iter()
+                .map(
+                    |k| if keyset.descending { format!("{} DESC", k.expr) } else { k.expr.clone() },
+                )
+                .collect();
+            format!("ORDER BY {}", keys.join(", "))
+        };
+        let Some(values) = after else {
+            let body = text(0, self.order_start.unwrap_or(self.end));
+            return Ok((format!("{} {order}", body.trim_end()), Vec::new()));
+        };
+
+        let numbered = match engine {
+            DatabaseType::Postgres => true,
+            // DuckDB cannot mix `?` with `$n`, so follow the query's own style
+            DatabaseType::DuckDB => tokenize(&self.chars, engine)
+                .windows(2)
+                .any(|w| w[0].tok == Tok::Punct('$') && w[1].start == w[0].end),
+            DatabaseType::SQLite | DatabaseType::MySQL => false,
+        };
+        let mut placeholders = Vec::with_capacity(values.len());
+        let mut extra = Vec::with_capacity(values.len());
+        for (i, value) in values.iter().enumerate() {
+            let placeholder = if numbered { format!("${}", bound + i + 1) } else { "?".into() };
+            match keyset.types.get(i) {
+                // Bound as text and cast, so the parameter never needs a native encoding
+                Some(ty) if engine == DatabaseType::Postgres => {
+                    placeholders.push(format!("{placeholder}::text::{ty}"));
+                    extra.push(Value::String(key_text(value)?));
+                }
+                _ => {
+                    key_text(value)?;
+                    placeholders.push(placeholder);
+                    extra.push(value.clone());
+                }
+            }
+        }
+        let op = if keyset.descending { "<" } else { ">" };
+        let predicate = if keyset.keys.len() == 1 {
+            format!("{} {op} {}", keyset.keys[0].expr, placeholders[0])
+        } else {
+            let exprs: Vec<&str> = keyset.keys.iter().map(|k| k.expr.as_str()).collect();
+            format!("({}) {op} ({})", exprs.join(", "), placeholders.join(", "))
+        };
+        let sql = match self.condition {
+            Some((start, end)) => {
+                format!("{}({}) AND {predicate} {order}", text(0, start), text(start, end))
+            }
+            None => format!("{} WHERE {predicate} {order}", text(0, self.from_end)),
+        };
+        Ok((sql, extra))
+    }
+}
+
+/// `PostgreSQL` key column types (as `information_schema` reports them) whose JSON
+/// rendering casts back to the same value, and the cast used for their bound value
+const PG_KEY_TYPES: &[(&str, &str)] = &[
+    ("smallint", "smallint"),
+    ("integer", "integer"),
+    ("bigint", "bigint"),
+    ("numeric", "numeric"),
+    ("real", "real"),
+    ("double precision", "double precision"),
+    ("text", "text"),
+    ("character varying", "varchar"),
+    ("character", "bpchar"),
+    ("boolean", "boolean"),
+    ("uuid", "uuid"),
+    ("date", "date"),
+    ("time without time zone", "time"),
+    ("time with time zone", "timetz"),
+    ("timestamp without time zone", "timestamp"),
+    ("timestamp with time zone", "timestamptz"),
+];
+
+/// Cast type per key on `PostgreSQL` (empty elsewhere); `None` when a key column
+/// is missing or has a type outside [`PG_KEY_TYPES`].
+fn key_types(table: &TableInfo, keys: &[SortKey], engine: DatabaseType) -> Option<Vec<String>> {
+    if engine != DatabaseType::Postgres {
+        return Some(Vec::new());
+    }
+    keys.iter()
+        .map(|key| {
+            let column = table.columns.iter().find(|c| c.name == key.column).or_else(|| {
+                table.columns.iter().find(|c| c.name.eq_ignore_ascii_case(&key.column))
+            })?;
+            PG_KEY_TYPES
+                .iter()
+                .find(|(name, _)| column.data_type.eq_ignore_ascii_case(name))
+                .map(|(_, cast)| (*cast).to_string())
+        })
+        .collect()
+}
+
+/// Text form of a key value from a cursor token; NULLs, arrays and objects are
+/// never issued as key values, so they mark a tampered token.
+fn key_text(value: &Value) -> Result<String> {
+    match value {
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::String(s) => Ok(s.clone()),
+        Value::Null | Value::Array(_) | Value::Object(_) => {
+            Err(PlenumError::invalid_input("Invalid cursor: unsupported key value"))
+        }
+    }
+}
+
+/// The outer query of a statement, as `--deterministic` ordering sees it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OuterQuery {
+    /// The statement is a `SELECT` or `WITH ... SELECT` whose rows may be reordered
+    pub is_select: bool,
+    /// The outer query has its own top-level ORDER BY
+    pub ordered: bool,
+    /// Statement text without a trailing `;`, safe to wrap in a subquery
+    pub body: String,
+}
+
+/// Classify `sql`'s outer query; ORDER BY inside subqueries, CTEs and window or
+/// aggregate calls does not count.
+pub(crate) fn outer_query(sql: &str, engine: DatabaseType) -> OuterQuery {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = tokenize(&chars, engine);
+    if tokens.last().is_some_and(|t| t.tok == Tok::Punct(';')) {
+        tokens.pop();
+    }
+    let is_select = tokens.first().is_some_and(|t| t.is_word("SELECT") || t.is_word("WITH"));
+    let ordered =
+        tokens.windows(2).any(|w| w[0].depth == 0 && w[0].is_word("ORDER") && w[1].is_word("BY"));
+    let body = chars[..tokens.last().map_or(0, |t| t.end)].iter().collect();
+    OuterQuery { is_select, ordered, body }
+}
+
+/// Quote an identifier for `engine`, doubling embedded quote characters.
+pub(crate) fn quote_ident(engine: DatabaseType, name: &str) -> String {
+    match engine {
+        DatabaseType::MySQL => format!("`{}`", name.replace('`', "``")),
+        _ => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{ColumnInfo, IndexInfo};
+    use serde_json::json;
+
+    fn column(name: &str, nullable: bool) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            data_type: "integer".to_string(),
+            nullable,
+            default: None,
+            comment: None,
+        }
+    }
+
+    fn table(primary_key: &[&str], columns: &[(&str, bool)], unique: &[&[&str]]) -> TableInfo {
+        TableInfo {
+            name: "orders".to_string(),
+            schema: None,
+            columns: columns.iter().map(|(n, nullable)| column(n, *nullable)).collect(),
+            primary_key: Some(primary_key.iter().map(|c| (*c).to_string()).collect()),
+            foreign_keys: Vec::new(),
+            indexes: unique
+                .iter()
+                .enumerate()
+                .map(|(i, cols)| IndexInfo {
+                    name: format!("uq_{i}"),
+                    columns: cols.iter().map(|c| (*c).to_string()).collect(),
+                    unique: true,
+                })
+                .collect(),
+            comment: None,
+            row_estimate: None,
+        }
+    }
+
+    fn result(columns: &[&str], rows: Vec<Vec<Value>>, truncated: bool) -> QueryResult {
+        QueryResult {
+            columns: columns.iter().map(|c| (*c).to_string()).collect(),
+            rows,
+            rows_affected: None,
+            execution_ms: 0,
+            rows_truncated: truncated,
+            truncated_by: None,
+            plan: None,
+            next_cursor: None,
+            ordering: None,
+        }
+    }
+
+    #[test]
+    fn test_outer_query_ignores_nested_order_by() {
+        let outer = outer_query("SELECT * FROM t ORDER BY id;", DatabaseType::Postgres);
+        assert!(outer.is_select && outer.ordered);
+        assert_eq!(outer.body, "SELECT * FROM t ORDER BY id");
+
+        let nested = outer_query(
+            "WITH r AS (SELECT * FROM t ORDER BY id LIMIT 5) \
+             SELECT id, row_number() OVER (ORDER BY id) FROM r -- ORDER BY id",
+            DatabaseType::Postgres,
+        );
+        assert!(nested.is_select);
+        assert!(!nested.ordered);
+
+        let pragma = outer_query("PRAGMA table_info(t)", DatabaseType::SQLite);
+        assert!(!pragma.is_select);
+    }
+
+    #[test]
+    fn test_parse_accepts_simple_scans() {
+        let shape = SelectShape::parse(
+            "SELECT id, name FROM public.orders o WHERE status = 'open' ORDER BY o.id DESC;",
+            DatabaseType::Postgres,
+        )
+        .unwrap();
+        assert_eq!(shape.schema.as_deref(), Some("public"));
+        assert_eq!(shape.table, "orders");
+        assert_eq!(shape.order_by, [SortKey { expr: "o.id".into(), column: "id".into() }]);
+        assert!(shape.descending);
+
+        let shape = SelectShape::parse("select * from `Order Items`", DatabaseType::MySQL).unwrap();
+        assert_eq!(shape.table, "Order Items");
+        assert!(shape.order_by.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_ineligible_queries() {
+        for sql in [
+            "SELECT * FROM a JOIN b ON a.id = b.a_id ORDER BY a.id",
+            "SELECT * FROM a, b",
+            "SELECT DISTINCT id FROM a ORDER BY id",
+            "SELECT id FROM a ORDER BY id LIMIT 10",
+            "SELECT status, count(*) FROM a GROUP BY status",
+            "SELECT id FROM a UNION SELECT id FROM b",
+            "SELECT id, row_number() OVER () FROM a",
+            "SELECT * FROM generate_series(1, 10)",
+            "SELECT id FROM a ORDER BY id ASC, name DESC",
+            "SELECT id FROM a ORDER BY 1",
+            "SELECT id FROM a ORDER BY lower(name)",
+            "WITH x AS (SELECT 1) SELECT * FROM x",
+        ] {
+            assert!(SelectShape::parse(sql, DatabaseType::Postgres).is_none(), "{sql}");
+        }
+        // Keywords inside subqueries and literals do not end the WHERE clause
+        assert!(SelectShape::parse(
+            "SELECT * FROM a WHERE id IN (SELECT a_id FROM b GROUP BY a_id) AND note <> 'LIMIT' ORDER BY id",
+            DatabaseType::Postgres
+        )
+        .is_some());
+    }
+
+    #[test]
+    fn test_resolve_requires_unique_non_null_keys() {
+        let fp = || "f".to_string();
+        let orders =
+            table(&["id"], &[("id", false), ("email", false), ("name", true)], &[&["email"]]);
+        let parse = |sql| SelectShape::parse(sql, DatabaseType::SQLite).unwrap();
+
+        assert!(parse("SELECT * FROM orders ORDER BY id")
+            .resolve(&orders, DatabaseType::SQLite, fp())
+            .is_some());
+        assert!(parse("SELECT * FROM orders ORDER BY email")
+            .resolve(&orders, DatabaseType::SQLite, fp())
+            .is_some());
+        assert!(
+            parse("SELECT * FROM orders ORDER BY name, id")
+                .resolve(&orders, DatabaseType::SQLite, fp())
+                .is_none(),
+            "nullable key"
+        );
+        assert!(parse("SELECT * FROM orders ORDER BY email, name")
+            .resolve(&orders, DatabaseType::SQLite, fp())
+            .is_none());
+
+        let implicit =
+            parse("SELECT * FROM orders").resolve(&orders, DatabaseType::SQLite, fp()).unwrap();
+        assert_eq!(implicit.keys[0].expr, "\"id\"");
+        let no_pk = table(&[], &[("id", false)], &[]);
+        assert!(parse("SELECT * FROM orders")
+            .resolve(&no_pk, DatabaseType::SQLite, fp())
+            .is_none());
+    }
+
+    #[test]
+    fn test_rewrite_binds_keyset_values() {
+        let shape = SelectShape::parse(
+            "SELECT * FROM t WHERE a = $1 OR b = 2 -- note\nORDER BY x, y",
+            DatabaseType::Postgres,
+        )
+        .unwrap();
+        let keyset = Keyset {
+            keys: shape.order_by.clone(),
+            descending: false,
+            fingerprint: String::new(),
+            types: vec!["integer".into(), "text".into()],
+        };
+        let (sql, extra) = shape
+            .rewrite(DatabaseType::Postgres, &keyset, Some(&[json!(5), json!("it's")]), 1)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM t WHERE (a = $1 OR b = 2) AND (x, y) > ($2::text::integer, $3::text::text) ORDER BY x, y"
+        );
+        assert_eq!(extra, [json!("5"), json!("it's")]);
+
+        let shape = SelectShape::parse("SELECT * FROM t -- trailing", DatabaseType::MySQL).unwrap();
+        let keyset = Keyset {
+            keys: vec![SortKey {
+                expr: quote_ident(DatabaseType::MySQL, "id"),
+                column: "id".into(),
+            }],
+            descending: false,
+            fingerprint: String::new(),
+            types: Vec::new(),
+        };
+        assert_eq!(
+            shape.rewrite(DatabaseType::MySQL, &keyset, None, 0).unwrap(),
+            ("SELECT * FROM t ORDER BY `id`".to_string(), Vec::new())
+        );
+        let (sql, extra) =
+            shape.rewrite(DatabaseType::MySQL, &keyset, Some(&[json!("a\\'b")]), 0).unwrap();
+        assert_eq!(sql, "SELECT * FROM t WHERE `id` > ? ORDER BY `id`");
+        assert_eq!(extra, [json!("a\\'b")]);
+
+        // DuckDB follows the query's own placeholder style
+        let shape =
+            SelectShape::parse("SELECT * FROM t WHERE a = $1 ORDER BY id", DatabaseType::DuckDB)
+                .unwrap();
+        let keyset = Keyset {
+            keys: shape.order_by.clone(),
+            descending: true,
+            fingerprint: String::new(),
+            types: Vec::new(),
+        };
+        let (sql, _) = shape.rewrite(DatabaseType::DuckDB, &keyset, Some(&[json!(3)]), 1).unwrap();
+        assert_eq!(sql, "SELECT * FROM t WHERE (a = $1) AND id < $2 ORDER BY id");
+    }
+
+    #[test]
+    fn test_tampered_token_values_stay_out_of_sql() {
+        let query = "SELECT * FROM orders ORDER BY id";
+        let shape = SelectShape::parse(query, DatabaseType::Postgres).unwrap();
+        let fp = fingerprint(DatabaseType::Postgres, query, &[]);
+        let forge = |a: Value, t: &str| {
+            CursorToken {
+                v: TOKEN_VERSION,
+                f: fp.clone(),
+                k: vec!["id".into()],
+                d: false,
+                a: vec![a],
+                t: vec![t.into()],
+            }
+            .encode()
+        };
+
+        let injection = "0) OR 1=1; DROP TABLE orders; --";
+        let token = CursorToken::decode(&forge(json!(injection), "integer")).unwrap();
+        let keyset = shape.keyset_for_token(&token, DatabaseType::Postgres, fp.clone()).unwrap();
+        let (sql, extra) =
+            shape.rewrite(DatabaseType::Postgres, &keyset, Some(&token.a), 0).unwrap();
+        assert_eq!(sql, "SELECT * FROM orders WHERE id > $1::text::integer ORDER BY id");
+        assert_eq!(extra, [json!(injection)]);
+
+        // The cast type is checked against the allowlist, values must be scalars
+        assert!(CursorToken::decode(&forge(json!(1), "integer; DROP TABLE orders")).is_err());
+        let token = CursorToken::decode(&forge(json!({"a": 1}), "integer")).unwrap();
+        let keyset = shape.keyset_for_token(&token, DatabaseType::Postgres, fp.clone()).unwrap();
+        assert!(shape.rewrite(DatabaseType::Postgres, &keyset, Some(&token.a), 0).is_err());
+
+        // Untyped tokens are not accepted on PostgreSQL
+        let mut untyped = CursorToken::decode(&forge(json!(1), "integer")).unwrap();
+        untyped.t.clear();
+        assert!(shape.keyset_for_token(&untyped, DatabaseType::Postgres, fp).is_err());
+    }
+
+    #[test]
+    fn test_resolve_requires_comparable_postgres_types() {
+        let mut orders = table(&["id"], &[("id", false), ("doc", false)], &[&["doc"]]);
+        let parse = |sql| SelectShape::parse(sql, DatabaseType::Postgres).unwrap();
+        let keyset = parse("SELECT * FROM orders")
+            .resolve(&orders, DatabaseType::Postgres, String::new())
+            .unwrap();
+        assert_eq!(keyset.types, ["integer"]);
+
+        orders.columns[1].data_type = "jsonb".to_string();
+        assert!(parse("SELECT * FROM orders ORDER BY doc")
+            .resolve(&orders, DatabaseType::Postgres, String::new())
+            .is_none());
+    }
+
+    #[test]
+    fn test_next_cursor_round_trips() {
+        let keyset = Keyset {
+            keys: vec![SortKey { expr: "id".into(), column: "id".into() }],
+            descending: true,
+            fingerprint: fingerprint(DatabaseType::SQLite, "SELECT * FROM t ORDER BY id DESC", &[]),
+            types: Vec::new(),
+        };
+        let page = result(
+            &["id", "name"],
+            vec![vec![json!(9), json!("a")], vec![json!(8), json!("b")]],
+            true,
+        );
+        let token = CursorToken::decode(&keyset.next_cursor(&page).unwrap()).unwrap();
+        assert_eq!(token.a, [json!(8)]);
+        assert!(token.d);
+        assert_eq!(token.f, keyset.fingerprint);
+
+        assert!(
+            keyset.next_cursor(&result(&["id"], vec![vec![json!(1)]], false)).is_none(),
+            "last page"
+        );
+        assert!(
+            keyset.next_cursor(&result(&["name"], vec![vec![json!("a")]], true)).is_none(),
+            "key not selected"
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert!(CursorToken::decode("not a cursor").is_err());
+        assert!(CursorToken::decode(
+            &base64::engine::general_purpose::URL_SAFE_NO_PAD.encode("{}")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_fingerprint_depends_on_query_and_params() {
+        let a = fingerprint(DatabaseType::Postgres, "SELECT 1", &[json!(1)]);
+        assert_eq!(a, fingerprint(DatabaseType::Postgres, " SELECT 1 ", &[json!(1)]));
+        assert_ne!(a, fingerprint(DatabaseType::Postgres, "SELECT 1", &[json!(2)]));
+        assert_ne!(a, fingerprint(DatabaseType::MySQL, "SELECT 1", &[json!(1)]));
+    }
+}
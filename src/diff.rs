@@ -1,17 +1,26 @@
-//! Schema diff computation
+//! Schema and data diff computation
 //!
 //! Computes a structural diff between two databases by calling existing `DatabaseEngine::introspect`
 //! operations on each connection independently. No engine-specific SQL is added here — all data
 //! comes from the standard suite of `IntrospectOperation` calls.
+//!
+//! The data diff runs one query on both connections through `DatabaseEngine::execute_stream`
+//! and matches the result rows by key columns (see [`compute_data_diff`]).
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::hash::{Hash, Hasher};
 
-use std::collections::BTreeMap;
+use serde_json::{Map, Value};
 
 use crate::engine::{
-    ColumnChange, ColumnInfo, ConnectionConfig, DatabaseEngine, DatabaseType, DefinitionChange,
-    ForeignKeyInfo, IndexInfo, IntrospectOperation, IntrospectResult, PrimaryKeyChange, SchemaDiff,
-    TableDiff, TableFields, TableInfo, ViewDiff, ViewInfo,
+    Capabilities, CellChange, ColumnChange, ColumnInfo, ConnectionConfig, DataDiff, DataDiffCounts,
+    DatabaseEngine, DatabaseType, DefinitionChange, ForeignKeyInfo, IndexInfo, IntrospectOperation,
+    IntrospectResult, PrimaryKeyChange, ResultColumn, RowChange, RowSink, SchemaDiff,
+    StreamSummary, TableDiff, TableFields, TableInfo, ViewDiff, ViewInfo,
 };
 use crate::error::{PlenumError, Result};
+use crate::params::QueryParams;
 
 #[cfg(feature = "duckdb")]
 use crate::engine::duckdb::DuckDbEngine;
@@ -409,6 +418,632 @@ fn diff_single_view(base: &ViewInfo, target: &ViewInfo) -> ViewDiff {
     }
 }
 
+/// Number of key-hash chunks the first data diff pass digests each result into
+const DATA_DIFF_CHUNKS: usize = 1024;
+
+/// Rows each side keeps in memory during the first pass. Larger results are
+/// fetched again for the differing chunks.
+const DATA_DIFF_INLINE_ROWS: usize = 10_000;
+
+/// Dispatch a streaming query to the appropriate engine.
+async fn engine_execute_stream(
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[Value],
+    caps: &Capabilities,
+    sink: &mut dyn RowSink,
+) -> Result<StreamSummary> {
+    match config.engine {
+        #[cfg(feature = "sqlite")]
+        DatabaseType::SQLite => SqliteEngine::execute_stream(config, sql, params, caps, sink).await,
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseType::SQLite => Err(PlenumError::invalid_input(
+            "SQLite engine not enabled. Build with --features sqlite.",
+        )),
+
+        #[cfg(feature = "postgres")]
+        DatabaseType::Postgres => {
+            PostgresEngine::execute_stream(config, sql, params, caps, sink).await
+        }
+        #[cfg(not(feature = "postgres"))]
+        DatabaseType::Postgres => Err(PlenumError::invalid_input(
+            "PostgreSQL engine not enabled. Build with --features postgres.",
+        )),
+
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => MySqlEngine::execute_stream(config, sql, params, caps, sink).await,
+        #[cfg(not(feature = "mysql"))]
+        DatabaseType::MySQL => Err(PlenumError::invalid_input(
+            "MySQL engine not enabled. Build with --features mysql.",
+        )),
+
+        #[cfg(feature = "duckdb")]
+        DatabaseType::DuckDB => DuckDbEngine::execute_stream(config, sql, params, caps, sink).await,
+        #[cfg(not(feature = "duckdb"))]
+        DatabaseType::DuckDB => Err(PlenumError::invalid_input(
+            "DuckDB engine not enabled. Build with --features duckdb.",
+        )),
+    }
+}
+
+/// Compare the results of one query on two connections, matching rows by `key`.
+///
+/// `params` are bound separately for each engine, so `:name` placeholders work
+/// across engines. Each result is first digested into [`DATA_DIFF_CHUNKS`] chunks
+/// by key hash, with one order-independent digest per column; only chunks whose
+/// digests differ, or that hold a repeated key, are compared key by key from
+/// each row's key and a digest of its shared columns; full rows are then read
+/// only for the rows the diff reports. Small results are compared from memory,
+/// larger ones are fetched again for each of these passes.
+///
+/// Both queries run without a row limit; `caps.max_rows` caps each reported row
+/// list instead, so at most that many full rows per list are kept, and
+/// `caps.timeout_ms` applies to every query.
+///
+/// "added" means present in `target` but not `base`.
+/// "removed" means present in `base` but not `target`.
+///
+/// # Errors
+/// Returns `INVALID_INPUT` when `key` is empty, a key column is missing from a
+/// result or a key is not unique, and the query's own error when it is rejected
+/// or fails on either connection.
+pub async fn compute_data_diff(
+    base: &ConnectionConfig,
+    target: &ConnectionConfig,
+    sql: &str,
+    params: &QueryParams,
+    key: &[String],
+    caps: &Capabilities,
+) -> Result<DataDiff> {
+    if key.is_empty() {
+        return Err(PlenumError::invalid_input(
+            "A data diff needs at least one key column to match rows by",
+        ));
+    }
+    let (base_sql, base_params) = prepare_diff_query(base, sql, params, caps)?;
+    let (target_sql, target_params) = prepare_diff_query(target, sql, params, caps)?;
+    let exec_caps = Capabilities::new(None, caps.timeout_ms);
+
+    let mut base_digest = DigestSink::new(key, "base");
+    let mut target_digest = DigestSink::new(key, "diff-against");
+    tokio::try_join!(
+        engine_execute_stream(base, &base_sql, &base_params, &exec_caps, &mut base_digest),
+        engine_execute_stream(target, &target_sql, &target_params, &exec_caps, &mut target_digest),
+    )?;
+
+    let columns: Vec<String> =
+        base_digest.columns.iter().filter(|c| target_digest.columns.contains(c)).cloned().collect();
+    let columns_added: Vec<String> = target_digest
+        .columns
+        .iter()
+        .filter(|c| !base_digest.columns.contains(c))
+        .cloned()
+        .collect();
+    let columns_removed: Vec<String> = base_digest
+        .columns
+        .iter()
+        .filter(|c| !target_digest.columns.contains(c))
+        .cloned()
+        .collect();
+
+    let differing: BTreeSet<usize> = (0..DATA_DIFF_CHUNKS)
+        .filter(|&chunk| {
+            base_digest.chunk_rows[chunk] != target_digest.chunk_rows[chunk]
+                || base_digest.chunk_keys[chunk] != target_digest.chunk_keys[chunk]
+                || columns.iter().any(|c| {
+                    base_digest.column_digest(c, chunk) != target_digest.column_digest(c, chunk)
+                })
+        })
+        .chain(base_digest.repeated.iter().copied())
+        .chain(target_digest.repeated.iter().copied())
+        .collect();
+
+    let mut diff = DataDiff {
+        key: key.to_vec(),
+        columns,
+        columns_added,
+        columns_removed,
+        base_rows: base_digest.rows,
+        target_rows: target_digest.rows,
+        counts: DataDiffCounts::default(),
+        rows_added: Vec::new(),
+        rows_removed: Vec::new(),
+        rows_changed: Vec::new(),
+        truncated: false,
+        chunks_compared: DATA_DIFF_CHUNKS,
+        chunks_differing: differing.len(),
+    };
+
+    if !differing.is_empty() {
+        let base_keys = chunk_keys(
+            &base_digest,
+            base,
+            &base_sql,
+            &base_params,
+            &exec_caps,
+            &differing,
+            &diff.columns,
+        )
+        .await?;
+        let target_keys = chunk_keys(
+            &target_digest,
+            target,
+            &target_sql,
+            &target_params,
+            &exec_caps,
+            &differing,
+            &diff.columns,
+        )
+        .await?;
+        let reported = classify_keys(&mut diff, base_keys, target_keys, caps.max_rows);
+
+        let base_wanted = reported.removed.iter().chain(&reported.changed).map(|(_, t)| t);
+        let base_rows = fetch_rows(
+            &base_digest,
+            base,
+            &base_sql,
+            &base_params,
+            &exec_caps,
+            &base_wanted.cloned().collect(),
+        )
+        .await?;
+        let target_wanted = reported.added.iter().chain(&reported.changed).map(|(_, t)| t);
+        let target_rows = fetch_rows(
+            &target_digest,
+            target,
+            &target_sql,
+            &target_params,
+            &exec_caps,
+            &target_wanted.cloned().collect(),
+        )
+        .await?;
+        report_rows(
+            &mut diff,
+            reported,
+            &base_digest.columns,
+            &target_digest.columns,
+            base_rows,
+            target_rows,
+        );
+    }
+    diff.counts.unchanged =
+        diff.base_rows.saturating_sub(diff.counts.removed + diff.counts.changed);
+    if let Some(max_rows) = caps.max_rows {
+        diff.truncated = diff.counts.added > max_rows
+            || diff.counts.removed > max_rows
+            || diff.counts.changed > max_rows;
+    }
+    Ok(diff)
+}
+
+/// Stream one side's result into `sink`: from the rows the first pass kept,
+/// otherwise by running the query again.
+async fn replay(
+    digest: &DigestSink<'_>,
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[Value],
+    caps: &Capabilities,
+    sink: &mut dyn RowSink,
+) -> Result<()> {
+    let Some(rows) = &digest.retained else {
+        return engine_execute_stream(config, sql, params, caps, sink).await.map(|_| ());
+    };
+    sink.columns(&digest.result_columns)?;
+    for row in rows {
+        if !sink.row(row.clone())? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Key and shared-column digest of every row in the differing chunks.
+async fn chunk_keys(
+    digest: &DigestSink<'_>,
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[Value],
+    caps: &Capabilities,
+    chunks: &BTreeSet<usize>,
+    shared: &[String],
+) -> Result<KeyDigests> {
+    let mut sink = ChunkSink::new(digest.key, digest.side, chunks, shared);
+    replay(digest, config, sql, params, caps, &mut sink).await?;
+    Ok(sink.keys)
+}
+
+/// Full rows of the `wanted` keys.
+async fn fetch_rows(
+    digest: &DigestSink<'_>,
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[Value],
+    caps: &Capabilities,
+    wanted: &BTreeSet<String>,
+) -> Result<BTreeMap<String, Vec<Value>>> {
+    if wanted.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    let mut sink = FetchSink::new(digest.key, digest.side, wanted);
+    replay(digest, config, sql, params, caps, &mut sink).await?;
+    Ok(sink.rows)
+}
+
+/// Bind `params` for the connection's engine and check the query is read-only.
+fn prepare_diff_query(
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &QueryParams,
+    caps: &Capabilities,
+) -> Result<(String, Vec<Value>)> {
+    let (sql, params) = params.clone().bind(sql, config.engine)?;
+    crate::capability::validate_query(&sql, caps, config.engine)?;
+    Ok((sql, params))
+}
+
+/// Rows of the differing chunks keyed by their canonical key text: (key values,
+/// digest of the columns both results share)
+type KeyDigests = BTreeMap<String, (Vec<Value>, u64)>;
+
+/// Positions of the key columns in `columns`, matching exact names first and
+/// then case-insensitively (engines fold unquoted identifiers differently).
+fn key_positions(key: &[String], columns: &[ResultColumn], side: &str) -> Result<Vec<usize>> {
+    key.iter()
+        .map(|k| {
+            columns
+                .iter()
+                .position(|c| c.name == *k)
+                .or_else(|| columns.iter().position(|c| c.name.eq_ignore_ascii_case(k)))
+                .ok_or_else(|| {
+                    PlenumError::invalid_input(format!(
+                        "Key column '{k}' is not in the {side} result; columns are: {}",
+                        columns.iter().map(|c| c.name.as_str()).collect::<Vec<_>>().join(", ")
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Key values of `row`, normalized, with their canonical text and chunk.
+fn row_key(row: &[Value], positions: &[usize]) -> (Vec<Value>, String, u64) {
+    let values: Vec<Value> = positions.iter().map(|&i| normalize_value(&row[i])).collect();
+    let text = Value::Array(values.clone()).to_string();
+    let hash = hash_text(&text);
+    (values, text, hash)
+}
+
+fn chunk_of(key_hash: u64) -> usize {
+    // DATA_DIFF_CHUNKS fits in a u64, and the remainder fits back in a usize.
+    usize::try_from(key_hash % DATA_DIFF_CHUNKS as u64).unwrap_or(0)
+}
+
+fn hash_text(text: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Normalize a value for comparison across engines: integral floats compare
+/// equal to integers (`1.0` from a `DOUBLE` column matches `1` from an `INTEGER`).
+fn normalize_value(value: &Value) -> Value {
+    match value {
+        Value::Number(n) if n.as_i64().is_none() && n.as_u64().is_none() => match n.as_f64() {
+            #[allow(clippy::float_cmp, clippy::cast_possible_truncation)]
+            Some(f) if f.trunc() == f && f.abs() < 9.0e15 => Value::from(f as i64),
+            _ => value.clone(),
+        },
+        Value::Array(items) => Value::Array(items.iter().map(normalize_value).collect()),
+        Value::Object(map) => {
+            Value::Object(map.iter().map(|(k, v)| (k.clone(), normalize_value(v))).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+/// First-pass sink: per-chunk row counts, key digests and per-column value
+/// digests, plus the rows themselves while they fit in [`DATA_DIFF_INLINE_ROWS`].
+///
+/// Every key hash is remembered so a repeated key is caught here: its chunk is
+/// compared row by row, which rejects the duplicate by its full key text.
+struct DigestSink<'a> {
+    key: &'a [String],
+    side: &'static str,
+    result_columns: Vec<ResultColumn>,
+    columns: Vec<String>,
+    key_positions: Vec<usize>,
+    rows: usize,
+    chunk_rows: Vec<u64>,
+    chunk_keys: Vec<u64>,
+    /// `column_digests[column][chunk]`, wrapping sums of (key, value) hashes
+    column_digests: Vec<Vec<u64>>,
+    key_hashes: HashSet<u64>,
+    /// Chunks holding a key hash seen more than once
+    repeated: BTreeSet<usize>,
+    retained: Option<Vec<Vec<Value>>>,
+}
+
+impl<'a> DigestSink<'a> {
+    fn new(key: &'a [String], side: &'static str) -> Self {
+        Self {
+            key,
+            side,
+            result_columns: Vec::new(),
+            columns: Vec::new(),
+            key_positions: Vec::new(),
+            rows: 0,
+            chunk_rows: vec![0; DATA_DIFF_CHUNKS],
+            chunk_keys: vec![0; DATA_DIFF_CHUNKS],
+            column_digests: Vec::new(),
+            key_hashes: HashSet::new(),
+            repeated: BTreeSet::new(),
+            retained: Some(Vec::new()),
+        }
+    }
+
+    fn column_digest(&self, column: &str, chunk: usize) -> u64 {
+        self.columns.iter().position(|c| c == column).map_or(0, |i| self.column_digests[i][chunk])
+    }
+}
+
+impl RowSink for DigestSink<'_> {
+    fn columns(&mut self, columns: &[ResultColumn]) -> Result<()> {
+        self.key_positions = key_positions(self.key, columns, self.side)?;
+        self.result_columns = columns.to_vec();
+        self.columns = columns.iter().map(|c| c.name.clone()).collect();
+        self.column_digests = vec![vec![0; DATA_DIFF_CHUNKS]; columns.len()];
+        Ok(())
+    }
+
+    fn row(&mut self, row: Vec<Value>) -> Result<bool> {
+        let (_, key_text, key_hash) = row_key(&row, &self.key_positions);
+        let chunk = chunk_of(key_hash);
+        self.rows += 1;
+        self.chunk_rows[chunk] += 1;
+        self.chunk_keys[chunk] = self.chunk_keys[chunk].wrapping_add(key_hash);
+        if !self.key_hashes.insert(key_hash) {
+            self.repeated.insert(chunk);
+        }
+        for (digests, value) in self.column_digests.iter_mut().zip(&row) {
+            let cell = hash_text(&format!("{key_text}\u{1f}{}", normalize_value(value)));
+            digests[chunk] = digests[chunk].wrapping_add(cell);
+        }
+        if self.rows > DATA_DIFF_INLINE_ROWS {
+            self.retained = None;
+        } else if let Some(retained) = self.retained.as_mut() {
+            retained.push(row);
+        }
+        Ok(true)
+    }
+}
+
+/// Second-pass sink: the key and shared-column digest of each row in the
+/// differing chunks, without the rows themselves.
+struct ChunkSink<'a> {
+    key: &'a [String],
+    side: &'static str,
+    chunks: &'a BTreeSet<usize>,
+    shared: &'a [String],
+    key_positions: Vec<usize>,
+    shared_positions: Vec<usize>,
+    keys: KeyDigests,
+}
+
+impl<'a> ChunkSink<'a> {
+    const fn new(
+        key: &'a [String],
+        side: &'static str,
+        chunks: &'a BTreeSet<usize>,
+        shared: &'a [String],
+    ) -> Self {
+        Self {
+            key,
+            side,
+            chunks,
+            shared,
+            key_positions: Vec::new(),
+            shared_positions: Vec::new(),
+            keys: KeyDigests::new(),
+        }
+    }
+}
+
+impl RowSink for ChunkSink<'_> {
+    fn columns(&mut self, columns: &[ResultColumn]) -> Result<()> {
+        self.key_positions = key_positions(self.key, columns, self.side)?;
+        self.shared_positions =
+            self.shared.iter().filter_map(|s| columns.iter().position(|c| c.name == *s)).collect();
+        Ok(())
+    }
+
+    fn row(&mut self, row: Vec<Value>) -> Result<bool> {
+        let (values, text, hash) = row_key(&row, &self.key_positions);
+        if !self.chunks.contains(&chunk_of(hash)) {
+            return Ok(true);
+        }
+        let shared: Vec<Value> =
+            self.shared_positions.iter().map(|&i| normalize_value(&row[i])).collect();
+        let digest = hash_text(&Value::Array(shared).to_string());
+        insert_keyed(&mut self.keys, text, (values, digest), self.key, self.side)?;
+        Ok(true)
+    }
+}
+
+/// Last-pass sink: the full rows of the reported keys, stopping once all are found.
+struct FetchSink<'a> {
+    key: &'a [String],
+    side: &'static str,
+    wanted: &'a BTreeSet<String>,
+    key_positions: Vec<usize>,
+    rows: BTreeMap<String, Vec<Value>>,
+}
+
+impl<'a> FetchSink<'a> {
+    const fn new(key: &'a [String], side: &'static str, wanted: &'a BTreeSet<String>) -> Self {
+        Self { key, side, wanted, key_positions: Vec::new(), rows: BTreeMap::new() }
+    }
+}
+
+impl RowSink for FetchSink<'_> {
+    fn columns(&mut self, columns: &[ResultColumn]) -> Result<()> {
+        self.key_positions = key_positions(self.key, columns, self.side)?;
+        Ok(())
+    }
+
+    fn row(&mut self, row: Vec<Value>) -> Result<bool> {
+        if self.rows.len() == self.wanted.len() {
+            return Ok(false);
+        }
+        let (_, text, _) = row_key(&row, &self.key_positions);
+        if self.wanted.contains(&text) {
+            self.rows.insert(text, row);
+        }
+        Ok(true)
+    }
+}
+
+fn insert_keyed<T>(
+    keyed: &mut BTreeMap<String, T>,
+    text: String,
+    entry: T,
+    key: &[String],
+    side: &str,
+) -> Result<()> {
+    if keyed.contains_key(&text) {
+        return Err(PlenumError::invalid_input(format!(
+            "Key ({}) is not unique in the {side} result: {text} appears more than once",
+            key.join(", ")
+        )));
+    }
+    keyed.insert(text, entry);
+    Ok(())
+}
+
+/// Keys of the rows a diff reports, in key order: (key values, key text)
+#[derive(Default)]
+struct ReportedKeys {
+    added: Vec<(Vec<Value>, String)>,
+    removed: Vec<(Vec<Value>, String)>,
+    changed: Vec<(Vec<Value>, String)>,
+}
+
+/// Count the added, removed and changed keys of the differing chunks into `diff`
+/// and keep the first `max_rows` of each in key order.
+fn classify_keys(
+    diff: &mut DataDiff,
+    mut base: KeyDigests,
+    target: KeyDigests,
+    max_rows: Option<usize>,
+) -> ReportedKeys {
+    let mut reported = ReportedKeys::default();
+    for (text, (key_values, digest)) in target {
+        match base.remove(&text) {
+            None => reported.added.push((key_values, text)),
+            Some((_, base_digest)) if base_digest != digest => {
+                reported.changed.push((key_values, text));
+            }
+            Some(_) => {}
+        }
+    }
+    reported.removed = base.into_iter().map(|(text, (key_values, _))| (key_values, text)).collect();
+
+    diff.counts.added = reported.added.len();
+    diff.counts.removed = reported.removed.len();
+    diff.counts.changed = reported.changed.len();
+    for keys in [&mut reported.added, &mut reported.removed, &mut reported.changed] {
+        keys.sort_by(|a, b| compare_keys(&a.0, &b.0));
+        keys.truncate(max_rows.unwrap_or(usize::MAX));
+    }
+    reported
+}
+
+/// Fill the reported row lists of `diff` from the fetched rows of each side.
+fn report_rows(
+    diff: &mut DataDiff,
+    reported: ReportedKeys,
+    base_columns: &[String],
+    target_columns: &[String],
+    mut base_rows: BTreeMap<String, Vec<Value>>,
+    mut target_rows: BTreeMap<String, Vec<Value>>,
+) {
+    let as_object = |columns: &[String], row: Vec<Value>| -> Map<String, Value> {
+        columns.iter().cloned().zip(row).collect()
+    };
+    let shared: Vec<(usize, usize)> = diff
+        .columns
+        .iter()
+        .filter_map(|c| {
+            Some((
+                base_columns.iter().position(|b| b == c)?,
+                target_columns.iter().position(|t| t == c)?,
+            ))
+        })
+        .collect();
+
+    // A key missing here vanished between passes on a live database
+    let rows_changed = reported
+        .changed
+        .into_iter()
+        .filter_map(|(key_values, text)| {
+            let base_row = base_rows.remove(&text)?;
+            let target_row = target_rows.remove(&text)?;
+            let changes = shared
+                .iter()
+                .filter(|(b, t)| normalize_value(&base_row[*b]) != normalize_value(&target_row[*t]))
+                .map(|(b, t)| CellChange {
+                    column: base_columns[*b].clone(),
+                    from: base_row[*b].clone(),
+                    to: target_row[*t].clone(),
+                })
+                .collect();
+            Some(RowChange { key: diff.key.iter().cloned().zip(key_values).collect(), changes })
+        })
+        .collect();
+    diff.rows_changed = rows_changed;
+    diff.rows_added = reported
+        .added
+        .into_iter()
+        .filter_map(|(_, text)| target_rows.remove(&text))
+        .map(|row| as_object(target_columns, row))
+        .collect();
+    diff.rows_removed = reported
+        .removed
+        .into_iter()
+        .filter_map(|(_, text)| base_rows.remove(&text))
+        .map(|row| as_object(base_columns, row))
+        .collect();
+}
+
+/// Order key tuples: nulls first, numbers numerically, strings lexically.
+fn compare_keys(a: &[Value], b: &[Value]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| compare_values(x, y))
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    const fn rank(v: &Value) -> u8 {
+        match v {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) | Value::Object(_) => 4,
+        }
+    }
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => match (x.as_i64(), y.as_i64()) {
+            (Some(x), Some(y)) => x.cmp(&y),
+            _ => x.as_f64().partial_cmp(&y.as_f64()).unwrap_or(Ordering::Equal),
+        },
+        (Value::String(x), Value::String(y)) => x.cmp(y),
+        (Value::Bool(x), Value::Bool(y)) => x.cmp(y),
+        _ => rank(a).cmp(&rank(b)).then_with(|| a.to_string().cmp(&b.to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -649,4 +1284,141 @@ mod tests {
         assert!(json.contains("\"views_removed\":[]"));
         assert!(json.contains("\"views_changed\":[]"));
     }
+
+    /// Run `rows` through the second and last pass sinks for every chunk.
+    fn chunk_pass(
+        columns: &[&str],
+        shared: &[String],
+        rows: &[Vec<Value>],
+    ) -> (KeyDigests, BTreeMap<String, Vec<Value>>) {
+        let all: BTreeSet<usize> = (0..DATA_DIFF_CHUNKS).collect();
+        let key = ["id".to_string()];
+        let columns: Vec<ResultColumn> = columns
+            .iter()
+            .map(|c| ResultColumn { name: (*c).to_string(), type_name: None })
+            .collect();
+        let mut sink = ChunkSink::new(&key, "base", &all, shared);
+        sink.columns(&columns).unwrap();
+        for row in rows {
+            sink.row(row.clone()).unwrap();
+        }
+        let wanted: BTreeSet<String> = sink.keys.keys().cloned().collect();
+        let mut fetch = FetchSink::new(&key, "base", &wanted);
+        fetch.columns(&columns).unwrap();
+        for row in rows {
+            fetch.row(row.clone()).unwrap();
+        }
+        (sink.keys, fetch.rows)
+    }
+
+    fn empty_data_diff(columns: &[&str]) -> DataDiff {
+        DataDiff {
+            key: vec!["id".to_string()],
+            columns: columns.iter().map(ToString::to_string).collect(),
+            columns_added: vec![],
+            columns_removed: vec![],
+            base_rows: 0,
+            target_rows: 0,
+            counts: DataDiffCounts::default(),
+            rows_added: vec![],
+            rows_removed: vec![],
+            rows_changed: vec![],
+            truncated: false,
+            chunks_compared: DATA_DIFF_CHUNKS,
+            chunks_differing: 0,
+        }
+    }
+
+    #[test]
+    fn test_compare_rows_added_removed_changed() {
+        use serde_json::json;
+
+        let base_columns = vec!["id".to_string(), "name".to_string(), "score".to_string()];
+        let target_columns = vec!["id".to_string(), "score".to_string(), "name".to_string()];
+        let mut diff = empty_data_diff(&["id", "name", "score"]);
+        let (base, base_rows) = chunk_pass(
+            &["id", "name", "score"],
+            &diff.columns,
+            &[
+                vec![json!(1), json!("a"), json!(1)],
+                vec![json!(2), json!("b"), json!(2)],
+                vec![json!(10), json!("j"), json!(10)],
+            ],
+        );
+        let (target, target_rows) = chunk_pass(
+            &["id", "score", "name"],
+            &diff.columns,
+            &[
+                vec![json!(1), json!(1.0), json!("a")],
+                vec![json!(2), json!(2.5), json!("B")],
+                vec![json!(3), json!(3), json!("c")],
+            ],
+        );
+        let reported = classify_keys(&mut diff, base, target, None);
+        report_rows(&mut diff, reported, &base_columns, &target_columns, base_rows, target_rows);
+
+        assert_eq!(diff.counts, DataDiffCounts { added: 1, removed: 1, changed: 1, unchanged: 0 });
+        assert_eq!(diff.rows_added[0]["id"], json!(3));
+        assert_eq!(diff.rows_removed[0]["name"], json!("j"));
+        let change = &diff.rows_changed[0];
+        assert_eq!(change.key["id"], json!(2));
+        // Columns are matched by name, not position, and 1 == 1.0
+        let columns: Vec<&str> = change.changes.iter().map(|c| c.column.as_str()).collect();
+        assert_eq!(columns, ["name", "score"]);
+        assert_eq!(change.changes[0].from, json!("b"));
+        assert_eq!(change.changes[0].to, json!("B"));
+    }
+
+    #[test]
+    fn test_classify_keys_counts_all_and_keeps_first_max_rows() {
+        use serde_json::json;
+
+        let mut diff = empty_data_diff(&["id"]);
+        let ids: Vec<Vec<Value>> = [30, 4, 12, 7].iter().map(|&n| vec![json!(n)]).collect();
+        let (target, _) = chunk_pass(&["id"], &diff.columns, &ids);
+        let reported = classify_keys(&mut diff, KeyDigests::new(), target, Some(2));
+        assert_eq!(diff.counts.added, 4, "counts cover every differing row");
+        let kept: Vec<&Value> = reported.added.iter().map(|(values, _)| &values[0]).collect();
+        assert_eq!(kept, [&json!(4), &json!(7)]);
+    }
+
+    #[test]
+    fn test_duplicate_key_is_invalid_input() {
+        use serde_json::json;
+
+        let mut rows = KeyDigests::new();
+        let key = ["id".to_string()];
+        insert_keyed(&mut rows, "[1]".to_string(), (vec![json!(1)], 0), &key, "base").unwrap();
+        let err = insert_keyed(&mut rows, "[1]".to_string(), (vec![json!(1)], 0), &key, "base")
+            .unwrap_err();
+        assert_eq!(err.error_code(), "INVALID_INPUT");
+        assert!(err.message().contains("not unique in the base result"), "{}", err.message());
+    }
+
+    #[test]
+    fn test_digest_pass_marks_chunks_with_repeated_keys() {
+        use serde_json::json;
+
+        let key = ["id".to_string()];
+        let mut sink = DigestSink::new(&key, "base");
+        sink.columns(&[ResultColumn { name: "id".to_string(), type_name: None }]).unwrap();
+        for id in [1, 2, 1] {
+            sink.row(vec![json!(id)]).unwrap();
+        }
+        let (_, _, hash) = row_key(&[json!(1)], &[0]);
+        assert_eq!(sink.repeated, BTreeSet::from([chunk_of(hash)]));
+    }
+
+    #[test]
+    fn test_compare_keys_orders_numbers_numerically() {
+        use serde_json::json;
+
+        let mut keys = vec![vec![json!(10)], vec![json!(9)], vec![json!(null)], vec![json!("a")]];
+        keys.sort_by(|a, b| compare_keys(a, b));
+        assert_eq!(keys, [vec![json!(null)], vec![json!(9)], vec![json!(10)], vec![json!("a")]]);
+        assert_eq!(
+            compare_keys(&[json!(1), json!("b")], &[json!(1), json!("a")]),
+            Ordering::Greater
+        );
+    }
 }
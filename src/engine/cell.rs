@@ -0,0 +1,170 @@
+//! Cell rendering shared by all engines
+//!
+//! Every engine's value-to-JSON conversion renders binary values through
+//! `CellFormat::binary`, so `--binary` behaves the same on every engine.
+//!
+//! `--max-cell-bytes` is applied by the output layer through [`limit_cells`], after
+//! cursors, result ordering, diffs and exports have seen the real values. A cell
+//! longer than `max_cell_bytes` becomes a marker object in the JSON output:
+//!
+//! ```json
+//! {"truncated": true, "original_bytes": 48213, "sha256": "9f86d0…", "prefix": "{\"items\": [..."}
+//! ```
+//!
+//! `original_bytes` and `sha256` describe the full rendered value (the string's
+//! UTF-8 bytes, or the serialized JSON of an array / object), so two truncated
+//! cells can still be compared. Numbers, booleans and NULL are never truncated.
+
+use base64::Engine;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::engine::{BinaryFormat, Capabilities};
+
+/// Per-query cell rendering options, taken from `Capabilities`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CellFormat {
+    binary: BinaryFormat,
+}
+
+impl CellFormat {
+    pub fn new(caps: &Capabilities) -> Self {
+        Self { binary: caps.binary_format.unwrap_or_default() }
+    }
+
+    /// Whether binary values are rendered as Base64, the historical default
+    pub fn is_base64(self) -> bool {
+        self.binary == BinaryFormat::Base64
+    }
+
+    /// Render a binary value according to `--binary`.
+    pub fn binary(self, bytes: &[u8]) -> Value {
+        match self.binary {
+            BinaryFormat::Base64 => {
+                Value::String(base64::engine::general_purpose::STANDARD.encode(bytes))
+            }
+            BinaryFormat::Hex => Value::String(to_hex(bytes)),
+            BinaryFormat::Omit => Value::Null,
+            BinaryFormat::LengthOnly => Value::Number(bytes.len().into()),
+        }
+    }
+}
+
+/// Replace every cell of `rows` longer than `max_cell_bytes` with a truncation marker.
+pub fn limit_cells(rows: &mut [Vec<Value>], max_cell_bytes: usize) {
+    for cell in rows.iter_mut().flatten() {
+        let value = std::mem::take(cell);
+        *cell = limit(value, max_cell_bytes);
+    }
+}
+
+/// Serialized size of `row` once its cells are limited to `max_cell_bytes`, as
+/// `--max-bytes` counts it.
+#[must_use]
+pub fn row_bytes(row: &[Value], max_cell_bytes: Option<usize>) -> usize {
+    let serialized = match max_cell_bytes {
+        Some(max) => {
+            let limited: Vec<Value> = row.iter().map(|cell| limit(cell.clone(), max)).collect();
+            serde_json::to_string(&limited)
+        }
+        None => serde_json::to_string(row),
+    };
+    serialized.map_or(0, |s| s.len())
+}
+
+/// Replace a rendered cell longer than `max` bytes with a truncation marker.
+fn limit(value: Value, max: usize) -> Value {
+    let text = match &value {
+        Value::String(s) if s.len() > max => s.clone(),
+        Value::Array(_) | Value::Object(_) => {
+            let serialized = value.to_string();
+            if serialized.len() <= max {
+                return value;
+            }
+            serialized
+        }
+        _ => return value,
+    };
+    truncation_marker(&text, max)
+}
+
+/// Marker object standing in for a cell longer than `max` bytes
+fn truncation_marker(text: &str, max: usize) -> Value {
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    serde_json::json!({
+        "truncated": true,
+        "original_bytes": text.len(),
+        "sha256": to_hex(&Sha256::digest(text.as_bytes())),
+        "prefix": &text[..end],
+    })
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    bytes.iter().fold(String::with_capacity(bytes.len() * 2), |mut out, b| {
+        let _ = write!(out, "{b:02x}");
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn format(binary: Option<BinaryFormat>) -> CellFormat {
+        CellFormat::new(&Capabilities { binary_format: binary, ..Capabilities::default() })
+    }
+
+    #[test]
+    fn test_binary_formats() {
+        let bytes = [0xde, 0xad, 0xbe, 0xef];
+        assert_eq!(format(None).binary(&bytes), json!("3q2+7w=="));
+        assert_eq!(format(Some(BinaryFormat::Hex)).binary(&bytes), json!("deadbeef"));
+        assert_eq!(format(Some(BinaryFormat::Omit)).binary(&bytes), Value::Null);
+        assert_eq!(format(Some(BinaryFormat::LengthOnly)).binary(&bytes), json!(4));
+    }
+
+    #[test]
+    fn test_limit_truncates_long_text_with_marker() {
+        assert_eq!(limit(json!("abcd"), 4), json!("abcd"));
+        assert_eq!(limit(json!(123_456_789), 4), json!(123_456_789));
+
+        let marker = limit(json!("hello world"), 4);
+        assert_eq!(marker["truncated"], true);
+        assert_eq!(marker["original_bytes"], 11);
+        assert_eq!(marker["prefix"], "hell");
+        assert_eq!(
+            marker["sha256"],
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+    }
+
+    #[test]
+    fn test_limit_measures_json_serialized_and_respects_char_boundaries() {
+        let marker = limit(json!({"items": [1, 2, 3]}), 8);
+        assert_eq!(marker["original_bytes"], 17);
+        assert_eq!(marker["prefix"], "{\"items\"");
+
+        // "é" is two bytes; the prefix never splits it
+        let marker = limit(json!("aéb"), 2);
+        assert_eq!(marker["prefix"], "a");
+        assert_eq!(marker["original_bytes"], 4);
+    }
+
+    #[test]
+    fn test_row_bytes_counts_markers_not_values() {
+        let mut rows = vec![vec![json!(1), json!("x".repeat(5000))]];
+        assert_eq!(row_bytes(&rows[0], None), 5006);
+        let limited = row_bytes(&rows[0], Some(16));
+        assert!(limited < 200, "marker size, got {limited}");
+
+        limit_cells(&mut rows, 16);
+        assert_eq!(rows[0][0], json!(1));
+        assert_eq!(rows[0][1]["original_bytes"], 5000);
+        assert_eq!(serde_json::to_string(&rows[0]).unwrap().len(), limited);
+    }
+}
@@ -0,0 +1,147 @@
+//! Cancellation and progress of one in-flight query
+//!
+//! The MCP server hands each tool call a `QueryControl` through
+//! `Capabilities::control`. Engines register a cancel hook as soon as they have a
+//! connection: `SQLite` and `DuckDB` fire the connection's interrupt handle,
+//! `PostgreSQL` sends a cancel request with the session's cancel token and `MySQL`
+//! issues `KILL QUERY` from a second connection. `RowFeed` counts every fetched row
+//! so the server can report progress while the query runs.
+//!
+//! The CLI never cancels: without a control, engines behave exactly as before.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::error::{PlenumError, Result};
+
+type CancelHook = Box<dyn FnOnce() + Send>;
+
+/// Shared cancel flag, cancel hooks and row counter of one tool call
+#[derive(Clone, Default)]
+pub struct QueryControl {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    rows_read: AtomicU64,
+    hooks: Mutex<Vec<CancelHook>>,
+}
+
+impl fmt::Debug for QueryControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryControl")
+            .field("cancelled", &self.is_cancelled())
+            .field("rows_read", &self.rows_read())
+            .finish_non_exhaustive()
+    }
+}
+
+impl QueryControl {
+    /// Create a control that is not cancelled and has read no rows
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the query: run every registered hook once. Later calls do nothing.
+    pub fn cancel(&self) {
+        if self.inner.cancelled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let hooks = std::mem::take(&mut *self.hooks());
+        for hook in hooks {
+            hook();
+        }
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Register `hook` to stop the engine's running statement on cancel.
+    ///
+    /// Runs `hook` right away when the query is already cancelled. Hooks may fire
+    /// after their statement has finished, so they must be harmless on an idle or
+    /// closed connection.
+    pub fn on_cancel(&self, hook: impl FnOnce() + Send + 'static) {
+        let mut hooks = self.hooks();
+        if self.is_cancelled() {
+            drop(hooks);
+            hook();
+        } else {
+            hooks.push(Box::new(hook));
+        }
+    }
+
+    /// Fail with `QUERY_FAILED` once the query has been cancelled; engines call this
+    /// after registering their hook, before starting the statement.
+    ///
+    /// # Errors
+    ///
+    /// Returns `QUERY_FAILED` when the query was cancelled.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(PlenumError::query_failed("Query cancelled by the client"));
+        }
+        Ok(())
+    }
+
+    /// Rows fetched so far, including rows skipped by `offset`
+    #[must_use]
+    pub fn rows_read(&self) -> u64 {
+        self.inner.rows_read.load(Ordering::Relaxed)
+    }
+
+    /// Count one fetched row
+    pub(crate) fn add_row(&self) {
+        self.inner.rows_read.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn hooks(&self) -> std::sync::MutexGuard<'_, Vec<CancelHook>> {
+        self.inner.hooks.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_runs_hooks_once() {
+        let control = QueryControl::new();
+        let fired = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&fired);
+        control.on_cancel(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        assert!(control.check().is_ok());
+
+        let server_side = control.clone();
+        server_side.cancel();
+        control.cancel();
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+        assert!(control.is_cancelled());
+        assert_eq!(control.check().unwrap_err().error_code(), "QUERY_FAILED");
+
+        // A hook registered after cancellation runs immediately
+        let counter = Arc::clone(&fired);
+        control.on_cancel(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        assert_eq!(fired.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_rows_read_is_shared() {
+        let control = QueryControl::new();
+        let feed_side = control.clone();
+        feed_side.add_row();
+        feed_side.add_row();
+        assert_eq!(control.rows_read(), 2);
+    }
+}
@@ -7,6 +7,9 @@
 //! - In-memory connections (`:memory:`)
 //! - Schema introspection via `DuckDB` catalog functions (`duckdb_tables()`,
 //!   `duckdb_views()`, `duckdb_columns()`, `duckdb_constraints()`, `duckdb_indexes()`)
+//! - Catalog objects beyond tables and views: sequences (`duckdb_sequences()`),
+//!   macros (`duckdb_functions()`), user types (`duckdb_types()`), CHECK/UNIQUE
+//!   constraints and attached databases (`duckdb_databases()`)
 //! - Capability-enforced query execution
 //!
 //! # Implementation Notes
@@ -25,13 +28,20 @@ use duckdb::types::{TimeUnit, Value};
 use duckdb::{params_from_iter, AccessMode, Config, Connection};
 use std::time::{Duration, Instant};
 
-use crate::capability::{strip_explain_prefix, validate_query};
+use crate::capability::{strip_explain_prefix, structured_explain_analyze, validate_query};
+use crate::engine::cell::CellFormat;
+use crate::engine::stream::{cancelled_or, reject_structured_explain, RowCollector, RowFeed};
 use crate::engine::{
-    is_explain_query, Capabilities, ColumnInfo, ConnectionConfig, ConnectionInfo, DatabaseEngine,
-    DatabaseType, ExplainFormat, ExplainPlanNode, ForeignKeyInfo, IndexInfo, IndexSummary,
-    IntrospectOperation, IntrospectResult, QueryResult, TableFields, TableInfo, ViewInfo,
+    filter_settings, is_explain_query, profile, AttachedDatabaseInfo, Capabilities, ColumnInfo,
+    ConnectionConfig, ConnectionInfo, ConstraintInfo, CustomTypeInfo, DatabaseEngine, DatabaseType,
+    ExplainFormat, ExplainPlanNode, FileAccess, ForeignKeyInfo, IndexInfo, IndexSummary,
+    InspectOperation, InspectResult, IntrospectOperation, IntrospectResult, MacroInfo,
+    PlanOperation, PrivilegeReport, PrivilegeWarning, QueryControl, QueryResult, ResultColumn,
+    RowSink, SampleOptions, SequenceInfo, SettingInfo, SizeOptions, SizeReport, StreamSummary,
+    TableFields, TableInfo, TableSize, ViewInfo,
 };
 use crate::error::{PlenumError, Result};
+use crate::params::ParamValue;
 
 /// `DuckDB` database engine implementation
 pub struct DuckDbEngine;
@@ -57,9 +67,16 @@ impl DatabaseEngine for DuckDbEngine {
             server_info: format!("DuckDB {version}"),
             connected_database: db_name,
             user: "N/A".to_string(), // DuckDB has no user concept
+            warnings: Vec::new(),
         })
     }
 
+    async fn audit_privileges(_config: &ConnectionConfig) -> Result<Vec<PrivilegeWarning>> {
+        // No users or grants: access is the file's permissions, and Plenum always
+        // opens the file read-only, so there is no credential to audit.
+        Ok(Vec::new())
+    }
+
     async fn introspect(
         config: &ConnectionConfig,
         operation: &IntrospectOperation,
@@ -76,6 +93,21 @@ impl DatabaseEngine for DuckDbEngine {
         }
 
         let schema_name = schema.unwrap_or(DEFAULT_SCHEMA);
+
+        // Sampling and profiling open their own single-threaded connection, so there
+        // is no catalog connection to open here.
+        if let IntrospectOperation::ProfileTable { name, options } = operation {
+            let sample =
+                sample_table_duckdb(&file_path, schema_name, name, &options.sample_options())?;
+            let profile = profile::profile_from_sample(name, Some(schema_name), &sample, options);
+            return Ok(IntrospectResult::TableProfile { profile });
+        }
+
+        if let IntrospectOperation::SampleTable { name, options } = operation {
+            let sample = sample_table_duckdb(&file_path, schema_name, name, options)?;
+            return Ok(IntrospectResult::TableSample { sample });
+        }
+
         let conn = open_connection(&file_path)?;
 
         let result = match operation {
@@ -92,41 +124,50 @@ impl DatabaseEngine for DuckDbEngine {
             IntrospectOperation::ViewDetails { name } => {
                 get_view_details_duckdb(&conn, schema_name, name)?
             }
+            IntrospectOperation::ListSequences => list_sequences_duckdb(&conn, schema_name)?,
+            IntrospectOperation::ListMacros => list_macros_duckdb(&conn, schema_name)?,
+            IntrospectOperation::ListTypes => list_types_duckdb(&conn, schema_name)?,
+            IntrospectOperation::ListConstraints { table } => {
+                list_constraints_duckdb(&conn, schema_name, table.as_deref())?
+            }
+            IntrospectOperation::ListAttachedDatabases => list_attached_databases_duckdb(&conn)?,
+            IntrospectOperation::ListPrivileges { .. } => list_privileges_duckdb(&file_path)?,
+            // ART indexes keep no usage counters or selectivity statistics to diagnose
+            IntrospectOperation::IndexHealth { .. } => {
+                return Err(PlenumError::invalid_input(format!(
+                    "DuckDB does not support {} operation (no index usage statistics)",
+                    operation.name()
+                )));
+            }
+            IntrospectOperation::Sizes { options } => sizes_duckdb(&conn, schema_name, options)?,
+            IntrospectOperation::Settings { pattern } => {
+                settings_duckdb(&conn, pattern.as_deref())?
+            }
+            IntrospectOperation::ProfileTable { .. } | IntrospectOperation::SampleTable { .. } => {
+                unreachable!("handled above")
+            }
         };
 
         Ok(result)
     }
 
+    async fn inspect(
+        _config: &ConnectionConfig,
+        operation: &InspectOperation,
+    ) -> Result<InspectResult> {
+        Err(PlenumError::invalid_input(format!(
+            "DuckDB does not support {} inspection (embedded database has no server to inspect)",
+            operation.name()
+        )))
+    }
+
     async fn execute(
         config: &ConnectionConfig,
         query: &str,
         params: &[serde_json::Value],
         caps: &Capabilities,
     ) -> Result<QueryResult> {
-        // Validate query against capabilities before opening any connection
-        validate_query(query, caps, DatabaseType::DuckDB)?;
-
-        let file_path = extract_file_path(config)?;
-        let conn = open_connection(&file_path)?;
-
-        // Interrupt-based statement timeout: obtain a handle before the query
-        // starts, then spawn a thread that fires the interrupt after timeout_ms.
-        // DuckDB checks the interrupt flag during execution, cancelling the
-        // query server-side rather than just abandoning the wait.
-        //
-        // The timer thread is detached and may outlive the connection: if the
-        // query finishes early, `handle.interrupt()` fires against an
-        // already-dropped `Connection`. This is safe by documented crate
-        // contract — `InterruptHandle` holds a mutex-guarded connection
-        // pointer that is nulled when the connection drops, making a late
-        // `interrupt()` a no-op (duckdb crate, `InterruptHandle::interrupt`).
-        if let Some(timeout_ms) = caps.timeout_ms {
-            let handle = conn.interrupt_handle();
-            std::thread::spawn(move || {
-                std::thread::sleep(Duration::from_millis(timeout_ms));
-                handle.interrupt();
-            });
-        }
+        let conn = open_query_connection(config, query, caps)?;
 
         // Structured explain path: rewrite to EXPLAIN (FORMAT JSON), normalize.
         if caps.explain_format == Some(ExplainFormat::Structured) {
@@ -136,9 +177,10 @@ impl DatabaseEngine for DuckDbEngine {
                      non-EXPLAIN queries must omit this flag",
                 ));
             }
+            let analyze = structured_explain_analyze(query, DatabaseType::DuckDB)?;
             let inner = strip_explain_prefix(query);
             let start = Instant::now();
-            let plan = execute_structured_explain_duckdb(&conn, &inner)?;
+            let plan = execute_structured_explain_duckdb(&conn, &inner, params, analyze)?;
             let elapsed = start.elapsed();
             return Ok(QueryResult {
                 columns: Vec::new(),
@@ -147,19 +189,48 @@ impl DatabaseEngine for DuckDbEngine {
                 execution_ms: elapsed.as_millis() as u64,
                 rows_truncated: false,
                 truncated_by: None,
-                plan: Some(plan),
+                plan: Some(Box::new(plan)),
+                next_cursor: None,
+                ordering: None,
             });
         }
 
-        let start = Instant::now();
-        let mut result = execute_query(&conn, query, params, caps)?;
-        let elapsed = start.elapsed();
-        result.execution_ms = elapsed.as_millis() as u64;
+        let mut rows = RowCollector::default();
+        let summary = execute_query(&conn, query, params, caps, &mut rows)
+            .map_err(|e| cancelled_or(caps, e))?;
+        Ok(rows.into_result(summary))
+    }
 
-        Ok(result)
+    async fn execute_stream(
+        config: &ConnectionConfig,
+        query: &str,
+        params: &[serde_json::Value],
+        caps: &Capabilities,
+        sink: &mut dyn RowSink,
+    ) -> Result<StreamSummary> {
+        reject_structured_explain(caps)?;
+        let conn = open_query_connection(config, query, caps)?;
+        execute_query(&conn, query, params, caps, sink).map_err(|e| cancelled_or(caps, e))
     }
 }
 
+/// Validate `query` before opening any connection, then open it with the
+/// `caps.timeout_ms` interrupt timer armed.
+fn open_query_connection(
+    config: &ConnectionConfig,
+    query: &str,
+    caps: &Capabilities,
+) -> Result<Connection> {
+    validate_query(query, caps, DatabaseType::DuckDB)?;
+
+    let file_path = extract_file_path(config)?;
+    let conn = open_connection(&file_path)?;
+
+    arm_interrupt_timer(&conn, caps.timeout_ms);
+    arm_cancel(&conn, caps.control.as_ref())?;
+    Ok(conn)
+}
+
 /// Validate the config targets `DuckDB` and extract the file path as a string.
 fn extract_file_path(config: &ConnectionConfig) -> Result<String> {
     if config.engine != DatabaseType::DuckDB {
@@ -214,6 +285,38 @@ fn open_connection(path: &str) -> Result<Connection> {
         .map_err(|e| PlenumError::connection_failed(format!("Failed to open DuckDB database: {e}")))
 }
 
+/// Interrupt-based statement timeout: obtain a handle before the query
+/// starts, then spawn a thread that fires the interrupt after `timeout_ms`.
+/// `DuckDB` checks the interrupt flag during execution, cancelling the
+/// query server-side rather than just abandoning the wait.
+///
+/// The timer thread is detached and may outlive the connection: if the
+/// query finishes early, `handle.interrupt()` fires against an
+/// already-dropped `Connection`. This is safe by documented crate
+/// contract — `InterruptHandle` holds a mutex-guarded connection
+/// pointer that is nulled when the connection drops, making a late
+/// `interrupt()` a no-op (duckdb crate, `InterruptHandle::interrupt`).
+fn arm_interrupt_timer(conn: &Connection, timeout_ms: Option<u64>) {
+    if let Some(timeout_ms) = timeout_ms {
+        let handle = conn.interrupt_handle();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(timeout_ms));
+            handle.interrupt();
+        });
+    }
+}
+
+/// Fire the interrupt when the MCP client cancels the query. Like the timer, a
+/// late cancel against a dropped connection is a no-op.
+fn arm_cancel(conn: &Connection, control: Option<&QueryControl>) -> Result<()> {
+    if let Some(control) = control {
+        let handle = conn.interrupt_handle();
+        control.on_cancel(move || handle.interrupt());
+        control.check()?;
+    }
+    Ok(())
+}
+
 /// Returns true when a duckdb error was caused by the interrupt handle firing.
 fn is_duckdb_interrupt(e: &duckdb::Error) -> bool {
     e.to_string().to_uppercase().contains("INTERRUPT")
@@ -403,6 +506,404 @@ fn get_view_details_duckdb(
     Ok(IntrospectResult::ViewDetails { view })
 }
 
+/// List sequences in a schema via `duckdb_sequences()`
+fn list_sequences_duckdb(conn: &Connection, schema: &str) -> Result<IntrospectResult> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT sequence_name, schema_name, start_value, min_value, max_value,
+                    increment_by, cycle, last_value
+             FROM duckdb_sequences()
+             WHERE database_name = current_database() AND schema_name = ?
+             ORDER BY sequence_name",
+        )
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to prepare sequence query: {e}"))
+        })?;
+
+    let sequences: Vec<SequenceInfo> = stmt
+        .query_map(params_from_iter([schema].iter()), |row| {
+            Ok(SequenceInfo {
+                name: row.get(0)?,
+                schema: Some(row.get(1)?),
+                start_value: row.get(2)?,
+                min_value: row.get(3)?,
+                max_value: row.get(4)?,
+                increment_by: row.get(5)?,
+                cycle: row.get(6)?,
+                last_value: row.get::<_, Option<i64>>(7)?,
+            })
+        })
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to query sequences: {e}"))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to collect sequences: {e}"))
+        })?;
+
+    Ok(IntrospectResult::SequenceList { sequences })
+}
+
+/// Report the database size from `pragma_database_size()` and per-table row estimates from
+/// `duckdb_tables()`. `DuckDB` stores tables in shared blocks and reports no per-table bytes.
+fn sizes_duckdb(
+    conn: &Connection,
+    schema: &str,
+    options: &SizeOptions,
+) -> Result<IntrospectResult> {
+    let database_bytes: Option<i64> = conn
+        .query_row(
+            "SELECT max(used_blocks * block_size) FROM pragma_database_size()
+             WHERE database_name = current_database()",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to query database size: {e}"))
+        })?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT table_name, schema_name, estimated_size FROM duckdb_tables()
+             WHERE NOT internal AND database_name = current_database() AND schema_name = ?",
+        )
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to prepare table size query: {e}"))
+        })?;
+    let tables: Vec<TableSize> = stmt
+        .query_map(params_from_iter([schema].iter()), |row| {
+            Ok(TableSize {
+                name: row.get(0)?,
+                schema: Some(row.get(1)?),
+                table_bytes: None,
+                index_bytes: None,
+                toast_bytes: None,
+                total_bytes: None,
+                row_estimate: row.get::<_, Option<i64>>(2)?.and_then(|n| u64::try_from(n).ok()),
+            })
+        })
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to query table sizes: {e}"))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to collect table sizes: {e}"))
+        })?;
+
+    let notes = vec![
+        "DuckDB reports no per-table bytes; tables are ranked by duckdb_tables().estimated_size \
+         (estimated rows)"
+            .to_string(),
+    ];
+    Ok(IntrospectResult::Sizes {
+        sizes: SizeReport::paginate(
+            database_bytes.and_then(|n| u64::try_from(n).ok()),
+            tables,
+            options,
+            notes,
+        ),
+    })
+}
+
+/// Report settings from `duckdb_settings()`.
+///
+/// `LOCAL` settings apply to the connection and are session-changeable; `GLOBAL` ones
+/// affect the whole database instance. Units are embedded in the values (e.g. `1.5 GiB`).
+fn settings_duckdb(conn: &Connection, pattern: Option<&str>) -> Result<IntrospectResult> {
+    let mut stmt =
+        conn.prepare("SELECT name, value, scope FROM duckdb_settings()").map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to prepare settings query: {e}"))
+        })?;
+    let settings: Vec<SettingInfo> = stmt
+        .query_map([], |row| {
+            Ok(SettingInfo {
+                name: row.get(0)?,
+                value: row.get(1)?,
+                unit: None,
+                source: None,
+                session_changeable: row.get::<_, Option<String>>(2)?.map(|scope| scope == "LOCAL"),
+            })
+        })
+        .map_err(|e| PlenumError::engine_error("duckdb", format!("Failed to query settings: {e}")))?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to collect settings: {e}"))
+        })?;
+
+    Ok(IntrospectResult::Settings { settings: filter_settings(settings, pattern) })
+}
+
+/// List user-defined scalar and table macros via `duckdb_functions()`
+///
+/// Built-in macros shipped with `DuckDB` are flagged `internal` and excluded.
+fn list_macros_duckdb(conn: &Connection, schema: &str) -> Result<IntrospectResult> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT function_name, schema_name, function_type,
+                    parameters, macro_definition
+             FROM duckdb_functions()
+             WHERE NOT internal
+               AND function_type IN ('macro', 'table_macro')
+               AND database_name = current_database()
+               AND schema_name = ?
+             ORDER BY function_name",
+        )
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to prepare macro query: {e}"))
+        })?;
+
+    let rows: Vec<(String, String, String, Value, Option<String>)> = stmt
+        .query_map(params_from_iter([schema].iter()), |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+        })
+        .map_err(|e| PlenumError::engine_error("duckdb", format!("Failed to query macros: {e}")))?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to collect macros: {e}"))
+        })?;
+
+    let macros = rows
+        .into_iter()
+        .map(|(name, macro_schema, kind, parameters, definition)| {
+            Ok(MacroInfo {
+                name,
+                schema: Some(macro_schema),
+                kind,
+                parameters: name_list(&parameters)?,
+                definition,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(IntrospectResult::MacroList { macros })
+}
+
+/// List user-defined types (enums, structs, aliases) via `duckdb_types()`
+///
+/// `duckdb_types()` reports only the top-level logical type, so the full
+/// definition (struct members, list element types) is resolved with
+/// `typeof(NULL::<type>)`.
+fn list_types_duckdb(conn: &Connection, schema: &str) -> Result<IntrospectResult> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT type_name, schema_name, logical_type, labels
+             FROM duckdb_types()
+             WHERE NOT internal
+               AND database_name = current_database()
+               AND schema_name = ?
+             ORDER BY type_name",
+        )
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to prepare type query: {e}"))
+        })?;
+
+    let rows: Vec<(String, String, String, Value)> = stmt
+        .query_map(params_from_iter([schema].iter()), |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+        .map_err(|e| PlenumError::engine_error("duckdb", format!("Failed to query types: {e}")))?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to collect types: {e}"))
+        })?;
+
+    let mut types = Vec::with_capacity(rows.len());
+    for (name, type_schema, logical_type, labels) in rows {
+        let sql = format!(
+            "SELECT typeof(CAST(NULL AS {}.{}))",
+            quote_identifier(&type_schema),
+            quote_identifier(&name)
+        );
+        let definition: String = conn.query_row(&sql, [], |row| row.get(0)).map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to resolve type '{name}': {e}"))
+        })?;
+
+        types.push(CustomTypeInfo {
+            name,
+            schema: Some(type_schema),
+            logical_type,
+            definition,
+            labels: name_list(&labels)?,
+        });
+    }
+
+    Ok(IntrospectResult::TypeList { types })
+}
+
+/// List CHECK and UNIQUE constraints (optionally filtered by table)
+///
+/// PRIMARY KEY and FOREIGN KEY constraints are reported through table details
+/// and are not repeated here. NOT NULL constraints are surfaced as column
+/// nullability.
+fn list_constraints_duckdb(
+    conn: &Connection,
+    schema: &str,
+    table_filter: Option<&str>,
+) -> Result<IntrospectResult> {
+    let (sql, params): (&str, Vec<&str>) = if let Some(table) = table_filter {
+        (
+            "SELECT constraint_name, table_name, constraint_type,
+                    constraint_column_names, constraint_text
+             FROM duckdb_constraints()
+             WHERE constraint_type IN ('CHECK', 'UNIQUE')
+               AND database_name = current_database()
+               AND schema_name = ? AND table_name = ?
+             ORDER BY table_name, constraint_index",
+            vec![schema, table],
+        )
+    } else {
+        (
+            "SELECT constraint_name, table_name, constraint_type,
+                    constraint_column_names, constraint_text
+             FROM duckdb_constraints()
+             WHERE constraint_type IN ('CHECK', 'UNIQUE')
+               AND database_name = current_database()
+               AND schema_name = ?
+             ORDER BY table_name, constraint_index",
+            vec![schema],
+        )
+    };
+
+    let mut stmt = conn.prepare(sql).map_err(|e| {
+        PlenumError::engine_error("duckdb", format!("Failed to prepare constraint query: {e}"))
+    })?;
+
+    let rows: Vec<(String, String, String, Value, Option<String>)> = stmt
+        .query_map(params_from_iter(params.iter()), |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+        })
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to query constraints: {e}"))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to collect constraints: {e}"))
+        })?;
+
+    let constraints = rows
+        .into_iter()
+        .map(|(name, table, constraint_type, columns, definition)| {
+            Ok(ConstraintInfo {
+                name,
+                table,
+                constraint_type,
+                columns: name_list(&columns)?,
+                definition,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(IntrospectResult::ConstraintList { constraints })
+}
+
+/// List attached databases with their path, storage type and access mode.
+///
+/// Unlike `ListDatabases`, this reports per-database details so agents can see
+/// which catalogs are file-backed, in-memory or attached from another engine.
+fn list_attached_databases_duckdb(conn: &Connection) -> Result<IntrospectResult> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT database_name, path, type, readonly
+             FROM duckdb_databases()
+             WHERE NOT internal
+             ORDER BY database_name",
+        )
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to prepare database query: {e}"))
+        })?;
+
+    let databases: Vec<AttachedDatabaseInfo> = stmt
+        .query_map([], |row| {
+            Ok(AttachedDatabaseInfo {
+                name: row.get(0)?,
+                path: row.get::<_, Option<String>>(1)?,
+                database_type: row.get(2)?,
+                readonly: row.get(3)?,
+            })
+        })
+        .map_err(|e| {
+            PlenumError::engine_error("duckdb", format!("Failed to query attached databases: {e}"))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| {
+            PlenumError::engine_error(
+                "duckdb",
+                format!("Failed to collect attached databases: {e}"),
+            )
+        })?;
+
+    Ok(IntrospectResult::AttachedDatabaseList { databases })
+}
+
+/// Report file-level access: `DuckDB` has no users or grants, so the file's
+/// permissions are the only access control.
+fn list_privileges_duckdb(file_path: &str) -> Result<IntrospectResult> {
+    let metadata = std::fs::metadata(file_path).map_err(|e| {
+        PlenumError::engine_error("duckdb", format!("Failed to read file metadata: {e}"))
+    })?;
+    Ok(IntrospectResult::Privileges {
+        privileges: PrivilegeReport {
+            user: "N/A".to_string(),
+            scope: "file".to_string(),
+            roles: Vec::new(),
+            tables: Vec::new(),
+            grants: Vec::new(),
+            file: Some(FileAccess {
+                path: file_path.to_string(),
+                writable: !metadata.permissions().readonly(),
+            }),
+        },
+    })
+}
+
+/// Draw a reproducible sample with `USING SAMPLE n ROWS (reservoir, seed)`.
+///
+/// `DuckDB` only guarantees a seeded sample is repeatable when the query runs
+/// single-threaded, so the sampling connection is pinned to one thread.
+fn sample_table_duckdb(
+    file_path: &str,
+    schema: &str,
+    table_name: &str,
+    options: &SampleOptions,
+) -> Result<QueryResult> {
+    let conn = open_connection(file_path)?;
+    conn.execute_batch("SET threads = 1").map_err(|e| {
+        PlenumError::engine_error("duckdb", format!("Failed to pin sampling to one thread: {e}"))
+    })?;
+    arm_interrupt_timer(&conn, options.timeout_ms);
+
+    let sql = format!(
+        "SELECT * FROM {}.{} USING SAMPLE {} ROWS (reservoir, {})",
+        quote_identifier(schema),
+        quote_identifier(table_name),
+        options.size,
+        options.seed
+    );
+    let caps = Capabilities::new(Some(options.size), options.timeout_ms);
+
+    let mut rows = RowCollector::default();
+    let summary = execute_query(&conn, &sql, &[], &caps, &mut rows)?;
+
+    Ok(rows.into_result(summary))
+}
+
+/// Names in a `VARCHAR[]` catalog column, read as a list so a name containing a
+/// comma stays whole; NULL is an empty list.
+fn name_list(value: &Value) -> Result<Vec<String>> {
+    match duckdb_value_to_json(value, CellFormat::default())? {
+        serde_json::Value::Array(items) => Ok(items
+            .into_iter()
+            .filter_map(|item| if let serde_json::Value::String(s) = item { Some(s) } else { None })
+            .collect()),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Quote an identifier for interpolation into `DuckDB` SQL.
+fn quote_identifier(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
 /// Get column info for a table or view via `duckdb_columns()`
 fn get_columns(conn: &Connection, schema: &str, table_name: &str) -> Result<Vec<ColumnInfo>> {
     let mut stmt = conn
@@ -464,7 +965,7 @@ fn introspect_table(conn: &Connection, schema: &str, table_name: &str) -> Result
     // Primary key from duckdb_constraints()
     let mut pk_stmt = conn
         .prepare(
-            "SELECT array_to_string(constraint_column_names, ',')
+            "SELECT constraint_column_names
              FROM duckdb_constraints()
              WHERE schema_name = ? AND table_name = ? AND constraint_type = 'PRIMARY KEY'",
         )
@@ -472,7 +973,7 @@ fn introspect_table(conn: &Connection, schema: &str, table_name: &str) -> Result
             PlenumError::engine_error("duckdb", format!("Failed to prepare pk query: {e}"))
         })?;
 
-    let pk_raw: Option<String> = pk_stmt
+    let pk_raw: Option<Value> = pk_stmt
         .query_row(params_from_iter([schema, table_name].iter()), |row| row.get(0))
         .map(Some)
         .or_else(|e| {
@@ -486,16 +987,12 @@ fn introspect_table(conn: &Connection, schema: &str, table_name: &str) -> Result
             }
         })?;
 
-    let primary_key = pk_raw.map(|raw| {
-        raw.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect::<Vec<_>>()
-    });
+    let primary_key = pk_raw.as_ref().map(name_list).transpose()?;
 
     // Foreign keys from duckdb_constraints()
     let mut fk_stmt = conn
         .prepare(
-            "SELECT array_to_string(constraint_column_names, ','),
-                    referenced_table,
-                    array_to_string(referenced_column_names, ',')
+            "SELECT constraint_column_names, referenced_table, referenced_column_names
              FROM duckdb_constraints()
              WHERE schema_name = ? AND table_name = ? AND constraint_type = 'FOREIGN KEY'
              ORDER BY constraint_index",
@@ -504,7 +1001,7 @@ fn introspect_table(conn: &Connection, schema: &str, table_name: &str) -> Result
             PlenumError::engine_error("duckdb", format!("Failed to prepare fk query: {e}"))
         })?;
 
-    let fk_rows: Vec<(String, String, String)> = fk_stmt
+    let fk_rows: Vec<(Value, String, Value)> = fk_stmt
         .query_map(params_from_iter([schema, table_name].iter()), |row| {
             Ok((row.get(0)?, row.get(1)?, row.get(2)?))
         })
@@ -519,13 +1016,15 @@ fn introspect_table(conn: &Connection, schema: &str, table_name: &str) -> Result
     let foreign_keys: Vec<ForeignKeyInfo> = fk_rows
         .into_iter()
         .enumerate()
-        .map(|(i, (cols, ref_table, ref_cols))| ForeignKeyInfo {
-            name: format!("fk_{table_name}_{i}"),
-            columns: cols.split(',').map(|s| s.trim().to_string()).collect(),
-            referenced_table: ref_table,
-            referenced_columns: ref_cols.split(',').map(|s| s.trim().to_string()).collect(),
+        .map(|(i, (cols, ref_table, ref_cols))| {
+            Ok(ForeignKeyInfo {
+                name: format!("fk_{table_name}_{i}"),
+                columns: name_list(&cols)?,
+                referenced_table: ref_table,
+                referenced_columns: name_list(&ref_cols)?,
+            })
         })
-        .collect();
+        .collect::<Result<_>>()?;
 
     // Indexes from duckdb_indexes()
     let IntrospectResult::IndexList { indexes: index_summaries } =
@@ -551,32 +1050,51 @@ fn introspect_table(conn: &Connection, schema: &str, table_name: &str) -> Result
     })
 }
 
-/// Run `EXPLAIN (FORMAT JSON)` on `inner_sql` and normalize the result into an
-/// `ExplainPlanNode` tree.
+/// Run `EXPLAIN (FORMAT JSON)` on `inner_sql` with `params` bound and normalize the
+/// result into an `ExplainPlanNode` tree.
 ///
 /// `DuckDB` returns rows of `(explain_key, explain_value)` where the value is a
 /// JSON array of plan nodes: `[{"name": ..., "extra_info": {...}, "children": [...]}]`.
+///
+/// With `analyze` the statement runs as `EXPLAIN (ANALYZE, FORMAT JSON)`, whose value
+/// is the query profile instead: `{"latency": ..., "children": [...]}` with
+/// `operator_name`, `operator_cardinality` and `operator_timing` (seconds) per node,
+/// under an `EXPLAIN_ANALYZE` operator that is left out of the normalized tree.
 fn execute_structured_explain_duckdb(
     conn: &Connection,
     inner_sql: &str,
+    params: &[serde_json::Value],
+    analyze: bool,
 ) -> Result<ExplainPlanNode> {
-    let sql = format!("EXPLAIN (FORMAT JSON) {inner_sql}");
+    let options = if analyze { "ANALYZE, FORMAT JSON" } else { "FORMAT JSON" };
+    let sql = format!("EXPLAIN ({options}) {inner_sql}");
 
     let mut stmt = conn.prepare(&sql).map_err(|e| {
-        PlenumError::query_failed(format!("Failed to prepare EXPLAIN (FORMAT JSON): {e}"))
+        PlenumError::query_failed(format!("Failed to prepare EXPLAIN ({options}): {e}"))
     })?;
 
-    let json_text: String = stmt.query_row([], |row| row.get(1)).map_err(|e| {
-        PlenumError::query_failed(format!("Failed to execute EXPLAIN (FORMAT JSON): {e}"))
-    })?;
+    let duckdb_params: Vec<Value> = params.iter().map(json_to_duckdb_value).collect();
+    let json_text: String =
+        stmt.query_row(params_from_iter(duckdb_params.iter()), |row| row.get(1)).map_err(|e| {
+            if is_duckdb_interrupt(&e) {
+                PlenumError::query_timeout("Query interrupted by DuckDB server-side timeout")
+            } else {
+                PlenumError::query_failed(format!("Failed to execute EXPLAIN ({options}): {e}"))
+            }
+        })?;
 
     let parsed: serde_json::Value = serde_json::from_str(&json_text).map_err(|e| {
         PlenumError::query_failed(format!("Failed to parse DuckDB EXPLAIN JSON: {e}"))
     })?;
 
     fn build_node(node: &serde_json::Value) -> ExplainPlanNode {
-        let node_type =
-            node.get("name").and_then(|v| v.as_str()).unwrap_or("UNKNOWN").trim().to_string();
+        let node_type = node
+            .get("name")
+            .or_else(|| node.get("operator_name"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("UNKNOWN")
+            .trim()
+            .to_string();
 
         let extra = node.get("extra_info");
         let relation = extra
@@ -586,32 +1104,94 @@ fn execute_structured_explain_duckdb(
         let estimated_rows = extra
             .and_then(|e| e.get("Estimated Cardinality"))
             .and_then(|v| v.as_str().map_or_else(|| v.as_f64(), |s| s.trim().parse::<f64>().ok()));
+        // Expressions are a string, or an array of strings that all apply
+        let expression = |key: &str| match extra?.get(key)? {
+            serde_json::Value::String(s) => Some(s.trim().to_string()),
+            serde_json::Value::Array(parts) => {
+                let parts: Vec<&str> = parts.iter().filter_map(|p| p.as_str()).collect();
+                Some(parts.join(" AND "))
+            }
+            _ => None,
+        };
+        let scan_type = extra.and_then(|e| e.get("Type")).and_then(|v| v.as_str());
+
+        ExplainPlanNode {
+            operation: duckdb_plan_operation(&node_type, scan_type),
+            node_type,
+            relation,
+            filter: expression("Filters").or_else(|| expression("Expression")),
+            join_condition: expression("Conditions"),
+            estimated_rows,
+            actual_rows: node.get("operator_cardinality").and_then(serde_json::Value::as_f64),
+            actual_time_ms: node
+                .get("operator_timing")
+                .and_then(serde_json::Value::as_f64)
+                .map(|seconds| seconds * 1000.0),
+            children: build_children(node),
+            ..ExplainPlanNode::default()
+        }
+    }
 
-        let children = node
-            .get("children")
-            .and_then(|v| v.as_array())
-            .map(|kids| kids.iter().map(build_node).collect())
-            .unwrap_or_default();
-
-        ExplainPlanNode { node_type, relation, estimated_rows, estimated_cost: None, children }
+    fn build_children(node: &serde_json::Value) -> Vec<ExplainPlanNode> {
+        let kids = node.get("children").and_then(|v| v.as_array()).map_or(&[][..], Vec::as_slice);
+        kids.iter()
+            .flat_map(|kid| {
+                // The profile's EXPLAIN_ANALYZE operator only wraps the real plan
+                if kid.get("operator_type").and_then(|v| v.as_str()) == Some("EXPLAIN_ANALYZE") {
+                    build_children(kid)
+                } else {
+                    vec![build_node(kid)]
+                }
+            })
+            .collect()
     }
 
-    let top_children: Vec<ExplainPlanNode> = match &parsed {
-        serde_json::Value::Array(nodes) => nodes.iter().map(build_node).collect(),
-        other => vec![build_node(other)],
+    let (children, actual_time_ms) = match &parsed {
+        serde_json::Value::Array(nodes) => (nodes.iter().map(build_node).collect(), None),
+        profile if analyze => (
+            build_children(profile),
+            profile.get("latency").and_then(serde_json::Value::as_f64).map(|s| s * 1000.0),
+        ),
+        other => (vec![build_node(other)], None),
     };
 
     Ok(ExplainPlanNode {
         node_type: "QUERY PLAN".to_string(),
-        relation: None,
-        estimated_rows: None,
-        estimated_cost: None,
-        children: top_children,
+        actual_time_ms,
+        children,
+        ..ExplainPlanNode::default()
     })
 }
 
+/// Categorize a `DuckDB` physical operator name; table scans report their access
+/// path in `extra_info.Type` ("Sequential Scan", "Index Scan").
+fn duckdb_plan_operation(name: &str, scan_type: Option<&str>) -> PlanOperation {
+    match name {
+        "SEQ_SCAN" | "TABLE_SCAN" if scan_type == Some("Index Scan") => PlanOperation::IndexScan,
+        "SEQ_SCAN" | "TABLE_SCAN" => PlanOperation::FullScan,
+        "INDEX_SCAN" => PlanOperation::IndexScan,
+        "HASH_JOIN" => PlanOperation::HashJoin,
+        "NESTED_LOOP_JOIN" | "BLOCKWISE_NL_JOIN" | "CROSS_PRODUCT" => PlanOperation::NestedLoop,
+        "PIECEWISE_MERGE_JOIN" => PlanOperation::MergeJoin,
+        "ORDER_BY" | "TOP_N" => PlanOperation::Sort,
+        "HASH_GROUP_BY" | "PERFECT_HASH_GROUP_BY" | "UNGROUPED_AGGREGATE" => {
+            PlanOperation::Aggregate
+        }
+        "WINDOW" | "STREAMING_WINDOW" => PlanOperation::Window,
+        "LIMIT" | "STREAMING_LIMIT" | "LIMIT_PERCENT" => PlanOperation::Limit,
+        "FILTER" => PlanOperation::Filter,
+        "PROJECTION" => PlanOperation::Projection,
+        "UNION" => PlanOperation::Union,
+        "CTE_SCAN" | "DELIM_SCAN" | "COLUMN_DATA_SCAN" => PlanOperation::SubqueryScan,
+        _ => PlanOperation::Other,
+    }
+}
+
 /// Convert a JSON parameter value to a `duckdb` native value for binding
 fn json_to_duckdb_value(val: &serde_json::Value) -> Value {
+    if let Some(Ok(typed)) = ParamValue::from_json(val) {
+        return typed_to_duckdb_value(&typed);
+    }
     match val {
         serde_json::Value::Null => Value::Null,
         serde_json::Value::Bool(b) => Value::Boolean(*b),
@@ -627,13 +1207,34 @@ fn json_to_duckdb_value(val: &serde_json::Value) -> Value {
     }
 }
 
+/// Bind a typed parameter natively. Decimals, UUIDs and JSON bind as text and
+/// are cast by `DuckDB`; arrays bind as JSON text since list binding is unsupported.
+fn typed_to_duckdb_value(typed: &ParamValue) -> Value {
+    use duckdb::types::TimeUnit;
+    match typed {
+        ParamValue::Null => Value::Null,
+        ParamValue::Bool(b) => Value::Boolean(*b),
+        ParamValue::Int(i) => Value::BigInt(*i),
+        ParamValue::Float(f) => Value::Double(*f),
+        ParamValue::Date(d) => Value::Date32(i32::try_from(d.days_since_epoch()).unwrap_or(0)),
+        ParamValue::Time(t) => Value::Time64(TimeUnit::Microsecond, t.micros_since_midnight()),
+        ParamValue::Timestamp(ts) => {
+            Value::Timestamp(TimeUnit::Microsecond, ts.micros_since_epoch())
+        }
+        ParamValue::Bytes(b) => Value::Blob(b.clone()),
+        other => Value::Text(other.to_text().unwrap_or_default()),
+    }
+}
+
 /// Execute query and return `QueryResult`
 fn execute_query(
     conn: &Connection,
     query: &str,
     params: &[serde_json::Value],
     caps: &Capabilities,
-) -> Result<QueryResult> {
+    sink: &mut dyn RowSink,
+) -> Result<StreamSummary> {
+    let start = Instant::now();
     let mut stmt = conn
         .prepare(query)
         .map_err(|e| PlenumError::query_failed(format!("Failed to prepare query: {e}")))?;
@@ -648,15 +1249,26 @@ fn execute_query(
         }
     })?;
 
-    // Column names are only available after execution in the duckdb crate.
-    let column_names: Vec<String> =
-        rows.as_ref().map(duckdb::Statement::column_names).unwrap_or_default();
+    // Column names and types are only available after execution in the duckdb crate.
+    let columns: Vec<ResultColumn> = rows
+        .as_ref()
+        .map(|stmt| {
+            stmt.column_names()
+                .into_iter()
+                .enumerate()
+                .map(|(idx, name)| ResultColumn {
+                    name,
+                    type_name: Some(
+                        format!("{:?}", stmt.column_logical_type(idx).id()).to_uppercase(),
+                    ),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let column_count = columns.len();
 
-    let offset = caps.offset.unwrap_or(0);
-    let max = caps.max_rows;
-    let mut pos = 0usize;
-    let mut rows_data: Vec<Vec<serde_json::Value>> = Vec::new();
-    let mut rows_truncated = false;
+    let mut feed = RowFeed::new(sink, caps);
+    feed.columns(&columns)?;
 
     loop {
         let next = rows.next().map_err(|e| {
@@ -670,40 +1282,25 @@ fn execute_query(
         })?;
         let Some(row) = next else { break };
 
-        // Skip offset rows
-        if pos < offset {
-            pos += 1;
-            continue;
-        }
-
-        // Probe one row past max_rows to detect truncation
-        if let Some(m) = max {
-            if rows_data.len() >= m {
-                rows_truncated = true;
-                break;
+        let cells = feed.cells();
+        let keep_fetching = feed.push(|| {
+            let mut values = Vec::with_capacity(column_count);
+            for idx in 0..column_count {
+                let value_ref = row.get_ref(idx).map_err(|e| {
+                    PlenumError::query_failed(format!("Failed to read column {idx}: {e}"))
+                })?;
+                values.push(duckdb_value_to_json(&value_ref.to_owned(), cells)?);
             }
+            Ok(values)
+        })?;
+        if !keep_fetching {
+            break;
         }
-
-        let mut values = Vec::with_capacity(column_names.len());
-        for idx in 0..column_names.len() {
-            let value_ref = row.get_ref(idx).map_err(|e| {
-                PlenumError::query_failed(format!("Failed to read column {idx}: {e}"))
-            })?;
-            values.push(duckdb_value_to_json(&value_ref.to_owned()));
-        }
-        rows_data.push(values);
-        pos += 1;
     }
 
-    Ok(QueryResult {
-        columns: column_names,
-        rows: rows_data,
-        rows_affected: None,
-        execution_ms: 0,
-        rows_truncated,
-        truncated_by: None,
-        plan: None,
-    })
+    let mut summary = feed.finish();
+    summary.execution_ms = start.elapsed().as_millis() as u64;
+    Ok(summary)
 }
 
 /// Format a `DuckDB` timestamp/time value (count of `unit` since the epoch /
@@ -748,22 +1345,27 @@ fn f64_to_json(f: f64) -> serde_json::Value {
 /// represent natively are stringified deterministically:
 /// - `HUGEINT` / `UHUGEINT` and `DECIMAL` → string (preserves precision)
 /// - `TIMESTAMP` / `DATE` / `TIME` → ISO-8601 string
-/// - `BLOB` → Base64 string
+/// - `BLOB` / `GEOMETRY` → rendered per `--binary` (Base64 string by default)
 /// - `INTERVAL` → object with `months` / `days` / `nanos`
 /// - Nested types (`LIST`, `ARRAY`, `STRUCT`, `MAP`, `UNION`, `ENUM`) convert
 ///   recursively to JSON arrays / objects.
-fn duckdb_value_to_json(value: &Value) -> serde_json::Value {
+///
+/// # Errors
+///
+/// Returns `QUERY_FAILED` for a value type this conversion does not know.
+fn duckdb_value_to_json(value: &Value, cells: CellFormat) -> Result<serde_json::Value> {
     use serde_json::Value as Json;
 
-    match value {
+    Ok(match value {
         Value::Null => Json::Null,
         Value::Boolean(b) => Json::Bool(*b),
         Value::TinyInt(i) => Json::Number((*i).into()),
         Value::SmallInt(i) => Json::Number((*i).into()),
         Value::Int(i) => Json::Number((*i).into()),
         Value::BigInt(i) => Json::Number((*i).into()),
-        // HUGEINT exceeds JSON's i64 range; preserve precision as a string
+        // HUGEINT / UHUGEINT exceed JSON's i64 range; preserve precision as a string
         Value::HugeInt(i) => Json::String(i.to_string()),
+        Value::UHugeInt(i) => Json::String(i.to_string()),
         Value::UTinyInt(i) => Json::Number((*i).into()),
         Value::USmallInt(i) => Json::Number((*i).into()),
         Value::UInt(i) => Json::Number((*i).into()),
@@ -774,10 +1376,8 @@ fn duckdb_value_to_json(value: &Value) -> serde_json::Value {
         Value::Decimal(d) => Json::String(d.to_string()),
         Value::Timestamp(unit, v) => format_timestamp(*unit, *v),
         Value::Text(s) | Value::Enum(s) => Json::String(s.clone()),
-        Value::Blob(b) => {
-            use base64::Engine;
-            Json::String(base64::engine::general_purpose::STANDARD.encode(b))
-        }
+        // GEOMETRY is carried as WKB bytes; encode like BLOB
+        Value::Blob(b) | Value::Geometry(b) => cells.binary(b),
         Value::Date32(d) => format_date(*d),
         Value::Time64(unit, v) => format_time(*unit, *v),
         Value::Interval { months, days, nanos } => serde_json::json!({
@@ -785,12 +1385,14 @@ fn duckdb_value_to_json(value: &Value) -> serde_json::Value {
             "days": days,
             "nanos": nanos,
         }),
-        Value::List(items) | Value::Array(items) => {
-            Json::Array(items.iter().map(duckdb_value_to_json).collect())
-        }
+        Value::List(items) | Value::Array(items) => Json::Array(
+            items.iter().map(|item| duckdb_value_to_json(item, cells)).collect::<Result<_>>()?,
+        ),
         Value::Struct(map) => {
-            let obj: serde_json::Map<String, Json> =
-                map.iter().map(|(k, v)| (k.clone(), duckdb_value_to_json(v))).collect();
+            let obj: serde_json::Map<String, Json> = map
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), duckdb_value_to_json(v, cells)?)))
+                .collect::<Result<_>>()?;
             Json::Object(obj)
         }
         Value::Map(map) => {
@@ -799,24 +1401,32 @@ fn duckdb_value_to_json(value: &Value) -> serde_json::Value {
                 .map(|(k, v)| {
                     let key = match k {
                         Value::Text(s) | Value::Enum(s) => s.clone(),
-                        other => match duckdb_value_to_json(other) {
+                        other => match duckdb_value_to_json(other, cells)? {
                             Json::String(s) => s,
                             j => j.to_string(),
                         },
                     };
-                    (key, duckdb_value_to_json(v))
+                    Ok((key, duckdb_value_to_json(v, cells)?))
                 })
-                .collect();
+                .collect::<Result<_>>()?;
             Json::Object(obj)
         }
-        Value::Union(inner) => duckdb_value_to_json(inner),
-    }
+        Value::Union(inner) => duckdb_value_to_json(inner, cells)?,
+        // `Value` is non-exhaustive; a variant added by a future driver release
+        // needs its own mapping rather than a guessed rendering
+        other => {
+            return Err(PlenumError::query_failed(format!(
+                "Unsupported DuckDB value type {}",
+                other.data_type()
+            )))
+        }
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::engine::DatabaseType;
+    use crate::engine::{DatabaseType, ProfileOptions, SampleOptions};
     use std::path::PathBuf;
     use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -1004,6 +1614,29 @@ mod tests {
         assert!(result.unwrap_err().message().contains("--database"));
     }
 
+    #[tokio::test]
+    async fn test_introspect_index_health_rejected() {
+        let config = ConnectionConfig::duckdb(":memory:".into());
+        let err = DuckDbEngine::introspect(
+            &config,
+            &IntrospectOperation::IndexHealth { table: None },
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error_code(), "INVALID_INPUT");
+        assert!(err.message().contains("IndexHealth"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_inspect_activity_rejected() {
+        let config = ConnectionConfig::duckdb(":memory:".into());
+        let err = DuckDbEngine::inspect(&config, &InspectOperation::Activity).await.unwrap_err();
+        assert_eq!(err.error_code(), "INVALID_INPUT");
+        assert!(err.message().contains("Activity"), "{err}");
+    }
+
     #[tokio::test]
     async fn test_execute_select_query() {
         let temp_file = fixture_path("select");
@@ -1322,6 +1955,282 @@ mod tests {
         let _ = std::fs::remove_file(&temp_file);
     }
 
+    #[tokio::test]
+    async fn test_introspect_catalog_objects() {
+        let temp_file = fixture_path("catalog");
+        let _ = std::fs::remove_file(&temp_file);
+
+        {
+            let conn = Connection::open(&temp_file).expect("create");
+            conn.execute_batch(
+                "CREATE SEQUENCE seq_ids START 100 INCREMENT BY 5;
+                 CREATE TYPE mood AS ENUM ('sad', 'ok', 'happy');
+                 CREATE TYPE point AS STRUCT(x INTEGER, y INTEGER);
+                 CREATE MACRO add_one(a) AS a + 1;
+                 CREATE MACRO top_n(n) AS TABLE SELECT * FROM range(n);
+                 CREATE TABLE products (
+                     id INTEGER PRIMARY KEY,
+                     sku TEXT UNIQUE,
+                     price DOUBLE CHECK (price > 0)
+                 );
+                 CREATE TABLE tags (name TEXT)",
+            )
+            .expect("seed");
+        }
+
+        let config = ConnectionConfig::duckdb(temp_file.clone());
+
+        // ListSequences
+        let result =
+            DuckDbEngine::introspect(&config, &IntrospectOperation::ListSequences, None, None)
+                .await
+                .expect("ListSequences failed");
+        let IntrospectResult::SequenceList { sequences } = result else {
+            panic!("Expected SequenceList")
+        };
+        assert_eq!(sequences.len(), 1);
+        assert_eq!(sequences[0].name, "seq_ids");
+        assert_eq!(sequences[0].start_value, 100);
+        assert_eq!(sequences[0].increment_by, 5);
+        assert!(!sequences[0].cycle);
+
+        // ListMacros — scalar and table macros; built-ins excluded
+        let result =
+            DuckDbEngine::introspect(&config, &IntrospectOperation::ListMacros, None, None)
+                .await
+                .expect("ListMacros failed");
+        let IntrospectResult::MacroList { macros } = result else { panic!("Expected MacroList") };
+        let names: Vec<&str> = macros.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["add_one", "top_n"]);
+        assert_eq!(macros[0].kind, "macro");
+        assert_eq!(macros[0].parameters, vec!["a"]);
+        assert_eq!(macros[1].kind, "table_macro");
+
+        // ListTypes — enum labels and resolved struct definition
+        let result = DuckDbEngine::introspect(&config, &IntrospectOperation::ListTypes, None, None)
+            .await
+            .expect("ListTypes failed");
+        let IntrospectResult::TypeList { types } = result else { panic!("Expected TypeList") };
+        let mood = types.iter().find(|t| t.name == "mood").expect("mood type");
+        assert_eq!(mood.logical_type, "ENUM");
+        assert_eq!(mood.labels, vec!["sad", "ok", "happy"]);
+        let point = types.iter().find(|t| t.name == "point").expect("point type");
+        assert_eq!(point.logical_type, "STRUCT");
+        assert!(point.definition.contains("x INTEGER"), "got {}", point.definition);
+        assert!(point.labels.is_empty());
+
+        // ListConstraints — CHECK + UNIQUE only, filterable by table
+        let result = DuckDbEngine::introspect(
+            &config,
+            &IntrospectOperation::ListConstraints { table: Some("products".to_string()) },
+            None,
+            None,
+        )
+        .await
+        .expect("ListConstraints failed");
+        let IntrospectResult::ConstraintList { constraints } = result else {
+            panic!("Expected ConstraintList")
+        };
+        assert!(constraints.iter().all(|c| c.table == "products"));
+        let check = constraints.iter().find(|c| c.constraint_type == "CHECK").expect("check");
+        assert_eq!(check.columns, vec!["price"]);
+        assert!(check.definition.as_deref().unwrap_or_default().contains("price"));
+        let unique = constraints.iter().find(|c| c.constraint_type == "UNIQUE").expect("unique");
+        assert_eq!(unique.columns, vec!["sku"]);
+
+        let result = DuckDbEngine::introspect(
+            &config,
+            &IntrospectOperation::ListConstraints { table: Some("tags".to_string()) },
+            None,
+            None,
+        )
+        .await
+        .expect("ListConstraints failed");
+        let IntrospectResult::ConstraintList { constraints } = result else {
+            panic!("Expected ConstraintList")
+        };
+        assert!(constraints.is_empty());
+
+        // ListAttachedDatabases — the fixture file itself, opened read-only
+        let result = DuckDbEngine::introspect(
+            &config,
+            &IntrospectOperation::ListAttachedDatabases,
+            None,
+            None,
+        )
+        .await
+        .expect("ListAttachedDatabases failed");
+        let IntrospectResult::AttachedDatabaseList { databases } = result else {
+            panic!("Expected AttachedDatabaseList")
+        };
+        assert_eq!(databases.len(), 1);
+        assert_eq!(databases[0].database_type, "duckdb");
+        assert!(databases[0].readonly);
+        assert!(databases[0].path.as_deref().is_some_and(|p| p.ends_with(".duckdb")));
+
+        let _ = std::fs::remove_file(&temp_file);
+    }
+
+    #[tokio::test]
+    async fn test_introspect_names_containing_commas() {
+        let temp_file = fixture_path("commas");
+        let _ = std::fs::remove_file(&temp_file);
+
+        {
+            let conn = Connection::open(&temp_file).expect("create");
+            conn.execute_batch(
+                "CREATE TYPE pair AS ENUM ('a,b', 'c');
+                 CREATE MACRO join_pair(\"x,y\", z) AS z;
+                 CREATE TABLE parent (\"a,b\" INTEGER, c INTEGER, PRIMARY KEY (\"a,b\", c));
+                 CREATE TABLE child (
+                     \"p,id\" INTEGER,
+                     c INTEGER,
+                     UNIQUE (\"p,id\", c),
+                     FOREIGN KEY (\"p,id\", c) REFERENCES parent (\"a,b\", c)
+                 )",
+            )
+            .expect("seed");
+        }
+        let config = ConnectionConfig::duckdb(temp_file.clone());
+
+        let details = |name: &str| IntrospectOperation::TableDetails {
+            name: name.to_string(),
+            fields: TableFields::all(),
+        };
+        let result = DuckDbEngine::introspect(&config, &details("parent"), None, None).await;
+        let Ok(IntrospectResult::TableDetails { table }) = result else {
+            panic!("Expected TableDetails: {result:?}")
+        };
+        assert_eq!(table.primary_key, Some(vec!["a,b".to_string(), "c".to_string()]));
+
+        let result = DuckDbEngine::introspect(&config, &details("child"), None, None).await;
+        let Ok(IntrospectResult::TableDetails { table }) = result else {
+            panic!("Expected TableDetails: {result:?}")
+        };
+        assert_eq!(table.foreign_keys[0].columns, ["p,id", "c"]);
+        assert_eq!(table.foreign_keys[0].referenced_columns, ["a,b", "c"]);
+
+        let result = DuckDbEngine::introspect(
+            &config,
+            &IntrospectOperation::ListConstraints { table: Some("child".to_string()) },
+            None,
+            None,
+        )
+        .await;
+        let Ok(IntrospectResult::ConstraintList { constraints }) = result else {
+            panic!("Expected ConstraintList: {result:?}")
+        };
+        let unique = constraints.iter().find(|c| c.constraint_type == "UNIQUE").expect("unique");
+        assert_eq!(unique.columns, ["p,id", "c"]);
+
+        let result =
+            DuckDbEngine::introspect(&config, &IntrospectOperation::ListMacros, None, None).await;
+        let Ok(IntrospectResult::MacroList { macros }) = result else {
+            panic!("Expected MacroList: {result:?}")
+        };
+        assert_eq!(macros[0].parameters, ["x,y", "z"]);
+
+        let result =
+            DuckDbEngine::introspect(&config, &IntrospectOperation::ListTypes, None, None).await;
+        let Ok(IntrospectResult::TypeList { types }) = result else {
+            panic!("Expected TypeList: {result:?}")
+        };
+        assert_eq!(types[0].labels, ["a,b", "c"]);
+
+        let _ = std::fs::remove_file(&temp_file);
+    }
+
+    #[tokio::test]
+    async fn test_introspect_profile_table() {
+        let temp_file = fixture_path("profile");
+        let _ = std::fs::remove_file(&temp_file);
+
+        {
+            let conn = Connection::open(&temp_file).expect("create");
+            conn.execute_batch(
+                "CREATE TABLE events (id INTEGER, kind TEXT, score DOUBLE);
+                 INSERT INTO events VALUES
+                     (1, 'click', 0.5), (2, 'click', NULL), (3, 'view', 2.5), (4, NULL, 1.0)",
+            )
+            .expect("seed");
+        }
+
+        let config = ConnectionConfig::duckdb(temp_file.clone());
+        let result = DuckDbEngine::introspect(
+            &config,
+            &IntrospectOperation::ProfileTable {
+                name: "events".to_string(),
+                options: ProfileOptions::default(),
+            },
+            None,
+            None,
+        )
+        .await
+        .expect("ProfileTable failed");
+        let IntrospectResult::TableProfile { profile } = result else {
+            panic!("Expected TableProfile")
+        };
+
+        assert_eq!(profile.schema.as_deref(), Some("main"));
+        assert_eq!(profile.source, "sample");
+        assert_eq!(profile.row_count, 4);
+        assert!(profile.exhaustive);
+
+        let kind = &profile.columns[1];
+        assert_eq!(kind.name, "kind");
+        assert!((kind.null_fraction - 0.25).abs() < f64::EPSILON);
+        assert_eq!(kind.distinct_count, 2);
+        assert_eq!(kind.top_values[0].value, serde_json::json!("click"));
+        assert_eq!(kind.top_values[0].count, 2);
+
+        let score = &profile.columns[2];
+        assert_eq!(score.min, Some(serde_json::json!(0.5)));
+        assert_eq!(score.max, Some(serde_json::json!(2.5)));
+
+        let _ = std::fs::remove_file(&temp_file);
+    }
+
+    #[tokio::test]
+    async fn test_introspect_sample_table_is_reproducible() {
+        let temp_file = fixture_path("sample");
+        let _ = std::fs::remove_file(&temp_file);
+
+        {
+            let conn = Connection::open(&temp_file).expect("create");
+            conn.execute_batch(
+                "CREATE TABLE events AS SELECT range AS id, range % 7 AS kind FROM range(5000)",
+            )
+            .expect("seed");
+        }
+
+        let config = ConnectionConfig::duckdb(temp_file.clone());
+        let sample = |seed| {
+            let config = config.clone();
+            async move {
+                let op = IntrospectOperation::SampleTable {
+                    name: "events".to_string(),
+                    options: SampleOptions { size: 20, seed, timeout_ms: None },
+                };
+                match DuckDbEngine::introspect(&config, &op, None, None)
+                    .await
+                    .expect("SampleTable failed")
+                {
+                    IntrospectResult::TableSample { sample } => sample,
+                    _ => panic!("Expected TableSample"),
+                }
+            }
+        };
+
+        let first = sample(7).await;
+        let second = sample(7).await;
+        assert_eq!(first.columns, vec!["id", "kind"]);
+        assert_eq!(first.rows.len(), 20);
+        assert_eq!(first.rows, second.rows, "same seed must return the same rows");
+        assert_ne!(first.rows, sample(8).await.rows, "a different seed should differ");
+
+        let _ = std::fs::remove_file(&temp_file);
+    }
+
     /// Prove that writes fail at the `DuckDB` storage layer independently of
     /// the parser: a connection opened with `AccessMode::ReadOnly` must reject
     /// direct DML/DDL without going through `validate_query`.
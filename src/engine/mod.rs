@@ -78,6 +78,21 @@ pub mod mysql;
 #[cfg(feature = "duckdb")]
 pub mod duckdb;
 
+// Column profiling shared by all engines
+pub(crate) mod profile;
+pub use profile::{summarize_query, SummarySink};
+
+// Row windowing and buffering shared by all engines' query loops
+pub(crate) mod stream;
+
+// Cell rendering (binary format, long-value truncation) shared by all engines
+pub(crate) mod cell;
+pub use cell::{limit_cells, row_bytes};
+
+// Cancellation and row progress of in-flight queries (MCP tool calls)
+pub(crate) mod control;
+pub use control::QueryControl;
+
 /// Supported database engine types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -238,6 +253,55 @@ pub struct ConnectionInfo {
 
     /// Connected user name
     pub user: String,
+
+    /// Least-privilege findings (populated by `connect --test`; empty when none)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<PrivilegeWarning>,
+}
+
+/// Least-privilege finding about the configured credentials
+///
+/// Plenum enforces read-only access itself, but a credential that could write
+/// is one bypassed check away from doing so; these findings make that visible.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PrivilegeWarning {
+    /// Finding code: `SUPERUSER`, `ELEVATED_ROLE`, `WRITE_PRIVILEGE`, `DDL_PRIVILEGE`,
+    /// `TABLE_OWNERSHIP`, `GRANT_OPTION`, `READ_REPLICA`, or `AUDIT_INCOMPLETE`
+    pub code: String,
+
+    /// "warning" when the credential could modify data or schema, "info" otherwise
+    pub severity: String,
+
+    /// Human-readable explanation
+    pub message: String,
+
+    /// Affected objects (tables, schemas, grant targets); empty when not object-specific
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub objects: Vec<String>,
+}
+
+impl PrivilegeWarning {
+    /// Finding that the credential could modify data or schema
+    #[must_use]
+    pub fn warning(code: &str, message: impl Into<String>, objects: Vec<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            severity: "warning".to_string(),
+            message: message.into(),
+            objects,
+        }
+    }
+
+    /// Informational finding (e.g. the server is a read-only replica)
+    #[must_use]
+    pub fn info(code: &str, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            severity: "info".to_string(),
+            message: message.into(),
+            objects: Vec::new(),
+        }
+    }
 }
 
 /// EXPLAIN output format for the `query` command
@@ -251,21 +315,237 @@ pub enum ExplainFormat {
     Structured,
 }
 
+/// How binary values (BLOB, BYTEA, binary strings) are rendered in query results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum BinaryFormat {
+    /// Base64 string (default)
+    #[default]
+    Base64,
+    /// Lowercase hex string
+    Hex,
+    /// Null: the value is left out entirely
+    Omit,
+    /// The value's length in bytes
+    LengthOnly,
+}
+
+impl BinaryFormat {
+    /// Parse a `--binary` value (`base64`, `hex`, `omit`, `length-only`).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "base64" => Some(Self::Base64),
+            "hex" => Some(Self::Hex),
+            "omit" => Some(Self::Omit),
+            "length-only" => Some(Self::LengthOnly),
+            _ => None,
+        }
+    }
+}
+
 /// Normalized EXPLAIN plan node — engine-stable shape agents can reason about
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+///
+/// The `actual_*`, `loops` and `buffers` fields are measured by `EXPLAIN ANALYZE`
+/// and are left out of estimate-only plans.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ExplainPlanNode {
     /// Engine-specific operation label (e.g. "Seq Scan", "Hash Join", "SCAN TABLE")
     pub node_type: String,
+    /// Engine-independent category of `node_type`
+    #[serde(default)]
+    pub operation: PlanOperation,
     /// Table or relation name; `null` when the node does not reference one
     pub relation: Option<String>,
+    /// Index the node reads; `null` for non-index nodes or when the engine does not name it
+    pub index: Option<String>,
+    /// Row filter applied by the node, in the engine's own expression syntax
+    pub filter: Option<String>,
+    /// Join condition of a join node, in the engine's own expression syntax
+    pub join_condition: Option<String>,
     /// Planner's estimated row count; `null` when the engine does not supply it
     pub estimated_rows: Option<f64>,
     /// Planner's estimated cost (engine-specific units); `null` when not available
     pub estimated_cost: Option<f64>,
+    /// Rows the node actually produced, per loop (`DuckDB`: in total)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual_rows: Option<f64>,
+    /// Time spent in the node and its children in milliseconds, per loop
+    /// (`DuckDB`: the operator's own time, in total)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual_time_ms: Option<f64>,
+    /// Number of times the node was executed (`PostgreSQL`, `MySQL`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loops: Option<u64>,
+    /// Buffer usage of the node and its children (`PostgreSQL`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buffers: Option<PlanBuffers>,
     /// Child plan nodes (empty for leaf nodes)
     pub children: Vec<Self>,
 }
 
+/// Engine-independent category of a plan node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanOperation {
+    /// Reads every row of a table
+    FullScan,
+    /// Reads rows through an index (lookup, range or full index scan)
+    IndexScan,
+    /// Answers from the index alone without visiting the table
+    IndexOnlyScan,
+    /// Hash join
+    HashJoin,
+    /// Nested-loop join (including block and cross-product variants)
+    NestedLoop,
+    /// Merge join over sorted inputs
+    MergeJoin,
+    /// Builds the hash table for a hash join
+    Hash,
+    /// Sorts rows (including top-N sorts)
+    Sort,
+    /// Grouping or aggregation
+    Aggregate,
+    /// Window function evaluation
+    Window,
+    /// Stops after a number of rows
+    Limit,
+    /// Discards rows that fail a condition
+    Filter,
+    /// Computes output expressions
+    Projection,
+    /// Stores an intermediate result for re-reading
+    Materialize,
+    /// Concatenates the results of several inputs (UNION, append)
+    Union,
+    /// Removes duplicate rows
+    Distinct,
+    /// Reads the result of a subquery, CTE or co-routine
+    SubqueryScan,
+    /// Any node without a category above
+    #[default]
+    Other,
+}
+
+/// Buffer counts of an analyzed plan node, in blocks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PlanBuffers {
+    /// Shared blocks found in the buffer cache
+    pub shared_hit: u64,
+    /// Shared blocks read from disk
+    pub shared_read: u64,
+    /// Shared blocks dirtied by the node
+    pub shared_dirtied: u64,
+    /// Shared blocks written out by the node
+    pub shared_written: u64,
+    /// Temporary blocks read (sorts and hashes spilled to disk)
+    pub temp_read: u64,
+    /// Temporary blocks written
+    pub temp_written: u64,
+}
+
+/// Index suggestions for one statement (`plenum advise`)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IndexAdvice {
+    /// Suggested indexes, in plan order; empty when nothing qualifies
+    pub suggestions: Vec<IndexSuggestion>,
+
+    /// Structured plan the suggestions were derived from
+    pub plan: ExplainPlanNode,
+}
+
+/// One suggested index; never executed by Plenum
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IndexSuggestion {
+    /// Table the index belongs to
+    pub table: String,
+
+    /// Schema name (for engines that support schemas)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// Suggested index name
+    pub index_name: String,
+
+    /// Indexed columns, in index order
+    pub columns: Vec<String>,
+
+    /// "`full_scan_filter`" or "sort"
+    pub reason: String,
+
+    /// Engine-specific DDL to review and run
+    pub statement: String,
+
+    /// Human-readable explanation, naming the plan node behind the suggestion
+    pub rationale: String,
+
+    /// Table row estimate from engine statistics; null when not available
+    pub table_rows: Option<i64>,
+
+    /// Planner's row estimate for the plan node behind the suggestion; null when not available
+    pub estimated_rows: Option<f64>,
+}
+
+/// Plan baselines recorded or checked for saved queries (`plenum plan record|check`)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlanReport {
+    /// One entry per saved query, sorted by name
+    pub queries: Vec<PlanCheck>,
+
+    /// Queries whose plan shape changed or whose estimated cost rose past the threshold
+    pub changed: usize,
+
+    /// Queries that could not be explained, compared or recorded
+    pub errors: usize,
+}
+
+/// Plan baseline status of one saved query
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlanCheck {
+    /// Saved query name
+    pub name: String,
+
+    /// "recorded", "unchanged", "`shape_changed`", "`cost_increased`", "`no_baseline`" or "error"
+    pub status: String,
+
+    /// Baseline file, relative to the project root
+    pub baseline: String,
+
+    /// Plan nodes whose operation, relation or index differ from the baseline
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<PlanChange>,
+
+    /// Root estimated cost in the baseline; null when not available
+    pub baseline_cost: Option<f64>,
+
+    /// Root estimated cost of the current plan; null when not available
+    pub current_cost: Option<f64>,
+
+    /// `current_cost / baseline_cost`; null unless both are available
+    pub cost_ratio: Option<f64>,
+
+    /// The saved query's SQL differs from the SQL the baseline was recorded for
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub sql_changed: bool,
+
+    /// Why the query could not be explained, compared or recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<crate::output::ErrorInfo>,
+}
+
+/// One plan node that differs from the baseline
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlanChange {
+    /// JSON pointer to the node within the plan (`""` is the root)
+    pub path: String,
+
+    /// Baseline node as "operation relation (index)"; null when the node was added
+    pub baseline: Option<String>,
+
+    /// Current node as "operation relation (index)"; null when the node was removed
+    pub current: Option<String>,
+}
+
 /// Return `true` when `sql` opens with the `EXPLAIN` keyword (case-insensitive).
 pub(crate) fn is_explain_query(sql: &str) -> bool {
     sql.trim().to_uppercase().starts_with("EXPLAIN")
@@ -301,13 +581,38 @@ pub struct Capabilities {
     /// EXPLAIN output format; `None` / `Native` preserves pre-REF-282 behavior
     #[serde(skip_serializing_if = "Option::is_none")]
     pub explain_format: Option<ExplainFormat>,
+
+    /// Longest rendered value (text, JSON, encoded binary) kept in a cell, in bytes.
+    /// Longer values are replaced by a truncation marker object in the JSON and NDJSON
+    /// output (see [`limit_cells`]); engines always return the real values.
+    /// None means no limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_cell_bytes: Option<usize>,
+
+    /// Rendering of binary values; `None` means `Base64`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary_format: Option<BinaryFormat>,
+
+    /// Cancellation and row progress shared with the caller (MCP tool calls).
+    /// None means the query cannot be cancelled.
+    #[serde(skip)]
+    pub control: Option<QueryControl>,
 }
 
 impl Capabilities {
     /// Create new capabilities with optional constraints
     #[must_use]
     pub const fn new(max_rows: Option<usize>, timeout_ms: Option<u64>) -> Self {
-        Self { max_rows, max_bytes: None, timeout_ms, offset: None, explain_format: None }
+        Self {
+            max_rows,
+            max_bytes: None,
+            timeout_ms,
+            offset: None,
+            explain_format: None,
+            max_cell_bytes: None,
+            binary_format: None,
+            control: None,
+        }
     }
 }
 
@@ -431,7 +736,67 @@ pub struct QueryResult {
 
     /// Normalized EXPLAIN plan; populated only when `--explain-format structured` is used
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub plan: Option<ExplainPlanNode>,
+    pub plan: Option<Box<ExplainPlanNode>>,
+
+    /// Opaque keyset cursor for the next page (pass back as `--cursor`); present only
+    /// when the result was truncated and the query's ordering is unique
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+
+    /// How the row order was fixed under `--deterministic`: "engine" (the query's own
+    /// ORDER BY), "imposed" (ordered by Plenum) or "unordered"; absent otherwise
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ordering: Option<String>,
+}
+
+/// Result-set column with its engine-native type name (used by `RowSink`)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResultColumn {
+    /// Column name
+    pub name: String,
+
+    /// Engine-native type name (e.g. `int4`, `var_string`, `INTEGER`); null when the
+    /// engine does not declare one (`SQLite` expressions)
+    #[serde(rename = "type")]
+    pub type_name: Option<String>,
+}
+
+/// Receives a query result incrementally from `DatabaseEngine::execute_stream`
+///
+/// Engines call `columns` exactly once, then `row` for each row in result order
+/// after `offset` and `max_rows` have been applied.
+pub trait RowSink: Send {
+    /// Accept the result columns (empty for statements that return no rows)
+    ///
+    /// # Errors
+    /// Returns an error when the sink cannot write its output.
+    fn columns(&mut self, columns: &[ResultColumn]) -> Result<()>;
+
+    /// Accept one row; return `false` to refuse it and stop fetching (byte budget
+    /// exhausted). A refused row is not part of the result.
+    ///
+    /// # Errors
+    /// Returns an error when the sink cannot write its output.
+    fn row(&mut self, row: Vec<serde_json::Value>) -> Result<bool>;
+}
+
+/// Outcome of a streamed query (returned by `DatabaseEngine::execute_stream`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamSummary {
+    /// Rows accepted by the sink
+    pub rows_returned: usize,
+
+    /// Number of rows affected (for statements that return no rows)
+    pub rows_affected: Option<u64>,
+
+    /// Whether more rows were available than were returned
+    pub rows_truncated: bool,
+
+    /// "bytes" when the sink refused a row; absent for `max_rows` truncation
+    pub truncated_by: Option<String>,
+
+    /// Query execution time in milliseconds, including time spent in the sink
+    pub execution_ms: u64,
 }
 
 /// Trim `result.rows` to fit within `max_bytes` of serialized JSON, at row boundaries.
@@ -440,10 +805,21 @@ pub struct QueryResult {
 /// cumulative total would exceed the budget, the result is truncated at that row boundary.
 /// Sets `rows_truncated = true` and `truncated_by = Some("bytes")` when truncation occurs.
 pub fn apply_byte_budget(result: &mut QueryResult, max_bytes: usize) {
+    apply_byte_budget_with_cell_limit(result, max_bytes, None);
+}
+
+/// [`apply_byte_budget`] for output that [`limit_cells`] will mark with
+/// `max_cell_bytes`: each row is measured as it will be printed, while the rows kept
+/// still hold their real values for the cursor.
+pub fn apply_byte_budget_with_cell_limit(
+    result: &mut QueryResult,
+    max_bytes: usize,
+    max_cell_bytes: Option<usize>,
+) {
     let mut byte_count: usize = 0;
     let mut cutoff: Option<usize> = None;
     for (i, row) in result.rows.iter().enumerate() {
-        let row_bytes = serde_json::to_string(row).map_or(0, |s| s.len());
+        let row_bytes = cell::row_bytes(row, max_cell_bytes);
         if byte_count + row_bytes > max_bytes {
             cutoff = Some(i);
             break;
@@ -504,6 +880,93 @@ pub enum IntrospectOperation {
         /// View name to introspect
         name: String,
     },
+
+    /// List all sequences (`DuckDB` only)
+    ListSequences,
+
+    /// List all scalar and table macros (`DuckDB` only)
+    ListMacros,
+
+    /// List user-defined types such as enums and structs (`DuckDB` only)
+    ListTypes,
+
+    /// List CHECK and UNIQUE constraints (`DuckDB` only; optionally filtered to a table)
+    ListConstraints {
+        /// Optional table name to filter constraints
+        table: Option<String>,
+    },
+
+    /// List attached databases with path, storage type and access mode (`DuckDB` only)
+    ListAttachedDatabases,
+
+    /// Profile a table's columns: null fraction, distinct count, min/max, top values
+    ProfileTable {
+        /// Table name to profile
+        name: String,
+        /// Sampling bounds and result size
+        options: ProfileOptions,
+    },
+
+    /// Draw a reproducible random sample of a table's rows
+    SampleTable {
+        /// Table name to sample
+        name: String,
+        /// Sample size, seed and timeout
+        options: SampleOptions,
+    },
+
+    /// Report the connected user's effective privileges (optionally filtered to a table)
+    ListPrivileges {
+        /// Optional table name to filter privileges
+        table: Option<String>,
+    },
+
+    /// Diagnose unused, duplicate and redundant indexes and bloated tables
+    /// (optionally filtered to a table)
+    IndexHealth {
+        /// Optional table name to filter findings
+        table: Option<String>,
+    },
+
+    /// Report table, index and TOAST storage sizes plus the database size,
+    /// largest tables first
+    Sizes {
+        /// Page bounds
+        options: SizeOptions,
+    },
+
+    /// Report server/session settings (optionally filtered by a glob pattern)
+    Settings {
+        /// Optional case-insensitive glob (`*`, `?`) matched against setting names
+        pattern: Option<String>,
+    },
+}
+
+impl IntrospectOperation {
+    /// Operation name as used in error messages (e.g. `ListSchemas`)
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::ListDatabases => "ListDatabases",
+            Self::ListSchemas => "ListSchemas",
+            Self::ListTables => "ListTables",
+            Self::ListViews => "ListViews",
+            Self::ListIndexes { .. } => "ListIndexes",
+            Self::TableDetails { .. } => "TableDetails",
+            Self::ViewDetails { .. } => "ViewDetails",
+            Self::ListSequences => "ListSequences",
+            Self::ListMacros => "ListMacros",
+            Self::ListTypes => "ListTypes",
+            Self::ListConstraints { .. } => "ListConstraints",
+            Self::ListAttachedDatabases => "ListAttachedDatabases",
+            Self::ProfileTable { .. } => "ProfileTable",
+            Self::SampleTable { .. } => "SampleTable",
+            Self::ListPrivileges { .. } => "ListPrivileges",
+            Self::IndexHealth { .. } => "IndexHealth",
+            Self::Sizes { .. } => "Sizes",
+            Self::Settings { .. } => "Settings",
+        }
+    }
 }
 
 /// Table detail field selectors
@@ -544,6 +1007,85 @@ impl TableFields {
     }
 }
 
+/// Default number of rows read when profiling by sampling
+pub const DEFAULT_PROFILE_SAMPLE_ROWS: usize = 10_000;
+
+/// Default number of most frequent values reported per column
+pub const DEFAULT_PROFILE_TOP_N: usize = 5;
+
+/// Column profiling options
+///
+/// Bounds the work done by `ProfileTable`. Sampling draws at most `sample_rows`
+/// rows with the engine's seeded `SampleTable` query under `timeout_ms`, the same
+/// guardrails `plenum query` applies.
+#[derive(Debug, Clone)]
+pub struct ProfileOptions {
+    /// Maximum rows to sample when profiling by sampling
+    pub sample_rows: usize,
+    /// Number of most frequent values to report per column
+    pub top_n: usize,
+    /// Timeout for the sampling query in milliseconds (None means no timeout)
+    pub timeout_ms: Option<u64>,
+}
+
+impl Default for ProfileOptions {
+    fn default() -> Self {
+        Self {
+            sample_rows: DEFAULT_PROFILE_SAMPLE_ROWS,
+            top_n: DEFAULT_PROFILE_TOP_N,
+            timeout_ms: None,
+        }
+    }
+}
+
+impl ProfileOptions {
+    /// The `SampleTable` options a sampled profile is drawn with (seed 0)
+    #[must_use]
+    pub fn sample_options(&self) -> SampleOptions {
+        SampleOptions { size: self.sample_rows, seed: 0, timeout_ms: self.timeout_ms }
+    }
+}
+
+/// Default number of rows returned by `SampleTable`
+pub const DEFAULT_SAMPLE_SIZE: usize = 10;
+
+/// Table sampling options
+///
+/// The same table contents, size and seed always select the same rows.
+#[derive(Debug, Clone)]
+pub struct SampleOptions {
+    /// Number of rows to return
+    pub size: usize,
+    /// Seed for the engine's sampling method
+    pub seed: u32,
+    /// Timeout for the sampling query in milliseconds (None means no timeout)
+    pub timeout_ms: Option<u64>,
+}
+
+impl Default for SampleOptions {
+    fn default() -> Self {
+        Self { size: DEFAULT_SAMPLE_SIZE, seed: 0, timeout_ms: None }
+    }
+}
+
+/// Default number of tables per page returned by `Sizes`
+pub const DEFAULT_SIZES_LIMIT: usize = 50;
+
+/// Size inventory paging options
+#[derive(Debug, Clone)]
+pub struct SizeOptions {
+    /// Maximum number of tables to return
+    pub limit: usize,
+    /// Number of tables to skip (after sorting)
+    pub offset: usize,
+}
+
+impl Default for SizeOptions {
+    fn default() -> Self {
+        Self { limit: DEFAULT_SIZES_LIMIT, offset: 0 }
+    }
+}
+
 /// Introspection result
 ///
 /// The result type depends on which `IntrospectOperation` was requested.
@@ -592,50 +1134,802 @@ pub enum IntrospectResult {
         /// View information
         view: ViewInfo,
     },
+
+    /// List of sequences
+    SequenceList {
+        /// Sequence definitions
+        sequences: Vec<SequenceInfo>,
+    },
+
+    /// List of macros
+    MacroList {
+        /// Macro definitions
+        macros: Vec<MacroInfo>,
+    },
+
+    /// List of user-defined types
+    TypeList {
+        /// Type definitions
+        types: Vec<CustomTypeInfo>,
+    },
+
+    /// List of CHECK / UNIQUE constraints
+    ConstraintList {
+        /// Constraint definitions
+        constraints: Vec<ConstraintInfo>,
+    },
+
+    /// List of attached databases
+    AttachedDatabaseList {
+        /// Attached database details
+        databases: Vec<AttachedDatabaseInfo>,
+    },
+
+    /// Column profile for a table
+    TableProfile {
+        /// Profile information
+        profile: TableProfile,
+    },
+
+    /// Reproducible random sample of a table's rows
+    TableSample {
+        /// Sampled rows in query result format
+        sample: QueryResult,
+    },
+
+    /// Effective privileges of the connected user
+    Privileges {
+        /// Privilege report
+        privileges: PrivilegeReport,
+    },
+
+    /// Index usage and health diagnostics
+    IndexHealth {
+        /// Findings and caveats
+        health: IndexHealthReport,
+    },
+
+    /// Storage size inventory
+    Sizes {
+        /// Database size and one page of table sizes
+        sizes: SizeReport,
+    },
+
+    /// Server/session settings
+    Settings {
+        /// Settings sorted by name
+        settings: Vec<SettingInfo>,
+    },
 }
 
-/// Index summary (used in `ListIndexes` operation)
+/// Sequence information (used in `ListSequences` operation)
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct IndexSummary {
-    /// Index name
+pub struct SequenceInfo {
+    /// Sequence name
     pub name: String,
 
-    /// Table the index belongs to
-    pub table: String,
+    /// Schema the sequence belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
 
-    /// Whether this is a unique index
-    pub unique: bool,
+    /// First value produced by the sequence
+    pub start_value: i64,
 
-    /// Column names included in the index
-    pub columns: Vec<String>,
+    /// Lower bound
+    pub min_value: i64,
+
+    /// Upper bound
+    pub max_value: i64,
+
+    /// Step between consecutive values
+    pub increment_by: i64,
+
+    /// Whether the sequence wraps around when a bound is reached
+    pub cycle: bool,
+
+    /// Last value handed out; null when the sequence has not been used
+    pub last_value: Option<i64>,
 }
 
-/// View information
+/// Macro information (used in `ListMacros` operation)
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct ViewInfo {
-    /// View name
+pub struct MacroInfo {
+    /// Macro name
     pub name: String,
 
-    /// Schema name (for engines that support schemas)
+    /// Schema the macro belongs to
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<String>,
 
-    /// View definition (SQL source, if available)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Macro kind: `macro` (scalar) or `table_macro`
+    pub kind: String,
+
+    /// Parameter names, in declaration order
+    pub parameters: Vec<String>,
+
+    /// Macro body (SQL expression or query); null when not available
     pub definition: Option<String>,
+}
 
-    /// View columns
-    pub columns: Vec<ColumnInfo>,
+/// User-defined type information (used in `ListTypes` operation)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CustomTypeInfo {
+    /// Type name
+    pub name: String,
+
+    /// Schema the type belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// Underlying logical type (e.g. "ENUM", "STRUCT", "INTEGER")
+    pub logical_type: String,
+
+    /// Fully resolved type definition (e.g. "STRUCT(a INTEGER, b VARCHAR)")
+    pub definition: String,
+
+    /// Enum labels in declaration order (empty for non-enum types)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
 }
 
-/// Database engine trait
-///
-/// All database engines implement this trait.
-/// Each method is stateless and takes a connection config as input.
-pub trait DatabaseEngine {
-    /// Validate connection and return connection information
-    ///
-    /// This method:
+/// Table constraint information (used in `ListConstraints` operation)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConstraintInfo {
+    /// Constraint name
+    pub name: String,
+
+    /// Table the constraint belongs to
+    pub table: String,
+
+    /// Constraint type: "CHECK" or "UNIQUE"
+    pub constraint_type: String,
+
+    /// Columns covered by the constraint
+    pub columns: Vec<String>,
+
+    /// Constraint source text (e.g. "CHECK((price > 0))"); null when not available
+    pub definition: Option<String>,
+}
+
+/// Attached database information (used in `ListAttachedDatabases` operation)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AttachedDatabaseInfo {
+    /// Catalog name the database is attached as
+    pub name: String,
+
+    /// File path; null for in-memory databases
+    pub path: Option<String>,
+
+    /// Storage type (e.g. "duckdb", "sqlite")
+    pub database_type: String,
+
+    /// Whether the database is attached read-only
+    pub readonly: bool,
+}
+
+/// Table profile (used in `ProfileTable` operation)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TableProfile {
+    /// Table name
+    pub table: String,
+
+    /// Schema name (for engines that support schemas)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// Where the numbers come from: "statistics" (planner statistics, e.g. `pg_stats`)
+    /// or "sample" (the engine's seeded `SampleTable` rows, seed 0)
+    pub source: String,
+
+    /// Rows the profile describes: estimated table rows for "statistics",
+    /// rows read for "sample"
+    pub row_count: u64,
+
+    /// Whether every row of the table was read, making counts exact
+    pub exhaustive: bool,
+
+    /// Per-column profiles in table column order
+    pub columns: Vec<ColumnProfile>,
+}
+
+/// Column profile (used in `ProfileTable` operation)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ColumnProfile {
+    /// Column name
+    pub name: String,
+
+    /// Fraction of rows where the column is NULL (0.0 – 1.0)
+    pub null_fraction: f64,
+
+    /// Approximate number of distinct non-NULL values (a lower bound when sampled)
+    pub distinct_count: u64,
+
+    /// Smallest non-NULL value; null when unknown or the column is entirely NULL
+    pub min: Option<serde_json::Value>,
+
+    /// Largest non-NULL value; null when unknown or the column is entirely NULL
+    pub max: Option<serde_json::Value>,
+
+    /// Most frequent non-NULL values, most frequent first
+    pub top_values: Vec<ValueFrequency>,
+}
+
+/// A value and how often it occurs (used in `ColumnProfile`)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ValueFrequency {
+    /// The value (rendered as text when read from planner statistics)
+    pub value: serde_json::Value,
+
+    /// Number of rows holding the value (estimated for "statistics")
+    pub count: u64,
+
+    /// Fraction of all rows holding the value (0.0 – 1.0)
+    pub frequency: f64,
+}
+
+/// Per-column statistics of a query result (used by `query --summarize`)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResultSummary {
+    /// Rows the query returned; every one of them was read, `max_rows` does not apply
+    pub row_count: u64,
+
+    /// Query execution time in milliseconds, including summarising
+    pub execution_ms: u64,
+
+    /// Per-column statistics in result column order
+    pub columns: Vec<ColumnSummary>,
+}
+
+/// Statistics for one result column (used in `ResultSummary`)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ColumnSummary {
+    /// Column name
+    pub name: String,
+
+    /// Engine-native type name; null when the engine does not declare one
+    #[serde(rename = "type")]
+    pub type_name: Option<String>,
+
+    /// Number of non-NULL values
+    pub count: u64,
+
+    /// Number of NULL values
+    pub null_count: u64,
+
+    /// Number of distinct non-NULL values; stops growing at the distinct cap
+    pub distinct_count: u64,
+
+    /// Whether `distinct_count` is exact (false once the cap was reached)
+    pub distinct_exact: bool,
+
+    /// Smallest non-NULL value; null when the column is entirely NULL
+    pub min: Option<serde_json::Value>,
+
+    /// Largest non-NULL value; null when the column is entirely NULL
+    pub max: Option<serde_json::Value>,
+
+    /// The first few distinct non-NULL values, in result order
+    pub examples: Vec<serde_json::Value>,
+}
+
+/// Effective privileges of the connected user (used in `ListPrivileges` operation)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PrivilegeReport {
+    /// User the privileges were resolved for ("N/A" for file-based engines)
+    pub user: String,
+
+    /// "database" when access is governed by the server's grant system,
+    /// "file" when only file-system permissions apply (`SQLite`, `DuckDB`)
+    pub scope: String,
+
+    /// Roles the user is a member of (`PostgreSQL`, `MySQL`)
+    pub roles: Vec<String>,
+
+    /// Per-table privileges and row-level security policies (`PostgreSQL`)
+    pub tables: Vec<TablePrivileges>,
+
+    /// Parsed `SHOW GRANTS` output (`MySQL`)
+    pub grants: Vec<GrantInfo>,
+
+    /// File access for file-based engines; null for server engines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<FileAccess>,
+}
+
+/// Index usage and health diagnostics (used in `IndexHealth` operation)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IndexHealthReport {
+    /// Findings ordered by table, then index
+    pub findings: Vec<IndexFinding>,
+
+    /// Caveats about the underlying statistics (e.g. when usage counters were last reset)
+    pub notes: Vec<String>,
+}
+
+/// Single index or table health finding
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IndexFinding {
+    /// Table name
+    pub table: String,
+
+    /// Schema name (for engines that support schemas)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// Index name; null for table-level findings such as bloat
+    pub index: Option<String>,
+
+    /// "unused", "duplicate", "redundant", "unselective", or "bloat"
+    pub reason: String,
+
+    /// Name of the measurement behind the finding (e.g. `idx_scan`, `dead_tuple_fraction`)
+    pub metric: String,
+
+    /// Value of the measurement
+    pub value: f64,
+
+    /// Human-readable explanation
+    pub detail: String,
+}
+
+/// Storage size inventory (used in `Sizes` operation)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SizeReport {
+    /// Size of the whole database in bytes, when the engine reports it
+    pub database_bytes: Option<u64>,
+
+    /// One page of tables, largest `total_bytes` first (unknown sizes last, then by row estimate)
+    pub tables: Vec<TableSize>,
+
+    /// Number of tables before paging
+    pub total_tables: usize,
+
+    /// Number of tables skipped before this page
+    pub offset: usize,
+
+    /// Whether more tables follow this page
+    pub has_more: bool,
+
+    /// Caveats about how sizes were measured
+    pub notes: Vec<String>,
+}
+
+impl SizeReport {
+    /// Sort `tables` largest first and keep the page selected by `options`.
+    #[must_use]
+    pub fn paginate(
+        database_bytes: Option<u64>,
+        mut tables: Vec<TableSize>,
+        options: &SizeOptions,
+        notes: Vec<String>,
+    ) -> Self {
+        tables.sort_by(|a, b| {
+            b.total_bytes
+                .cmp(&a.total_bytes)
+                .then_with(|| b.row_estimate.cmp(&a.row_estimate))
+                .then_with(|| (&a.schema, &a.name).cmp(&(&b.schema, &b.name)))
+        });
+        let total_tables = tables.len();
+        let tables: Vec<TableSize> =
+            tables.into_iter().skip(options.offset).take(options.limit).collect();
+        let has_more = options.offset.saturating_add(tables.len()) < total_tables;
+        Self { database_bytes, tables, total_tables, offset: options.offset, has_more, notes }
+    }
+}
+
+/// One server or session setting (used in `Settings` operation)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SettingInfo {
+    /// Setting name as the engine spells it
+    pub name: String,
+
+    /// Current value rendered as text (null when unset)
+    pub value: Option<String>,
+
+    /// Unit of `value` (e.g. `ms`, `8kB`, `bytes`), when the engine reports one
+    pub unit: Option<String>,
+
+    /// Where the current value came from (e.g. `default`, `configuration file`), when known
+    pub source: Option<String>,
+
+    /// Whether the connected session could change this setting for itself
+    /// (null when the engine does not expose it)
+    pub session_changeable: Option<bool>,
+}
+
+/// Match `text` against a case-insensitive glob pattern.
+///
+/// `*` matches any run of characters and `?` matches exactly one; everything
+/// else matches literally. The pattern must cover the whole text.
+#[must_use]
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` seen and the text position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, tried)) => {
+                    p = star + 1;
+                    t = tried + 1;
+                    backtrack = Some((star, tried + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Keep settings whose name matches `pattern` (all when `None`) and sort by name.
+#[must_use]
+pub fn filter_settings(mut settings: Vec<SettingInfo>, pattern: Option<&str>) -> Vec<SettingInfo> {
+    if let Some(pattern) = pattern {
+        settings.retain(|s| glob_match(pattern, &s.name));
+    }
+    settings.sort_by_key(|s| s.name.to_lowercase());
+    settings
+}
+
+/// Storage used by one table
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TableSize {
+    /// Table name
+    pub name: String,
+
+    /// Schema name (for engines that support schemas)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// Bytes used by the table's rows, excluding indexes and TOAST
+    pub table_bytes: Option<u64>,
+
+    /// Bytes used by the table's indexes
+    pub index_bytes: Option<u64>,
+
+    /// Bytes used by out-of-line (TOAST) storage, including its index (`PostgreSQL` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toast_bytes: Option<u64>,
+
+    /// Total bytes: table, indexes and TOAST
+    pub total_bytes: Option<u64>,
+
+    /// Estimated row count, when the engine keeps one
+    pub row_estimate: Option<u64>,
+}
+
+/// Privileges held on a single table or view
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TablePrivileges {
+    /// Table name
+    pub table: String,
+
+    /// Schema name
+    pub schema: String,
+
+    /// Table-level privileges (e.g. "SELECT", "INSERT")
+    pub privileges: Vec<String>,
+
+    /// Column-level privileges not already covered by a table-level privilege
+    pub column_privileges: Vec<ColumnPrivileges>,
+
+    /// Whether row-level security is enabled on the table
+    pub rls_enabled: bool,
+
+    /// Whether row-level security also applies to the table owner
+    pub rls_forced: bool,
+
+    /// Row-level security policies defined on the table
+    pub policies: Vec<RlsPolicy>,
+}
+
+/// Privileges held on a single column
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ColumnPrivileges {
+    /// Column name
+    pub column: String,
+
+    /// Column privileges (e.g. "SELECT", "UPDATE")
+    pub privileges: Vec<String>,
+}
+
+/// Row-level security policy
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RlsPolicy {
+    /// Policy name
+    pub name: String,
+
+    /// Command the policy applies to ("ALL", "SELECT", "INSERT", "UPDATE", "DELETE")
+    pub command: String,
+
+    /// Whether the policy is permissive (OR-combined) rather than restrictive (AND-combined)
+    pub permissive: bool,
+
+    /// Roles the policy applies to ("public" means every role)
+    pub roles: Vec<String>,
+
+    /// Whether the policy applies to the connected user
+    pub applies_to_user: bool,
+
+    /// Row filter for existing rows (USING expression)
+    pub using: Option<String>,
+
+    /// Row filter for new rows (WITH CHECK expression)
+    pub with_check: Option<String>,
+}
+
+/// Single privilege grant parsed from `SHOW GRANTS`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GrantInfo {
+    /// Privileges granted on the whole object (e.g. "SELECT", "ALL PRIVILEGES")
+    pub privileges: Vec<String>,
+
+    /// Privileges granted on individual columns
+    pub column_privileges: Vec<ColumnPrivileges>,
+
+    /// Object kind when not a table or database (e.g. "PROCEDURE", "FUNCTION")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_type: Option<String>,
+
+    /// Database the grant applies to ("*" for all databases)
+    pub database: String,
+
+    /// Table or routine the grant applies to ("*" for all objects in the database)
+    pub object: String,
+
+    /// Whether the user may grant these privileges to others
+    pub with_grant_option: bool,
+}
+
+/// File access for file-based engines
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FileAccess {
+    /// Database file path
+    pub path: String,
+
+    /// Whether the file is writable according to its permissions
+    /// (Plenum itself always opens it read-only)
+    pub writable: bool,
+}
+
+/// Index summary (used in `ListIndexes` operation)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IndexSummary {
+    /// Index name
+    pub name: String,
+
+    /// Table the index belongs to
+    pub table: String,
+
+    /// Whether this is a unique index
+    pub unique: bool,
+
+    /// Column names included in the index
+    pub columns: Vec<String>,
+}
+
+/// View information
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ViewInfo {
+    /// View name
+    pub name: String,
+
+    /// Schema name (for engines that support schemas)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// View definition (SQL source, if available)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+
+    /// View columns
+    pub columns: Vec<ColumnInfo>,
+}
+
+/// Default number of statements returned by a `TopQueries` inspection
+pub const DEFAULT_TOP_QUERIES_LIMIT: usize = 10;
+
+/// Inspection operation types
+///
+/// Inspections report live server state (sessions, locks, statement
+/// statistics) rather than schema. Each operation returns different data
+/// (see `InspectResult`).
+#[derive(Debug, Clone)]
+pub enum InspectOperation {
+    /// Sessions currently connected to the server and the lock waits between them
+    Activity,
+
+    /// Most expensive normalized statements from the server's statement statistics
+    TopQueries {
+        /// Metric to rank statements by
+        order_by: QueryRanking,
+        /// Maximum number of statements to return
+        limit: usize,
+    },
+}
+
+impl InspectOperation {
+    /// Operation name as used in error messages (e.g. `Activity`)
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Activity => "Activity",
+            Self::TopQueries { .. } => "TopQueries",
+        }
+    }
+}
+
+/// Metric used to rank statements in a `TopQueries` inspection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryRanking {
+    /// Cumulative execution time across all calls
+    TotalTime,
+    /// Average execution time per call
+    MeanTime,
+    /// Number of executions
+    Calls,
+    /// Rows returned or affected across all calls
+    Rows,
+}
+
+impl QueryRanking {
+    /// Parse a ranking name; accepts `total_time` and `total-time` spellings
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.replace('-', "_").as_str() {
+            "total_time" => Some(Self::TotalTime),
+            "mean_time" => Some(Self::MeanTime),
+            "calls" => Some(Self::Calls),
+            "rows" => Some(Self::Rows),
+            _ => None,
+        }
+    }
+}
+
+/// Inspection result
+///
+/// The result type depends on which `InspectOperation` was requested.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InspectResult {
+    /// Live sessions and lock waits
+    Activity {
+        /// Activity report
+        activity: ActivityReport,
+    },
+
+    /// Most expensive normalized statements
+    TopQueries {
+        /// Top-queries report
+        top_queries: TopQueriesReport,
+    },
+}
+
+/// Live sessions and lock waits (used in `Activity` inspection)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ActivityReport {
+    /// Sessions ordered by pid, excluding Plenum's own
+    pub sessions: Vec<SessionActivity>,
+
+    /// Lock-wait edges: `waiting_pid` is blocked by `blocking_pid`
+    pub lock_waits: Vec<LockWait>,
+}
+
+impl ActivityReport {
+    /// Replace string and numeric literals in every session's query text with `?`
+    pub fn redact_queries(&mut self, engine: DatabaseType) {
+        for session in &mut self.sessions {
+            if let Some(query) = &session.query {
+                session.query = Some(crate::capability::redact_literals(query, engine));
+            }
+        }
+    }
+}
+
+/// Single server session
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SessionActivity {
+    /// Backend process ID (`PostgreSQL`) or connection ID (`MySQL`)
+    pub pid: i64,
+
+    /// Session user
+    pub user: Option<String>,
+
+    /// Current database
+    pub database: Option<String>,
+
+    /// Client application name, when the engine reports one
+    pub application: Option<String>,
+
+    /// Session state (e.g. "active", "idle in transaction" on `PostgreSQL`; "Query", "Sleep" on `MySQL`)
+    pub state: Option<String>,
+
+    /// Current or most recent statement text
+    pub query: Option<String>,
+
+    /// Time spent in the current state, in milliseconds
+    pub duration_ms: Option<u64>,
+
+    /// What the session is waiting on (e.g. "Lock:transactionid"); null when not waiting
+    pub wait_event: Option<String>,
+}
+
+/// Lock-wait edge between two sessions
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LockWait {
+    /// Session waiting for the lock
+    pub waiting_pid: i64,
+
+    /// Session holding (or queued ahead for) a conflicting lock
+    pub blocking_pid: i64,
+
+    /// Lock type (e.g. "relation", "transactionid" on `PostgreSQL`; "RECORD", "TABLE" on `MySQL`)
+    pub lock_type: Option<String>,
+
+    /// Requested lock mode
+    pub mode: Option<String>,
+
+    /// Locked object, when the lock is on a table
+    pub object: Option<String>,
+}
+
+/// Statement statistics ranked by one metric (used in `TopQueries` inspection)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TopQueriesReport {
+    /// Metric the statements are ranked by (descending)
+    pub order_by: QueryRanking,
+
+    /// Ranked statements
+    pub statements: Vec<StatementStats>,
+
+    /// Caveats about the source (statistics unavailable, text hidden, counters since reset)
+    pub notes: Vec<String>,
+}
+
+/// Aggregated statistics for one normalized statement
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StatementStats {
+    /// Engine digest identifying the normalized statement (`queryid` on `PostgreSQL`, `DIGEST` on `MySQL`)
+    pub digest: String,
+
+    /// Normalized statement text with literals replaced by placeholders
+    pub query: String,
+
+    /// Database the statement ran in, when the engine reports one
+    pub database: Option<String>,
+
+    /// Number of executions
+    pub calls: u64,
+
+    /// Cumulative execution time in milliseconds
+    pub total_time_ms: f64,
+
+    /// Average execution time per call in milliseconds
+    pub mean_time_ms: f64,
+
+    /// Rows returned or affected across all calls
+    pub rows: u64,
+}
+
+/// Database engine trait
+///
+/// All database engines implement this trait.
+/// Each method is stateless and takes a connection config as input.
+pub trait DatabaseEngine {
+    /// Validate connection and return connection information
+    ///
+    /// This method:
     /// 1. Opens a connection using the provided config
     /// 2. Retrieves server version and connection metadata
     /// 3. Closes the connection
@@ -646,6 +1940,16 @@ pub trait DatabaseEngine {
         config: &ConnectionConfig,
     ) -> impl std::future::Future<Output = Result<ConnectionInfo>> + Send;
 
+    /// Audit the configured credentials against Plenum's least-privilege model
+    ///
+    /// Reports whether the user could write despite Plenum's read-only guards:
+    /// write or DDL grants, superuser status, table ownership. Also reports
+    /// whether the server is a read-only replica. Returns an empty list when
+    /// there is nothing to report.
+    fn audit_privileges(
+        config: &ConnectionConfig,
+    ) -> impl std::future::Future<Output = Result<Vec<PrivilegeWarning>>> + Send;
+
     /// Introspect database schema
     ///
     /// This method:
@@ -672,6 +1976,19 @@ pub trait DatabaseEngine {
         schema: Option<&str>,
     ) -> impl std::future::Future<Output = Result<IntrospectResult>> + Send;
 
+    /// Inspect live server state
+    ///
+    /// Opens a connection, reads the engine's activity views, and closes it.
+    ///
+    /// # Errors
+    /// - Operation not supported by engine (file-based engines have no server sessions)
+    /// - Connection failure
+    /// - Missing privileges on the underlying system views
+    fn inspect(
+        config: &ConnectionConfig,
+        operation: &InspectOperation,
+    ) -> impl std::future::Future<Output = Result<InspectResult>> + Send;
+
     /// Execute a query with capability constraints
     ///
     /// This method:
@@ -692,6 +2009,19 @@ pub trait DatabaseEngine {
         params: &[serde_json::Value],
         caps: &Capabilities,
     ) -> impl std::future::Future<Output = Result<QueryResult>> + Send;
+
+    /// Execute a query, handing rows to `sink` as they are fetched
+    ///
+    /// Same guards as `execute` (read-only validation, `timeout_ms`, `offset`,
+    /// `max_rows`), but rows are fetched incrementally and never buffered as a
+    /// whole result set. Structured EXPLAIN is not supported.
+    fn execute_stream(
+        config: &ConnectionConfig,
+        query: &str,
+        params: &[serde_json::Value],
+        caps: &Capabilities,
+        sink: &mut dyn RowSink,
+    ) -> impl std::future::Future<Output = Result<StreamSummary>> + Send;
 }
 
 /// Change to a column's properties between two schemas
@@ -794,6 +2124,74 @@ pub struct SchemaDiff {
     pub views_changed: Vec<ViewDiff>,
 }
 
+/// One column that differs between the base and diff-against versions of a row
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CellChange {
+    /// Column name
+    pub column: String,
+    /// Value in the base connection
+    pub from: serde_json::Value,
+    /// Value in the diff-against connection
+    pub to: serde_json::Value,
+}
+
+/// A row whose key is present on both connections with different values
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RowChange {
+    /// Key column values identifying the row
+    pub key: serde_json::Map<String, serde_json::Value>,
+    /// Compared columns whose values differ, in result column order
+    pub changes: Vec<CellChange>,
+}
+
+/// Row counts of a data diff; complete even when the row lists are truncated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DataDiffCounts {
+    /// Keys present only in the diff-against result
+    pub added: usize,
+    /// Keys present only in the base result
+    pub removed: usize,
+    /// Keys present in both results with different values
+    pub changed: usize,
+    /// Keys present in both results with identical values
+    pub unchanged: usize,
+}
+
+/// Data diff between the results of the same query on two connections
+///
+/// Produced by `plenum query --diff-against <name> --key <column>`. Rows are
+/// matched by their key columns and compared on the columns both results return.
+/// Row lists are sorted by key for deterministic output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DataDiff {
+    /// Key columns rows are matched on
+    pub key: Vec<String>,
+    /// Columns returned by both queries and compared, in base result order
+    pub columns: Vec<String>,
+    /// Columns returned only by the diff-against query
+    pub columns_added: Vec<String>,
+    /// Columns returned only by the base query
+    pub columns_removed: Vec<String>,
+    /// Rows returned on the base connection
+    pub base_rows: usize,
+    /// Rows returned on the diff-against connection
+    pub target_rows: usize,
+    /// Number of added, removed, changed and unchanged rows
+    pub counts: DataDiffCounts,
+    /// Rows present only in the diff-against result, as column → value objects
+    pub rows_added: Vec<serde_json::Map<String, serde_json::Value>>,
+    /// Rows present only in the base result, as column → value objects
+    pub rows_removed: Vec<serde_json::Map<String, serde_json::Value>>,
+    /// Rows present in both results with per-column differences
+    pub rows_changed: Vec<RowChange>,
+    /// Whether a row list was cut at `max_rows` (see `counts` for the totals)
+    pub truncated: bool,
+    /// Key-hash chunks both results were compared in
+    pub chunks_compared: usize,
+    /// Chunks whose digests differed and were compared row by row
+    pub chunks_differing: usize,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -938,6 +2336,8 @@ mod tests {
             rows_truncated: false,
             truncated_by: None,
             plan: None,
+            next_cursor: None,
+            ordering: None,
         };
         // Budget tight enough for 2 rows but not 3
         apply_byte_budget(&mut result, 30);
@@ -957,6 +2357,8 @@ mod tests {
             rows_truncated: false,
             truncated_by: None,
             plan: None,
+            next_cursor: None,
+            ordering: None,
         };
         apply_byte_budget(&mut result, 1_000_000);
         assert_eq!(result.rows.len(), 2);
@@ -975,10 +2377,90 @@ mod tests {
             rows_truncated: false,
             truncated_by: None,
             plan: None,
+            next_cursor: None,
+            ordering: None,
         };
         apply_byte_budget(&mut result, 0);
         assert_eq!(result.rows.len(), 0);
         assert!(result.rows_truncated);
         assert_eq!(result.truncated_by.as_deref(), Some("bytes"));
     }
+
+    fn table_size(name: &str, total_bytes: Option<u64>, row_estimate: Option<u64>) -> TableSize {
+        TableSize {
+            name: name.to_string(),
+            schema: None,
+            table_bytes: total_bytes,
+            index_bytes: total_bytes.map(|_| 0),
+            toast_bytes: None,
+            total_bytes,
+            row_estimate,
+        }
+    }
+
+    #[test]
+    fn test_size_report_sorts_largest_first_unknown_last() {
+        let tables = vec![
+            table_size("unknown_big", None, Some(1000)),
+            table_size("small", Some(10), None),
+            table_size("b_large", Some(500), None),
+            table_size("a_large", Some(500), None),
+            table_size("unknown_small", None, Some(1)),
+        ];
+        let report = SizeReport::paginate(Some(2048), tables, &SizeOptions::default(), Vec::new());
+        let names: Vec<&str> = report.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["a_large", "b_large", "small", "unknown_big", "unknown_small"]);
+        assert_eq!(report.total_tables, 5);
+        assert!(!report.has_more);
+    }
+
+    #[test]
+    fn test_size_report_paginates_after_sorting() {
+        let tables = (1..=5).map(|n| table_size(&format!("t{n}"), Some(n * 100), None)).collect();
+        let report =
+            SizeReport::paginate(None, tables, &SizeOptions { limit: 2, offset: 1 }, Vec::new());
+        let names: Vec<&str> = report.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["t4", "t3"]);
+        assert_eq!(report.total_tables, 5);
+        assert_eq!(report.offset, 1);
+        assert!(report.has_more);
+
+        let tables = (1..=5).map(|n| table_size(&format!("t{n}"), Some(n * 100), None)).collect();
+        let last =
+            SizeReport::paginate(None, tables, &SizeOptions { limit: 2, offset: 4 }, Vec::new());
+        assert_eq!(last.tables.len(), 1);
+        assert!(!last.has_more);
+    }
+
+    #[test]
+    fn test_glob_match_wildcards() {
+        assert!(glob_match("max_conn*", "max_connections"));
+        assert!(glob_match("*timeout*", "statement_timeout"));
+        assert!(glob_match("work_me?", "work_mem"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("TimeZone", "timezone"));
+        assert!(glob_match("a*b*c", "aXXbYYbZc"));
+        assert!(!glob_match("timeout", "statement_timeout"));
+        assert!(!glob_match("work_me?", "work_memory"));
+        assert!(!glob_match("a*b*c", "aXXbYY"));
+    }
+
+    #[test]
+    fn test_filter_settings_matches_and_sorts() {
+        let setting = |name: &str| SettingInfo {
+            name: name.to_string(),
+            value: None,
+            unit: None,
+            source: None,
+            session_changeable: None,
+        };
+        let settings = vec![setting("work_mem"), setting("TimeZone"), setting("statement_timeout")];
+        let all = filter_settings(settings.clone(), None);
+        let names: Vec<&str> = all.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["statement_timeout", "TimeZone", "work_mem"]);
+
+        let matched = filter_settings(settings, Some("*time*"));
+        let names: Vec<&str> = matched.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["statement_timeout", "TimeZone"]);
+    }
 }
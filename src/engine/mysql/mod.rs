@@ -23,14 +23,21 @@ use mysql_async::{prelude::*, Conn, OptsBuilder, Params, Row, SslOpts, Value};
 use std::collections::HashMap; // Used for grouping foreign keys during introspection
 use std::time::{Duration, Instant};
 
-use crate::capability::{strip_explain_prefix, validate_query};
+use crate::capability::{strip_explain_prefix, structured_explain_analyze, validate_query};
+use crate::engine::cell::CellFormat;
+use crate::engine::stream::{cancelled_or, reject_structured_explain, RowCollector, RowFeed};
 use crate::engine::{
-    is_explain_query, Capabilities, ColumnInfo, ConnectionConfig, ConnectionInfo, DatabaseEngine,
-    DatabaseType, ExplainFormat, ExplainPlanNode, ForeignKeyInfo, IndexInfo, IndexSummary,
-    IntrospectOperation, IntrospectResult, QueryResult, SslMode, TableFields, TableInfo, TlsConfig,
-    ViewInfo,
+    filter_settings, is_explain_query, profile, ActivityReport, Capabilities, ColumnInfo,
+    ColumnPrivileges, ConnectionConfig, ConnectionInfo, DatabaseEngine, DatabaseType,
+    ExplainFormat, ExplainPlanNode, ForeignKeyInfo, GrantInfo, IndexFinding, IndexHealthReport,
+    IndexInfo, IndexSummary, InspectOperation, InspectResult, IntrospectOperation,
+    IntrospectResult, LockWait, PlanOperation, PrivilegeReport, PrivilegeWarning, QueryControl,
+    QueryRanking, QueryResult, ResultColumn, RowSink, SampleOptions, SessionActivity, SettingInfo,
+    SizeOptions, SizeReport, SslMode, StatementStats, StreamSummary, TableFields, TableInfo,
+    TableSize, TlsConfig, TopQueriesReport, ViewInfo,
 };
 use crate::error::{PlenumError, Result};
+use crate::params::{Date, ParamValue, Time};
 
 /// Extra grace added to the server-side `MAX_EXECUTION_TIME` on top of the
 /// client-side deadline.
@@ -45,6 +52,15 @@ use crate::error::{PlenumError, Result};
 /// success, defeating the timeout (REF-258 Bug 4).
 const SERVER_TIMEOUT_BACKSTOP_GRACE: Duration = Duration::from_secs(5);
 
+/// Tables are reported as bloated once `data_free` reaches this many bytes...
+///
+/// `InnoDB` keeps a few extents free in every file-per-table tablespace, so small
+/// amounts of free space are normal.
+const BLOAT_MIN_FREE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// ...and this fraction of the table's total allocated space.
+const BLOAT_MIN_FREE_FRACTION: f64 = 0.2;
+
 /// `MySQL` database engine implementation
 pub struct MySqlEngine;
 
@@ -118,7 +134,25 @@ impl DatabaseEngine for MySqlEngine {
             .await
             .map_err(|e| PlenumError::connection_failed(format!("Failed to disconnect: {e}")))?;
 
-        Ok(ConnectionInfo { database_version, server_info, connected_database, user })
+        Ok(ConnectionInfo {
+            database_version,
+            server_info,
+            connected_database,
+            user,
+            warnings: Vec::new(),
+        })
+    }
+
+    async fn audit_privileges(config: &ConnectionConfig) -> Result<Vec<PrivilegeWarning>> {
+        let opts = build_mysql_opts(config)?;
+        let mut conn = Conn::new(opts).await.map_err(|e| {
+            PlenumError::connection_failed(format!("Failed to connect to MySQL: {e}"))
+        })?;
+        let warnings = audit_privileges_mysql(&mut conn).await?;
+        conn.disconnect().await.map_err(|e| {
+            PlenumError::engine_error("mysql", format!("Failed to disconnect: {e}"))
+        })?;
+        Ok(warnings)
     }
 
     async fn introspect(
@@ -184,6 +218,71 @@ impl DatabaseEngine for MySqlEngine {
                 let target_schema = determine_target_schema(&mut conn, schema).await?;
                 get_view_details_mysql(&mut conn, &target_schema, name).await?
             }
+
+            IntrospectOperation::ListSequences
+            | IntrospectOperation::ListMacros
+            | IntrospectOperation::ListTypes
+            | IntrospectOperation::ListConstraints { .. }
+            | IntrospectOperation::ListAttachedDatabases => {
+                return Err(PlenumError::invalid_input(format!(
+                    "MySQL does not support {} operation (DuckDB catalog introspection only)",
+                    operation.name()
+                )));
+            }
+
+            // information_schema carries no per-column value statistics, so always sample
+            IntrospectOperation::ProfileTable { name, options } => {
+                let target_schema = determine_target_schema(&mut conn, schema).await?;
+                let key_columns = sample_key_columns(&mut conn, &target_schema, name).await?;
+                let sql = sample_query_mysql(
+                    &target_schema,
+                    name,
+                    &key_columns,
+                    &options.sample_options(),
+                );
+                let profile = profile::profile_by_sampling::<Self>(
+                    &effective_config,
+                    &sql,
+                    name,
+                    Some(&target_schema),
+                    options,
+                )
+                .await?;
+                IntrospectResult::TableProfile { profile }
+            }
+
+            // No native seeded sampling: order by a seeded hash of the primary key
+            // (or of every column when there is none) and take the first rows.
+            IntrospectOperation::SampleTable { name, options } => {
+                let target_schema = determine_target_schema(&mut conn, schema).await?;
+                let key_columns = sample_key_columns(&mut conn, &target_schema, name).await?;
+                let sql = sample_query_mysql(&target_schema, name, &key_columns, options);
+                let caps = Capabilities::new(Some(options.size), options.timeout_ms);
+                let sample = Self::execute(&effective_config, &sql, &[], &caps).await?;
+                IntrospectResult::TableSample { sample }
+            }
+
+            IntrospectOperation::ListPrivileges { table } => match table {
+                Some(table) => {
+                    let target_schema = determine_target_schema(&mut conn, schema).await?;
+                    list_privileges_mysql(&mut conn, Some((&target_schema, table))).await?
+                }
+                None => list_privileges_mysql(&mut conn, None).await?,
+            },
+
+            IntrospectOperation::IndexHealth { table } => {
+                let target_schema = determine_target_schema(&mut conn, schema).await?;
+                index_health_mysql(&mut conn, &target_schema, table.as_deref()).await?
+            }
+
+            IntrospectOperation::Sizes { options } => {
+                let target_schema = determine_target_schema(&mut conn, schema).await?;
+                sizes_mysql(&mut conn, &target_schema, options).await?
+            }
+
+            IntrospectOperation::Settings { pattern } => {
+                settings_mysql(&mut conn, pattern.as_deref()).await?
+            }
         };
 
         // Close connection
@@ -194,12 +293,10 @@ impl DatabaseEngine for MySqlEngine {
         Ok(result)
     }
 
-    async fn execute(
+    async fn inspect(
         config: &ConnectionConfig,
-        query: &str,
-        params: &[serde_json::Value],
-        caps: &Capabilities,
-    ) -> Result<QueryResult> {
+        operation: &InspectOperation,
+    ) -> Result<InspectResult> {
         // Validate config is for MySQL
         if config.engine != DatabaseType::MySQL {
             return Err(PlenumError::invalid_input(format!(
@@ -208,51 +305,32 @@ impl DatabaseEngine for MySqlEngine {
             )));
         }
 
-        // Validate query against capabilities
-        validate_query(query, caps, DatabaseType::MySQL)?;
-
-        // Build connection options
         let opts = build_mysql_opts(config)?;
-
-        // Connect to MySQL
         let mut conn = Conn::new(opts).await.map_err(|e| {
             PlenumError::connection_failed(format!("Failed to connect to MySQL: {e}"))
         })?;
 
-        // Defense in depth: enforce session-level read-only at the database layer.
-        // This rejects DML writes even if the SQL parser is somehow bypassed (REF-261).
-        // Note: MySQL DDL (CREATE/DROP/ALTER) causes implicit commits and is not covered
-        // by transaction read-only mode — that class is already blocked by the parser.
-        conn.exec_drop("SET SESSION TRANSACTION READ ONLY", ()).await.map_err(|e| {
-            PlenumError::engine_error(
-                "mysql",
-                format!("Failed to enforce session read-only mode: {e}"),
-            )
+        let result = match operation {
+            InspectOperation::Activity => activity_mysql(&mut conn).await?,
+            InspectOperation::TopQueries { order_by, limit } => {
+                top_queries_mysql(&mut conn, *order_by, *limit).await?
+            }
+        };
+
+        conn.disconnect().await.map_err(|e| {
+            PlenumError::engine_error("mysql", format!("Failed to disconnect: {e}"))
         })?;
 
-        // Set server-side MAX_EXECUTION_TIME as a cleanup backstop so MySQL eventually
-        // cancels a query the client has abandoned. It is set LONGER than the client-side
-        // deadline (see SERVER_TIMEOUT_BACKSTOP_GRACE): the client-side timeout is the
-        // authoritative one, because MAX_EXECUTION_TIME does not reliably error for
-        // statements that swallow the interrupt (e.g. SELECT SLEEP()). Only applies to
-        // SELECT statements in MySQL.
-        if let Some(timeout_ms) = caps.timeout_ms {
-            let server_limit = Duration::from_millis(timeout_ms) + SERVER_TIMEOUT_BACKSTOP_GRACE;
-            conn.exec_drop(
-                format!("SET SESSION MAX_EXECUTION_TIME = {}", server_limit.as_millis()),
-                (),
-            )
-            .await
-            .map_err(|e| {
-                PlenumError::engine_error("mysql", format!("Failed to set MAX_EXECUTION_TIME: {e}"))
-            })?;
-        }
+        Ok(result)
+    }
 
-        // The client-side tokio timeout is the authoritative deadline: it fires at exactly
-        // timeout_ms and surfaces QUERY_TIMEOUT. This guarantees a bounded query that runs
-        // to the limit is reported as a timeout error rather than as a success with partial
-        // or interrupted data (REF-258 Bug 4) — even for statements like SELECT SLEEP() that
-        // the server-side MAX_EXECUTION_TIME would let return "successfully".
+    async fn execute(
+        config: &ConnectionConfig,
+        query: &str,
+        params: &[serde_json::Value],
+        caps: &Capabilities,
+    ) -> Result<QueryResult> {
+        let mut conn = connect_for_query(config, query, caps).await?;
 
         // Structured explain path: rewrite to EXPLAIN FORMAT=JSON, normalize the plan tree.
         if caps.explain_format == Some(ExplainFormat::Structured) {
@@ -262,9 +340,27 @@ impl DatabaseEngine for MySqlEngine {
                      non-EXPLAIN queries must omit this flag",
                 ));
             }
+            let analyze = structured_explain_analyze(query, DatabaseType::MySQL)?;
             let inner = strip_explain_prefix(query);
             let start = Instant::now();
-            let plan = execute_structured_explain_mysql(&mut conn, &inner).await?;
+            let plan = if analyze {
+                // EXPLAIN ANALYZE runs the query, so it gets the same client-side deadline
+                let explain = execute_structured_explain_analyze_mysql(&mut conn, &inner, params);
+                match caps.timeout_ms {
+                    Some(timeout_ms) => {
+                        tokio::time::timeout(Duration::from_millis(timeout_ms), explain)
+                            .await
+                            .map_err(|_| {
+                                PlenumError::query_timeout(format!(
+                                    "Query exceeded the client-side timeout of {timeout_ms}ms"
+                                ))
+                            })??
+                    }
+                    None => explain.await?,
+                }
+            } else {
+                execute_structured_explain_mysql(&mut conn, &inner, params).await?
+            };
             let elapsed = start.elapsed();
             conn.disconnect().await.ok();
             return Ok(QueryResult {
@@ -274,34 +370,149 @@ impl DatabaseEngine for MySqlEngine {
                 execution_ms: elapsed.as_millis() as u64,
                 rows_truncated: false,
                 truncated_by: None,
-                plan: Some(plan),
+                plan: Some(Box::new(plan)),
+                next_cursor: None,
+                ordering: None,
             });
         }
 
-        let start = Instant::now();
-        let mut query_result = if let Some(timeout_ms) = caps.timeout_ms {
-            let deadline = Duration::from_millis(timeout_ms);
-            tokio::time::timeout(deadline, execute_query(&mut conn, query, params, caps))
-                .await
-                .map_err(|_| {
-                PlenumError::query_timeout(format!(
-                    "Query exceeded the client-side timeout of {timeout_ms}ms"
-                ))
-            })??
-        } else {
-            execute_query(&mut conn, query, params, caps).await?
-        };
-
-        let elapsed = start.elapsed();
-        query_result.execution_ms = elapsed.as_millis() as u64;
+        let mut rows = RowCollector::default();
+        let summary = execute_with_deadline(&mut conn, query, params, caps, &mut rows)
+            .await
+            .map_err(|e| cancelled_or(caps, e))?;
 
         // Close connection
         conn.disconnect().await.map_err(|e| {
             PlenumError::engine_error("mysql", format!("Failed to disconnect: {e}"))
         })?;
 
-        Ok(query_result)
+        Ok(rows.into_result(summary))
+    }
+
+    async fn execute_stream(
+        config: &ConnectionConfig,
+        query: &str,
+        params: &[serde_json::Value],
+        caps: &Capabilities,
+        sink: &mut dyn RowSink,
+    ) -> Result<StreamSummary> {
+        reject_structured_explain(caps)?;
+        let mut conn = connect_for_query(config, query, caps).await?;
+        let summary = execute_with_deadline(&mut conn, query, params, caps, sink)
+            .await
+            .map_err(|e| cancelled_or(caps, e))?;
+        conn.disconnect().await.map_err(|e| {
+            PlenumError::engine_error("mysql", format!("Failed to disconnect: {e}"))
+        })?;
+        Ok(summary)
+    }
+}
+
+/// Validate `query`, connect, and arm the session's read-only mode and
+/// `MAX_EXECUTION_TIME` backstop.
+async fn connect_for_query(
+    config: &ConnectionConfig,
+    query: &str,
+    caps: &Capabilities,
+) -> Result<Conn> {
+    // Validate config is for MySQL
+    if config.engine != DatabaseType::MySQL {
+        return Err(PlenumError::invalid_input(format!(
+            "Expected MySQL engine, got {}",
+            config.engine
+        )));
+    }
+
+    // Validate query against capabilities
+    validate_query(query, caps, DatabaseType::MySQL)?;
+
+    // Build connection options
+    let opts = build_mysql_opts(config)?;
+
+    // Connect to MySQL
+    let mut conn = Conn::new(opts.clone())
+        .await
+        .map_err(|e| PlenumError::connection_failed(format!("Failed to connect to MySQL: {e}")))?;
+
+    if let Some(control) = &caps.control {
+        arm_cancel(conn.id(), opts, control)?;
+    }
+
+    // Defense in depth: enforce session-level read-only at the database layer.
+    // This rejects DML writes even if the SQL parser is somehow bypassed (REF-261).
+    // Note: MySQL DDL (CREATE/DROP/ALTER) causes implicit commits and is not covered
+    // by transaction read-only mode — that class is already blocked by the parser.
+    conn.exec_drop("SET SESSION TRANSACTION READ ONLY", ()).await.map_err(|e| {
+        PlenumError::engine_error("mysql", format!("Failed to enforce session read-only mode: {e}"))
+    })?;
+
+    // Set server-side MAX_EXECUTION_TIME as a cleanup backstop so MySQL eventually
+    // cancels a query the client has abandoned. It is set LONGER than the client-side
+    // deadline (see SERVER_TIMEOUT_BACKSTOP_GRACE): the client-side timeout is the
+    // authoritative one, because MAX_EXECUTION_TIME does not reliably error for
+    // statements that swallow the interrupt (e.g. SELECT SLEEP()). Only applies to
+    // SELECT statements in MySQL.
+    if let Some(timeout_ms) = caps.timeout_ms {
+        let server_limit = Duration::from_millis(timeout_ms) + SERVER_TIMEOUT_BACKSTOP_GRACE;
+        conn.exec_drop(
+            format!("SET SESSION MAX_EXECUTION_TIME = {}", server_limit.as_millis()),
+            (),
+        )
+        .await
+        .map_err(|e| {
+            PlenumError::engine_error("mysql", format!("Failed to set MAX_EXECUTION_TIME: {e}"))
+        })?;
     }
+
+    Ok(conn)
+}
+
+/// Issue `KILL QUERY` for session `connection_id` from a second connection when the
+/// MCP client cancels the query. `KILL QUERY` stops only the running statement, so
+/// a late cancel leaves the (soon closed) session untouched.
+fn arm_cancel(connection_id: u32, opts: OptsBuilder, control: &QueryControl) -> Result<()> {
+    let runtime = tokio::runtime::Handle::current();
+    control.on_cancel(move || {
+        runtime.spawn(async move {
+            if let Ok(mut killer) = Conn::new(opts).await {
+                let _ = killer.query_drop(format!("KILL QUERY {connection_id}")).await;
+                let _ = killer.disconnect().await;
+            }
+        });
+    });
+    control.check()
+}
+
+/// Run `execute_query` under the client-side deadline and record its duration.
+///
+/// The client-side tokio timeout is the authoritative deadline: it fires at exactly
+/// `timeout_ms` and surfaces `QUERY_TIMEOUT`. This guarantees a bounded query that runs
+/// to the limit is reported as a timeout error rather than as a success with partial
+/// or interrupted data (REF-258 Bug 4) — even for statements like `SELECT SLEEP()` that
+/// the server-side `MAX_EXECUTION_TIME` would let return "successfully".
+async fn execute_with_deadline(
+    conn: &mut Conn,
+    query: &str,
+    params: &[serde_json::Value],
+    caps: &Capabilities,
+    sink: &mut dyn RowSink,
+) -> Result<StreamSummary> {
+    let start = Instant::now();
+    let mut summary = if let Some(timeout_ms) = caps.timeout_ms {
+        let deadline = Duration::from_millis(timeout_ms);
+        tokio::time::timeout(deadline, execute_query(conn, query, params, caps, sink))
+            .await
+            .map_err(|_| {
+                PlenumError::query_timeout(format!(
+                    "Query exceeded the client-side timeout of {timeout_ms}ms"
+                ))
+            })??
+    } else {
+        execute_query(conn, query, params, caps, sink).await?
+    };
+
+    summary.execution_ms = start.elapsed().as_millis() as u64;
+    Ok(summary)
 }
 
 /// Build `MySQL` connection options from `ConnectionConfig`
@@ -935,9 +1146,672 @@ async fn get_index_columns(
     Ok(columns)
 }
 
+/// Report the current user's grants from `SHOW GRANTS`.
+///
+/// With a table filter, only grants covering that table (directly, through its
+/// database, or globally) are returned.
+async fn list_privileges_mysql(
+    conn: &mut Conn,
+    table_filter: Option<(&str, &str)>,
+) -> Result<IntrospectResult> {
+    let user: String = conn
+        .query_first("SELECT CURRENT_USER()")
+        .await
+        .map_err(|e| {
+            PlenumError::engine_error("mysql", format!("Failed to query current user: {e}"))
+        })?
+        .ok_or_else(|| PlenumError::engine_error("mysql", "No user returned".to_string()))?;
+
+    let lines: Vec<String> = conn
+        .query("SHOW GRANTS")
+        .await
+        .map_err(|e| PlenumError::engine_error("mysql", format!("Failed to show grants: {e}")))?;
+
+    let mut roles = Vec::new();
+    let mut grants = Vec::new();
+    for line in &lines {
+        match parse_mysql_grant(line) {
+            Some(MysqlGrant::Roles(granted)) => roles.extend(granted),
+            Some(MysqlGrant::Privileges(grant)) => {
+                let covers = table_filter.is_none_or(|(schema, table)| {
+                    (grant.database == "*" || grant.database == schema)
+                        && (grant.object == "*" || grant.object == table)
+                });
+                if covers {
+                    grants.push(grant);
+                }
+            }
+            None => {}
+        }
+    }
+
+    Ok(IntrospectResult::Privileges {
+        privileges: PrivilegeReport {
+            user,
+            scope: "database".to_string(),
+            roles,
+            tables: Vec::new(),
+            grants,
+            file: None,
+        },
+    })
+}
+
+/// Report data and index sizes for base tables in the target schema from `information_schema.TABLES`.
+///
+/// The database size is the sum over all tables in the schema, not just the returned page.
+async fn sizes_mysql(
+    conn: &mut Conn,
+    schema: &str,
+    options: &SizeOptions,
+) -> Result<IntrospectResult> {
+    let query = "SELECT table_name, data_length, index_length, table_rows
+                 FROM information_schema.tables
+                 WHERE table_schema = ? AND table_type = 'BASE TABLE'";
+    let rows: Vec<Row> = conn.exec(query, (schema,)).await.map_err(|e| {
+        PlenumError::engine_error("mysql", format!("Failed to query table sizes: {e}"))
+    })?;
+    let mut tables = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let name: String = row.get(0).ok_or_else(|| {
+            PlenumError::engine_error("mysql", "Failed to extract table name".to_string())
+        })?;
+        let table_bytes: Option<u64> = row.get::<Option<u64>, _>(1).flatten();
+        let index_bytes: Option<u64> = row.get::<Option<u64>, _>(2).flatten();
+        tables.push(TableSize {
+            name,
+            schema: Some(schema.to_string()),
+            table_bytes,
+            index_bytes,
+            toast_bytes: None,
+            total_bytes: table_bytes.zip(index_bytes).map(|(data, index)| data + index),
+            row_estimate: row.get::<Option<u64>, _>(3).flatten(),
+        });
+    }
+    let database_bytes = tables.iter().filter_map(|t| t.total_bytes).sum();
+
+    let notes =
+        vec!["Sizes are storage-engine estimates from information_schema.TABLES, cached for \
+         information_schema_stats_expiry seconds; ANALYZE TABLE refreshes them"
+            .to_string()];
+    Ok(IntrospectResult::Sizes {
+        sizes: SizeReport::paginate(Some(database_bytes), tables, options, notes),
+    })
+}
+
+/// Report session system variables from `SHOW SESSION VARIABLES`.
+///
+/// The value source comes from `performance_schema.variables_info` and the scope from
+/// `performance_schema.variables_metadata` (8.0.32+); when either is unreadable the
+/// corresponding field is null rather than failing the report. `MySQL` reports no units.
+async fn settings_mysql(conn: &mut Conn, pattern: Option<&str>) -> Result<IntrospectResult> {
+    let rows: Vec<Row> = conn.query("SHOW SESSION VARIABLES").await.map_err(|e| {
+        PlenumError::engine_error("mysql", format!("Failed to query session variables: {e}"))
+    })?;
+
+    let sources: HashMap<String, String> = conn
+        .query::<(String, String), _>(
+            "SELECT variable_name, variable_source FROM performance_schema.variables_info",
+        )
+        .await
+        .map(|rows| rows.into_iter().collect())
+        .unwrap_or_default();
+    let scopes: HashMap<String, String> = conn
+        .query::<(String, String), _>(
+            "SELECT variable_name, variable_scope FROM performance_schema.variables_metadata",
+        )
+        .await
+        .map(|rows| rows.into_iter().collect())
+        .unwrap_or_default();
+
+    let mut settings = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let name: String = row.get(0).ok_or_else(|| {
+            PlenumError::engine_error("mysql", "Failed to extract variable name".to_string())
+        })?;
+        let value: Option<String> = row.get::<Option<String>, _>(1).flatten();
+        settings.push(SettingInfo {
+            source: sources.get(&name).map(|source| source.to_lowercase()),
+            session_changeable: scopes.get(&name).map(|scope| scope != "GLOBAL"),
+            name,
+            value,
+            unit: None,
+        });
+    }
+
+    Ok(IntrospectResult::Settings { settings: filter_settings(settings, pattern) })
+}
+
+/// Diagnose unused, duplicate and redundant indexes and tables with reclaimable space.
+///
+/// Index findings come from the `sys` schema views over `performance_schema`; when `sys`
+/// is unavailable those checks are skipped with a note rather than failing the report.
+#[allow(clippy::cast_precision_loss)]
+async fn index_health_mysql(
+    conn: &mut Conn,
+    schema: &str,
+    table_filter: Option<&str>,
+) -> Result<IntrospectResult> {
+    let mut findings = Vec::new();
+    let mut notes = Vec::new();
+
+    match sys_index_findings_mysql(conn, schema, table_filter).await {
+        Ok(found) => {
+            findings.extend(found);
+            notes.push(
+                "Index usage counters come from performance_schema and reset when the server restarts"
+                    .to_string(),
+            );
+        }
+        Err(e) => notes.push(format!(
+            "Unused and redundant index checks skipped: sys schema unavailable ({e})"
+        )),
+    }
+
+    let bloat_query = "SELECT table_name, data_free, data_length + index_length
+                       FROM information_schema.tables
+                       WHERE table_schema = ? AND (? IS NULL OR table_name = ?)
+                       AND table_type = 'BASE TABLE' AND data_free >= ?";
+    let rows: Vec<Row> = conn
+        .exec(bloat_query, (schema, table_filter, table_filter, BLOAT_MIN_FREE_BYTES))
+        .await
+        .map_err(|e| {
+            PlenumError::engine_error("mysql", format!("Failed to query table storage: {e}"))
+        })?;
+    for row in rows {
+        let table: String = row.get(0).ok_or_else(|| {
+            PlenumError::engine_error("mysql", "Failed to extract table name".to_string())
+        })?;
+        let free: u64 = row.get::<Option<u64>, _>(1).flatten().unwrap_or(0);
+        let used: u64 = row.get::<Option<u64>, _>(2).flatten().unwrap_or(0);
+        let fraction = free as f64 / (free + used) as f64;
+        if fraction < BLOAT_MIN_FREE_FRACTION {
+            continue;
+        }
+        findings.push(IndexFinding {
+            table,
+            schema: Some(schema.to_string()),
+            index: None,
+            reason: "bloat".to_string(),
+            metric: "free_space_fraction".to_string(),
+            value: fraction,
+            detail: format!("{free} bytes free of {} allocated", free + used),
+        });
+    }
+
+    findings.sort_by(|a, b| (&a.table, &a.index).cmp(&(&b.table, &b.index)));
+
+    Ok(IntrospectResult::IndexHealth { health: IndexHealthReport { findings, notes } })
+}
+
+/// Read `sys.schema_unused_indexes` and `sys.schema_redundant_indexes` for one schema
+#[allow(clippy::cast_precision_loss)]
+async fn sys_index_findings_mysql(
+    conn: &mut Conn,
+    schema: &str,
+    table_filter: Option<&str>,
+) -> Result<Vec<IndexFinding>> {
+    let mut findings = Vec::new();
+
+    let unused_query = "SELECT object_name, index_name
+                        FROM sys.schema_unused_indexes
+                        WHERE object_schema = ? AND (? IS NULL OR object_name = ?)";
+    let rows: Vec<(String, String)> =
+        conn.exec(unused_query, (schema, table_filter, table_filter)).await.map_err(|e| {
+            PlenumError::engine_error("mysql", format!("Failed to query unused indexes: {e}"))
+        })?;
+    for (table, index) in rows {
+        findings.push(IndexFinding {
+            table,
+            schema: Some(schema.to_string()),
+            index: Some(index),
+            reason: "unused".to_string(),
+            metric: "index_reads".to_string(),
+            value: 0.0,
+            detail: "No reads recorded since the server started".to_string(),
+        });
+    }
+
+    let redundant_query = "SELECT table_name, redundant_index_name, redundant_index_columns,
+                                  dominant_index_name, dominant_index_columns
+                           FROM sys.schema_redundant_indexes
+                           WHERE table_schema = ? AND (? IS NULL OR table_name = ?)";
+    let rows: Vec<(String, String, String, String, String)> =
+        conn.exec(redundant_query, (schema, table_filter, table_filter)).await.map_err(|e| {
+            PlenumError::engine_error("mysql", format!("Failed to query redundant indexes: {e}"))
+        })?;
+    for (table, index, columns, dominant, dominant_columns) in rows {
+        let (reason, detail) = if columns == dominant_columns {
+            ("duplicate", format!("Same key columns as {dominant}"))
+        } else {
+            ("redundant", format!("Key columns are a leading prefix of {dominant}"))
+        };
+        findings.push(IndexFinding {
+            table,
+            schema: Some(schema.to_string()),
+            index: Some(index),
+            reason: reason.to_string(),
+            metric: "key_columns".to_string(),
+            value: columns.split(',').count() as f64,
+            detail,
+        });
+    }
+
+    Ok(findings)
+}
+
+/// Report foreground sessions from `performance_schema.threads` and `InnoDB` lock waits
+/// from `performance_schema.data_lock_waits` (`MySQL` 8.0+).
+///
+/// `state` is the thread's command ("Query", "Sleep") and `wait_event` its processlist
+/// state (e.g. "Waiting for table metadata lock"). Durations have one-second resolution.
+async fn activity_mysql(conn: &mut Conn) -> Result<InspectResult> {
+    let sessions_query = "SELECT t.PROCESSLIST_ID, t.PROCESSLIST_USER, t.PROCESSLIST_DB,
+                                 a.ATTR_VALUE, t.PROCESSLIST_COMMAND, t.PROCESSLIST_INFO,
+                                 t.PROCESSLIST_TIME, NULLIF(t.PROCESSLIST_STATE, '')
+                          FROM performance_schema.threads t
+                          LEFT JOIN performance_schema.session_connect_attrs a
+                            ON a.PROCESSLIST_ID = t.PROCESSLIST_ID
+                           AND a.ATTR_NAME = 'program_name'
+                          WHERE t.TYPE = 'FOREGROUND'
+                            AND t.PROCESSLIST_ID IS NOT NULL
+                            AND t.PROCESSLIST_ID <> CONNECTION_ID()
+                          ORDER BY t.PROCESSLIST_ID";
+    let rows: Vec<Row> = conn.query(sessions_query).await.map_err(|e| {
+        PlenumError::engine_error(
+            "mysql",
+            format!("Failed to query performance_schema.threads: {e}"),
+        )
+    })?;
+    let mut sessions = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let pid: u64 = row.get(0).ok_or_else(|| {
+            PlenumError::engine_error("mysql", "Failed to extract processlist id".to_string())
+        })?;
+        let seconds: Option<i64> = row.get::<Option<i64>, _>(6).flatten();
+        sessions.push(SessionActivity {
+            pid: i64::try_from(pid).unwrap_or(i64::MAX),
+            user: get_optional_string(row, 1),
+            database: get_optional_string(row, 2),
+            application: get_optional_string(row, 3),
+            state: get_optional_string(row, 4),
+            query: get_optional_string(row, 5),
+            duration_ms: seconds.and_then(|s| u64::try_from(s).ok()).map(|s| s * 1000),
+            wait_event: get_optional_string(row, 7),
+        });
+    }
+
+    let locks_query = "SELECT rt.PROCESSLIST_ID, bt.PROCESSLIST_ID, l.LOCK_TYPE, l.LOCK_MODE,
+                              CONCAT_WS('.', l.OBJECT_SCHEMA, l.OBJECT_NAME)
+                       FROM performance_schema.data_lock_waits w
+                       JOIN performance_schema.threads rt ON rt.THREAD_ID = w.REQUESTING_THREAD_ID
+                       JOIN performance_schema.threads bt ON bt.THREAD_ID = w.BLOCKING_THREAD_ID
+                       LEFT JOIN performance_schema.data_locks l
+                         ON l.ENGINE_LOCK_ID = w.REQUESTING_ENGINE_LOCK_ID
+                       WHERE rt.PROCESSLIST_ID IS NOT NULL AND bt.PROCESSLIST_ID IS NOT NULL
+                       ORDER BY rt.PROCESSLIST_ID, bt.PROCESSLIST_ID";
+    let rows: Vec<Row> = conn.query(locks_query).await.map_err(|e| {
+        PlenumError::engine_error("mysql", format!("Failed to query lock waits: {e}"))
+    })?;
+    let mut lock_waits = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let waiting: u64 = row.get(0).ok_or_else(|| {
+            PlenumError::engine_error("mysql", "Failed to extract waiting thread".to_string())
+        })?;
+        let blocking: u64 = row.get(1).ok_or_else(|| {
+            PlenumError::engine_error("mysql", "Failed to extract blocking thread".to_string())
+        })?;
+        lock_waits.push(LockWait {
+            waiting_pid: i64::try_from(waiting).unwrap_or(i64::MAX),
+            blocking_pid: i64::try_from(blocking).unwrap_or(i64::MAX),
+            lock_type: get_optional_string(row, 2),
+            mode: get_optional_string(row, 3),
+            object: get_optional_string(row, 4).filter(|o| !o.is_empty()),
+        });
+    }
+
+    Ok(InspectResult::Activity { activity: ActivityReport { sessions, lock_waits } })
+}
+
+/// Picoseconds per millisecond (`performance_schema` timer unit)
+const PICOSECONDS_PER_MS: f64 = 1e9;
+
+/// Rank normalized statements from `performance_schema.events_statements_summary_by_digest`.
+///
+/// Reports a note instead of failing when `performance_schema` is disabled or unreadable.
+#[allow(clippy::cast_precision_loss)]
+async fn top_queries_mysql(
+    conn: &mut Conn,
+    order_by: QueryRanking,
+    limit: usize,
+) -> Result<InspectResult> {
+    let report = |statements, notes| InspectResult::TopQueries {
+        top_queries: TopQueriesReport { order_by, statements, notes },
+    };
+
+    let enabled: Option<bool> =
+        conn.query_first("SELECT @@performance_schema").await.map_err(|e| {
+            PlenumError::engine_error("mysql", format!("Failed to query performance_schema: {e}"))
+        })?;
+    if enabled != Some(true) {
+        return Ok(report(
+            Vec::new(),
+            vec!["performance_schema is disabled; statement digests are not collected".to_string()],
+        ));
+    }
+
+    let order_column = match order_by {
+        QueryRanking::TotalTime => "SUM_TIMER_WAIT",
+        QueryRanking::MeanTime => "AVG_TIMER_WAIT",
+        QueryRanking::Calls => "COUNT_STAR",
+        QueryRanking::Rows => "SUM_ROWS_SENT + SUM_ROWS_AFFECTED",
+    };
+    let query = format!(
+        "SELECT DIGEST, DIGEST_TEXT, SCHEMA_NAME, COUNT_STAR, SUM_TIMER_WAIT, AVG_TIMER_WAIT,
+                SUM_ROWS_SENT + SUM_ROWS_AFFECTED
+         FROM performance_schema.events_statements_summary_by_digest
+         WHERE DIGEST IS NOT NULL
+         ORDER BY {order_column} DESC, DIGEST
+         LIMIT ?"
+    );
+    let limit = u64::try_from(limit).unwrap_or(u64::MAX);
+    let rows: Vec<Row> = match conn.exec(query, (limit,)).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            return Ok(report(
+                Vec::new(),
+                vec![format!(
+                    "Statement digests unavailable: cannot read performance_schema ({e})"
+                )],
+            ));
+        }
+    };
+
+    let mut statements = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let digest: String = row.get::<Option<String>, _>(0).flatten().ok_or_else(|| {
+            PlenumError::engine_error("mysql", "Failed to extract statement digest".to_string())
+        })?;
+        let total_ps: u64 = row.get::<Option<u64>, _>(4).flatten().unwrap_or(0);
+        let mean_ps: u64 = row.get::<Option<u64>, _>(5).flatten().unwrap_or(0);
+        statements.push(StatementStats {
+            digest,
+            query: get_optional_string(row, 1).unwrap_or_default(),
+            database: get_optional_string(row, 2),
+            calls: row.get::<Option<u64>, _>(3).flatten().unwrap_or(0),
+            total_time_ms: total_ps as f64 / PICOSECONDS_PER_MS,
+            mean_time_ms: mean_ps as f64 / PICOSECONDS_PER_MS,
+            rows: row.get::<Option<u64>, _>(6).flatten().unwrap_or(0),
+        });
+    }
+
+    let notes =
+        vec!["Digest statistics cover activity since server start or the last truncation of \
+         events_statements_summary_by_digest; digest text is truncated at \
+         performance_schema_max_digest_length"
+            .to_string()];
+    Ok(report(statements, notes))
+}
+
+/// Privileges that modify rows
+const MYSQL_WRITE_PRIVILEGES: &[&str] = &["INSERT", "UPDATE", "DELETE"];
+
+/// Privileges that create, alter or drop schema objects
+const MYSQL_DDL_PRIVILEGES: &[&str] = &[
+    "CREATE",
+    "DROP",
+    "ALTER",
+    "INDEX",
+    "CREATE VIEW",
+    "TRIGGER",
+    "CREATE ROUTINE",
+    "ALTER ROUTINE",
+    "EVENT",
+];
+
+/// Least-privilege audit for `connect --test`: classify `SHOW GRANTS` and check `@@read_only`.
+async fn audit_privileges_mysql(conn: &mut Conn) -> Result<Vec<PrivilegeWarning>> {
+    let lines: Vec<String> = conn
+        .query("SHOW GRANTS")
+        .await
+        .map_err(|e| PlenumError::engine_error("mysql", format!("Failed to show grants: {e}")))?;
+    let grants: Vec<GrantInfo> = lines
+        .iter()
+        .filter_map(|line| match parse_mysql_grant(line) {
+            Some(MysqlGrant::Privileges(grant)) => Some(grant),
+            _ => None,
+        })
+        .collect();
+    let mut warnings = grant_warnings_mysql(&grants);
+
+    let read_only: Option<bool> =
+        conn.query_first("SELECT @@global.read_only").await.map_err(|e| {
+            PlenumError::engine_error("mysql", format!("Failed to query read_only: {e}"))
+        })?;
+    if read_only == Some(true) {
+        warnings.push(PrivilegeWarning::info(
+            "READ_REPLICA",
+            "Server has read_only enabled (typically a replica); writes from non-SUPER users are rejected",
+        ));
+    }
+
+    Ok(warnings)
+}
+
+/// Classify parsed grants into least-privilege warnings, naming each grant target once.
+fn grant_warnings_mysql(grants: &[GrantInfo]) -> Vec<PrivilegeWarning> {
+    let mut superuser = Vec::new();
+    let mut write = Vec::new();
+    let mut ddl = Vec::new();
+    let mut grant_option = Vec::new();
+
+    for grant in grants {
+        let target = match &grant.object_type {
+            Some(kind) => format!("{kind} {}.{}", grant.database, grant.object),
+            None => format!("{}.{}", grant.database, grant.object),
+        };
+        let held: Vec<&str> = grant
+            .privileges
+            .iter()
+            .chain(grant.column_privileges.iter().flat_map(|c| &c.privileges))
+            .map(String::as_str)
+            .collect();
+        let all = held.iter().any(|p| *p == "ALL" || *p == "ALL PRIVILEGES");
+        let global = grant.database == "*";
+
+        if held.contains(&"SUPER") || (all && global) {
+            superuser.push(target.clone());
+        }
+        if all || held.iter().any(|p| MYSQL_WRITE_PRIVILEGES.contains(p)) {
+            write.push(target.clone());
+        }
+        if all || held.iter().any(|p| MYSQL_DDL_PRIVILEGES.contains(p)) {
+            ddl.push(target.clone());
+        }
+        if grant.with_grant_option {
+            grant_option.push(target);
+        }
+    }
+
+    let mut warnings = Vec::new();
+    for (code, message, targets) in [
+        ("SUPERUSER", "User holds SUPER or ALL PRIVILEGES on *.*", superuser),
+        ("WRITE_PRIVILEGE", "User holds INSERT, UPDATE or DELETE", write),
+        ("DDL_PRIVILEGE", "User can create, alter or drop objects", ddl),
+        ("GRANT_OPTION", "User can grant its privileges to others", grant_option),
+    ] {
+        if !targets.is_empty() {
+            warnings.push(PrivilegeWarning::warning(code, message, targets));
+        }
+    }
+    warnings
+}
+
+/// One line of `SHOW GRANTS` output
+#[derive(Debug)]
+enum MysqlGrant {
+    /// `GRANT <privileges> ON <object> TO <user>`
+    Privileges(GrantInfo),
+    /// `GRANT <role>[, <role>] TO <user>`
+    Roles(Vec<String>),
+}
+
+/// Parse a `SHOW GRANTS` line; lines that are not GRANT statements (e.g. partial
+/// revokes) yield `None`.
+fn parse_mysql_grant(line: &str) -> Option<MysqlGrant> {
+    let line = line.trim().strip_prefix("GRANT ")?;
+    let to = find_top_level(line, " TO ")?;
+    let head = &line[..to];
+    let with_grant_option = line.ends_with(" WITH GRANT OPTION");
+
+    let Some(on) = find_top_level(head, " ON ") else {
+        let roles = split_top_level(head, ',')
+            .into_iter()
+            .map(|role| {
+                split_top_level(role, '@')
+                    .into_iter()
+                    .map(unquote_mysql)
+                    .collect::<Vec<_>>()
+                    .join("@")
+            })
+            .collect();
+        return Some(MysqlGrant::Roles(roles));
+    };
+
+    let mut privileges = Vec::new();
+    let mut column_privileges: Vec<ColumnPrivileges> = Vec::new();
+    for item in split_top_level(&head[..on], ',') {
+        let Some((privilege, columns)) = item.split_once('(') else {
+            privileges.push(item.to_string());
+            continue;
+        };
+        let privilege = privilege.trim().to_string();
+        for column in split_top_level(columns.trim_end_matches(')'), ',') {
+            let column = unquote_mysql(column);
+            match column_privileges.iter_mut().find(|c| c.column == column) {
+                Some(existing) => existing.privileges.push(privilege.clone()),
+                None => column_privileges
+                    .push(ColumnPrivileges { column, privileges: vec![privilege.clone()] }),
+            }
+        }
+    }
+
+    let mut target = head[on + " ON ".len()..].trim();
+    let mut object_type = None;
+    for kind in ["TABLE ", "PROCEDURE ", "FUNCTION "] {
+        if let Some(rest) = target.strip_prefix(kind) {
+            target = rest;
+            object_type = (kind != "TABLE ").then(|| kind.trim().to_string());
+        }
+    }
+    let (database, object) = match find_top_level(target, ".") {
+        Some(dot) => (unquote_mysql(&target[..dot]), unquote_mysql(&target[dot + 1..])),
+        None => (unquote_mysql(target), "*".to_string()),
+    };
+
+    Some(MysqlGrant::Privileges(GrantInfo {
+        privileges,
+        column_privileges,
+        object_type,
+        database,
+        object,
+        with_grant_option,
+    }))
+}
+
+/// Byte offset of the first `pattern` outside backticks, quotes and parentheses
+fn find_top_level(text: &str, pattern: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    let mut depth = 0usize;
+    for (idx, ch) in text.char_indices() {
+        match (quote, ch) {
+            (Some(q), _) if ch == q => quote = None,
+            (None, '`' | '\'' | '"') => quote = Some(ch),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, _) if depth == 0 && text[idx..].starts_with(pattern) => return Some(idx),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split `text` on `separator` outside backticks, quotes and parentheses, trimming each part
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while let Some(idx) = find_top_level(rest, separator.encode_utf8(&mut [0; 4])) {
+        parts.push(rest[..idx].trim());
+        rest = &rest[idx + separator.len_utf8()..];
+    }
+    parts.push(rest.trim());
+    parts
+}
+
+/// Strip `MySQL` identifier or string quoting, un-doubling embedded quote characters
+fn unquote_mysql(text: &str) -> String {
+    let text = text.trim();
+    for quote in ['`', '\'', '"'] {
+        if text.len() >= 2 && text.starts_with(quote) && text.ends_with(quote) {
+            let doubled = format!("{quote}{quote}");
+            return text[1..text.len() - 1].replace(&doubled, &quote.to_string());
+        }
+    }
+    text.to_string()
+}
+
+/// Columns a sample is hashed on: the primary key, or every column when there is none.
+async fn sample_key_columns(
+    conn: &mut Conn,
+    schema: &str,
+    table_name: &str,
+) -> Result<Vec<String>> {
+    let key_columns = match introspect_primary_key(conn, schema, table_name).await? {
+        Some(pk) => pk,
+        None => introspect_columns(conn, schema, table_name)
+            .await?
+            .into_iter()
+            .map(|c| c.name)
+            .collect(),
+    };
+    if key_columns.is_empty() {
+        return Err(PlenumError::invalid_input(format!(
+            "Table '{table_name}' not found in schema '{schema}'"
+        )));
+    }
+    Ok(key_columns)
+}
+
+/// Build an ordered-hash sample query: rows sorted by `MD5(seed | key columns)`, with the
+/// key columns themselves as a tie-breaker so the order is total.
+fn sample_query_mysql(
+    schema: &str,
+    table_name: &str,
+    key_columns: &[String],
+    options: &SampleOptions,
+) -> String {
+    let keys: Vec<String> = key_columns.iter().map(|c| quote_identifier(c)).collect();
+    let keys = keys.join(", ");
+    format!(
+        "SELECT * FROM {}.{} ORDER BY MD5(CONCAT_WS('|', {}, {keys})), {keys} LIMIT {}",
+        quote_identifier(schema),
+        quote_identifier(table_name),
+        options.seed,
+        options.size
+    )
+}
+
+/// Quote an identifier for interpolation into `MySQL` SQL.
+fn quote_identifier(ident: &str) -> String {
+    format!("`{}`", ident.replace('`', "``"))
+}
+
 /// Convert a JSON value to a `mysql_async` native `Value` for parameter binding.
 /// Uses `?` `MySQL` placeholders.
 fn json_to_mysql_value(val: &serde_json::Value) -> Value {
+    if let Some(Ok(typed)) = ParamValue::from_json(val) {
+        return typed_to_mysql_value(&typed);
+    }
     match val {
         serde_json::Value::Null => Value::NULL,
         serde_json::Value::Bool(b) => Value::Int(i64::from(*b)),
@@ -953,6 +1827,49 @@ fn json_to_mysql_value(val: &serde_json::Value) -> Value {
     }
 }
 
+/// Bind a typed parameter natively: dates and times as `MySQL` temporal values
+/// (offset timestamps converted to UTC), bytes as raw binary, arrays as JSON text.
+fn typed_to_mysql_value(typed: &ParamValue) -> Value {
+    match typed {
+        ParamValue::Null => Value::NULL,
+        ParamValue::Bool(b) => Value::Int(i64::from(*b)),
+        ParamValue::Int(i) => Value::Int(*i),
+        ParamValue::Float(f) => Value::Double(*f),
+        ParamValue::Date(d) => mysql_datetime(*d, Time::default()),
+        ParamValue::Time(t) => Value::Time(
+            false,
+            0,
+            mysql_part(t.hour),
+            mysql_part(t.minute),
+            mysql_part(t.second),
+            t.micros,
+        ),
+        ParamValue::Timestamp(ts) => {
+            let ts = ts.to_utc();
+            mysql_datetime(ts.date, ts.time)
+        }
+        ParamValue::Bytes(b) => Value::Bytes(b.clone()),
+        other => Value::Bytes(other.to_text().unwrap_or_default().into_bytes()),
+    }
+}
+
+fn mysql_datetime(date: Date, time: Time) -> Value {
+    Value::Date(
+        u16::try_from(date.year).unwrap_or(0),
+        mysql_part(date.month),
+        mysql_part(date.day),
+        mysql_part(time.hour),
+        mysql_part(time.minute),
+        mysql_part(time.second),
+        time.micros,
+    )
+}
+
+/// Date/time components are validated on parse, so they always fit a `u8`.
+fn mysql_part(n: u32) -> u8 {
+    u8::try_from(n).unwrap_or(0)
+}
+
 /// Returns true when a `mysql_async` error is a server-side `MAX_EXECUTION_TIME` timeout (error 3024).
 fn is_mysql_statement_timeout(e: &mysql_async::Error) -> bool {
     match e {
@@ -980,7 +1897,8 @@ async fn execute_query(
     query: &str,
     params: &[serde_json::Value],
     caps: &Capabilities,
-) -> Result<QueryResult> {
+    sink: &mut dyn RowSink,
+) -> Result<StreamSummary> {
     // Classify the statement. MySQL async doesn't have a prepare-then-check
     // pattern like tokio-postgres, so use a keyword heuristic. `EXPLAIN` (in all
     // its FORMAT/ANALYZE/EXTENDED forms) returns a result set and must be treated
@@ -1008,45 +1926,23 @@ async fn execute_query(
         Params::Positional(params.iter().map(json_to_mysql_value).collect())
     };
 
+    let mut feed = RowFeed::new(sink, caps);
+
     if returns_rows {
         // Query returns rows. Use the text protocol for unparameterized queries
         // (so EXPLAIN/transaction-adjacent statements execute) and the prepared
-        // protocol only when bound params are present.
-        let rows: Vec<Row> = if use_text_protocol {
-            conn.query(query).await
-        } else {
-            conn.exec(query, mysql_params).await
-        }
-        .map_err(|e| map_mysql_exec_error(&e))?;
-
-        // Get column names from first row (if any)
-        let column_names: Vec<String> = if let Some(first_row) = rows.first() {
-            first_row.columns_ref().iter().map(|col| col.name_str().to_string()).collect()
+        // protocol only when bound params are present. Rows are read off the wire
+        // one at a time rather than collected.
+        if use_text_protocol {
+            let result = conn.query_iter(query).await.map_err(|e| map_mysql_exec_error(&e))?;
+            stream_rows(result, &mut feed).await?;
         } else {
-            Vec::new()
-        };
-
-        // Apply offset and max_rows with truncation detection
-        let offset = caps.offset.unwrap_or(0);
-        let effective = if offset <= rows.len() { &rows[offset..] } else { &[][..] };
-        let max = caps.max_rows.unwrap_or(usize::MAX);
-        let rows_truncated = effective.len() > max;
-        let take = effective.len().min(max);
-
-        let mut rows_data = Vec::new();
-        for row in &effective[..take] {
-            rows_data.push(row_to_json(row)?);
+            let result =
+                conn.exec_iter(query, mysql_params).await.map_err(|e| map_mysql_exec_error(&e))?;
+            stream_rows(result, &mut feed).await?;
         }
 
-        Ok(QueryResult {
-            columns: column_names,
-            rows: rows_data,
-            rows_affected: None,
-            execution_ms: 0,
-            rows_truncated,
-            truncated_by: None,
-            plan: None,
-        })
+        Ok(feed.finish())
     } else {
         // Non-row statement (e.g. transaction control: BEGIN/START TRANSACTION).
         // These have no result set; capture affected rows. Prefer the text protocol
@@ -1064,26 +1960,70 @@ async fn execute_query(
             affected
         };
 
-        Ok(QueryResult {
-            columns: Vec::new(),
-            rows: Vec::new(),
-            rows_affected: Some(rows_affected),
-            execution_ms: 0,
-            rows_truncated: false,
-            truncated_by: None,
-            plan: None,
+        feed.columns(&[])?;
+        let mut summary = feed.finish();
+        summary.rows_affected = Some(rows_affected);
+        Ok(summary)
+    }
+}
+
+/// Feed a result set's columns and rows to `feed`, then discard whatever it did not take.
+async fn stream_rows<P: Protocol>(
+    mut result: mysql_async::QueryResult<'_, 'static, P>,
+    feed: &mut RowFeed<'_>,
+) -> Result<()> {
+    let columns: Vec<ResultColumn> = result
+        .columns()
+        .map(|columns| {
+            columns
+                .iter()
+                .map(|c| ResultColumn {
+                    name: c.name_str().to_string(),
+                    type_name: Some(
+                        format!("{:?}", c.column_type())
+                            .trim_start_matches("MYSQL_TYPE_")
+                            .to_lowercase(),
+                    ),
+                })
+                .collect()
         })
+        .unwrap_or_default();
+    feed.columns(&columns)?;
+
+    let cells = feed.cells();
+    while let Some(row) = result.next().await.map_err(|e| map_mysql_exec_error(&e))? {
+        if !feed.push(|| row_to_json(&row, cells))? {
+            break;
+        }
     }
+    result.drop_result().await.map_err(|e| map_mysql_exec_error(&e))
 }
 
-/// Execute `EXPLAIN FORMAT=JSON` against the inner SQL and normalize the result.
+/// Run an EXPLAIN statement, binding `params` through the prepared-statement protocol
+/// when there are any (the same protocol choice as `execute_query`).
+async fn query_explain(
+    conn: &mut Conn,
+    sql: String,
+    params: &[serde_json::Value],
+) -> std::result::Result<Vec<Row>, mysql_async::Error> {
+    if params.is_empty() {
+        conn.query(sql).await
+    } else {
+        let params = Params::Positional(params.iter().map(json_to_mysql_value).collect());
+        conn.exec(sql, params).await
+    }
+}
+
+/// Execute `EXPLAIN FORMAT=JSON` against the inner SQL with `params` bound and
+/// normalize the result.
 async fn execute_structured_explain_mysql(
     conn: &mut Conn,
     inner_sql: &str,
+    params: &[serde_json::Value],
 ) -> Result<ExplainPlanNode> {
     let sql = format!("EXPLAIN FORMAT=JSON {inner_sql}");
 
-    let rows: Vec<Row> = conn.query(sql).await.map_err(|e| {
+    let rows = query_explain(conn, sql, params).await.map_err(|e| {
         PlenumError::query_failed(format!("Failed to execute EXPLAIN FORMAT=JSON: {e}"))
     })?;
 
@@ -1119,10 +2059,9 @@ fn normalize_mysql_query_block(block: &serde_json::Value) -> ExplainPlanNode {
 
     ExplainPlanNode {
         node_type: "query_block".to_string(),
-        relation: None,
-        estimated_rows: None,
         estimated_cost,
         children: collect_mysql_children(block),
+        ..ExplainPlanNode::default()
     }
 }
 
@@ -1144,6 +2083,21 @@ fn collect_mysql_children(block: &serde_json::Value) -> Vec<ExplainPlanNode> {
         }
     }
 
+    // ORDER BY wraps the tables it reads; a filesort is its own node
+    if let Some(ordering) = block.get("ordering_operation") {
+        let inner = collect_mysql_children(ordering);
+        if ordering.get("using_filesort").and_then(serde_json::Value::as_bool).unwrap_or(false) {
+            children.push(ExplainPlanNode {
+                node_type: "filesort".to_string(),
+                operation: PlanOperation::Sort,
+                children: inner,
+                ..ExplainPlanNode::default()
+            });
+        } else {
+            children.extend(inner);
+        }
+    }
+
     // Derived / subquery blocks
     for key in &["select_list_subqueries", "attached_subqueries", "grouping_operation"] {
         if let Some(arr) = block.get(key).and_then(serde_json::Value::as_array) {
@@ -1176,15 +2130,226 @@ fn normalize_mysql_table_node(table: &serde_json::Value) -> ExplainPlanNode {
         .and_then(serde_json::Value::as_str)
         .and_then(|s| s.parse::<f64>().ok());
 
-    ExplainPlanNode { node_type, relation, estimated_rows, estimated_cost, children: Vec::new() }
+    let using_index = table.get("using_index").and_then(serde_json::Value::as_bool);
+
+    ExplainPlanNode {
+        operation: mysql_access_operation(&node_type, using_index.unwrap_or(false)),
+        node_type,
+        relation,
+        index: table.get("key").and_then(serde_json::Value::as_str).map(String::from),
+        filter: table
+            .get("attached_condition")
+            .and_then(serde_json::Value::as_str)
+            .map(String::from),
+        estimated_rows,
+        estimated_cost,
+        ..ExplainPlanNode::default()
+    }
+}
+
+/// Categorize a `MySQL` JSON-plan `access_type`; `using_index` marks a covering index.
+fn mysql_access_operation(access_type: &str, using_index: bool) -> PlanOperation {
+    if access_type == "ALL" {
+        return PlanOperation::FullScan;
+    }
+    let via_index = matches!(
+        access_type,
+        "index"
+            | "range"
+            | "ref"
+            | "eq_ref"
+            | "ref_or_null"
+            | "const"
+            | "index_merge"
+            | "fulltext"
+            | "unique_subquery"
+            | "index_subquery"
+    );
+    match (via_index, using_index) {
+        (true, true) => PlanOperation::IndexOnlyScan,
+        (true, false) => PlanOperation::IndexScan,
+        (false, _) => PlanOperation::Other,
+    }
+}
+
+/// Categorize a `MySQL` TREE-format node label such as "Table scan", "Inner hash join"
+/// or "Covering index lookup".
+fn mysql_tree_operation(node_type: &str) -> PlanOperation {
+    let label = node_type.to_lowercase();
+    if label == "table scan" {
+        PlanOperation::FullScan
+    } else if label.contains("covering index") {
+        PlanOperation::IndexOnlyScan
+    } else if label.contains("index") && (label.contains("scan") || label.contains("lookup")) {
+        PlanOperation::IndexScan
+    } else if label.contains("hash join") {
+        PlanOperation::HashJoin
+    } else if label == "hash" {
+        PlanOperation::Hash
+    } else if label.starts_with("nested loop") {
+        PlanOperation::NestedLoop
+    } else if label.starts_with("filter") {
+        PlanOperation::Filter
+    } else if label.starts_with("sort") {
+        PlanOperation::Sort
+    } else if label.starts_with("window") {
+        PlanOperation::Window
+    } else if label.contains("aggregate") || label.starts_with("group") {
+        PlanOperation::Aggregate
+    } else if label.starts_with("limit") {
+        PlanOperation::Limit
+    } else if label.starts_with("materialize") {
+        PlanOperation::Materialize
+    } else if label.starts_with("union") || label.starts_with("append") {
+        PlanOperation::Union
+    } else if label.starts_with("remove duplicates") {
+        PlanOperation::Distinct
+    } else {
+        PlanOperation::Other
+    }
+}
+
+/// Execute `EXPLAIN ANALYZE` (`MySQL` 8.0.18+) against the inner SQL and normalize
+/// its TREE output.
+async fn execute_structured_explain_analyze_mysql(
+    conn: &mut Conn,
+    inner_sql: &str,
+    params: &[serde_json::Value],
+) -> Result<ExplainPlanNode> {
+    let sql = format!("EXPLAIN ANALYZE {inner_sql}");
+
+    let rows = query_explain(conn, sql, params).await.map_err(|e| {
+        if is_mysql_statement_timeout(&e) {
+            map_mysql_exec_error(&e)
+        } else {
+            PlenumError::query_failed(format!("Failed to execute EXPLAIN ANALYZE: {e}"))
+        }
+    })?;
+
+    // MySQL returns one text column containing the TREE-format plan
+    let tree: String = rows
+        .first()
+        .and_then(|row| row.get(0))
+        .ok_or_else(|| PlenumError::query_failed("EXPLAIN ANALYZE returned no plan".to_string()))?;
+
+    parse_mysql_analyze_tree(&tree).ok_or_else(|| {
+        PlenumError::query_failed("Unexpected MySQL EXPLAIN ANALYZE output (no plan lines)")
+    })
+}
+
+/// Build a plan tree from `EXPLAIN ANALYZE` TREE text.
+///
+/// Every node is one `-> ` line, nested by indentation. Several top-level lines
+/// are wrapped in a `QUERY PLAN` root.
+fn parse_mysql_analyze_tree(tree: &str) -> Option<ExplainPlanNode> {
+    // Open ancestors of the next line, with their indentation
+    let mut stack: Vec<(usize, ExplainPlanNode)> = Vec::new();
+    let mut roots = Vec::new();
+
+    let mut close_to = |stack: &mut Vec<(usize, ExplainPlanNode)>, indent: usize| {
+        while stack.last().is_some_and(|(level, _)| *level >= indent) {
+            let Some((_, node)) = stack.pop() else { break };
+            match stack.last_mut() {
+                Some((_, parent)) => parent.children.push(node),
+                None => roots.push(node),
+            }
+        }
+    };
+
+    for line in tree.lines() {
+        let text = line.trim_start();
+        let Some(text) = text.strip_prefix("->") else { continue };
+        let indent = line.len() - line.trim_start().len();
+        close_to(&mut stack, indent);
+        stack.push((indent, parse_mysql_analyze_line(text.trim())));
+    }
+    close_to(&mut stack, 0);
+
+    match roots.len() {
+        0 => None,
+        1 => roots.pop(),
+        _ => Some(ExplainPlanNode {
+            node_type: "QUERY PLAN".to_string(),
+            children: roots,
+            ..ExplainPlanNode::default()
+        }),
+    }
+}
+
+/// Parse one TREE line: `<label>  (cost=C rows=R) (actual time=F..L rows=N loops=K)`.
+///
+/// `actual time` is first-row..all-rows in milliseconds, per loop; the node keeps
+/// the all-rows time. A `(never executed)` node ran zero times.
+fn parse_mysql_analyze_line(text: &str) -> ExplainPlanNode {
+    let label_end = [" (cost=", " (actual time=", " (never executed)"]
+        .iter()
+        .filter_map(|marker| text.find(marker))
+        .min()
+        .unwrap_or(text.len());
+    let label = text[..label_end].trim();
+
+    // "Table scan on t", "Index lookup on t using idx (id=o.cid)", "Filter: (o.n > 5)",
+    // "Inner hash join (o.cid = c.id)"
+    let (head, detail) = label.split_once(": ").map_or((label, None), |(h, d)| (h, Some(d)));
+    let mut node = ExplainPlanNode::default();
+    let table_start = ["scan on ", "lookup on "]
+        .iter()
+        .find_map(|marker| head.find(marker).map(|at| at + marker.len()));
+    let node_type = match table_start {
+        Some(table_start) => {
+            let mut words = head[table_start..].split_whitespace();
+            let table = words.next().filter(|t| !t.starts_with('<'));
+            node.relation = table.map(|t| t.trim_matches('`').to_string());
+            if words.next() == Some("using") {
+                node.index = words.next().map(|i| i.trim_matches('`').to_string());
+            }
+            &head[..table_start - " on ".len()]
+        }
+        None => match head.split_once(" (") {
+            Some((join, condition)) if join.ends_with(" join") => {
+                node.join_condition =
+                    Some(format!("({condition}")).filter(|c| c != "(no condition)");
+                join
+            }
+            _ => head,
+        },
+    };
+    node.operation = mysql_tree_operation(node_type);
+    if node.operation == PlanOperation::Filter {
+        node.filter = detail.map(|d| d.trim().to_string());
+    }
+
+    // Value of `key=` inside the parenthesized group that starts with `group`
+    let field = |group: &str, key: &str| -> Option<&str> {
+        let start = text.find(group)? + 1;
+        let body = &text[start..start + text[start..].find(')')?];
+        let value = body.split_whitespace().find_map(|kv| kv.strip_prefix(key))?;
+        value.rsplit("..").next()
+    };
+    let number = |group: &str, key: &str| field(group, key).and_then(|v| v.parse::<f64>().ok());
+    let never_executed = text.contains("(never executed)");
+
+    ExplainPlanNode {
+        node_type: node_type.trim().to_string(),
+        estimated_rows: number("(cost=", "rows="),
+        estimated_cost: number("(cost=", "cost="),
+        actual_rows: if never_executed { Some(0.0) } else { number("(actual time=", "rows=") },
+        actual_time_ms: number("(actual time=", "time="),
+        loops: if never_executed {
+            Some(0)
+        } else {
+            field("(actual time=", "loops=").and_then(|v| v.parse().ok())
+        },
+        ..node
+    }
 }
 
 /// Convert a `MySQL` row to a JSON-safe `Vec`
-fn row_to_json(row: &Row) -> Result<Vec<serde_json::Value>> {
+fn row_to_json(row: &Row, cells: CellFormat) -> Result<Vec<serde_json::Value>> {
     let mut values = Vec::with_capacity(row.columns_ref().len());
 
     for idx in 0..row.columns_ref().len() {
-        let value = mysql_value_to_json(row, idx)?;
+        let value = mysql_value_to_json(row, idx, cells)?;
         values.push(value);
     }
 
@@ -1192,7 +2357,11 @@ fn row_to_json(row: &Row) -> Result<Vec<serde_json::Value>> {
 }
 
 /// Convert `MySQL` value to JSON value
-fn mysql_value_to_json(row: &Row, idx: usize) -> Result<serde_json::Value> {
+///
+/// `--binary` other than Base64 applies to every value of a binary string or BLOB
+/// column; Base64 keeps the historical rendering, where binary values that happen
+/// to be valid UTF-8 are returned as text.
+fn mysql_value_to_json(row: &Row, idx: usize, cells: CellFormat) -> Result<serde_json::Value> {
     let value = row
         .as_ref(idx)
         .ok_or_else(|| PlenumError::query_failed(format!("Failed to get value at index {idx}")))?;
@@ -1200,15 +2369,17 @@ fn mysql_value_to_json(row: &Row, idx: usize) -> Result<serde_json::Value> {
     let json_value = match value {
         Value::NULL => serde_json::Value::Null,
 
+        Value::Bytes(bytes) if !cells.is_base64() && is_binary_column(&row.columns_ref()[idx]) => {
+            cells.binary(bytes)
+        }
+
         Value::Bytes(bytes) => {
             // Try to convert to UTF-8 string first
             if let Ok(s) = std::str::from_utf8(bytes) {
                 serde_json::Value::String(s.to_string())
             } else {
-                // Binary data - encode as Base64
-                use base64::Engine;
-                let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
-                serde_json::Value::String(encoded)
+                // Binary data - encode per --binary
+                cells.binary(bytes)
             }
         }
 
@@ -1243,6 +2414,26 @@ fn mysql_value_to_json(row: &Row, idx: usize) -> Result<serde_json::Value> {
     Ok(json_value)
 }
 
+/// Whether a result column holds binary strings (`BINARY`, `VARBINARY`, `BLOB`):
+/// a string or BLOB type in the `binary` character set. Numeric and temporal
+/// columns also report `binary` in the text protocol, so the type is checked too.
+fn is_binary_column(column: &mysql_async::Column) -> bool {
+    use mysql_async::consts::ColumnType;
+
+    const BINARY_CHARSET: u16 = 63;
+    column.character_set() == BINARY_CHARSET
+        && matches!(
+            column.column_type(),
+            ColumnType::MYSQL_TYPE_STRING
+                | ColumnType::MYSQL_TYPE_VAR_STRING
+                | ColumnType::MYSQL_TYPE_VARCHAR
+                | ColumnType::MYSQL_TYPE_BLOB
+                | ColumnType::MYSQL_TYPE_TINY_BLOB
+                | ColumnType::MYSQL_TYPE_MEDIUM_BLOB
+                | ColumnType::MYSQL_TYPE_LONG_BLOB
+        )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1258,6 +2449,179 @@ mod tests {
         assert_eq!(info, "MariaDB 10.11.2");
     }
 
+    #[test]
+    fn test_sample_query_mysql_orders_by_seeded_hash() {
+        let options = SampleOptions { size: 5, seed: 7, timeout_ms: None };
+        let keys = vec!["customer_id".to_string(), "order_no".to_string()];
+        let sql = sample_query_mysql("shop", "orders", &keys, &options);
+        assert_eq!(
+            sql,
+            "SELECT * FROM `shop`.`orders` \
+             ORDER BY MD5(CONCAT_WS('|', 7, `customer_id`, `order_no`)), `customer_id`, `order_no` \
+             LIMIT 5"
+        );
+    }
+
+    fn parse_privileges(line: &str) -> GrantInfo {
+        match parse_mysql_grant(line) {
+            Some(MysqlGrant::Privileges(grant)) => grant,
+            other => panic!("expected privilege grant, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_mysql_grant_database_privileges() {
+        let grant = parse_privileges("GRANT SELECT, INSERT ON `shop`.* TO `app`@`%`");
+        assert_eq!(grant.privileges, vec!["SELECT", "INSERT"]);
+        assert_eq!(grant.database, "shop");
+        assert_eq!(grant.object, "*");
+        assert!(grant.column_privileges.is_empty());
+        assert!(grant.object_type.is_none());
+        assert!(!grant.with_grant_option);
+
+        let global =
+            parse_privileges("GRANT ALL PRIVILEGES ON *.* TO `root`@`localhost` WITH GRANT OPTION");
+        assert_eq!(global.privileges, vec!["ALL PRIVILEGES"]);
+        assert_eq!((global.database.as_str(), global.object.as_str()), ("*", "*"));
+        assert!(global.with_grant_option);
+    }
+
+    #[test]
+    fn test_parse_mysql_grant_column_and_routine_privileges() {
+        let grant = parse_privileges(
+            "GRANT SELECT (`id`, `email`), UPDATE (`email`) ON `shop`.`users` TO `app`@`%`",
+        );
+        assert!(grant.privileges.is_empty());
+        assert_eq!(grant.object, "users");
+        let columns: Vec<(&str, Vec<&str>)> = grant
+            .column_privileges
+            .iter()
+            .map(|c| (c.column.as_str(), c.privileges.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(columns, vec![("id", vec!["SELECT"]), ("email", vec!["SELECT", "UPDATE"])]);
+
+        let routine = parse_privileges("GRANT EXECUTE ON PROCEDURE `shop`.`refund` TO `app`@`%`");
+        assert_eq!(routine.object_type.as_deref(), Some("PROCEDURE"));
+        assert_eq!(routine.object, "refund");
+    }
+
+    #[test]
+    fn test_parse_mysql_grant_roles_and_revokes() {
+        match parse_mysql_grant("GRANT `reader`@`%`,`auditor`@`localhost` TO `app`@`%`") {
+            Some(MysqlGrant::Roles(roles)) => {
+                assert_eq!(roles, vec!["reader@%", "auditor@localhost"]);
+            }
+            other => panic!("expected role grant, got {other:?}"),
+        }
+        assert!(parse_mysql_grant("REVOKE INSERT ON `mysql`.* FROM `app`@`%`").is_none());
+    }
+
+    #[test]
+    fn test_grant_warnings_mysql_classifies_privileges() {
+        let grants: Vec<GrantInfo> = [
+            "GRANT USAGE ON *.* TO `app`@`%`",
+            "GRANT SELECT, INSERT ON `shop`.* TO `app`@`%`",
+            "GRANT SELECT, CREATE VIEW ON `shop`.`orders` TO `app`@`%` WITH GRANT OPTION",
+        ]
+        .iter()
+        .map(|line| parse_privileges(line))
+        .collect();
+        let warnings = grant_warnings_mysql(&grants);
+        let summary: Vec<(&str, Vec<&str>)> = warnings
+            .iter()
+            .map(|w| (w.code.as_str(), w.objects.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("WRITE_PRIVILEGE", vec!["shop.*"]),
+                ("DDL_PRIVILEGE", vec!["shop.orders"]),
+                ("GRANT_OPTION", vec!["shop.orders"]),
+            ]
+        );
+        assert!(warnings.iter().all(|w| w.severity == "warning"));
+    }
+
+    #[test]
+    fn test_grant_warnings_mysql_read_only_user_is_clean() {
+        let grants = vec![
+            parse_privileges("GRANT USAGE ON *.* TO `reader`@`%`"),
+            parse_privileges("GRANT SELECT, SHOW VIEW ON `shop`.* TO `reader`@`%`"),
+        ];
+        assert!(grant_warnings_mysql(&grants).is_empty());
+
+        let root = parse_privileges("GRANT ALL PRIVILEGES ON *.* TO `root`@`%`");
+        let codes: Vec<String> =
+            grant_warnings_mysql(&[root]).into_iter().map(|w| w.code).collect();
+        assert_eq!(codes, vec!["SUPERUSER", "WRITE_PRIVILEGE", "DDL_PRIVILEGE"]);
+    }
+
+    #[test]
+    fn test_parse_mysql_analyze_tree() {
+        let tree = "\
+-> Nested loop inner join  (cost=4.75 rows=10) (actual time=0.061..0.093 rows=10 loops=1)
+    -> Filter: (o.total > 5)  (cost=1.25 rows=3.33) (actual time=0.02..0.04 rows=10 loops=1)
+        -> Table scan on o  (cost=1.25 rows=10) (actual time=0.018..0.035 rows=10 loops=1)
+    -> Single-row index lookup on `c` using PRIMARY (id=o.customer_id)  \
+(cost=0.26 rows=1) (actual time=0.0025..0.0026 rows=1 loops=10)
+    -> Table scan on <temporary>  (never executed)
+";
+        let plan = parse_mysql_analyze_tree(tree).unwrap();
+        assert_eq!(plan.node_type, "Nested loop inner join");
+        assert_eq!(plan.estimated_cost, Some(4.75));
+        assert_eq!(plan.actual_time_ms, Some(0.093));
+        assert_eq!(plan.children.len(), 3);
+
+        assert_eq!(plan.operation, PlanOperation::NestedLoop);
+
+        let filter = &plan.children[0];
+        assert_eq!(
+            (filter.node_type.as_str(), filter.operation),
+            ("Filter", PlanOperation::Filter)
+        );
+        assert_eq!(filter.filter.as_deref(), Some("(o.total > 5)"));
+        assert_eq!(filter.estimated_rows, Some(3.33));
+        assert_eq!(filter.children[0].node_type, "Table scan");
+        assert_eq!(filter.children[0].operation, PlanOperation::FullScan);
+        assert_eq!(filter.children[0].relation.as_deref(), Some("o"));
+
+        let lookup = &plan.children[1];
+        assert_eq!(lookup.node_type, "Single-row index lookup");
+        assert_eq!(lookup.operation, PlanOperation::IndexScan);
+        assert_eq!(lookup.relation.as_deref(), Some("c"));
+        assert_eq!(lookup.index.as_deref(), Some("PRIMARY"));
+        assert_eq!((lookup.actual_rows, lookup.loops), (Some(1.0), Some(10)));
+
+        let temporary = &plan.children[2];
+        assert_eq!(temporary.relation, None);
+        assert_eq!((temporary.actual_rows, temporary.loops), (Some(0.0), Some(0)));
+        assert_eq!(temporary.actual_time_ms, None);
+
+        let tree = "\
+-> Inner hash join (o.customer_id = c.id)  (cost=3.5 rows=10) (actual time=0.1..0.2 rows=10 loops=1)
+    -> Covering index scan on o using idx_customer  (cost=1 rows=10)
+    -> Hash
+        -> Table scan on c  (cost=0.55 rows=3)
+";
+        let join = parse_mysql_analyze_tree(tree).unwrap();
+        assert_eq!(join.node_type, "Inner hash join");
+        assert_eq!(join.operation, PlanOperation::HashJoin);
+        assert_eq!(join.join_condition.as_deref(), Some("(o.customer_id = c.id)"));
+        let scan = &join.children[0];
+        assert_eq!(scan.operation, PlanOperation::IndexOnlyScan);
+        assert_eq!(scan.index.as_deref(), Some("idx_customer"));
+        assert_eq!(scan.actual_rows, None);
+        assert_eq!(join.children[1].operation, PlanOperation::Hash);
+    }
+
+    #[test]
+    fn test_mysql_access_operation() {
+        assert_eq!(mysql_access_operation("ALL", false), PlanOperation::FullScan);
+        assert_eq!(mysql_access_operation("ref", false), PlanOperation::IndexScan);
+        assert_eq!(mysql_access_operation("range", true), PlanOperation::IndexOnlyScan);
+        assert_eq!(mysql_access_operation("system", false), PlanOperation::Other);
+    }
+
     // Note: Integration tests require a running MySQL instance
     // They are marked with #[ignore] and should be run with:
     // cargo test --features mysql -- --ignored
@@ -1842,4 +3206,28 @@ mod tests {
         assert_eq!(result.children.len(), 1);
         assert_eq!(result.children[0].relation, Some("items".to_string()));
     }
+
+    #[test]
+    fn test_normalize_mysql_query_block_with_ordering() {
+        let table = serde_json::json!({
+            "table_name": "orders",
+            "access_type": "ALL",
+            "attached_condition": "(`shop`.`orders`.`status` = 'shipped')"
+        });
+        let block = serde_json::json!({
+            "ordering_operation": { "using_filesort": true, "table": table }
+        });
+        let result = normalize_mysql_query_block(&block);
+        let sort = &result.children[0];
+        assert_eq!((sort.node_type.as_str(), sort.operation), ("filesort", PlanOperation::Sort));
+        assert_eq!(sort.children[0].relation.as_deref(), Some("orders"));
+        assert_eq!(sort.children[0].operation, PlanOperation::FullScan);
+
+        // Order served by an index: no sort node, the table stays visible
+        let block = serde_json::json!({
+            "ordering_operation": { "using_filesort": false, "table": table }
+        });
+        let result = normalize_mysql_query_block(&block);
+        assert_eq!(result.children[0].relation.as_deref(), Some("orders"));
+    }
 }
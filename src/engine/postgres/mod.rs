@@ -22,19 +22,43 @@
 use std::time::{Duration, Instant};
 use tokio_postgres::{error::SqlState, Client, Config, NoTls, Row};
 
-use crate::capability::{strip_explain_prefix, validate_query};
+use crate::capability::{strip_explain_prefix, structured_explain_analyze, validate_query};
+use crate::engine::cell::CellFormat;
+use crate::engine::stream::{cancelled_or, reject_structured_explain, RowCollector, RowFeed};
 use crate::engine::{
-    is_explain_query, Capabilities, ColumnInfo, ConnectionConfig, ConnectionInfo, DatabaseEngine,
-    DatabaseType, ExplainFormat, ExplainPlanNode, ForeignKeyInfo, IndexInfo, IntrospectOperation,
-    IntrospectResult, QueryResult, SslMode, TableInfo, TlsConfig,
+    filter_settings, is_explain_query, profile, ActivityReport, Capabilities, ColumnInfo,
+    ColumnPrivileges, ColumnProfile, ConnectionConfig, ConnectionInfo, DatabaseEngine,
+    DatabaseType, ExplainFormat, ExplainPlanNode, ForeignKeyInfo, IndexFinding, IndexHealthReport,
+    IndexInfo, InspectOperation, InspectResult, IntrospectOperation, IntrospectResult, LockWait,
+    PlanBuffers, PlanOperation, PrivilegeReport, PrivilegeWarning, QueryControl, QueryRanking,
+    QueryResult, ResultColumn, RlsPolicy, RowSink, SampleOptions, SessionActivity, SettingInfo,
+    SizeOptions, SizeReport, SslMode, StatementStats, StreamSummary, TableInfo, TablePrivileges,
+    TableProfile, TableSize, TlsConfig, TopQueriesReport, ValueFrequency,
 };
 use crate::error::{PlenumError, Result};
+use crate::params::ParamValue;
 
 /// Extra grace added to the client-side timeout backstop on top of the server-side
 /// `statement_timeout`. Ensures `PostgreSQL` cancels the query and reports `QUERY_TIMEOUT`
 /// before the client-side guard trips; the guard only matters if the server never responds.
 const CLIENT_TIMEOUT_BACKSTOP_GRACE: Duration = Duration::from_secs(5);
 
+/// Rows fetched per round trip from a query's portal; bounds client memory per query.
+const FETCH_BATCH_ROWS: i32 = 1000;
+
+/// Objects listed per least-privilege finding; the message still reports the full count.
+const MAX_WARNING_OBJECTS: usize = 20;
+
+/// Tables are reported as bloated once dead tuples reach this count...
+const BLOAT_MIN_DEAD_TUPLES: i64 = 1000;
+
+/// ...and this fraction of all (live + dead) tuples.
+const BLOAT_MIN_DEAD_FRACTION: f64 = 0.2;
+
+/// `pg_stats` is considered fresh when the rows modified since the last ANALYZE are at
+/// most this fraction of the table. Staler statistics fall back to sampling.
+const PG_STATS_MAX_STALE_FRACTION: f64 = 0.1;
+
 /// `PostgreSQL` database engine implementation
 pub struct PostgresEngine;
 
@@ -91,9 +115,16 @@ impl DatabaseEngine for PostgresEngine {
             server_info: version_string,
             connected_database,
             user,
+            warnings: Vec::new(),
         })
     }
 
+    async fn audit_privileges(config: &ConnectionConfig) -> Result<Vec<PrivilegeWarning>> {
+        let pg_config = build_pg_config(config)?;
+        let client = pg_connect(&pg_config, config.tls.as_ref()).await?;
+        audit_privileges_postgres(&client).await
+    }
+
     async fn introspect(
         config: &ConnectionConfig,
         operation: &IntrospectOperation,
@@ -150,17 +181,82 @@ impl DatabaseEngine for PostgresEngine {
                 let target_schema = determine_target_schema(&client, schema).await?;
                 get_view_details_postgres(&client, &target_schema, name).await?
             }
+
+            IntrospectOperation::ListSequences
+            | IntrospectOperation::ListMacros
+            | IntrospectOperation::ListTypes
+            | IntrospectOperation::ListConstraints { .. }
+            | IntrospectOperation::ListAttachedDatabases => {
+                return Err(PlenumError::invalid_input(format!(
+                    "PostgreSQL does not support {} operation (DuckDB catalog introspection only)",
+                    operation.name()
+                )));
+            }
+
+            IntrospectOperation::ProfileTable { name, options } => {
+                let target_schema = determine_target_schema(&client, schema).await?;
+                let stats =
+                    profile_from_pg_stats(&client, &target_schema, name, options.top_n).await?;
+                let profile = if let Some(profile) = stats {
+                    profile
+                } else {
+                    let (_, row_estimate) =
+                        introspect_table_meta(&client, &target_schema, name).await?;
+                    let sample_options = options.sample_options();
+                    let sql =
+                        sample_query_postgres(&target_schema, name, row_estimate, &sample_options);
+                    let mut profile = profile::profile_by_sampling::<Self>(
+                        &effective_config,
+                        &sql,
+                        name,
+                        Some(&target_schema),
+                        options,
+                    )
+                    .await?;
+                    // Below 100% a short sample does not mean every row was read
+                    profile.exhaustive &= sample_percent(row_estimate, &sample_options) >= 100.0;
+                    profile
+                };
+                IntrospectResult::TableProfile { profile }
+            }
+
+            IntrospectOperation::SampleTable { name, options } => {
+                let target_schema = determine_target_schema(&client, schema).await?;
+                let (_, row_estimate) =
+                    introspect_table_meta(&client, &target_schema, name).await?;
+                let sql = sample_query_postgres(&target_schema, name, row_estimate, options);
+                let caps = Capabilities::new(Some(options.size), options.timeout_ms);
+                let sample = Self::execute(&effective_config, &sql, &[], &caps).await?;
+                IntrospectResult::TableSample { sample }
+            }
+
+            IntrospectOperation::ListPrivileges { table } => {
+                let target_schema = determine_target_schema(&client, schema).await?;
+                list_privileges_postgres(&client, &target_schema, table.as_deref()).await?
+            }
+
+            IntrospectOperation::IndexHealth { table } => {
+                let target_schema = determine_target_schema(&client, schema).await?;
+                index_health_postgres(&client, &target_schema, table.as_deref()).await?
+            }
+
+            IntrospectOperation::Sizes { options } => {
+                let target_schema = determine_target_schema(&client, schema).await?;
+                sizes_postgres(&client, &target_schema, options).await?
+            }
+
+            IntrospectOperation::Settings { pattern } => {
+                settings_postgres(&client, pattern.as_deref()).await?
+            }
         };
 
         Ok(result)
     }
 
-    async fn execute(
+    async fn inspect(
         config: &ConnectionConfig,
-        query: &str,
-        params: &[serde_json::Value],
-        caps: &Capabilities,
-    ) -> Result<QueryResult> {
+        operation: &InspectOperation,
+    ) -> Result<InspectResult> {
         // Validate config is for PostgreSQL
         if config.engine != DatabaseType::Postgres {
             return Err(PlenumError::invalid_input(format!(
@@ -169,41 +265,27 @@ impl DatabaseEngine for PostgresEngine {
             )));
         }
 
-        // Validate query against capabilities
-        validate_query(query, caps, DatabaseType::Postgres)?;
-
-        // Build connection config
         let pg_config = build_pg_config(config)?;
-
-        // Connect to PostgreSQL (TLS or plaintext depending on config)
         let client = pg_connect(&pg_config, config.tls.as_ref()).await?;
 
-        // Defense in depth: enforce session-level read-only at the database layer.
-        // This rejects writes even if the SQL parser is somehow bypassed (REF-261).
-        client.execute("SET default_transaction_read_only = ON", &[]).await.map_err(|e| {
-            PlenumError::engine_error(
-                "postgres",
-                format!("Failed to enforce session read-only mode: {e}"),
-            )
-        })?;
-
-        // Set server-side statement timeout so PostgreSQL cancels the query if it exceeds
-        // the limit. This prevents resource leaks — the server kills the query rather than
-        // the client just abandoning the wait.
-        if let Some(timeout_ms) = caps.timeout_ms {
-            client
-                .execute(&format!("SET statement_timeout = '{timeout_ms}ms'"), &[])
-                .await
-                .map_err(|e| {
-                    PlenumError::engine_error(
-                        "postgres",
-                        format!("Failed to set statement_timeout: {e}"),
-                    )
-                })?;
+        match operation {
+            InspectOperation::Activity => activity_postgres(&client).await,
+            InspectOperation::TopQueries { order_by, limit } => {
+                top_queries_postgres(&client, *order_by, *limit).await
+            }
         }
+    }
+
+    async fn execute(
+        config: &ConnectionConfig,
+        query: &str,
+        params: &[serde_json::Value],
+        caps: &Capabilities,
+    ) -> Result<QueryResult> {
+        let mut client = connect_for_query(config, query, caps).await?;
 
         // Structured explain path: rewrite to EXPLAIN (FORMAT JSON), normalize the plan tree.
-        // The server-side statement_timeout (set above) still bounds this query.
+        // The server-side statement_timeout (set in connect_for_query) still bounds this query.
         if caps.explain_format == Some(ExplainFormat::Structured) {
             if !is_explain_query(query) {
                 return Err(PlenumError::invalid_input(
@@ -211,9 +293,11 @@ impl DatabaseEngine for PostgresEngine {
                      non-EXPLAIN queries must omit this flag",
                 ));
             }
+            let analyze = structured_explain_analyze(query, DatabaseType::Postgres)?;
             let inner = strip_explain_prefix(query);
             let start = Instant::now();
-            let plan = execute_structured_explain_postgres(&client, &inner).await?;
+            let plan =
+                execute_structured_explain_postgres(&client, &inner, params, analyze).await?;
             let elapsed = start.elapsed();
             return Ok(QueryResult {
                 columns: Vec::new(),
@@ -222,35 +306,146 @@ impl DatabaseEngine for PostgresEngine {
                 execution_ms: elapsed.as_millis() as u64,
                 rows_truncated: false,
                 truncated_by: None,
-                plan: Some(plan),
+                plan: Some(Box::new(plan)),
+                next_cursor: None,
+                ordering: None,
             });
         }
 
-        // Execute with a client-side tokio timeout as a backstop for unresponsive servers.
-        // The backstop is deliberately longer than the server-side statement_timeout (by a
-        // fixed grace) so PostgreSQL cancels the query first and surfaces QUERY_TIMEOUT; the
-        // client-side guard only fires if the server never responds (e.g. a stalled socket),
-        // avoiding a race where both fire at the same deadline.
-        let start = Instant::now();
-        let mut query_result = if let Some(timeout_ms) = caps.timeout_ms {
-            let backstop = Duration::from_millis(timeout_ms) + CLIENT_TIMEOUT_BACKSTOP_GRACE;
-            tokio::time::timeout(backstop, execute_query(&client, query, params, caps))
-                .await
-                .map_err(|_| {
-                    PlenumError::query_failed(format!(
-                        "Client-side timeout of {}ms exceeded (server-side statement_timeout should have fired first)",
-                        backstop.as_millis()
-                    ))
-                })??
-        } else {
-            execute_query(&client, query, params, caps).await?
-        };
+        let mut rows = RowCollector::default();
+        let summary = execute_with_backstop(&mut client, query, params, caps, &mut rows)
+            .await
+            .map_err(|e| cancelled_or(caps, e))?;
+        Ok(rows.into_result(summary))
+    }
+
+    async fn execute_stream(
+        config: &ConnectionConfig,
+        query: &str,
+        params: &[serde_json::Value],
+        caps: &Capabilities,
+        sink: &mut dyn RowSink,
+    ) -> Result<StreamSummary> {
+        reject_structured_explain(caps)?;
+        let mut client = connect_for_query(config, query, caps).await?;
+        execute_with_backstop(&mut client, query, params, caps, sink)
+            .await
+            .map_err(|e| cancelled_or(caps, e))
+    }
+}
+
+/// Validate `query`, connect, and arm the session's read-only mode and `statement_timeout`.
+async fn connect_for_query(
+    config: &ConnectionConfig,
+    query: &str,
+    caps: &Capabilities,
+) -> Result<Client> {
+    // Validate config is for PostgreSQL
+    if config.engine != DatabaseType::Postgres {
+        return Err(PlenumError::invalid_input(format!(
+            "Expected PostgreSQL engine, got {}",
+            config.engine
+        )));
+    }
+
+    // Validate query against capabilities
+    validate_query(query, caps, DatabaseType::Postgres)?;
+
+    // Build connection config
+    let pg_config = build_pg_config(config)?;
+
+    // Connect to PostgreSQL (TLS or plaintext depending on config)
+    let client = pg_connect(&pg_config, config.tls.as_ref()).await?;
+
+    // Defense in depth: enforce session-level read-only at the database layer.
+    // This rejects writes even if the SQL parser is somehow bypassed (REF-261).
+    client.execute("SET default_transaction_read_only = ON", &[]).await.map_err(|e| {
+        PlenumError::engine_error(
+            "postgres",
+            format!("Failed to enforce session read-only mode: {e}"),
+        )
+    })?;
+
+    // Set server-side statement timeout so PostgreSQL cancels the query if it exceeds
+    // the limit. This prevents resource leaks — the server kills the query rather than
+    // the client just abandoning the wait.
+    if let Some(timeout_ms) = caps.timeout_ms {
+        client.execute(&format!("SET statement_timeout = '{timeout_ms}ms'"), &[]).await.map_err(
+            |e| {
+                PlenumError::engine_error(
+                    "postgres",
+                    format!("Failed to set statement_timeout: {e}"),
+                )
+            },
+        )?;
+    }
 
-        let elapsed = start.elapsed();
-        query_result.execution_ms = elapsed.as_millis() as u64;
+    if let Some(control) = &caps.control {
+        arm_cancel(&client, config.tls.as_ref(), control)?;
+    }
 
-        Ok(query_result)
+    Ok(client)
+}
+
+/// Send a cancel request for the session's running statement when the MCP client
+/// cancels the query. The request travels on a new connection with the session's
+/// TLS settings; a cancel that arrives after the statement finished is ignored by
+/// the server.
+fn arm_cancel(
+    client: &Client,
+    tls_config: Option<&TlsConfig>,
+    control: &QueryControl,
+) -> Result<()> {
+    let token = client.cancel_token();
+    let runtime = tokio::runtime::Handle::current();
+    match tls_config.filter(|t| t.sslmode != SslMode::Disable) {
+        None => control.on_cancel(move || {
+            runtime.spawn(async move {
+                let _ = token.cancel_query(NoTls).await;
+            });
+        }),
+        Some(tls) => {
+            let tls = postgres_native_tls::MakeTlsConnector::new(build_pg_native_tls(tls)?);
+            control.on_cancel(move || {
+                runtime.spawn(async move {
+                    let _ = token.cancel_query(tls).await;
+                });
+            });
+        }
     }
+    control.check()
+}
+
+/// Run `execute_query` under the client-side timeout backstop and record its duration.
+///
+/// The backstop is deliberately longer than the server-side `statement_timeout` (by a
+/// fixed grace) so `PostgreSQL` cancels the query first and surfaces `QUERY_TIMEOUT`; the
+/// client-side guard only fires if the server never responds (e.g. a stalled socket),
+/// avoiding a race where both fire at the same deadline.
+async fn execute_with_backstop(
+    client: &mut Client,
+    query: &str,
+    params: &[serde_json::Value],
+    caps: &Capabilities,
+    sink: &mut dyn RowSink,
+) -> Result<StreamSummary> {
+    let start = Instant::now();
+    let mut summary = if let Some(timeout_ms) = caps.timeout_ms {
+        let backstop = Duration::from_millis(timeout_ms) + CLIENT_TIMEOUT_BACKSTOP_GRACE;
+        tokio::time::timeout(backstop, execute_query(client, query, params, caps, sink))
+            .await
+            .map_err(|_| {
+                PlenumError::query_failed(format!(
+                    "Client-side timeout of {}ms exceeded (server-side statement_timeout should have fired first)",
+                    backstop.as_millis()
+                ))
+            })??
+    } else {
+        execute_query(client, query, params, caps, sink).await?
+    };
+
+    summary.execution_ms = start.elapsed().as_millis() as u64;
+    Ok(summary)
 }
 
 /// Build `PostgreSQL` connection config from `ConnectionConfig`
@@ -630,6 +825,878 @@ async fn get_view_details_postgres(
     Ok(IntrospectResult::ViewDetails { view })
 }
 
+/// Build a column profile from `pg_stats` when the statistics are fresh.
+///
+/// Returns `Ok(None)` when the table has never been analyzed, has changed by more than
+/// `PG_STATS_MAX_STALE_FRACTION` since, or any column lacks a visible `pg_stats` row
+/// (e.g. no SELECT privilege on it); the caller then falls back to sampling.
+/// Values are rendered as text, and min/max come from the histogram bounds.
+async fn profile_from_pg_stats(
+    client: &Client,
+    schema: &str,
+    table_name: &str,
+    top_n: usize,
+) -> Result<Option<TableProfile>> {
+    let freshness_query = "
+        SELECT c.reltuples::float8,
+               COALESCE(s.last_analyze, s.last_autoanalyze) IS NOT NULL,
+               COALESCE(s.n_mod_since_analyze, 0)::float8
+        FROM pg_catalog.pg_class c
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        LEFT JOIN pg_catalog.pg_stat_all_tables s ON s.relid = c.oid
+        WHERE n.nspname = $1 AND c.relname = $2";
+
+    let row = client.query_opt(freshness_query, &[&schema, &table_name]).await.map_err(|e| {
+        PlenumError::engine_error(
+            "postgres",
+            format!("Failed to query statistics freshness for {schema}.{table_name}: {e}"),
+        )
+    })?;
+
+    let Some(row) = row else { return Ok(None) };
+    let reltuples: f64 = row.get(0);
+    let analyzed: bool = row.get(1);
+    let modified: f64 = row.get(2);
+    if !analyzed || reltuples <= 0.0 || modified > reltuples * PG_STATS_MAX_STALE_FRACTION {
+        return Ok(None);
+    }
+
+    let stats_query = "
+        SELECT a.attname, s.null_frac::float8, s.n_distinct::float8,
+               array_to_json(s.most_common_vals::text::text[]),
+               array_to_json(s.most_common_freqs),
+               array_to_json(s.histogram_bounds::text::text[])
+        FROM pg_catalog.pg_attribute a
+        JOIN pg_catalog.pg_class c ON c.oid = a.attrelid
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        LEFT JOIN pg_catalog.pg_stats s
+            ON s.schemaname = n.nspname AND s.tablename = c.relname AND s.attname = a.attname
+        WHERE n.nspname = $1 AND c.relname = $2 AND a.attnum > 0 AND NOT a.attisdropped
+        ORDER BY a.attnum";
+
+    let rows = client.query(stats_query, &[&schema, &table_name]).await.map_err(|e| {
+        PlenumError::engine_error(
+            "postgres",
+            format!("Failed to query pg_stats for {schema}.{table_name}: {e}"),
+        )
+    })?;
+
+    let mut columns = Vec::with_capacity(rows.len());
+    for row in rows {
+        let name: String = row.get(0);
+        let Some(null_fraction) = row.get::<_, Option<f64>>(1) else { return Ok(None) };
+        let n_distinct: f64 = row.get::<_, Option<f64>>(2).unwrap_or(0.0);
+        let mcv = json_array(row.get(3));
+        let mcf = json_array(row.get(4));
+        let histogram = json_array(row.get(5));
+
+        // Negative n_distinct is minus the ratio of distinct values to rows
+        let distinct_count = if n_distinct < 0.0 {
+            estimate_count(-n_distinct * reltuples)
+        } else {
+            estimate_count(n_distinct)
+        };
+
+        let top_values = mcv
+            .into_iter()
+            .zip(mcf.iter().filter_map(serde_json::Value::as_f64))
+            .take(top_n)
+            .map(|(value, frequency)| ValueFrequency {
+                value,
+                count: estimate_count(frequency * reltuples),
+                frequency,
+            })
+            .collect();
+
+        columns.push(ColumnProfile {
+            name,
+            null_fraction,
+            distinct_count,
+            min: histogram.first().cloned(),
+            max: histogram.last().cloned(),
+            top_values,
+        });
+    }
+
+    Ok(Some(TableProfile {
+        table: table_name.to_string(),
+        schema: Some(schema.to_string()),
+        source: "statistics".to_string(),
+        row_count: estimate_count(reltuples),
+        exhaustive: false,
+        columns,
+    }))
+}
+
+/// Least-privilege audit for `connect --test`.
+///
+/// A superuser passes every privilege check, so object-level checks are skipped
+/// for one; they would only restate the superuser finding for every table.
+async fn audit_privileges_postgres(client: &Client) -> Result<Vec<PrivilegeWarning>> {
+    let role_query = "
+        SELECT current_user::text, current_database()::text,
+               rolsuper, rolcreaterole, rolcreatedb, rolbypassrls,
+               has_database_privilege(current_database(), 'CREATE'),
+               pg_is_in_recovery()
+        FROM pg_catalog.pg_roles
+        WHERE rolname = current_user";
+    let row = client.query_one(role_query, &[]).await.map_err(|e| {
+        PlenumError::engine_error("postgres", format!("Failed to query role attributes: {e}"))
+    })?;
+    let user: String = row.get(0);
+    let database: String = row.get(1);
+    let in_recovery: bool = row.get(7);
+
+    let mut warnings = Vec::new();
+    if row.get::<_, bool>(2) {
+        warnings.push(PrivilegeWarning::warning(
+            "SUPERUSER",
+            format!("User '{user}' is a superuser and bypasses every privilege check"),
+            Vec::new(),
+        ));
+    } else {
+        let attributes: Vec<&str> = [(3, "CREATEROLE"), (4, "CREATEDB"), (5, "BYPASSRLS")]
+            .into_iter()
+            .filter(|&(idx, _)| row.get::<_, bool>(idx))
+            .map(|(_, name)| name)
+            .collect();
+        if !attributes.is_empty() {
+            warnings.push(PrivilegeWarning::warning(
+                "ELEVATED_ROLE",
+                format!("User '{user}' has role attributes {}", attributes.join(", ")),
+                Vec::new(),
+            ));
+        }
+        if row.get::<_, bool>(6) {
+            warnings.push(PrivilegeWarning::warning(
+                "DDL_PRIVILEGE",
+                format!("User '{user}' can create schemas in database '{database}'"),
+                vec![database.clone()],
+            ));
+        }
+
+        let schemas_query = "
+            SELECT nspname::text
+            FROM pg_catalog.pg_namespace
+            WHERE has_schema_privilege(oid, 'CREATE')
+              AND nspname NOT IN ('pg_catalog', 'information_schema')
+              AND nspname NOT LIKE 'pg\\_toast%' AND nspname NOT LIKE 'pg\\_temp\\_%'
+            ORDER BY nspname";
+        let schemas = query_names(client, schemas_query, "schema CREATE privileges").await?;
+        if let Some(warning) = object_warning(
+            "DDL_PRIVILEGE",
+            &format!("User '{user}' can create objects in"),
+            "schema",
+            schemas,
+        ) {
+            warnings.push(warning);
+        }
+
+        let owned_query = "
+            SELECT n.nspname || '.' || c.relname
+            FROM pg_catalog.pg_class c
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            WHERE c.relkind IN ('r', 'p', 'v', 'm', 'f')
+              AND pg_has_role(current_user, c.relowner, 'USAGE')
+              AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+              AND n.nspname NOT LIKE 'pg\\_toast%'
+            ORDER BY 1";
+        let owned = query_names(client, owned_query, "table ownership").await?;
+        if let Some(warning) = object_warning(
+            "TABLE_OWNERSHIP",
+            &format!("User '{user}' owns (and may alter or drop)"),
+            "table",
+            owned,
+        ) {
+            warnings.push(warning);
+        }
+
+        let writable_query = "
+            SELECT n.nspname || '.' || c.relname
+            FROM pg_catalog.pg_class c
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            WHERE c.relkind IN ('r', 'p', 'v', 'f')
+              AND NOT pg_has_role(current_user, c.relowner, 'USAGE')
+              AND has_table_privilege(c.oid, 'INSERT, UPDATE, DELETE, TRUNCATE')
+              AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+            ORDER BY 1";
+        let writable = query_names(client, writable_query, "table write privileges").await?;
+        if let Some(warning) = object_warning(
+            "WRITE_PRIVILEGE",
+            &format!("User '{user}' holds INSERT, UPDATE, DELETE or TRUNCATE on"),
+            "table",
+            writable,
+        ) {
+            warnings.push(warning);
+        }
+    }
+
+    if in_recovery {
+        warnings.push(PrivilegeWarning::info(
+            "READ_REPLICA",
+            "Server is a read replica (pg_is_in_recovery); writes are rejected regardless of grants",
+        ));
+    }
+
+    Ok(warnings)
+}
+
+/// Run a query returning a single text column
+async fn query_names(client: &Client, query: &str, what: &str) -> Result<Vec<String>> {
+    let rows = client.query(query, &[]).await.map_err(|e| {
+        PlenumError::engine_error("postgres", format!("Failed to query {what}: {e}"))
+    })?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// Build a warning naming at most `MAX_WARNING_OBJECTS` objects; `None` when there are none.
+fn object_warning(
+    code: &str,
+    prefix: &str,
+    noun: &str,
+    mut objects: Vec<String>,
+) -> Option<PrivilegeWarning> {
+    if objects.is_empty() {
+        return None;
+    }
+    let count = objects.len();
+    let plural = if count == 1 { "" } else { "s" };
+    objects.truncate(MAX_WARNING_OBJECTS);
+    Some(PrivilegeWarning::warning(code, format!("{prefix} {count} {noun}{plural}"), objects))
+}
+
+/// Report the current user's effective privileges on tables in the target schema.
+///
+/// Privileges come from `has_table_privilege` / `has_column_privilege`, so grants
+/// inherited through role membership and PUBLIC are included.
+async fn list_privileges_postgres(
+    client: &Client,
+    schema: &str,
+    table_filter: Option<&str>,
+) -> Result<IntrospectResult> {
+    let user_row = client.query_one("SELECT current_user::text", &[]).await.map_err(|e| {
+        PlenumError::engine_error("postgres", format!("Failed to query current user: {e}"))
+    })?;
+    let user: String = user_row.get(0);
+
+    let roles_query = "
+        SELECT rolname::text
+        FROM pg_catalog.pg_roles
+        WHERE rolname <> current_user AND pg_has_role(current_user, oid, 'MEMBER')
+        ORDER BY rolname";
+    let roles = client
+        .query(roles_query, &[])
+        .await
+        .map_err(|e| {
+            PlenumError::engine_error("postgres", format!("Failed to query role membership: {e}"))
+        })?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    let tables_query = "
+        SELECT c.relname::text,
+               array_remove(ARRAY[
+                   CASE WHEN has_table_privilege(c.oid, 'SELECT') THEN 'SELECT' END,
+                   CASE WHEN has_table_privilege(c.oid, 'INSERT') THEN 'INSERT' END,
+                   CASE WHEN has_table_privilege(c.oid, 'UPDATE') THEN 'UPDATE' END,
+                   CASE WHEN has_table_privilege(c.oid, 'DELETE') THEN 'DELETE' END,
+                   CASE WHEN has_table_privilege(c.oid, 'TRUNCATE') THEN 'TRUNCATE' END,
+                   CASE WHEN has_table_privilege(c.oid, 'REFERENCES') THEN 'REFERENCES' END,
+                   CASE WHEN has_table_privilege(c.oid, 'TRIGGER') THEN 'TRIGGER' END
+               ], NULL),
+               c.relrowsecurity,
+               c.relforcerowsecurity
+        FROM pg_catalog.pg_class c
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        WHERE n.nspname = $1
+          AND c.relkind IN ('r', 'p', 'v', 'm', 'f')
+          AND ($2::text IS NULL OR c.relname = $2)
+        ORDER BY c.relname";
+    let table_rows = client.query(tables_query, &[&schema, &table_filter]).await.map_err(|e| {
+        PlenumError::engine_error(
+            "postgres",
+            format!("Failed to query table privileges in schema '{schema}': {e}"),
+        )
+    })?;
+
+    // Column grants only matter where the table-level privilege is missing
+    let columns_query = "
+        SELECT relname, attname, privileges
+        FROM (
+            SELECT c.relname::text AS relname, a.attname::text AS attname, a.attnum,
+                   array_remove(ARRAY[
+                       CASE WHEN NOT has_table_privilege(c.oid, 'SELECT')
+                             AND has_column_privilege(c.oid, a.attnum, 'SELECT')
+                            THEN 'SELECT' END,
+                       CASE WHEN NOT has_table_privilege(c.oid, 'INSERT')
+                             AND has_column_privilege(c.oid, a.attnum, 'INSERT')
+                            THEN 'INSERT' END,
+                       CASE WHEN NOT has_table_privilege(c.oid, 'UPDATE')
+                             AND has_column_privilege(c.oid, a.attnum, 'UPDATE')
+                            THEN 'UPDATE' END,
+                       CASE WHEN NOT has_table_privilege(c.oid, 'REFERENCES')
+                             AND has_column_privilege(c.oid, a.attnum, 'REFERENCES')
+                            THEN 'REFERENCES' END
+                   ], NULL) AS privileges
+            FROM pg_catalog.pg_class c
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            JOIN pg_catalog.pg_attribute a ON a.attrelid = c.oid
+            WHERE n.nspname = $1
+              AND c.relkind IN ('r', 'p', 'v', 'm', 'f')
+              AND ($2::text IS NULL OR c.relname = $2)
+              AND a.attnum > 0 AND NOT a.attisdropped
+        ) cols
+        WHERE cardinality(privileges) > 0
+        ORDER BY relname, attnum";
+    let column_rows =
+        client.query(columns_query, &[&schema, &table_filter]).await.map_err(|e| {
+            PlenumError::engine_error(
+                "postgres",
+                format!("Failed to query column privileges in schema '{schema}': {e}"),
+            )
+        })?;
+
+    let policies_query = "
+        SELECT tablename::text, policyname::text, cmd, permissive = 'PERMISSIVE',
+               roles::text[],
+               EXISTS (
+                   SELECT 1 FROM unnest(roles) AS r(role)
+                   WHERE CASE WHEN r.role = 'public' THEN true
+                              ELSE pg_has_role(current_user, r.role, 'MEMBER') END
+               ),
+               qual, with_check
+        FROM pg_catalog.pg_policies
+        WHERE schemaname = $1 AND ($2::text IS NULL OR tablename = $2)
+        ORDER BY tablename, policyname";
+    let policy_rows =
+        client.query(policies_query, &[&schema, &table_filter]).await.map_err(|e| {
+            PlenumError::engine_error(
+                "postgres",
+                format!("Failed to query row-level security policies in schema '{schema}': {e}"),
+            )
+        })?;
+
+    let tables = table_rows
+        .iter()
+        .map(|row| {
+            let table: String = row.get(0);
+            let column_privileges = column_rows
+                .iter()
+                .filter(|col| col.get::<_, String>(0) == table)
+                .map(|col| ColumnPrivileges { column: col.get(1), privileges: col.get(2) })
+                .collect();
+            let policies = policy_rows
+                .iter()
+                .filter(|policy| policy.get::<_, String>(0) == table)
+                .map(|policy| RlsPolicy {
+                    name: policy.get(1),
+                    command: policy.get(2),
+                    permissive: policy.get(3),
+                    roles: policy.get(4),
+                    applies_to_user: policy.get(5),
+                    using: policy.get(6),
+                    with_check: policy.get(7),
+                })
+                .collect();
+            TablePrivileges {
+                schema: schema.to_string(),
+                privileges: row.get(1),
+                column_privileges,
+                rls_enabled: row.get(2),
+                rls_forced: row.get(3),
+                policies,
+                table,
+            }
+        })
+        .collect();
+
+    Ok(IntrospectResult::Privileges {
+        privileges: PrivilegeReport {
+            user,
+            scope: "database".to_string(),
+            roles,
+            tables,
+            grants: Vec::new(),
+            file: None,
+        },
+    })
+}
+
+/// Diagnose unused, duplicate and redundant indexes and bloated tables in the target schema.
+///
+/// Usage counters come from `pg_stat_user_indexes` and cover activity on this server since
+/// the last statistics reset. Unique and primary key indexes enforce constraints, so they are
+/// never reported as unused or redundant.
+#[allow(clippy::cast_precision_loss)]
+async fn index_health_postgres(
+    client: &Client,
+    schema: &str,
+    table_filter: Option<&str>,
+) -> Result<IntrospectResult> {
+    let mut findings = Vec::new();
+
+    let unused_query = "
+        SELECT s.relname::text, s.indexrelname::text, s.idx_scan,
+               pg_relation_size(s.indexrelid)
+        FROM pg_catalog.pg_stat_user_indexes s
+        JOIN pg_catalog.pg_index i ON i.indexrelid = s.indexrelid
+        WHERE s.schemaname = $1
+          AND ($2::text IS NULL OR s.relname = $2)
+          AND s.idx_scan = 0
+          AND NOT i.indisunique
+          AND NOT i.indisprimary";
+    let rows = client.query(unused_query, &[&schema, &table_filter]).await.map_err(|e| {
+        PlenumError::engine_error("postgres", format!("Failed to query index usage: {e}"))
+    })?;
+    for row in rows {
+        let scans: i64 = row.get(2);
+        let bytes: i64 = row.get(3);
+        findings.push(IndexFinding {
+            table: row.get(0),
+            schema: Some(schema.to_string()),
+            index: Some(row.get(1)),
+            reason: "unused".to_string(),
+            metric: "idx_scan".to_string(),
+            value: scans as f64,
+            detail: format!("Never scanned since statistics were reset; occupies {bytes} bytes"),
+        });
+    }
+
+    // A non-unique index is covered by another index of the same access method whose
+    // key columns (and operator classes) are identical or extend it. Exact duplicates
+    // keep the unique or older index. Expression and partial indexes are skipped.
+    let overlap_query = "
+        SELECT table_name, index_name, covering_name, exact, bytes
+        FROM (
+            SELECT DISTINCT ON (b.indexrelid)
+                   t.relname::text AS table_name, ib.relname::text AS index_name,
+                   ia.relname::text AS covering_name,
+                   a.indkey::text = b.indkey::text AS exact,
+                   pg_relation_size(b.indexrelid) AS bytes
+            FROM pg_catalog.pg_index b
+            JOIN pg_catalog.pg_index a
+              ON a.indrelid = b.indrelid AND a.indexrelid <> b.indexrelid
+            JOIN pg_catalog.pg_class t ON t.oid = b.indrelid
+            JOIN pg_catalog.pg_namespace n ON n.oid = t.relnamespace
+            JOIN pg_catalog.pg_class ib ON ib.oid = b.indexrelid
+            JOIN pg_catalog.pg_class ia ON ia.oid = a.indexrelid
+            WHERE n.nspname = $1
+              AND ($2::text IS NULL OR t.relname = $2)
+              AND NOT b.indisunique
+              AND ib.relam = ia.relam
+              AND b.indexprs IS NULL AND b.indpred IS NULL
+              AND a.indexprs IS NULL AND a.indpred IS NULL
+              AND (
+                  (a.indkey::text = b.indkey::text
+                   AND a.indclass::text = b.indclass::text
+                   AND (a.indisunique OR a.indexrelid < b.indexrelid))
+                  OR (a.indkey::text LIKE b.indkey::text || ' %'
+                      AND a.indclass::text LIKE b.indclass::text || ' %')
+              )
+            ORDER BY b.indexrelid, exact DESC, ia.relname
+        ) covered";
+    let rows = client.query(overlap_query, &[&schema, &table_filter]).await.map_err(|e| {
+        PlenumError::engine_error("postgres", format!("Failed to query index definitions: {e}"))
+    })?;
+    for row in rows {
+        let covering: String = row.get(2);
+        let exact: bool = row.get(3);
+        let bytes: i64 = row.get(4);
+        let (reason, detail) = if exact {
+            ("duplicate", format!("Same key columns as {covering}"))
+        } else {
+            ("redundant", format!("Key columns are a leading prefix of {covering}"))
+        };
+        findings.push(IndexFinding {
+            table: row.get(0),
+            schema: Some(schema.to_string()),
+            index: Some(row.get(1)),
+            reason: reason.to_string(),
+            metric: "index_bytes".to_string(),
+            value: bytes as f64,
+            detail,
+        });
+    }
+
+    let bloat_query = "
+        SELECT relname::text, n_live_tup, n_dead_tup, last_autovacuum::text, last_vacuum::text
+        FROM pg_catalog.pg_stat_user_tables
+        WHERE schemaname = $1
+          AND ($2::text IS NULL OR relname = $2)
+          AND n_dead_tup >= $3";
+    let rows = client
+        .query(bloat_query, &[&schema, &table_filter, &BLOAT_MIN_DEAD_TUPLES])
+        .await
+        .map_err(|e| {
+        PlenumError::engine_error("postgres", format!("Failed to query table statistics: {e}"))
+    })?;
+    for row in rows {
+        let live: i64 = row.get(1);
+        let dead: i64 = row.get(2);
+        let fraction = dead as f64 / (live + dead) as f64;
+        if fraction < BLOAT_MIN_DEAD_FRACTION {
+            continue;
+        }
+        let last_vacuum: Option<String> = row.get::<_, Option<String>>(3).or_else(|| row.get(4));
+        findings.push(IndexFinding {
+            table: row.get(0),
+            schema: Some(schema.to_string()),
+            index: None,
+            reason: "bloat".to_string(),
+            metric: "dead_tuple_fraction".to_string(),
+            value: fraction,
+            detail: format!(
+                "{dead} dead of {} tuples; last vacuumed {}",
+                live + dead,
+                last_vacuum.as_deref().unwrap_or("never")
+            ),
+        });
+    }
+
+    findings.sort_by(|a, b| (&a.table, &a.index).cmp(&(&b.table, &b.index)));
+
+    let stats_reset: Option<String> = client
+        .query_opt(
+            "SELECT stats_reset::text FROM pg_catalog.pg_stat_database
+             WHERE datname = current_database()",
+            &[],
+        )
+        .await
+        .map_err(|e| {
+            PlenumError::engine_error("postgres", format!("Failed to query statistics reset: {e}"))
+        })?
+        .and_then(|row| row.get(0));
+    let notes = vec![match stats_reset {
+        Some(reset) => format!("Index usage counters cover activity since {reset}"),
+        None => "Index usage counters have never been reset on this server".to_string(),
+    }];
+
+    Ok(IntrospectResult::IndexHealth { health: IndexHealthReport { findings, notes } })
+}
+
+/// Report table, index and TOAST sizes for tables and materialized views in the target schema.
+///
+/// `table_bytes` is `pg_table_size` minus TOAST (heap, free space map and visibility map), so
+/// `table_bytes + index_bytes + toast_bytes` equals `pg_total_relation_size`. Partitioned
+/// parents hold no storage of their own; their partitions are listed individually.
+async fn sizes_postgres(
+    client: &Client,
+    schema: &str,
+    options: &SizeOptions,
+) -> Result<IntrospectResult> {
+    let query = "
+        SELECT c.relname::text, pg_catalog.pg_table_size(c.oid) - t.bytes,
+               pg_catalog.pg_indexes_size(c.oid), t.bytes,
+               pg_catalog.pg_total_relation_size(c.oid),
+               CASE WHEN c.reltuples < 0 THEN NULL ELSE c.reltuples::bigint END
+        FROM pg_catalog.pg_class c
+        JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+        CROSS JOIN LATERAL (
+            SELECT COALESCE(pg_catalog.pg_total_relation_size(NULLIF(c.reltoastrelid, 0)), 0) AS bytes
+        ) t
+        WHERE n.nspname = $1 AND c.relkind IN ('r', 'm')";
+    let rows = client.query(query, &[&schema]).await.map_err(|e| {
+        PlenumError::engine_error("postgres", format!("Failed to query relation sizes: {e}"))
+    })?;
+    let bytes = |value: i64| u64::try_from(value).ok();
+    let tables = rows
+        .iter()
+        .map(|row| TableSize {
+            name: row.get(0),
+            schema: Some(schema.to_string()),
+            table_bytes: bytes(row.get(1)),
+            index_bytes: bytes(row.get(2)),
+            toast_bytes: bytes(row.get(3)),
+            total_bytes: bytes(row.get(4)),
+            row_estimate: row.get::<_, Option<i64>>(5).and_then(|n| u64::try_from(n).ok()),
+        })
+        .collect();
+
+    let database_bytes: i64 = client
+        .query_one("SELECT pg_catalog.pg_database_size(current_database())", &[])
+        .await
+        .map_err(|e| {
+            PlenumError::engine_error("postgres", format!("Failed to query database size: {e}"))
+        })?
+        .get(0);
+
+    Ok(IntrospectResult::Sizes {
+        sizes: SizeReport::paginate(bytes(database_bytes), tables, options, Vec::new()),
+    })
+}
+
+/// Report run-time parameters from `pg_settings`.
+///
+/// A setting is session-changeable when its context is `user`, or `superuser` and the
+/// connected role is a superuser; `postmaster`, `sighup` and backend contexts need a
+/// reload or restart.
+async fn settings_postgres(client: &Client, pattern: Option<&str>) -> Result<IntrospectResult> {
+    let query = "
+        SELECT name, setting, unit, source,
+               context = 'user'
+                   OR (context = 'superuser' AND pg_catalog.current_setting('is_superuser') = 'on')
+        FROM pg_catalog.pg_settings";
+    let rows = client.query(query, &[]).await.map_err(|e| {
+        PlenumError::engine_error("postgres", format!("Failed to query pg_settings: {e}"))
+    })?;
+    let settings = rows
+        .iter()
+        .map(|row| SettingInfo {
+            name: row.get(0),
+            value: row.get(1),
+            unit: row.get(2),
+            source: row.get(3),
+            session_changeable: row.get(4),
+        })
+        .collect();
+
+    Ok(IntrospectResult::Settings { settings: filter_settings(settings, pattern) })
+}
+
+/// Report client sessions from `pg_stat_activity` and lock waits from `pg_locks`.
+///
+/// Without `pg_read_all_stats`, other users' query text reads `<insufficient privilege>`.
+/// Lock waits come from `pg_blocking_pids`, so sessions queued behind another waiter
+/// are reported as blocked by it as well as by the holder.
+async fn activity_postgres(client: &Client) -> Result<InspectResult> {
+    let sessions_query = "
+        SELECT pid, usename::text, datname::text, NULLIF(application_name, ''), state,
+               NULLIF(query, ''),
+               GREATEST(EXTRACT(EPOCH FROM clock_timestamp() - state_change) * 1000, 0)::bigint,
+               wait_event_type || ':' || wait_event
+        FROM pg_catalog.pg_stat_activity
+        WHERE backend_type = 'client backend' AND pid <> pg_backend_pid()
+        ORDER BY pid";
+    let rows = client.query(sessions_query, &[]).await.map_err(|e| {
+        PlenumError::engine_error("postgres", format!("Failed to query pg_stat_activity: {e}"))
+    })?;
+    let sessions = rows
+        .iter()
+        .map(|row| SessionActivity {
+            pid: i64::from(row.get::<_, i32>(0)),
+            user: row.get(1),
+            database: row.get(2),
+            application: row.get(3),
+            state: row.get(4),
+            query: row.get(5),
+            duration_ms: row.get::<_, Option<i64>>(6).and_then(|ms| u64::try_from(ms).ok()),
+            wait_event: row.get(7),
+        })
+        .collect();
+
+    let locks_query = "
+        SELECT w.pid, b.pid, l.locktype, l.mode, l.relation::regclass::text
+        FROM pg_catalog.pg_stat_activity w
+        CROSS JOIN LATERAL unnest(pg_catalog.pg_blocking_pids(w.pid)) AS b(pid)
+        LEFT JOIN LATERAL (
+            SELECT locktype, mode, relation
+            FROM pg_catalog.pg_locks
+            WHERE pid = w.pid AND NOT granted
+            LIMIT 1
+        ) l ON true
+        WHERE w.wait_event_type = 'Lock'
+        ORDER BY w.pid, b.pid";
+    let rows = client.query(locks_query, &[]).await.map_err(|e| {
+        PlenumError::engine_error("postgres", format!("Failed to query lock waits: {e}"))
+    })?;
+    let lock_waits = rows
+        .iter()
+        .map(|row| LockWait {
+            waiting_pid: i64::from(row.get::<_, i32>(0)),
+            blocking_pid: i64::from(row.get::<_, i32>(1)),
+            lock_type: row.get(2),
+            mode: row.get(3),
+            object: row.get(4),
+        })
+        .collect();
+
+    Ok(InspectResult::Activity { activity: ActivityReport { sessions, lock_waits } })
+}
+
+/// Rank normalized statements from `pg_stat_statements`, aggregated per `queryid` and database.
+///
+/// Reports a note instead of failing when the extension is not installed in this database
+/// or its library is not in `shared_preload_libraries`. `PostgreSQL` 13 renamed
+/// `total_time`/`mean_time` to `total_exec_time`/`mean_exec_time`.
+async fn top_queries_postgres(
+    client: &Client,
+    order_by: QueryRanking,
+    limit: usize,
+) -> Result<InspectResult> {
+    let report = |statements, notes| InspectResult::TopQueries {
+        top_queries: TopQueriesReport { order_by, statements, notes },
+    };
+
+    let extension_schema: Option<String> = client
+        .query_opt(
+            "SELECT n.nspname::text
+             FROM pg_catalog.pg_extension e
+             JOIN pg_catalog.pg_namespace n ON n.oid = e.extnamespace
+             WHERE e.extname = 'pg_stat_statements'",
+            &[],
+        )
+        .await
+        .map_err(|e| {
+            PlenumError::engine_error("postgres", format!("Failed to query extensions: {e}"))
+        })?
+        .map(|row| row.get(0));
+    let Some(extension_schema) = extension_schema else {
+        return Ok(report(
+            Vec::new(),
+            vec!["pg_stat_statements is not installed in this database; it needs \
+                  shared_preload_libraries = 'pg_stat_statements' and CREATE EXTENSION \
+                  pg_stat_statements"
+                .to_string()],
+        ));
+    };
+
+    let version: i32 = client
+        .query_one("SELECT current_setting('server_version_num')::int", &[])
+        .await
+        .map_err(|e| {
+            PlenumError::engine_error("postgres", format!("Failed to query server version: {e}"))
+        })?
+        .get(0);
+    let total_column = if version >= 130_000 { "total_exec_time" } else { "total_time" };
+    // Positions in the select list below
+    let order_position = match order_by {
+        QueryRanking::TotalTime => 5,
+        QueryRanking::MeanTime => 6,
+        QueryRanking::Calls => 4,
+        QueryRanking::Rows => 7,
+    };
+    let schema = quote_identifier(&extension_schema);
+    let query = format!(
+        "SELECT s.queryid::text, min(s.query), d.datname::text, sum(s.calls)::bigint,
+                sum(s.{total_column})::float8,
+                COALESCE(sum(s.{total_column}) / NULLIF(sum(s.calls), 0), 0)::float8,
+                sum(s.rows)::bigint
+         FROM {schema}.pg_stat_statements s
+         LEFT JOIN pg_catalog.pg_database d ON d.oid = s.dbid
+         WHERE s.queryid IS NOT NULL
+         GROUP BY s.queryid, d.datname
+         ORDER BY {order_position} DESC, s.queryid
+         LIMIT $1"
+    );
+    let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+    let rows = match client.query(&query, &[&limit]).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            if let Some(db) = e
+                .as_db_error()
+                .filter(|db| *db.code() == SqlState::OBJECT_NOT_IN_PREREQUISITE_STATE)
+            {
+                return Ok(report(
+                    Vec::new(),
+                    vec![format!("pg_stat_statements is unavailable: {}", db.message())],
+                ));
+            }
+            return Err(PlenumError::engine_error(
+                "postgres",
+                format!("Failed to query pg_stat_statements: {e}"),
+            ));
+        }
+    };
+
+    let statements: Vec<StatementStats> = rows
+        .iter()
+        .map(|row| StatementStats {
+            digest: row.get(0),
+            query: row.get(1),
+            database: row.get(2),
+            calls: u64::try_from(row.get::<_, i64>(3)).unwrap_or(0),
+            total_time_ms: row.get(4),
+            mean_time_ms: row.get(5),
+            rows: u64::try_from(row.get::<_, i64>(6)).unwrap_or(0),
+        })
+        .collect();
+
+    let mut notes = Vec::new();
+    if version >= 140_000 {
+        let reset: Option<String> = client
+            .query_opt(
+                &format!("SELECT stats_reset::text FROM {schema}.pg_stat_statements_info"),
+                &[],
+            )
+            .await
+            .map_err(|e| {
+                PlenumError::engine_error(
+                    "postgres",
+                    format!("Failed to query pg_stat_statements_info: {e}"),
+                )
+            })?
+            .and_then(|row| row.get(0));
+        if let Some(reset) = reset {
+            notes.push(format!("Statement statistics cover activity since {reset}"));
+        }
+    }
+    if statements.iter().any(|s| s.query == "<insufficient privilege>") {
+        notes.push(
+            "Text of other users' statements is hidden; grant pg_read_all_stats to see it"
+                .to_string(),
+        );
+    }
+
+    Ok(report(statements, notes))
+}
+
+/// Sampling percentage for `size` rows: the planner's row estimate with 2x headroom so
+/// the `LIMIT` is usually reached; a table without an estimate counts as 100%.
+#[allow(clippy::cast_precision_loss)]
+fn sample_percent(row_estimate: Option<i64>, options: &SampleOptions) -> f64 {
+    row_estimate
+        .filter(|&rows| rows > 0)
+        .map_or(100.0, |rows| (options.size as f64 * 200.0 / rows as f64).min(100.0))
+}
+
+/// Build a seeded sample query returning about `size` rows.
+///
+/// With a row estimate this is `TABLESAMPLE BERNOULLI ... REPEATABLE (seed)` at
+/// [`sample_percent`]. A table the planner has never analyzed would be read at
+/// 100% and cut to its first rows, so its rows are ordered by a seeded `md5` of
+/// their text instead.
+fn sample_query_postgres(
+    schema: &str,
+    table_name: &str,
+    row_estimate: Option<i64>,
+    options: &SampleOptions,
+) -> String {
+    let relation = format!("{}.{}", quote_identifier(schema), quote_identifier(table_name));
+    if row_estimate.is_none_or(|rows| rows <= 0) {
+        return format!(
+            "SELECT plenum_t.* FROM {relation} AS plenum_t \
+             ORDER BY md5('{}|' || plenum_t::text) LIMIT {}",
+            options.seed, options.size
+        );
+    }
+    format!(
+        "SELECT * FROM {relation} TABLESAMPLE BERNOULLI ({}) REPEATABLE ({}) LIMIT {}",
+        sample_percent(row_estimate, options),
+        options.seed,
+        options.size
+    )
+}
+
+/// Unwrap an optional JSON array column (NULL → empty)
+fn json_array(value: Option<serde_json::Value>) -> Vec<serde_json::Value> {
+    match value {
+        Some(serde_json::Value::Array(items)) => items,
+        _ => Vec::new(),
+    }
+}
+
+/// Round a non-negative statistics estimate to a whole count
+#[allow(clippy::cast_sign_loss)]
+fn estimate_count(estimate: f64) -> u64 {
+    estimate.max(0.0).round() as u64
+}
+
+/// Quote an identifier for interpolation into `PostgreSQL` SQL.
+fn quote_identifier(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
 /// Introspect table columns (includes column comments from `pg_description`)
 async fn introspect_columns(
     client: &Client,
@@ -877,10 +1944,16 @@ fn is_statement_timeout(e: &tokio_postgres::Error) -> bool {
 
 /// Convert a JSON value to a boxed `tokio-postgres` `ToSql` trait object for parameter binding.
 /// Uses `$1`/`$2`/… Postgres placeholders.
+///
+/// Typed parameters (`{"$type": "uuid", "value": ...}`) bind through [`TypedParam`]
+/// as the exact native type the statement expects.
 fn json_to_pg_value(
     val: &serde_json::Value,
 ) -> Box<dyn tokio_postgres::types::ToSql + Sync + Send> {
     use tokio_postgres::types::ToSql;
+    if let Some(Ok(typed)) = ParamValue::from_json(val) {
+        return Box::new(TypedParam(typed));
+    }
     match val {
         serde_json::Value::Null => Box::new(Option::<String>::None) as Box<dyn ToSql + Sync + Send>,
         serde_json::Value::Bool(b) => Box::new(*b),
@@ -896,13 +1969,186 @@ fn json_to_pg_value(
     }
 }
 
+/// A typed parameter, encoded for whichever type the server inferred for its placeholder.
+///
+/// Integers fit `int2`/`int4`/`int8`/`numeric`, decimals are encoded as exact
+/// `numeric`, and arrays use the element type of the inferred array type. A value
+/// that cannot represent the inferred type is an error rather than a silent cast.
+#[derive(Debug)]
+struct TypedParam(ParamValue);
+
+impl tokio_postgres::types::ToSql for TypedParam {
+    fn to_sql(
+        &self,
+        ty: &tokio_postgres::types::Type,
+        out: &mut bytes::BytesMut,
+    ) -> std::result::Result<tokio_postgres::types::IsNull, Box<dyn std::error::Error + Sync + Send>>
+    {
+        use tokio_postgres::types::{IsNull, Type};
+
+        let mismatch = || -> Box<dyn std::error::Error + Sync + Send> {
+            format!("a {} parameter cannot bind to {ty}", param_kind(&self.0)).into()
+        };
+        match &self.0 {
+            ParamValue::Null => Ok(IsNull::Yes),
+            ParamValue::Bool(b) => bind_exact(b, ty, out, mismatch),
+            ParamValue::Int(i) => match *ty {
+                Type::INT2 => i16::try_from(*i)
+                    .map_err(|_| format!("int parameter {i} is out of range for {ty}"))?
+                    .to_sql(ty, out),
+                Type::INT4 => i32::try_from(*i)
+                    .map_err(|_| format!("int parameter {i} is out of range for {ty}"))?
+                    .to_sql(ty, out),
+                Type::INT8 => i.to_sql(ty, out),
+                Type::NUMERIC => encode_numeric(&i.to_string(), out),
+                #[allow(clippy::cast_precision_loss)]
+                Type::FLOAT8 => (*i as f64).to_sql(ty, out),
+                _ => Err(mismatch()),
+            },
+            ParamValue::Float(f) => match *ty {
+                #[allow(clippy::cast_possible_truncation)]
+                Type::FLOAT4 => (*f as f32).to_sql(ty, out),
+                Type::FLOAT8 => f.to_sql(ty, out),
+                Type::NUMERIC => encode_numeric(&f.to_string(), out),
+                _ => Err(mismatch()),
+            },
+            ParamValue::Decimal(d) => match *ty {
+                Type::NUMERIC => encode_numeric(d, out),
+                Type::FLOAT4 | Type::FLOAT8 => d.parse::<f64>()?.to_sql(ty, out),
+                _ => Err(mismatch()),
+            },
+            ParamValue::Text(s) => bind_exact(s, ty, out, mismatch),
+            ParamValue::Uuid(u) => bind_exact(&uuid::Uuid::from_u128(*u), ty, out, mismatch),
+            ParamValue::Date(d) => bind_exact(&naive_date(*d)?, ty, out, mismatch),
+            ParamValue::Time(t) => bind_exact(&naive_time(*t)?, ty, out, mismatch),
+            ParamValue::Timestamp(ts) => match *ty {
+                Type::TIMESTAMPTZ => {
+                    chrono::DateTime::<chrono::Utc>::from_timestamp_micros(ts.micros_since_epoch())
+                        .ok_or_else(mismatch)?
+                        .to_sql(ty, out)
+                }
+                Type::TIMESTAMP => {
+                    let ts = ts.to_utc();
+                    naive_date(ts.date)?.and_time(naive_time(ts.time)?).to_sql(ty, out)
+                }
+                _ => Err(mismatch()),
+            },
+            ParamValue::Json(v) => bind_exact(v, ty, out, mismatch),
+            ParamValue::Bytes(b) => bind_exact(b, ty, out, mismatch),
+            ParamValue::Array(_, items) => {
+                let items: Vec<Self> = items.iter().cloned().map(Self).collect();
+                bind_exact(&items, ty, out, mismatch)
+            }
+        }
+    }
+
+    fn accepts(_ty: &tokio_postgres::types::Type) -> bool {
+        // The concrete type is checked per value in `to_sql`.
+        true
+    }
+
+    tokio_postgres::types::to_sql_checked!();
+}
+
+/// Encode `value` if its Rust type accepts `ty`, else report the mismatch.
+fn bind_exact<T: tokio_postgres::types::ToSql>(
+    value: &T,
+    ty: &tokio_postgres::types::Type,
+    out: &mut bytes::BytesMut,
+    mismatch: impl FnOnce() -> Box<dyn std::error::Error + Sync + Send>,
+) -> std::result::Result<tokio_postgres::types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+    if T::accepts(ty) {
+        value.to_sql(ty, out)
+    } else {
+        Err(mismatch())
+    }
+}
+
+/// Type name of a typed parameter for mismatch errors
+fn param_kind(value: &ParamValue) -> String {
+    match value {
+        ParamValue::Null => "null".to_string(),
+        ParamValue::Bool(_) => "bool".to_string(),
+        ParamValue::Int(_) => "int".to_string(),
+        ParamValue::Float(_) => "float".to_string(),
+        ParamValue::Decimal(_) => "decimal".to_string(),
+        ParamValue::Text(_) => "text".to_string(),
+        ParamValue::Uuid(_) => "uuid".to_string(),
+        ParamValue::Date(_) => "date".to_string(),
+        ParamValue::Time(_) => "time".to_string(),
+        ParamValue::Timestamp(_) => "timestamp".to_string(),
+        ParamValue::Json(_) => "json".to_string(),
+        ParamValue::Bytes(_) => "bytes".to_string(),
+        ParamValue::Array(ty, _) => format!("{}[]", ty.as_str()),
+    }
+}
+
+fn naive_date(d: crate::params::Date) -> std::result::Result<chrono::NaiveDate, String> {
+    chrono::NaiveDate::from_ymd_opt(d.year, d.month, d.day)
+        .ok_or_else(|| format!("invalid date {d}"))
+}
+
+fn naive_time(t: crate::params::Time) -> std::result::Result<chrono::NaiveTime, String> {
+    chrono::NaiveTime::from_hms_micro_opt(t.hour, t.minute, t.second, t.micros)
+        .ok_or_else(|| format!("invalid time {t}"))
+}
+
+/// Encode decimal text (`[-]digits[.digits]`) as binary `numeric`: base-10000
+/// digit groups with a weight (position of the first group relative to the
+/// decimal point), a sign and the display scale.
+fn encode_numeric(
+    text: &str,
+    out: &mut bytes::BytesMut,
+) -> std::result::Result<tokio_postgres::types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+    use bytes::BufMut;
+
+    let (negative, unsigned) =
+        text.strip_prefix('-').map_or((false, text.trim_start_matches('+')), |t| (true, t));
+    let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    if !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid decimal '{text}'").into());
+    }
+    let scale = i16::try_from(frac.len())?;
+
+    // Pad the integer part on the left and the fraction on the right to whole groups.
+    let int_pad = (4 - int.len() % 4) % 4;
+    let frac_pad = (4 - frac.len() % 4) % 4;
+    let padded = format!("{}{int}{frac}{}", "0".repeat(int_pad), "0".repeat(frac_pad));
+    let mut groups: Vec<i16> = padded
+        .as_bytes()
+        .chunks(4)
+        .map(|c| c.iter().fold(0, |acc, d| acc * 10 + i16::from(d - b'0')))
+        .collect();
+    let mut weight = i16::try_from((int.len() + int_pad) / 4)? - 1;
+
+    let leading = groups.iter().take_while(|g| **g == 0).count();
+    groups.drain(..leading);
+    weight -= i16::try_from(leading)?;
+    while groups.last() == Some(&0) {
+        groups.pop();
+    }
+    if groups.is_empty() {
+        weight = 0;
+    }
+
+    out.put_i16(i16::try_from(groups.len())?);
+    out.put_i16(weight);
+    out.put_u16(if negative && !groups.is_empty() { 0x4000 } else { 0x0000 });
+    out.put_i16(scale);
+    for group in groups {
+        out.put_i16(group);
+    }
+    Ok(tokio_postgres::types::IsNull::No)
+}
+
 /// Execute query and return `QueryResult`
 async fn execute_query(
-    client: &Client,
+    client: &mut Client,
     query: &str,
     params: &[serde_json::Value],
     caps: &Capabilities,
-) -> Result<QueryResult> {
+    sink: &mut dyn RowSink,
+) -> Result<StreamSummary> {
     // Execute query
     let stmt = client
         .prepare(query)
@@ -918,94 +2164,113 @@ async fn execute_query(
         .map(|v| v.as_ref() as &(dyn tokio_postgres::types::ToSql + Sync))
         .collect();
 
-    // Check if this is a SELECT query (returns rows)
-    let is_select = !stmt.columns().is_empty();
-
-    if is_select {
-        // SELECT query - execute and collect rows
-        let rows = client.query(&stmt, &param_refs).await.map_err(|e| {
-            if is_statement_timeout(&e) {
-                PlenumError::query_timeout(format!(
-                    "Query cancelled by PostgreSQL server-side statement_timeout: {e}"
-                ))
-            } else {
-                PlenumError::query_failed(format!("Failed to execute query: {e}"))
-            }
-        })?;
-
-        // Get column names
-        let column_names: Vec<String> =
-            stmt.columns().iter().map(|c| c.name().to_string()).collect();
+    let columns: Vec<ResultColumn> = stmt
+        .columns()
+        .iter()
+        .map(|c| ResultColumn {
+            name: c.name().to_string(),
+            type_name: Some(c.type_().name().to_string()),
+        })
+        .collect();
+    let mut feed = RowFeed::new(sink, caps);
+    feed.columns(&columns)?;
 
-        // Apply offset and max_rows with truncation detection
-        let offset = caps.offset.unwrap_or(0);
-        let effective = if offset <= rows.len() { &rows[offset..] } else { &[][..] };
-        let max = caps.max_rows.unwrap_or(usize::MAX);
-        let rows_truncated = effective.len() > max;
-        let take = effective.len().min(max);
+    // Check if this is a SELECT query (returns rows)
+    if columns.is_empty() {
+        // Non-SELECT query (INSERT, UPDATE, DELETE, DDL)
+        let rows_affected =
+            client.execute(&stmt, &param_refs).await.map_err(|e| map_pg_exec_error(&e))?;
+        let mut summary = feed.finish();
+        summary.rows_affected = Some(rows_affected);
+        return Ok(summary);
+    }
 
-        let mut rows_data = Vec::new();
-        for row in &effective[..take] {
-            rows_data.push(row_to_json(&column_names, row)?);
+    // SELECT query - fetch through a portal (an unnamed server-side cursor) in batches,
+    // so at most one batch is held in memory and fetching stops as soon as max_rows or
+    // the sink is satisfied. Portals only live inside a transaction.
+    let transaction = client
+        .transaction()
+        .await
+        .map_err(|e| PlenumError::query_failed(format!("Failed to open read transaction: {e}")))?;
+    let portal = transaction.bind(&stmt, &param_refs).await.map_err(|e| map_pg_exec_error(&e))?;
+    let cells = feed.cells();
+    'fetch: loop {
+        let batch = transaction
+            .query_portal(&portal, FETCH_BATCH_ROWS)
+            .await
+            .map_err(|e| map_pg_exec_error(&e))?;
+        for row in &batch {
+            if !feed.push(|| row_to_json(columns.len(), row, cells))? {
+                break 'fetch;
+            }
+        }
+        if batch.len() < FETCH_BATCH_ROWS as usize {
+            break;
         }
+    }
+    transaction
+        .rollback()
+        .await
+        .map_err(|e| PlenumError::query_failed(format!("Failed to close read transaction: {e}")))?;
 
-        Ok(QueryResult {
-            columns: column_names,
-            rows: rows_data,
-            rows_affected: None,
-            execution_ms: 0,
-            rows_truncated,
-            truncated_by: None,
-            plan: None,
-        })
+    Ok(feed.finish())
+}
+
+/// Map a query execution error, recognising server-side `statement_timeout` cancellation.
+fn map_pg_exec_error(e: &tokio_postgres::Error) -> PlenumError {
+    if is_statement_timeout(e) {
+        PlenumError::query_timeout(format!(
+            "Query cancelled by PostgreSQL server-side statement_timeout: {e}"
+        ))
     } else {
-        // Non-SELECT query (INSERT, UPDATE, DELETE, DDL)
-        let rows_affected = client.execute(&stmt, &param_refs).await.map_err(|e| {
-            if is_statement_timeout(&e) {
-                PlenumError::query_timeout(format!(
-                    "Query cancelled by PostgreSQL server-side statement_timeout: {e}"
-                ))
-            } else {
-                PlenumError::query_failed(format!("Failed to execute query: {e}"))
+        // Parameter encoding errors (e.g. a typed parameter that does not fit the
+        // column) carry the useful detail in the source error.
+        match std::error::Error::source(e).filter(|_| e.as_db_error().is_none()) {
+            Some(source) => {
+                PlenumError::query_failed(format!("Failed to execute query: {e}: {source}"))
             }
-        })?;
-
-        Ok(QueryResult {
-            columns: Vec::new(),
-            rows: Vec::new(),
-            rows_affected: Some(rows_affected),
-            execution_ms: 0,
-            rows_truncated: false,
-            truncated_by: None,
-            plan: None,
-        })
+            None => PlenumError::query_failed(format!("Failed to execute query: {e}")),
+        }
     }
 }
 
-/// Execute `EXPLAIN (FORMAT JSON)` against the inner SQL and normalize the result.
+/// Execute `EXPLAIN (FORMAT JSON)` against the inner SQL with `params` bound and
+/// normalize the result.
+///
+/// With `analyze` the statement runs as `EXPLAIN (ANALYZE, BUFFERS, FORMAT JSON)`,
+/// adding actual rows, timings, loops and buffer counts to every node.
 async fn execute_structured_explain_postgres(
     client: &Client,
     inner_sql: &str,
+    params: &[serde_json::Value],
+    analyze: bool,
 ) -> Result<ExplainPlanNode> {
-    let sql = format!("EXPLAIN (FORMAT JSON) {inner_sql}");
+    let options = if analyze { "ANALYZE, BUFFERS, FORMAT JSON" } else { "FORMAT JSON" };
+    let sql = format!("EXPLAIN ({options}) {inner_sql}");
 
-    let rows = client.query(sql.as_str(), &[]).await.map_err(|e| {
-        PlenumError::query_failed(format!("Failed to execute EXPLAIN (FORMAT JSON): {e}"))
+    let pg_params: Vec<Box<dyn tokio_postgres::types::ToSql + Sync + Send>> =
+        params.iter().map(json_to_pg_value).collect();
+    let param_refs: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> = pg_params
+        .iter()
+        .map(|v| v.as_ref() as &(dyn tokio_postgres::types::ToSql + Sync))
+        .collect();
+    let rows = client.query(sql.as_str(), &param_refs).await.map_err(|e| {
+        if is_statement_timeout(&e) {
+            map_pg_exec_error(&e)
+        } else {
+            PlenumError::query_failed(format!("Failed to execute EXPLAIN ({options}): {e}"))
+        }
     })?;
 
     let row = rows.first().ok_or_else(|| {
-        PlenumError::query_failed("EXPLAIN (FORMAT JSON) returned no rows".to_string())
+        PlenumError::query_failed(format!("EXPLAIN ({options}) returned no rows"))
     })?;
 
-    // PostgreSQL returns a single text column ("QUERY PLAN") containing the JSON array
-    let plan_json: String = row.try_get(0).map_err(|e| {
+    // PostgreSQL returns a single `json` column ("QUERY PLAN") containing the plan array
+    let plan_value: serde_json::Value = row.try_get(0).map_err(|e| {
         PlenumError::query_failed(format!("Failed to read EXPLAIN JSON column: {e}"))
     })?;
 
-    let plan_value: serde_json::Value = serde_json::from_str(&plan_json).map_err(|e| {
-        PlenumError::query_failed(format!("Failed to parse EXPLAIN JSON from PostgreSQL: {e}"))
-    })?;
-
     // PostgreSQL wraps the plan in [{Plan: {...}}, ...]; we take the first entry
     let plan_obj = plan_value
         .as_array()
@@ -1021,14 +2286,24 @@ async fn execute_structured_explain_postgres(
 }
 
 /// Recursively normalize a `PostgreSQL` EXPLAIN JSON plan node.
+///
+/// The actual-execution keys (`Actual Rows`, `Shared Hit Blocks`, ...) are only
+/// present in `EXPLAIN ANALYZE` output; estimate-only plans leave them `None`.
 fn normalize_pg_plan_node(node: &serde_json::Value) -> ExplainPlanNode {
-    let node_type =
-        node.get("Node Type").and_then(serde_json::Value::as_str).unwrap_or("unknown").to_string();
+    let number = |key: &str| node.get(key).and_then(serde_json::Value::as_f64);
+    let blocks = |key: &str| node.get(key).and_then(serde_json::Value::as_u64);
+    let text = |key: &str| node.get(key).and_then(serde_json::Value::as_str).map(String::from);
 
-    let relation = node.get("Relation Name").and_then(serde_json::Value::as_str).map(String::from);
+    let node_type = text("Node Type").unwrap_or_else(|| "unknown".to_string());
 
-    let estimated_rows = node.get("Plan Rows").and_then(serde_json::Value::as_f64);
-    let estimated_cost = node.get("Total Cost").and_then(serde_json::Value::as_f64);
+    let buffers = blocks("Shared Hit Blocks").map(|shared_hit| PlanBuffers {
+        shared_hit,
+        shared_read: blocks("Shared Read Blocks").unwrap_or(0),
+        shared_dirtied: blocks("Shared Dirtied Blocks").unwrap_or(0),
+        shared_written: blocks("Shared Written Blocks").unwrap_or(0),
+        temp_read: blocks("Temp Read Blocks").unwrap_or(0),
+        temp_written: blocks("Temp Written Blocks").unwrap_or(0),
+    });
 
     let children = node
         .get("Plans")
@@ -1036,15 +2311,58 @@ fn normalize_pg_plan_node(node: &serde_json::Value) -> ExplainPlanNode {
         .map(|plans| plans.iter().map(normalize_pg_plan_node).collect())
         .unwrap_or_default();
 
-    ExplainPlanNode { node_type, relation, estimated_rows, estimated_cost, children }
+    ExplainPlanNode {
+        operation: pg_plan_operation(&node_type),
+        node_type,
+        relation: text("Relation Name"),
+        index: text("Index Name"),
+        filter: text("Filter"),
+        join_condition: text("Hash Cond")
+            .or_else(|| text("Merge Cond"))
+            .or_else(|| text("Join Filter")),
+        estimated_rows: number("Plan Rows"),
+        estimated_cost: number("Total Cost"),
+        actual_rows: number("Actual Rows"),
+        actual_time_ms: number("Actual Total Time"),
+        loops: blocks("Actual Loops"),
+        buffers,
+        children,
+    }
 }
 
-/// Convert a `PostgreSQL` row to a JSON-safe `Vec`
-fn row_to_json(column_names: &[String], row: &Row) -> Result<Vec<serde_json::Value>> {
-    let mut values = Vec::with_capacity(column_names.len());
+/// Categorize a `PostgreSQL` plan `Node Type`.
+fn pg_plan_operation(node_type: &str) -> PlanOperation {
+    match node_type {
+        "Seq Scan" => PlanOperation::FullScan,
+        "Index Scan" | "Bitmap Heap Scan" | "Bitmap Index Scan" => PlanOperation::IndexScan,
+        "Index Only Scan" => PlanOperation::IndexOnlyScan,
+        "Hash Join" => PlanOperation::HashJoin,
+        "Nested Loop" => PlanOperation::NestedLoop,
+        "Merge Join" => PlanOperation::MergeJoin,
+        "Hash" => PlanOperation::Hash,
+        "Sort" | "Incremental Sort" => PlanOperation::Sort,
+        "Aggregate" | "Group" => PlanOperation::Aggregate,
+        "WindowAgg" => PlanOperation::Window,
+        "Limit" => PlanOperation::Limit,
+        "Result" => PlanOperation::Projection,
+        "Materialize" | "Memoize" => PlanOperation::Materialize,
+        "Append" | "Merge Append" | "Recursive Union" | "SetOp" => PlanOperation::Union,
+        "Unique" => PlanOperation::Distinct,
+        "Subquery Scan" | "CTE Scan" | "WorkTable Scan" => PlanOperation::SubqueryScan,
+        _ => PlanOperation::Other,
+    }
+}
 
-    for idx in 0..column_names.len() {
-        let value = postgres_value_to_json(row, idx)?;
+/// Convert a `PostgreSQL` row to a JSON-safe `Vec`
+fn row_to_json(
+    column_count: usize,
+    row: &Row,
+    cells: CellFormat,
+) -> Result<Vec<serde_json::Value>> {
+    let mut values = Vec::with_capacity(column_count);
+
+    for idx in 0..column_count {
+        let value = postgres_value_to_json(row, idx, cells)?;
         values.push(value);
     }
 
@@ -1072,7 +2390,7 @@ impl<'a> tokio_postgres::types::FromSql<'a> for NullProbe {
 }
 
 /// Convert `PostgreSQL` value to JSON value
-fn postgres_value_to_json(row: &Row, idx: usize) -> Result<serde_json::Value> {
+fn postgres_value_to_json(row: &Row, idx: usize, cells: CellFormat) -> Result<serde_json::Value> {
     use tokio_postgres::types::Type;
 
     let column = &row.columns()[idx];
@@ -1145,14 +2463,12 @@ fn postgres_value_to_json(row: &Row, idx: usize) -> Result<serde_json::Value> {
             v
         }
 
-        // BYTEA (binary data) - encode as Base64
+        // BYTEA (binary data) - encode per --binary (Base64 by default)
         Type::BYTEA => {
-            let v: Vec<u8> = row.try_get(idx).map_err(|e| {
+            let v: &[u8] = row.try_get(idx).map_err(|e| {
                 PlenumError::query_failed(format!("Failed to get bytea value: {e}"))
             })?;
-            use base64::Engine;
-            let encoded = base64::engine::general_purpose::STANDARD.encode(&v);
-            serde_json::Value::String(encoded)
+            cells.binary(v)
         }
 
         // Timestamps - convert to ISO 8601 strings
@@ -1228,6 +2544,7 @@ fn postgres_value_to_json(row: &Row, idx: usize) -> Result<serde_json::Value> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     // Note: These tests require a running PostgreSQL instance
     // They are integration tests that should be run with:
@@ -1665,6 +2982,31 @@ mod tests {
         assert_eq!(columns_multi, vec!["user_id", "order_date"]);
     }
 
+    #[test]
+    fn test_sample_query_postgres_percentage() {
+        let options = SampleOptions { size: 10, seed: 42, timeout_ms: None };
+
+        // 10 rows from 1000 → 1% with 2x headroom = 2%
+        let sql = sample_query_postgres("public", "users", Some(1000), &options);
+        assert_eq!(
+            sql,
+            r#"SELECT * FROM "public"."users" TABLESAMPLE BERNOULLI (2) REPEATABLE (42) LIMIT 10"#
+        );
+
+        // Tiny tables are sampled at 100%
+        let sql = sample_query_postgres("public", "users", Some(5), &options);
+        assert!(sql.contains("BERNOULLI (100)"), "{sql}");
+
+        // Unanalyzed tables (no estimate) are ordered by a seeded hash, not cut to a prefix
+        for estimate in [None, Some(-1), Some(0)] {
+            let sql = sample_query_postgres("public", "users", estimate, &options);
+            assert_eq!(
+                sql,
+                r#"SELECT plenum_t.* FROM "public"."users" AS plenum_t ORDER BY md5('42|' || plenum_t::text) LIMIT 10"#
+            );
+        }
+    }
+
     /// Prove that writes fail at the `PostgreSQL` session layer independently of the parser.
     ///
     /// Opens a raw connection, applies `SET default_transaction_read_only = ON` (same as
@@ -1958,4 +3300,116 @@ mod tests {
         assert!(qr.plan.is_none(), "plan must be absent in native mode");
         assert!(!qr.rows.is_empty(), "native mode must return raw plan rows");
     }
+
+    /// Encode a typed parameter for `ty` the way a bound statement would.
+    fn encode_typed(
+        value: &serde_json::Value,
+        ty: &tokio_postgres::types::Type,
+    ) -> std::result::Result<Vec<u8>, String> {
+        use tokio_postgres::types::ToSql;
+        let param = ParamValue::from_json(value)
+            .expect("typed parameter object")
+            .map_err(|e| e.to_string())?;
+        let mut out = bytes::BytesMut::new();
+        TypedParam(param).to_sql(ty, &mut out).map_err(|e| e.to_string())?;
+        Ok(out.to_vec())
+    }
+
+    /// Split binary `numeric` into (weight, sign, display scale, digit groups).
+    fn numeric_parts(bytes: &[u8]) -> (i16, u16, i16, Vec<i16>) {
+        let word = |i: usize| [bytes[2 * i], bytes[2 * i + 1]];
+        let count = usize::from(u16::from_be_bytes(word(0)));
+        let groups = (0..count).map(|i| i16::from_be_bytes(word(4 + i))).collect();
+        (
+            i16::from_be_bytes(word(1)),
+            u16::from_be_bytes(word(2)),
+            i16::from_be_bytes(word(3)),
+            groups,
+        )
+    }
+
+    #[test]
+    fn test_encode_numeric_edge_cases() {
+        use tokio_postgres::types::Type;
+        let numeric = |v: &str| {
+            numeric_parts(
+                &encode_typed(&json!({"$type": "decimal", "value": v}), &Type::NUMERIC).unwrap(),
+            )
+        };
+
+        // Trailing zeros keep their display scale but not their digit groups
+        assert_eq!(numeric("12.50"), (0, 0, 2, vec![12, 5000]));
+        assert_eq!(numeric("1.0000"), (0, 0, 4, vec![1]));
+        assert_eq!(numeric("100000000"), (2, 0, 0, vec![1]));
+        // A value for a negative-scale column (numeric(5,-2)) is sent at scale 0
+        // and rounded by the server
+        assert_eq!(numeric("12300"), (1, 0, 0, vec![1, 2300]));
+        assert_eq!(numeric("-0.000120"), (-1, 0x4000, 6, vec![1, 2000]));
+        // Negative zero is plain zero
+        assert_eq!(numeric("-0.00"), (0, 0, 2, vec![]));
+        // Floats are rendered without an exponent before encoding
+        let tiny = encode_typed(&json!({"$type": "float", "value": 1e-7}), &Type::NUMERIC).unwrap();
+        assert_eq!(numeric_parts(&tiny), (-2, 0, 7, vec![10]));
+        // Exponent notation is rejected before it reaches the encoder
+        assert!(encode_typed(&json!({"$type": "decimal", "value": "1e5"}), &Type::NUMERIC).is_err());
+    }
+
+    #[test]
+    fn test_encode_dates_and_times_edge_cases() {
+        use tokio_postgres::types::Type;
+        let i32_at = |b: Vec<u8>| i32::from_be_bytes(b.try_into().expect("4 bytes"));
+        let i64_at = |b: Vec<u8>| i64::from_be_bytes(b.try_into().expect("8 bytes"));
+        // PostgreSQL counts days and microseconds from 2000-01-01
+        let pg_epoch_days = 10_957;
+
+        // Year 0 is 1 BC in the proleptic Gregorian calendar both sides use
+        let bc =
+            encode_typed(&json!({"$type": "date", "value": "0000-12-31"}), &Type::DATE).unwrap();
+        let chrono_days = chrono::NaiveDate::from_ymd_opt(0, 12, 31)
+            .unwrap()
+            .signed_duration_since(chrono::NaiveDate::from_ymd_opt(1970, 1, 1).unwrap())
+            .num_days();
+        let Ok(ParamValue::Date(date)) =
+            ParamValue::from_json(&json!({"$type": "date", "value": "0000-12-31"})).unwrap()
+        else {
+            panic!("expected date");
+        };
+        assert_eq!(date.days_since_epoch(), chrono_days);
+        assert_eq!(i64::from(i32_at(bc)), chrono_days - pg_epoch_days);
+        // Negative years and `BC` suffixes are not accepted
+        for bad in ["-0044-03-15", "0044-03-15 BC"] {
+            assert!(
+                encode_typed(&json!({"$type": "date", "value": bad}), &Type::DATE).is_err(),
+                "{bad}"
+            );
+        }
+
+        // Leap seconds are rejected rather than rolled over
+        assert!(encode_typed(&json!({"$type": "time", "value": "23:59:60"}), &Type::TIME).is_err());
+        assert!(encode_typed(
+            &json!({"$type": "timestamp", "value": "2016-12-31T23:59:60Z"}),
+            &Type::TIMESTAMPTZ
+        )
+        .is_err());
+
+        // Fractional seconds down to microseconds, no further
+        let time = encode_typed(&json!({"$type": "time", "value": "23:59:59.999999"}), &Type::TIME)
+            .unwrap();
+        assert_eq!(i64_at(time), 86_399_999_999);
+        assert!(encode_typed(&json!({"$type": "time", "value": "00:00:00.1234567"}), &Type::TIME)
+            .is_err());
+        let at = encode_typed(
+            &json!({"$type": "timestamp", "value": "2000-01-01T00:00:00.5+01:00"}),
+            &Type::TIMESTAMPTZ,
+        )
+        .unwrap();
+        assert_eq!(i64_at(at), -3_599_500_000);
+        // A pre-epoch instant with an offset binds to `timestamp` in UTC
+        let local = encode_typed(
+            &json!({"$type": "timestamp", "value": "1970-01-01T00:59:59.999999+01:00"}),
+            &Type::TIMESTAMP,
+        )
+        .unwrap();
+        assert_eq!(i64_at(local), -1 - pg_epoch_days * 86_400_000_000);
+    }
 }
@@ -0,0 +1,393 @@
+//! Column profiling shared by all engines
+//!
+//! Engines without usable planner statistics profile a table by reading a
+//! bounded sample through their own `execute` path, so the read-only guards,
+//! `timeout_ms` and the row cap apply exactly as they do for `plenum query`.
+//! The sampled rows are then summarised here, independent of the engine.
+//!
+//! `query --summarize` reuses the same value ordering, but summarises an arbitrary
+//! query's rows as they stream out of `execute_stream`, without collecting them.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+use crate::engine::{
+    Capabilities, ColumnProfile, ColumnSummary, ConnectionConfig, DatabaseEngine, ProfileOptions,
+    QueryResult, ResultColumn, ResultSummary, RowSink, TableProfile, ValueFrequency,
+};
+use crate::error::Result;
+
+/// Distinct values tracked per column before `distinct_count` stops being exact
+const SUMMARY_DISTINCT_CAP: usize = 10_000;
+
+/// Example values reported per column
+const SUMMARY_EXAMPLES: usize = 3;
+
+/// Profile a table from `sample_sql` run through `E::execute`.
+///
+/// `sample_sql` is the engine's seeded `SampleTable` query for
+/// [`ProfileOptions::sample_options`], so the profile describes the same rows
+/// `--sample` draws rather than whichever rows the table stores first. `table`
+/// and `schema` are only echoed back in the result.
+pub async fn profile_by_sampling<E: DatabaseEngine>(
+    config: &ConnectionConfig,
+    sample_sql: &str,
+    table: &str,
+    schema: Option<&str>,
+    options: &ProfileOptions,
+) -> Result<TableProfile> {
+    let caps = Capabilities::new(Some(options.sample_rows), options.timeout_ms);
+    let sample = E::execute(config, sample_sql, &[], &caps).await?;
+    Ok(profile_from_sample(table, schema, &sample, options))
+}
+
+/// Summarise sampled rows into a `TableProfile` with `source = "sample"`.
+///
+/// The profile is exhaustive when the sample came back smaller than the cap,
+/// i.e. every row of the table was read. Samples that can come back short
+/// without reading every row (`PostgreSQL`'s Bernoulli sampling below 100%)
+/// clear `exhaustive` afterwards.
+pub fn profile_from_sample(
+    table: &str,
+    schema: Option<&str>,
+    sample: &QueryResult,
+    options: &ProfileOptions,
+) -> TableProfile {
+    let row_count = sample.rows.len() as u64;
+    let columns = sample
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            let values = sample.rows.iter().map(|row| row.get(idx).unwrap_or(&Value::Null));
+            profile_column(name, values, row_count, options.top_n)
+        })
+        .collect();
+
+    TableProfile {
+        table: table.to_string(),
+        schema: schema.map(String::from),
+        source: "sample".to_string(),
+        row_count,
+        exhaustive: sample.rows.len() < options.sample_rows,
+        columns,
+    }
+}
+
+/// Profile a single column from its sampled values
+fn profile_column<'a>(
+    name: &str,
+    values: impl Iterator<Item = &'a Value>,
+    row_count: u64,
+    top_n: usize,
+) -> ColumnProfile {
+    let mut nulls = 0u64;
+    let mut counts: HashMap<String, (&Value, u64)> = HashMap::new();
+    let mut min: Option<&Value> = None;
+    let mut max: Option<&Value> = None;
+
+    for value in values {
+        if value.is_null() {
+            nulls += 1;
+            continue;
+        }
+        counts.entry(value.to_string()).or_insert((value, 0)).1 += 1;
+        if min.is_none_or(|m| compare_values(value, m) == Ordering::Less) {
+            min = Some(value);
+        }
+        if max.is_none_or(|m| compare_values(value, m) == Ordering::Greater) {
+            max = Some(value);
+        }
+    }
+
+    // Most frequent first; ties broken by the serialized value so output is deterministic.
+    let mut ranked: Vec<(String, &Value, u64)> =
+        counts.into_iter().map(|(key, (value, count))| (key, value, count)).collect();
+    ranked.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+    let distinct_count = ranked.len() as u64;
+
+    let top_values = ranked
+        .into_iter()
+        .take(top_n)
+        .map(|(_, value, count)| ValueFrequency {
+            value: value.clone(),
+            count,
+            frequency: fraction(count, row_count),
+        })
+        .collect();
+
+    ColumnProfile {
+        name: name.to_string(),
+        null_fraction: fraction(nulls, row_count),
+        distinct_count,
+        min: min.cloned(),
+        max: max.cloned(),
+        top_values,
+    }
+}
+
+/// Summarise every row `sql` returns through `E::execute_stream`.
+///
+/// Only `timeout_ms` applies: `max_rows`, `max_bytes` and `offset` bound returned
+/// rows, and a summary returns none, so the statistics always cover the full result.
+///
+/// # Errors
+/// Returns the engine's error when the query fails.
+pub async fn summarize_query<E: DatabaseEngine>(
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[Value],
+    timeout_ms: Option<u64>,
+) -> Result<ResultSummary> {
+    let caps = Capabilities::new(None, timeout_ms);
+    let mut sink = SummarySink::default();
+    let streamed = E::execute_stream(config, sql, params, &caps, &mut sink).await?;
+    Ok(sink.finish(streamed.execution_ms))
+}
+
+/// `RowSink` that folds rows into per-column statistics and then drops them
+#[derive(Debug, Default)]
+pub struct SummarySink {
+    columns: Vec<ResultColumn>,
+    stats: Vec<ColumnStats>,
+    row_count: u64,
+}
+
+/// Running statistics for one column
+#[derive(Debug, Default)]
+struct ColumnStats {
+    count: u64,
+    null_count: u64,
+    distinct: HashSet<String>,
+    distinct_capped: bool,
+    min: Option<Value>,
+    max: Option<Value>,
+    examples: Vec<Value>,
+}
+
+impl SummarySink {
+    /// Finish the summary; `execution_ms` comes from the engine's `StreamSummary`.
+    #[must_use]
+    pub fn finish(self, execution_ms: u64) -> ResultSummary {
+        let columns = self
+            .columns
+            .into_iter()
+            .zip(self.stats)
+            .map(|(column, stats)| ColumnSummary {
+                name: column.name,
+                type_name: column.type_name,
+                count: stats.count,
+                null_count: stats.null_count,
+                distinct_count: stats.distinct.len() as u64,
+                distinct_exact: !stats.distinct_capped,
+                min: stats.min,
+                max: stats.max,
+                examples: stats.examples,
+            })
+            .collect();
+        ResultSummary { row_count: self.row_count, execution_ms, columns }
+    }
+}
+
+impl RowSink for SummarySink {
+    fn columns(&mut self, columns: &[ResultColumn]) -> Result<()> {
+        self.columns = columns.to_vec();
+        self.stats = columns.iter().map(|_| ColumnStats::default()).collect();
+        Ok(())
+    }
+
+    fn row(&mut self, row: Vec<Value>) -> Result<bool> {
+        self.row_count += 1;
+        for (stats, value) in self.stats.iter_mut().zip(row) {
+            stats.add(value);
+        }
+        Ok(true)
+    }
+}
+
+impl ColumnStats {
+    fn add(&mut self, value: Value) {
+        if value.is_null() {
+            self.null_count += 1;
+            return;
+        }
+        self.count += 1;
+
+        if !self.distinct_capped {
+            let key = value.to_string();
+            if self.distinct.len() < SUMMARY_DISTINCT_CAP {
+                if self.distinct.insert(key) && self.examples.len() < SUMMARY_EXAMPLES {
+                    self.examples.push(value.clone());
+                }
+            } else if !self.distinct.contains(&key) {
+                self.distinct_capped = true;
+            }
+        }
+
+        if self.min.as_ref().is_none_or(|m| compare_values(&value, m) == Ordering::Less) {
+            self.min = Some(value.clone());
+        }
+        if self.max.as_ref().is_none_or(|m| compare_values(&value, m) == Ordering::Greater) {
+            self.max = Some(value);
+        }
+    }
+}
+
+/// Order JSON values: numbers numerically, strings lexically (ISO dates sort correctly),
+/// booleans false < true; mixed kinds fall back to their serialized form.
+pub fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => match (x.as_f64(), y.as_f64()) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            _ => x.to_string().cmp(&y.to_string()),
+        },
+        (Value::String(x), Value::String(y)) => x.cmp(y),
+        (Value::Bool(x), Value::Bool(y)) => x.cmp(y),
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn fraction(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample(columns: &[&str], rows: Vec<Vec<Value>>) -> QueryResult {
+        QueryResult {
+            columns: columns.iter().map(|c| (*c).to_string()).collect(),
+            rows,
+            rows_affected: None,
+            execution_ms: 0,
+            rows_truncated: false,
+            truncated_by: None,
+            plan: None,
+            next_cursor: None,
+            ordering: None,
+        }
+    }
+
+    #[test]
+    fn test_profile_from_sample_counts_and_bounds() {
+        let result = sample(
+            &["id", "status"],
+            vec![
+                vec![json!(3), json!("active")],
+                vec![json!(1), json!("active")],
+                vec![json!(10), Value::Null],
+                vec![json!(2), json!("banned")],
+            ],
+        );
+        let options = ProfileOptions { sample_rows: 100, top_n: 1, timeout_ms: None };
+        let profile = profile_from_sample("users", None, &result, &options);
+
+        assert_eq!(profile.source, "sample");
+        assert_eq!(profile.row_count, 4);
+        assert!(profile.exhaustive);
+
+        let id = &profile.columns[0];
+        assert_eq!(id.min, Some(json!(1)));
+        assert_eq!(id.max, Some(json!(10)), "numbers must compare numerically, not lexically");
+        assert_eq!(id.distinct_count, 4);
+
+        let status = &profile.columns[1];
+        assert!((status.null_fraction - 0.25).abs() < f64::EPSILON);
+        assert_eq!(status.distinct_count, 2);
+        assert_eq!(status.top_values.len(), 1);
+        assert_eq!(status.top_values[0].value, json!("active"));
+        assert_eq!(status.top_values[0].count, 2);
+        assert!((status.top_values[0].frequency - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_profile_from_sample_at_cap_is_not_exhaustive() {
+        let result = sample(&["n"], vec![vec![json!(1)], vec![json!(1)]]);
+        let options = ProfileOptions { sample_rows: 2, top_n: 5, timeout_ms: None };
+        let profile = profile_from_sample("t", Some("public"), &result, &options);
+        assert!(!profile.exhaustive);
+        assert_eq!(profile.schema.as_deref(), Some("public"));
+        assert_eq!(profile.columns[0].top_values[0].count, 2);
+    }
+
+    #[test]
+    fn test_profile_top_values_tie_break_is_deterministic() {
+        let result = sample(
+            &["c"],
+            vec![vec![json!("b")], vec![json!("a")], vec![json!("c")], vec![json!("a")]],
+        );
+        let options = ProfileOptions { sample_rows: 10, top_n: 3, timeout_ms: None };
+        let profile = profile_from_sample("t", None, &result, &options);
+        let values: Vec<&Value> = profile.columns[0].top_values.iter().map(|v| &v.value).collect();
+        assert_eq!(values, vec![&json!("a"), &json!("b"), &json!("c")]);
+    }
+
+    fn summarize(columns: &[&str], rows: Vec<Vec<Value>>) -> ResultSummary {
+        let mut sink = SummarySink::default();
+        let columns: Vec<ResultColumn> = columns
+            .iter()
+            .map(|c| ResultColumn { name: (*c).to_string(), type_name: Some("int4".into()) })
+            .collect();
+        sink.columns(&columns).unwrap();
+        for row in rows {
+            assert!(sink.row(row).unwrap());
+        }
+        sink.finish(7)
+    }
+
+    #[test]
+    fn test_summary_counts_bounds_and_examples() {
+        let summary = summarize(
+            &["n", "tag"],
+            vec![
+                vec![json!(9), json!("x")],
+                vec![json!(10), Value::Null],
+                vec![json!(2), json!("x")],
+                vec![json!(9), json!("y")],
+            ],
+        );
+        assert_eq!(summary.row_count, 4);
+        assert_eq!(summary.execution_ms, 7);
+
+        let n = &summary.columns[0];
+        assert_eq!(n.type_name.as_deref(), Some("int4"));
+        assert_eq!((n.count, n.null_count, n.distinct_count), (4, 0, 3));
+        assert!(n.distinct_exact);
+        assert_eq!(n.min, Some(json!(2)));
+        assert_eq!(n.max, Some(json!(10)), "numbers must compare numerically, not lexically");
+        assert_eq!(n.examples, vec![json!(9), json!(10), json!(2)]);
+
+        let tag = &summary.columns[1];
+        assert_eq!((tag.count, tag.null_count, tag.distinct_count), (3, 1, 2));
+        assert_eq!(tag.examples, vec![json!("x"), json!("y")]);
+    }
+
+    #[test]
+    fn test_summary_all_null_column_has_no_bounds() {
+        let summary = summarize(&["c"], vec![vec![Value::Null], vec![Value::Null]]);
+        let c = &summary.columns[0];
+        assert_eq!((c.count, c.null_count, c.distinct_count), (0, 2, 0));
+        assert_eq!((c.min.clone(), c.max.clone()), (None, None));
+        assert!(c.examples.is_empty());
+    }
+
+    #[test]
+    fn test_summary_distinct_count_is_capped() {
+        let rows = (0..=SUMMARY_DISTINCT_CAP as u64 + 5).map(|i| vec![json!(i)]).collect();
+        let summary = summarize(&["n"], rows);
+        let n = &summary.columns[0];
+        assert_eq!(n.distinct_count, SUMMARY_DISTINCT_CAP as u64);
+        assert!(!n.distinct_exact);
+        assert_eq!(n.count, SUMMARY_DISTINCT_CAP as u64 + 6, "counts stay complete past the cap");
+        assert_eq!(n.max, Some(json!(SUMMARY_DISTINCT_CAP as u64 + 5)));
+    }
+}
@@ -16,17 +16,68 @@
 //! - Row limits enforced in application code
 //! - No explicit schema support (`SQLite` uses catalogs)
 
-use rusqlite::{Connection, OpenFlags, Row};
+use rusqlite::{Connection, OpenFlags, OptionalExtension, Row};
 use std::collections::HashMap; // Used for grouping foreign keys during introspection
 use std::time::{Duration, Instant};
 
-use crate::capability::{strip_explain_prefix, validate_query};
+use crate::capability::{strip_explain_prefix, structured_explain_analyze, validate_query};
+use crate::engine::cell::CellFormat;
+use crate::engine::stream::{cancelled_or, reject_structured_explain, RowCollector, RowFeed};
 use crate::engine::{
-    is_explain_query, Capabilities, ColumnInfo, ConnectionConfig, ConnectionInfo, DatabaseEngine,
-    DatabaseType, ExplainFormat, ExplainPlanNode, ForeignKeyInfo, IndexInfo, IntrospectOperation,
-    IntrospectResult, QueryResult, TableInfo,
+    filter_settings, is_explain_query, profile, Capabilities, ColumnInfo, ConnectionConfig,
+    ConnectionInfo, DatabaseEngine, DatabaseType, ExplainFormat, ExplainPlanNode, FileAccess,
+    ForeignKeyInfo, IndexFinding, IndexHealthReport, IndexInfo, InspectOperation, InspectResult,
+    IntrospectOperation, IntrospectResult, PlanOperation, PrivilegeReport, PrivilegeWarning,
+    QueryResult, ResultColumn, RowSink, SampleOptions, SettingInfo, SizeOptions, SizeReport,
+    StreamSummary, TableInfo, TableSize,
 };
 use crate::error::{PlenumError, Result};
+use crate::params::ParamValue;
+
+/// Tables with fewer rows are never reported as having unselective indexes.
+const UNSELECTIVE_MIN_ROWS: u64 = 1000;
+
+/// Setting pragmas reported by `--settings`: (name, unit, per-connection).
+///
+/// Only pragmas whose read form has no side effects are listed. Per-connection pragmas
+/// can be changed by the session; the rest are stored in the database file.
+const SQLITE_SETTINGS: &[(&str, Option<&str>, bool)] = &[
+    ("analysis_limit", None, true),
+    ("application_id", None, false),
+    ("auto_vacuum", None, false),
+    ("automatic_index", None, true),
+    ("busy_timeout", Some("ms"), true),
+    ("cache_size", None, true),
+    ("cache_spill", Some("pages"), true),
+    ("cell_size_check", None, true),
+    ("defer_foreign_keys", None, true),
+    ("encoding", None, false),
+    ("foreign_keys", None, true),
+    ("freelist_count", Some("pages"), false),
+    ("ignore_check_constraints", None, true),
+    ("journal_mode", None, false),
+    ("journal_size_limit", Some("bytes"), true),
+    ("legacy_alter_table", None, true),
+    ("locking_mode", None, true),
+    ("max_page_count", Some("pages"), true),
+    ("page_count", Some("pages"), false),
+    ("page_size", Some("bytes"), false),
+    ("query_only", None, true),
+    ("read_uncommitted", None, true),
+    ("recursive_triggers", None, true),
+    ("reverse_unordered_selects", None, true),
+    ("schema_version", None, false),
+    ("secure_delete", None, true),
+    ("synchronous", None, true),
+    ("temp_store", None, true),
+    ("threads", None, true),
+    ("trusted_schema", None, true),
+    ("user_version", None, false),
+];
+
+/// An index is unselective when each distinct value of its leading column matches at
+/// least this fraction of the table (i.e. fewer than ten distinct leading values).
+const UNSELECTIVE_MIN_FRACTION: f64 = 0.1;
 
 /// `SQLite` database engine implementation
 pub struct SqliteEngine;
@@ -71,9 +122,16 @@ impl DatabaseEngine for SqliteEngine {
             server_info: format!("SQLite {version}"),
             connected_database: db_name,
             user: "N/A".to_string(), // SQLite has no user concept
+            warnings: Vec::new(),
         })
     }
 
+    async fn audit_privileges(_config: &ConnectionConfig) -> Result<Vec<PrivilegeWarning>> {
+        // No users or grants: access is the file's permissions, and Plenum always
+        // opens the file read-only, so there is no credential to audit.
+        Ok(Vec::new())
+    }
+
     async fn introspect(
         config: &ConnectionConfig,
         operation: &IntrospectOperation,
@@ -144,60 +202,69 @@ impl DatabaseEngine for SqliteEngine {
             }
 
             IntrospectOperation::ViewDetails { name } => get_view_details_sqlite(&conn, name)?,
+
+            IntrospectOperation::ListSequences
+            | IntrospectOperation::ListMacros
+            | IntrospectOperation::ListTypes
+            | IntrospectOperation::ListConstraints { .. }
+            | IntrospectOperation::ListAttachedDatabases => {
+                return Err(PlenumError::invalid_input(format!(
+                    "SQLite does not support {} operation (DuckDB catalog introspection only)",
+                    operation.name()
+                )));
+            }
+
+            // SQLite keeps no per-column statistics, so profiling always samples
+            IntrospectOperation::ProfileTable { name, options } => {
+                ensure_rowid_table(&conn, name)?;
+                let sql = sample_query_sqlite(name, &options.sample_options());
+                let profile =
+                    profile::profile_by_sampling::<Self>(config, &sql, name, None, options).await?;
+                IntrospectResult::TableProfile { profile }
+            }
+
+            // No native seeded sampling: order rows by a seeded integer hash of the rowid
+            IntrospectOperation::SampleTable { name, options } => {
+                ensure_rowid_table(&conn, name)?;
+                let sql = sample_query_sqlite(name, options);
+                let caps = Capabilities::new(Some(options.size), options.timeout_ms);
+                let sample = Self::execute(config, &sql, &[], &caps).await?;
+                IntrospectResult::TableSample { sample }
+            }
+
+            IntrospectOperation::ListPrivileges { .. } => list_privileges_sqlite(path_str)?,
+
+            IntrospectOperation::IndexHealth { table } => {
+                index_health_sqlite(&conn, table.as_deref())?
+            }
+
+            IntrospectOperation::Sizes { options } => sizes_sqlite(&conn, options)?,
+
+            IntrospectOperation::Settings { pattern } => {
+                settings_sqlite(&conn, pattern.as_deref())?
+            }
         };
 
         Ok(result)
     }
 
+    async fn inspect(
+        _config: &ConnectionConfig,
+        operation: &InspectOperation,
+    ) -> Result<InspectResult> {
+        Err(PlenumError::invalid_input(format!(
+            "SQLite does not support {} inspection (file-based database has no server to inspect)",
+            operation.name()
+        )))
+    }
+
     async fn execute(
         config: &ConnectionConfig,
         query: &str,
         params: &[serde_json::Value],
         caps: &Capabilities,
     ) -> Result<QueryResult> {
-        // Validate config is for SQLite
-        if config.engine != DatabaseType::SQLite {
-            return Err(PlenumError::invalid_input(format!(
-                "Expected SQLite engine, got {}",
-                config.engine
-            )));
-        }
-
-        // Validate query against capabilities
-        validate_query(query, caps, DatabaseType::SQLite)?;
-
-        // Extract file path
-        let file_path = config
-            .file
-            .as_ref()
-            .ok_or_else(|| PlenumError::invalid_input("SQLite requires 'file' parameter"))?;
-
-        // Open connection (read-only: defense in depth at OS/VFS level — writes are
-        // rejected by SQLite itself even if the parser is somehow bypassed)
-        let path_str = file_path.to_str().ok_or_else(|| {
-            PlenumError::invalid_input("SQLite file path contains invalid UTF-8 characters")
-        })?;
-        let conn = open_connection(path_str, true)?;
-
-        // Set busy_timeout for lock-contention waits (database file locked by another writer).
-        if let Some(timeout_ms) = caps.timeout_ms {
-            conn.busy_timeout(Duration::from_millis(timeout_ms)).map_err(|e| {
-                PlenumError::engine_error("sqlite", format!("Failed to set busy_timeout: {e}"))
-            })?;
-        }
-
-        // Interrupt-based statement timeout: obtain a handle before the query starts,
-        // then spawn a thread that fires sqlite3_interrupt after timeout_ms. SQLite checks
-        // for interrupts between VM steps, cancelling the query server-side rather than
-        // just abandoning the wait. Per SQLite docs, interrupt() on an idle connection is
-        // a no-op, so the timer is harmless if the query finishes first.
-        if let Some(timeout_ms) = caps.timeout_ms {
-            let handle = conn.get_interrupt_handle();
-            std::thread::spawn(move || {
-                std::thread::sleep(Duration::from_millis(timeout_ms));
-                handle.interrupt();
-            });
-        }
+        let conn = open_query_connection(config, query, caps)?;
 
         // Structured explain path: rewrite to EXPLAIN QUERY PLAN, normalize the plan tree.
         if caps.explain_format == Some(ExplainFormat::Structured) {
@@ -207,9 +274,11 @@ impl DatabaseEngine for SqliteEngine {
                      non-EXPLAIN queries must omit this flag",
                 ));
             }
+            // SQLite only has the estimated plan; EXPLAIN ANALYZE is rejected
+            structured_explain_analyze(query, DatabaseType::SQLite)?;
             let inner = strip_explain_prefix(query);
             let start = Instant::now();
-            let plan = execute_structured_explain_sqlite(&conn, &inner)?;
+            let plan = execute_structured_explain_sqlite(&conn, &inner, params)?;
             let elapsed = start.elapsed();
             return Ok(QueryResult {
                 columns: Vec::new(),
@@ -218,20 +287,91 @@ impl DatabaseEngine for SqliteEngine {
                 execution_ms: elapsed.as_millis() as u64,
                 rows_truncated: false,
                 truncated_by: None,
-                plan: Some(plan),
+                plan: Some(Box::new(plan)),
+                next_cursor: None,
+                ordering: None,
             });
         }
 
-        // Execute query
-        let start = Instant::now();
-        let mut result = execute_query(&conn, query, params, caps)?;
-        let elapsed = start.elapsed();
-        result.execution_ms = elapsed.as_millis() as u64;
+        let mut rows = RowCollector::default();
+        let summary = execute_query(&conn, query, params, caps, &mut rows)
+            .map_err(|e| cancelled_or(caps, e))?;
+        Ok(rows.into_result(summary))
+    }
 
-        Ok(result)
+    async fn execute_stream(
+        config: &ConnectionConfig,
+        query: &str,
+        params: &[serde_json::Value],
+        caps: &Capabilities,
+        sink: &mut dyn RowSink,
+    ) -> Result<StreamSummary> {
+        reject_structured_explain(caps)?;
+        let conn = open_query_connection(config, query, caps)?;
+        execute_query(&conn, query, params, caps, sink).map_err(|e| cancelled_or(caps, e))
     }
 }
 
+/// Validate `query` and open a read-only connection armed with `caps.timeout_ms`.
+fn open_query_connection(
+    config: &ConnectionConfig,
+    query: &str,
+    caps: &Capabilities,
+) -> Result<Connection> {
+    // Validate config is for SQLite
+    if config.engine != DatabaseType::SQLite {
+        return Err(PlenumError::invalid_input(format!(
+            "Expected SQLite engine, got {}",
+            config.engine
+        )));
+    }
+
+    // Validate query against capabilities
+    validate_query(query, caps, DatabaseType::SQLite)?;
+
+    // Extract file path
+    let file_path = config
+        .file
+        .as_ref()
+        .ok_or_else(|| PlenumError::invalid_input("SQLite requires 'file' parameter"))?;
+
+    // Open connection (read-only: defense in depth at OS/VFS level — writes are
+    // rejected by SQLite itself even if the parser is somehow bypassed)
+    let path_str = file_path.to_str().ok_or_else(|| {
+        PlenumError::invalid_input("SQLite file path contains invalid UTF-8 characters")
+    })?;
+    let conn = open_connection(path_str, true)?;
+
+    // Set busy_timeout for lock-contention waits (database file locked by another writer).
+    if let Some(timeout_ms) = caps.timeout_ms {
+        conn.busy_timeout(Duration::from_millis(timeout_ms)).map_err(|e| {
+            PlenumError::engine_error("sqlite", format!("Failed to set busy_timeout: {e}"))
+        })?;
+    }
+
+    // Interrupt-based statement timeout: obtain a handle before the query starts,
+    // then spawn a thread that fires sqlite3_interrupt after timeout_ms. SQLite checks
+    // for interrupts between VM steps, cancelling the query server-side rather than
+    // just abandoning the wait. Per SQLite docs, interrupt() on an idle connection is
+    // a no-op, so the timer is harmless if the query finishes first.
+    if let Some(timeout_ms) = caps.timeout_ms {
+        let handle = conn.get_interrupt_handle();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(timeout_ms));
+            handle.interrupt();
+        });
+    }
+
+    // MCP cancellation fires the same interrupt
+    if let Some(control) = &caps.control {
+        let handle = conn.get_interrupt_handle();
+        control.on_cancel(move || handle.interrupt());
+        control.check()?;
+    }
+
+    Ok(conn)
+}
+
 /// Open `SQLite` connection with appropriate flags
 fn open_connection(path: &str, read_only: bool) -> Result<Connection> {
     let flags = if read_only {
@@ -670,9 +810,414 @@ fn get_sqlite_row_estimate(conn: &Connection, table_name: &str) -> Option<i64> {
     .and_then(|stat| stat.split_whitespace().next().and_then(|s| s.parse::<i64>().ok()))
 }
 
+/// Report per-table storage from the `dbstat` virtual table, or only the database size
+/// (`page_count * page_size`) when `SQLite` was built without `dbstat`.
+fn sizes_sqlite(conn: &Connection, options: &SizeOptions) -> Result<IntrospectResult> {
+    let database_bytes: i64 = conn
+        .query_row(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| {
+            PlenumError::engine_error("sqlite", format!("Failed to query page count: {e}"))
+        })?;
+
+    let mut stmt = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'")
+        .map_err(|e| PlenumError::engine_error("sqlite", format!("Failed to query tables: {e}")))?;
+    let names: Vec<String> =
+        stmt.query_map([], |row| row.get(0)).and_then(Iterator::collect).map_err(|e| {
+            PlenumError::engine_error("sqlite", format!("Failed to fetch table names: {e}"))
+        })?;
+
+    // (table, is_index) -> bytes; autoindexes belong to their table via tbl_name
+    let mut notes = Vec::new();
+    let mut pages: HashMap<(String, bool), u64> = HashMap::new();
+    let has_dbstat = if let Ok(mut stmt) = conn.prepare(
+        "SELECT m.tbl_name, m.type = 'index', SUM(s.pgsize)
+         FROM dbstat s JOIN sqlite_master m ON m.name = s.name
+         GROUP BY m.tbl_name, m.type = 'index'",
+    ) {
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(((row.get::<_, String>(0)?, row.get::<_, bool>(1)?), row.get::<_, i64>(2)?))
+            })
+            .and_then(Iterator::collect::<rusqlite::Result<Vec<_>>>)
+            .map_err(|e| {
+                PlenumError::engine_error("sqlite", format!("Failed to read dbstat: {e}"))
+            })?;
+        pages.extend(rows.into_iter().map(|(key, bytes)| (key, u64::try_from(bytes).unwrap_or(0))));
+        true
+    } else {
+        notes.push(
+            "dbstat virtual table is not available in this SQLite build; \
+             only the database size is reported"
+                .to_string(),
+        );
+        false
+    };
+    let tables = names
+        .into_iter()
+        .map(|name| {
+            let (table_bytes, index_bytes) = if has_dbstat {
+                (
+                    Some(pages.get(&(name.clone(), false)).copied().unwrap_or(0)),
+                    Some(pages.get(&(name.clone(), true)).copied().unwrap_or(0)),
+                )
+            } else {
+                (None, None)
+            };
+            let row_estimate =
+                get_sqlite_row_estimate(conn, &name).and_then(|n| u64::try_from(n).ok());
+            TableSize {
+                name,
+                schema: None,
+                table_bytes,
+                index_bytes,
+                toast_bytes: None,
+                total_bytes: table_bytes.zip(index_bytes).map(|(table, index)| table + index),
+                row_estimate,
+            }
+        })
+        .collect();
+
+    Ok(IntrospectResult::Sizes {
+        sizes: SizeReport::paginate(u64::try_from(database_bytes).ok(), tables, options, notes),
+    })
+}
+
+/// Read the curated setting pragmas through their table-valued functions.
+///
+/// Pragmas missing from this build's `pragma_list` are skipped. `SQLite` has no notion of
+/// where a value came from, so `source` is always null.
+fn settings_sqlite(conn: &Connection, pattern: Option<&str>) -> Result<IntrospectResult> {
+    let mut stmt = conn.prepare("SELECT name FROM pragma_pragma_list").map_err(|e| {
+        PlenumError::engine_error("sqlite", format!("Failed to query pragma list: {e}"))
+    })?;
+    let available: std::collections::HashSet<String> =
+        stmt.query_map([], |row| row.get(0)).and_then(Iterator::collect).map_err(|e| {
+            PlenumError::engine_error("sqlite", format!("Failed to fetch pragma list: {e}"))
+        })?;
+
+    let mut settings = Vec::new();
+    for &(name, unit, session_changeable) in SQLITE_SETTINGS {
+        if !available.contains(name) {
+            continue;
+        }
+        let value = conn
+            .query_row(&format!("SELECT * FROM pragma_{name}()"), [], |row| {
+                Ok(match row.get_ref(0)? {
+                    rusqlite::types::ValueRef::Null => None,
+                    rusqlite::types::ValueRef::Integer(n) => Some(n.to_string()),
+                    rusqlite::types::ValueRef::Real(f) => Some(f.to_string()),
+                    rusqlite::types::ValueRef::Text(t) | rusqlite::types::ValueRef::Blob(t) => {
+                        Some(String::from_utf8_lossy(t).into_owned())
+                    }
+                })
+            })
+            .optional()
+            .map_err(|e| {
+                PlenumError::engine_error("sqlite", format!("Failed to read PRAGMA {name}: {e}"))
+            })?
+            .flatten();
+        settings.push(SettingInfo {
+            name: name.to_string(),
+            value,
+            unit: unit.map(str::to_string),
+            source: None,
+            session_changeable: Some(session_changeable),
+        });
+    }
+
+    Ok(IntrospectResult::Settings { settings: filter_settings(settings, pattern) })
+}
+
+/// Report file-level access: `SQLite` has no users or grants, so the file's
+/// permissions are the only access control.
+fn list_privileges_sqlite(path: &str) -> Result<IntrospectResult> {
+    let metadata = std::fs::metadata(path).map_err(|e| {
+        PlenumError::engine_error("sqlite", format!("Failed to read file metadata: {e}"))
+    })?;
+    Ok(IntrospectResult::Privileges {
+        privileges: PrivilegeReport {
+            user: "N/A".to_string(),
+            scope: "file".to_string(),
+            roles: Vec::new(),
+            tables: Vec::new(),
+            grants: Vec::new(),
+            file: Some(FileAccess {
+                path: path.to_string(),
+                writable: !metadata.permissions().readonly(),
+            }),
+        },
+    })
+}
+
+/// Key definition of a plain (non-partial, non-expression) index
+struct SqliteIndexKey {
+    name: String,
+    unique: bool,
+    /// (column id, descending, collation) per key column
+    keys: Vec<(i64, bool, String)>,
+}
+
+/// Diagnose duplicate, redundant and unselective indexes.
+///
+/// `SQLite` keeps no usage counters, so unused indexes cannot be detected. Selectivity
+/// comes from `sqlite_stat1`, which only exists once ANALYZE has run.
+#[allow(clippy::cast_precision_loss)]
+fn index_health_sqlite(conn: &Connection, table_filter: Option<&str>) -> Result<IntrospectResult> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT name FROM sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+             AND (?1 IS NULL OR name = ?1)
+             ORDER BY name",
+        )
+        .map_err(|e| PlenumError::engine_error("sqlite", format!("Failed to query tables: {e}")))?;
+    let tables: Vec<String> =
+        stmt.query_map([table_filter], |row| row.get(0)).and_then(Iterator::collect).map_err(
+            |e| PlenumError::engine_error("sqlite", format!("Failed to fetch table names: {e}")),
+        )?;
+
+    let has_stats: bool = conn
+        .query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'sqlite_stat1'",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| {
+            PlenumError::engine_error("sqlite", format!("Failed to look up sqlite_stat1: {e}"))
+        })?;
+
+    let mut findings = Vec::new();
+    for table in &tables {
+        let indexes = sqlite_index_keys(conn, table)?;
+        for candidate in indexes.iter().filter(|i| !i.unique) {
+            let duplicate = indexes.iter().find(|other| {
+                other.name != candidate.name
+                    && other.keys == candidate.keys
+                    && (other.unique || other.name < candidate.name)
+            });
+            let covering =
+                duplicate.map(|other| ("duplicate", "Same key columns as", other)).or_else(|| {
+                    indexes
+                        .iter()
+                        .find(|other| {
+                            other.keys.len() > candidate.keys.len()
+                                && other.keys.starts_with(&candidate.keys)
+                        })
+                        .map(|other| ("redundant", "Key columns are a leading prefix of", other))
+                });
+            if let Some((reason, detail, other)) = covering {
+                findings.push(IndexFinding {
+                    table: table.clone(),
+                    schema: None,
+                    index: Some(candidate.name.clone()),
+                    reason: reason.to_string(),
+                    metric: "key_columns".to_string(),
+                    value: candidate.keys.len() as f64,
+                    detail: format!("{detail} {}", other.name),
+                });
+            }
+        }
+
+        if has_stats {
+            findings.extend(unselective_indexes_sqlite(conn, table)?);
+        }
+    }
+
+    findings.sort_by(|a, b| (&a.table, &a.index).cmp(&(&b.table, &b.index)));
+
+    let mut notes =
+        vec!["SQLite keeps no index usage counters; unused indexes cannot be detected".to_string()];
+    if !has_stats {
+        notes.push("sqlite_stat1 not found; run ANALYZE to enable selectivity checks".to_string());
+    }
+
+    Ok(IntrospectResult::IndexHealth { health: IndexHealthReport { findings, notes } })
+}
+
+/// Key definitions of the plain indexes on a table (partial and expression indexes are skipped)
+fn sqlite_index_keys(conn: &Connection, table_name: &str) -> Result<Vec<SqliteIndexKey>> {
+    let mut list_stmt = conn
+        .prepare("SELECT name, \"unique\", partial FROM pragma_index_list(?1) ORDER BY name")
+        .map_err(|e| {
+            PlenumError::engine_error(
+                "sqlite",
+                format!("Failed to prepare index_list for {table_name}: {e}"),
+            )
+        })?;
+    let listed: Vec<(String, bool, bool)> = list_stmt
+        .query_map([table_name], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .and_then(Iterator::collect)
+        .map_err(|e| {
+            PlenumError::engine_error(
+                "sqlite",
+                format!("Failed to query index_list for {table_name}: {e}"),
+            )
+        })?;
+
+    let mut key_stmt = conn
+        .prepare(
+            "SELECT cid, \"desc\", coll FROM pragma_index_xinfo(?1) WHERE key = 1 ORDER BY seqno",
+        )
+        .map_err(|e| {
+            PlenumError::engine_error("sqlite", format!("Failed to prepare index_xinfo: {e}"))
+        })?;
+
+    let mut indexes = Vec::new();
+    for (name, unique, partial) in listed {
+        if partial {
+            continue;
+        }
+        let keys: Vec<(i64, bool, String)> = key_stmt
+            .query_map([&name], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .and_then(Iterator::collect)
+            .map_err(|e| {
+                PlenumError::engine_error(
+                    "sqlite",
+                    format!("Failed to query index columns for {name}: {e}"),
+                )
+            })?;
+        // Expression columns have a negative column id
+        if keys.iter().any(|(cid, _, _)| *cid < 0) {
+            continue;
+        }
+        indexes.push(SqliteIndexKey { name, unique, keys });
+    }
+
+    Ok(indexes)
+}
+
+/// Indexes whose leading column barely narrows the table, according to `sqlite_stat1`.
+///
+/// Each stat is "nrow rows-per-key..."; the second figure is the average number of rows
+/// sharing one value of the leading column.
+#[allow(clippy::cast_precision_loss)]
+fn unselective_indexes_sqlite(conn: &Connection, table_name: &str) -> Result<Vec<IndexFinding>> {
+    let mut stmt = conn
+        .prepare("SELECT idx, stat FROM sqlite_stat1 WHERE tbl = ?1 AND idx IS NOT NULL")
+        .map_err(|e| {
+            PlenumError::engine_error("sqlite", format!("Failed to query sqlite_stat1: {e}"))
+        })?;
+    let stats: Vec<(String, String)> = stmt
+        .query_map([table_name], |row| Ok((row.get(0)?, row.get(1)?)))
+        .and_then(Iterator::collect)
+        .map_err(|e| {
+            PlenumError::engine_error("sqlite", format!("Failed to read sqlite_stat1: {e}"))
+        })?;
+
+    let mut findings = Vec::new();
+    for (index, stat) in stats {
+        let mut figures = stat.split_whitespace().map(str::parse::<u64>);
+        let (Some(Ok(rows)), Some(Ok(per_key))) = (figures.next(), figures.next()) else {
+            continue;
+        };
+        if rows < UNSELECTIVE_MIN_ROWS || (per_key as f64) < rows as f64 * UNSELECTIVE_MIN_FRACTION
+        {
+            continue;
+        }
+        findings.push(IndexFinding {
+            table: table_name.to_string(),
+            schema: None,
+            index: Some(index),
+            reason: "unselective".to_string(),
+            metric: "avg_rows_per_key".to_string(),
+            value: per_key as f64,
+            detail: format!("Each leading-column value matches about {per_key} of {rows} rows"),
+        });
+    }
+
+    Ok(findings)
+}
+
+/// Fail unless `table_name` is an ordinary rowid table (sampling hashes the rowid).
+fn ensure_rowid_table(conn: &Connection, table_name: &str) -> Result<()> {
+    let sql: Option<String> = conn
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [table_name],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| {
+            PlenumError::engine_error("sqlite", format!("Failed to look up table: {e}"))
+        })?;
+
+    match sql {
+        None => Err(PlenumError::invalid_input(format!("Table '{table_name}' not found"))),
+        Some(ddl) if ddl.to_uppercase().contains("WITHOUT ROWID") => {
+            Err(PlenumError::invalid_input(format!(
+                "Table '{table_name}' is a WITHOUT ROWID table; sampling requires a rowid"
+            )))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// Multiplier of the 32-bit integer mix used to order `SQLite` samples
+const SAMPLE_MIX_MULTIPLIER: u64 = 0x045D_9F3B;
+const SAMPLE_MIX_MASK: u64 = 0xFFFF_FFFF;
+
+/// The 32-bit integer mix of `sample_query_sqlite`, applied to the seed up front.
+const fn sample_mix(mut h: u64) -> u64 {
+    h = (((h >> 16) ^ h) * SAMPLE_MIX_MULTIPLIER) & SAMPLE_MIX_MASK;
+    h = (((h >> 16) ^ h) * SAMPLE_MIX_MULTIPLIER) & SAMPLE_MIX_MASK;
+    (h >> 16) ^ h
+}
+
+/// Build an ordered-hash sample query for `SQLite`.
+///
+/// `SQLite` has no hash function, so rows are ordered by a 32-bit integer mix
+/// (xorshift-multiply, XOR spelled `(a | b) - (a & b)`) of the rowid, combined
+/// by XOR with the mixed seed and mixed again. Hashing the seed separately keeps adjacent
+/// seeds from drawing the same rows shifted by one. Each step is a stacked
+/// subquery referencing the previous one by name; ties are broken by rowid,
+/// making the order total.
+fn sample_query_sqlite(table_name: &str, options: &SampleOptions) -> String {
+    const MASK: u64 = SAMPLE_MIX_MASK;
+    let xor = |a: &str, b: &str| format!("(({a} | {b}) - ({a} & {b}))");
+    let xorshift = xor("(h >> 16)", "h");
+    let round = format!("({xorshift} * {SAMPLE_MIX_MULTIPLIER}) & {MASK}");
+    let seed = sample_mix(u64::from(options.seed)).to_string();
+    let mix_seed = xor("h", &seed);
+    let steps = [&round, &round, &xorshift, &mix_seed, &round, &round, &xorshift];
+
+    let table = quote_identifier(table_name);
+    let mut hashed = format!("SELECT rowid AS r, rowid & {MASK} AS h FROM {table}");
+    for step in steps {
+        hashed = format!("SELECT r, {step} AS h FROM ({hashed})");
+    }
+    format!(
+        "SELECT plenum_t.* FROM {table} AS plenum_t JOIN (\
+         {hashed} ORDER BY h, r LIMIT {}) AS plenum_s ON plenum_t.rowid = plenum_s.r \
+         ORDER BY plenum_s.h, plenum_s.r",
+        options.size
+    )
+}
+
+/// Quote an identifier for interpolation into `SQLite` SQL.
+fn quote_identifier(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
 /// Convert a JSON value to a `rusqlite` native value for parameter binding
 fn json_to_sqlite_value(val: &serde_json::Value) -> rusqlite::types::Value {
     use rusqlite::types::Value;
+    // SQLite has no uuid/date/decimal storage classes, so typed values other than
+    // numbers and bytes bind as their canonical text.
+    if let Some(Ok(typed)) = ParamValue::from_json(val) {
+        return match typed {
+            ParamValue::Null => Value::Null,
+            ParamValue::Bool(b) => Value::Integer(i64::from(b)),
+            ParamValue::Int(i) => Value::Integer(i),
+            ParamValue::Float(f) => Value::Real(f),
+            ParamValue::Bytes(b) => Value::Blob(b),
+            other => Value::Text(other.to_text().unwrap_or_default()),
+        };
+    }
     match val {
         serde_json::Value::Null => Value::Null,
         serde_json::Value::Bool(b) => Value::Integer(i64::from(*b)),
@@ -690,13 +1235,15 @@ fn json_to_sqlite_value(val: &serde_json::Value) -> rusqlite::types::Value {
 
 /// Returns true when a rusqlite error is `SQLITE_INTERRUPT` (code 9), meaning
 /// the interrupt handle fired and `SQLite` cancelled the running statement.
-/// Run `EXPLAIN QUERY PLAN` on `inner_sql` and normalize the result into an `ExplainPlanNode` tree.
+/// Run `EXPLAIN QUERY PLAN` on `inner_sql` with `params` bound and normalize the result
+/// into an `ExplainPlanNode` tree.
 ///
 /// `SQLite`'s EXPLAIN QUERY PLAN returns rows of `(id, parent, notused, detail)`.
 /// We build a parent-pointer tree and return the virtual root containing all top-level nodes.
 fn execute_structured_explain_sqlite(
     conn: &Connection,
     inner_sql: &str,
+    params: &[serde_json::Value],
 ) -> Result<ExplainPlanNode> {
     let sql = format!("EXPLAIN QUERY PLAN {inner_sql}");
 
@@ -711,8 +1258,10 @@ fn execute_structured_explain_sqlite(
         PlenumError::query_failed(format!("Failed to prepare EXPLAIN QUERY PLAN: {e}"))
     })?;
 
+    let sqlite_params: Vec<rusqlite::types::Value> =
+        params.iter().map(json_to_sqlite_value).collect();
     let rows: Vec<PlanRow> = stmt
-        .query_map([], |row| {
+        .query_map(rusqlite::params_from_iter(&sqlite_params), |row| {
             Ok(PlanRow {
                 id: row.get::<_, i64>(0).unwrap_or(0),
                 parent: row.get::<_, i64>(1).unwrap_or(0),
@@ -740,13 +1289,7 @@ fn execute_structured_explain_sqlite(
             .get(&row.id)
             .map(|kids| kids.iter().map(|k| build_node(k, children_map)).collect())
             .unwrap_or_default();
-        ExplainPlanNode {
-            node_type: row.detail.clone(),
-            relation: None,
-            estimated_rows: None,
-            estimated_cost: None,
-            children,
-        }
+        ExplainPlanNode { children, ..describe_sqlite_plan_detail(&row.detail) }
     }
 
     // Top-level nodes are those with parent = 0 (SQLite uses 0 as the root sentinel)
@@ -756,13 +1299,68 @@ fn execute_structured_explain_sqlite(
 
     Ok(ExplainPlanNode {
         node_type: "QUERY PLAN".to_string(),
-        relation: None,
-        estimated_rows: None,
-        estimated_cost: None,
         children: top_children,
+        ..ExplainPlanNode::default()
     })
 }
 
+/// Categorize one `EXPLAIN QUERY PLAN` detail line, e.g. `SCAN t`,
+/// `SEARCH t USING COVERING INDEX idx (a=?)`, `USE TEMP B-TREE FOR ORDER BY`.
+///
+/// Older `SQLite` versions spell scans `SCAN TABLE t`; both forms are accepted.
+fn describe_sqlite_plan_detail(detail: &str) -> ExplainPlanNode {
+    let words: Vec<&str> = detail.split_whitespace().collect();
+    let mut node = ExplainPlanNode { node_type: detail.to_string(), ..ExplainPlanNode::default() };
+
+    match words.as_slice() {
+        ["SCAN", "CONSTANT", "ROW"] => {}
+        [verb @ ("SCAN" | "SEARCH"), rest @ ..] => {
+            let rest = rest.strip_prefix(&["TABLE"]).unwrap_or(rest);
+            node.relation = rest.first().map(|t| (*t).to_string());
+            let using = rest.iter().position(|w| *w == "USING").map(|at| &rest[at + 1..]);
+            node.operation = match using {
+                Some(access) if access.contains(&"COVERING") => PlanOperation::IndexOnlyScan,
+                Some(_) => PlanOperation::IndexScan,
+                None if *verb == "SEARCH" => PlanOperation::IndexScan,
+                None => PlanOperation::FullScan,
+            };
+            // "INDEX idx", "INTEGER PRIMARY KEY", "PRIMARY KEY"; automatic indexes are unnamed
+            node.index = using.and_then(|access| {
+                if access.contains(&"KEY") {
+                    return Some("PRIMARY KEY".to_string());
+                }
+                let at = access.iter().position(|w| *w == "INDEX")?;
+                access.get(at + 1).filter(|name| !name.starts_with('(')).map(|n| (*n).to_string())
+            });
+        }
+        ["USE", "TEMP", "B-TREE", "FOR", purpose @ ..] => {
+            node.operation = if purpose.contains(&"ORDER") {
+                PlanOperation::Sort
+            } else if purpose.contains(&"GROUP") {
+                PlanOperation::Aggregate
+            } else if purpose.contains(&"DISTINCT") {
+                PlanOperation::Distinct
+            } else {
+                PlanOperation::Other
+            };
+        }
+        ["BLOOM", "FILTER", "ON", table, condition @ ..] => {
+            node.operation = PlanOperation::Filter;
+            node.relation = Some((*table).to_string());
+            node.filter = Some(condition.join(" ")).filter(|c| !c.is_empty());
+        }
+        ["COMPOUND", "QUERY"] | ["UNION" | "EXCEPT" | "INTERSECT", ..] => {
+            node.operation = PlanOperation::Union;
+        }
+        ["MATERIALIZE", ..] => node.operation = PlanOperation::Materialize,
+        ["CO-ROUTINE", ..] | [.., "SUBQUERY"] | [.., "SUBQUERY", _] => {
+            node.operation = PlanOperation::SubqueryScan;
+        }
+        _ => {}
+    }
+    node
+}
+
 fn is_sqlite_interrupt(e: &rusqlite::Error) -> bool {
     matches!(
         e,
@@ -777,26 +1375,36 @@ fn execute_query(
     query: &str,
     params: &[serde_json::Value],
     caps: &Capabilities,
-) -> Result<QueryResult> {
+    sink: &mut dyn RowSink,
+) -> Result<StreamSummary> {
+    let start = Instant::now();
+
     // Prepare statement
     let mut stmt = conn
         .prepare(query)
         .map_err(|e| PlenumError::query_failed(format!("Failed to prepare query: {e}")))?;
 
-    // Get column names
-    let column_names: Vec<String> = stmt.column_names().iter().map(|s| (*s).to_string()).collect();
+    // Column names and declared types (expressions have no declared type)
+    let columns: Vec<ResultColumn> = stmt
+        .columns()
+        .iter()
+        .map(|c| ResultColumn {
+            name: c.name().to_string(),
+            type_name: c.decl_type().map(str::to_string),
+        })
+        .collect();
+    let column_count = columns.len();
 
     // Convert JSON params to rusqlite native values for server-side binding
     let sqlite_params: Vec<rusqlite::types::Value> =
         params.iter().map(json_to_sqlite_value).collect();
 
-    // Execute query and collect rows
-    let mut rows_data = Vec::new();
+    let mut feed = RowFeed::new(sink, caps);
+    feed.columns(&columns)?;
     let mut rows_affected: Option<u64> = None;
-    let mut rows_truncated = false;
 
     // Check if this is a SELECT query (has columns)
-    if column_names.is_empty() {
+    if column_count == 0 {
         // Non-SELECT query (INSERT, UPDATE, DELETE, DDL) — blocked by capability checks in
         // practice; handled here for completeness.
         stmt.execute(rusqlite::params_from_iter(&sqlite_params)).map_err(|e| {
@@ -812,8 +1420,8 @@ fn execute_query(
         // Get rows affected (only for DML statements)
         rows_affected = Some(conn.changes());
     } else {
-        // SELECT query - collect result set
-        let rows = stmt.query(rusqlite::params_from_iter(&sqlite_params)).map_err(|e| {
+        // SELECT query - step through the result set one row at a time
+        let mut rows = stmt.query(rusqlite::params_from_iter(&sqlite_params)).map_err(|e| {
             if is_sqlite_interrupt(&e) {
                 PlenumError::query_timeout(
                     "Query interrupted by SQLite server-side timeout".to_string(),
@@ -823,63 +1431,39 @@ fn execute_query(
             }
         })?;
 
-        let mapped_rows = rows.mapped(|row| row_to_json(&column_names, row)).collect::<Vec<_>>();
-
-        let offset = caps.offset.unwrap_or(0);
-        let max = caps.max_rows;
-        let mut pos = 0usize;
-
-        for row_result in mapped_rows {
-            let row = row_result.map_err(|e| {
-                if is_sqlite_interrupt(&e) {
-                    PlenumError::query_timeout(
-                        "Query interrupted by SQLite server-side timeout during row fetch"
-                            .to_string(),
-                    )
-                } else {
-                    PlenumError::query_failed(format!("Failed to fetch row: {e}"))
-                }
-            })?;
-
-            // Skip offset rows
-            if pos < offset {
-                pos += 1;
-                continue;
+        let fetch_error = |e: rusqlite::Error| {
+            if is_sqlite_interrupt(&e) {
+                PlenumError::query_timeout(
+                    "Query interrupted by SQLite server-side timeout during row fetch".to_string(),
+                )
+            } else {
+                PlenumError::query_failed(format!("Failed to fetch row: {e}"))
             }
-
-            // Probe one row past max_rows to detect truncation
-            if let Some(m) = max {
-                if rows_data.len() >= m {
-                    rows_truncated = true;
-                    break;
-                }
+        };
+        let cells = feed.cells();
+        while let Some(row) = rows.next().map_err(fetch_error)? {
+            if !feed.push(|| row_to_json(column_count, row, cells).map_err(fetch_error))? {
+                break;
             }
-
-            rows_data.push(row);
-            pos += 1;
         }
     }
 
-    Ok(QueryResult {
-        columns: column_names,
-        rows: rows_data,
-        rows_affected,
-        execution_ms: 0,
-        rows_truncated,
-        truncated_by: None,
-        plan: None,
-    })
+    let mut summary = feed.finish();
+    summary.rows_affected = rows_affected;
+    summary.execution_ms = start.elapsed().as_millis() as u64;
+    Ok(summary)
 }
 
 /// Convert a `SQLite` row to a JSON-safe `Vec`
 fn row_to_json(
-    column_names: &[String],
+    column_count: usize,
     row: &Row,
+    cells: CellFormat,
 ) -> std::result::Result<Vec<serde_json::Value>, rusqlite::Error> {
-    let mut values = Vec::with_capacity(column_names.len());
+    let mut values = Vec::with_capacity(column_count);
 
-    for idx in 0..column_names.len() {
-        let value = sqlite_value_to_json(row, idx)?;
+    for idx in 0..column_count {
+        let value = sqlite_value_to_json(row, idx, cells)?;
         values.push(value);
     }
 
@@ -890,6 +1474,7 @@ fn row_to_json(
 fn sqlite_value_to_json(
     row: &Row,
     idx: usize,
+    cells: CellFormat,
 ) -> std::result::Result<serde_json::Value, rusqlite::Error> {
     use rusqlite::types::ValueRef;
 
@@ -910,19 +1495,51 @@ fn sqlite_value_to_json(
             })?;
             serde_json::Value::String(text.to_string())
         }
-        ValueRef::Blob(b) => {
-            // Encode BLOB as Base64 for JSON safety
-            use base64::Engine;
-            let encoded = base64::engine::general_purpose::STANDARD.encode(b);
-            serde_json::Value::String(encoded)
-        }
+        // Encode BLOB per --binary (Base64 by default) for JSON safety
+        ValueRef::Blob(b) => cells.binary(b),
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::engine::DatabaseType;
+    use crate::engine::{DatabaseType, QueryControl};
+
+    #[test]
+    fn test_describe_sqlite_plan_detail() {
+        let describe = |detail: &str| {
+            let node = describe_sqlite_plan_detail(detail);
+            (node.operation, node.relation, node.index)
+        };
+        let some = |s: &str| Some(s.to_string());
+        assert_eq!(describe("SCAN u"), (PlanOperation::FullScan, some("u"), None));
+        assert_eq!(describe("SCAN TABLE u"), (PlanOperation::FullScan, some("u"), None));
+        assert_eq!(
+            describe("SEARCH t USING INTEGER PRIMARY KEY (rowid=?)"),
+            (PlanOperation::IndexScan, some("t"), some("PRIMARY KEY"))
+        );
+        assert_eq!(
+            describe("SEARCH t USING INDEX tn (n>?)"),
+            (PlanOperation::IndexScan, some("t"), some("tn"))
+        );
+        assert_eq!(
+            describe("SCAN t USING COVERING INDEX tn"),
+            (PlanOperation::IndexOnlyScan, some("t"), some("tn"))
+        );
+        assert_eq!(
+            describe("SEARCH u USING AUTOMATIC COVERING INDEX (tid=?)"),
+            (PlanOperation::IndexOnlyScan, some("u"), None)
+        );
+        assert_eq!(describe("USE TEMP B-TREE FOR ORDER BY").0, PlanOperation::Sort);
+        assert_eq!(describe("USE TEMP B-TREE FOR GROUP BY").0, PlanOperation::Aggregate);
+        assert_eq!(describe("UNION USING TEMP B-TREE").0, PlanOperation::Union);
+        assert_eq!(describe("CORRELATED SCALAR SUBQUERY 2").0, PlanOperation::SubqueryScan);
+        assert_eq!(describe("SCAN CONSTANT ROW"), (PlanOperation::Other, None, None));
+
+        let bloom = describe_sqlite_plan_detail("BLOOM FILTER ON u (tid=?)");
+        assert_eq!(bloom.operation, PlanOperation::Filter);
+        assert_eq!(bloom.filter.as_deref(), Some("(tid=?)"));
+    }
 
     #[tokio::test]
     async fn test_validate_connection_memory() {
@@ -1511,6 +2128,30 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_execute_cancelled_by_client() {
+        // Cancelling the control fires the interrupt handle registered at connect time
+        let config = ConnectionConfig::sqlite(":memory:".into());
+        let control = QueryControl::new();
+        let caps = Capabilities { control: Some(control.clone()), ..Capabilities::default() };
+        let sql = "WITH RECURSIVE cnt(x) AS \
+                   (VALUES(1) UNION ALL SELECT x+1 FROM cnt WHERE x < 1000000000) \
+                   SELECT count(*) FROM cnt";
+
+        let canceller = control.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            canceller.cancel();
+        });
+        let err = SqliteEngine::execute(&config, sql, &[], &caps).await.unwrap_err();
+        assert_eq!(err.error_code(), "QUERY_FAILED");
+        assert!(err.message().contains("cancelled"), "unexpected message: {}", err.message());
+
+        // An already-cancelled control never starts the query
+        let err = SqliteEngine::execute(&config, "SELECT 1", &[], &caps).await.unwrap_err();
+        assert!(err.message().contains("cancelled"));
+    }
+
     // =========================================================================
     // Parameterized query tests (REF-259)
     // =========================================================================
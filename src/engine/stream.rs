@@ -0,0 +1,218 @@
+//! Row windowing shared by all engines' query loops
+//!
+//! Every engine fetches rows incrementally and offers each one to a `RowFeed`,
+//! which skips `offset` rows, stops one row past `max_rows` to detect truncation,
+//! and forwards the kept rows to a `RowSink`. `execute` collects them with a
+//! `RowCollector`; `execute_stream` hands them straight to the caller's sink.
+
+use serde_json::Value;
+
+use crate::engine::cell::CellFormat;
+use crate::engine::{
+    Capabilities, ExplainFormat, QueryControl, QueryResult, ResultColumn, RowSink, StreamSummary,
+};
+use crate::error::{PlenumError, Result};
+
+/// Reject `--explain-format structured` for streamed execution: a normalized plan
+/// is a single tree, not a row stream.
+pub fn reject_structured_explain(caps: &Capabilities) -> Result<()> {
+    if caps.explain_format == Some(ExplainFormat::Structured) {
+        return Err(PlenumError::invalid_input(
+            "--explain-format structured cannot be streamed; use the default JSON output",
+        ));
+    }
+    Ok(())
+}
+
+/// Report `e` as a client cancellation when `caps.control` was cancelled: engines
+/// only see the interrupt or cancel request it triggered, which reads as a timeout.
+pub fn cancelled_or(caps: &Capabilities, e: PlenumError) -> PlenumError {
+    caps.control.as_ref().and_then(|control| control.check().err()).unwrap_or(e)
+}
+
+/// Applies `offset` and `max_rows` to fetched rows and forwards the kept ones to a sink.
+pub struct RowFeed<'a> {
+    sink: &'a mut dyn RowSink,
+    offset: usize,
+    max_rows: Option<usize>,
+    skipped: usize,
+    summary: StreamSummary,
+    cells: CellFormat,
+    control: Option<QueryControl>,
+}
+
+impl<'a> RowFeed<'a> {
+    /// Window rows for `sink` according to `caps.offset` and `caps.max_rows`.
+    pub fn new(sink: &'a mut dyn RowSink, caps: &Capabilities) -> Self {
+        Self {
+            sink,
+            offset: caps.offset.unwrap_or(0),
+            max_rows: caps.max_rows,
+            skipped: 0,
+            summary: StreamSummary::default(),
+            cells: CellFormat::new(caps),
+            control: caps.control.clone(),
+        }
+    }
+
+    /// Cell rendering options engines apply when converting fetched values.
+    pub const fn cells(&self) -> CellFormat {
+        self.cells
+    }
+
+    /// Forward the result columns to the sink.
+    pub fn columns(&mut self, columns: &[ResultColumn]) -> Result<()> {
+        self.sink.columns(columns)
+    }
+
+    /// Offer the next fetched row; `convert` only runs for rows that are kept.
+    ///
+    /// Returns `false` once the engine should stop fetching: the row was one past
+    /// `max_rows` or the sink refused it.
+    pub fn push(&mut self, convert: impl FnOnce() -> Result<Vec<Value>>) -> Result<bool> {
+        if let Some(control) = &self.control {
+            control.add_row();
+        }
+        if self.skipped < self.offset {
+            self.skipped += 1;
+            return Ok(true);
+        }
+        if self.max_rows.is_some_and(|max| self.summary.rows_returned >= max) {
+            self.summary.rows_truncated = true;
+            return Ok(false);
+        }
+        if !self.sink.row(convert()?)? {
+            self.summary.rows_truncated = true;
+            self.summary.truncated_by = Some("bytes".to_string());
+            return Ok(false);
+        }
+        self.summary.rows_returned += 1;
+        Ok(true)
+    }
+
+    /// Finish the feed; `execution_ms` is left for the engine to fill in.
+    pub fn finish(self) -> StreamSummary {
+        self.summary
+    }
+}
+
+/// `RowSink` that buffers every row for the JSON envelope (used by `execute`).
+#[derive(Debug, Default)]
+pub struct RowCollector {
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl RowSink for RowCollector {
+    fn columns(&mut self, columns: &[ResultColumn]) -> Result<()> {
+        self.columns = columns.iter().map(|c| c.name.clone()).collect();
+        Ok(())
+    }
+
+    fn row(&mut self, row: Vec<Value>) -> Result<bool> {
+        self.rows.push(row);
+        Ok(true)
+    }
+}
+
+impl RowCollector {
+    /// Assemble the buffered rows and `summary` into a `QueryResult`.
+    pub fn into_result(self, summary: StreamSummary) -> QueryResult {
+        QueryResult {
+            columns: self.columns,
+            rows: self.rows,
+            rows_affected: summary.rows_affected,
+            execution_ms: summary.execution_ms,
+            rows_truncated: summary.rows_truncated,
+            truncated_by: summary.truncated_by,
+            plan: None,
+            next_cursor: None,
+            ordering: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Sink that refuses rows once `limit` rows have been accepted
+    struct Budget {
+        limit: usize,
+        rows: Vec<Vec<Value>>,
+    }
+
+    impl RowSink for Budget {
+        fn columns(&mut self, _columns: &[ResultColumn]) -> Result<()> {
+            Ok(())
+        }
+
+        fn row(&mut self, row: Vec<Value>) -> Result<bool> {
+            if self.rows.len() >= self.limit {
+                return Ok(false);
+            }
+            self.rows.push(row);
+            Ok(true)
+        }
+    }
+
+    fn feed_all(feed: &mut RowFeed<'_>, count: i64) -> usize {
+        let mut offered = 0;
+        for n in 0..count {
+            offered += 1;
+            if !feed.push(|| Ok(vec![json!(n)])).unwrap() {
+                break;
+            }
+        }
+        offered
+    }
+
+    #[test]
+    fn test_row_feed_applies_offset_and_max_rows() {
+        let mut collector = RowCollector::default();
+        let control = QueryControl::new();
+        let caps = Capabilities {
+            offset: Some(2),
+            control: Some(control.clone()),
+            ..Capabilities::new(Some(3), None)
+        };
+        let mut feed = RowFeed::new(&mut collector, &caps);
+        let offered = feed_all(&mut feed, 10);
+        let summary = feed.finish();
+
+        assert_eq!(offered, 6, "stops one row past max_rows");
+        assert_eq!(control.rows_read(), 6, "skipped rows count as read");
+        assert_eq!(summary.rows_returned, 3);
+        assert!(summary.rows_truncated);
+        assert_eq!(summary.truncated_by, None);
+        let result = collector.into_result(summary);
+        assert_eq!(result.rows, vec![vec![json!(2)], vec![json!(3)], vec![json!(4)]]);
+    }
+
+    #[test]
+    fn test_row_feed_exact_fit_is_not_truncated() {
+        let mut collector = RowCollector::default();
+        let caps = Capabilities::new(Some(3), None);
+        let mut feed = RowFeed::new(&mut collector, &caps);
+        feed_all(&mut feed, 3);
+        let summary = feed.finish();
+        assert_eq!(summary.rows_returned, 3);
+        assert!(!summary.rows_truncated);
+    }
+
+    #[test]
+    fn test_row_feed_sink_refusal_truncates_by_bytes() {
+        let mut sink = Budget { limit: 2, rows: Vec::new() };
+        let caps = Capabilities::new(None, None);
+        let mut feed = RowFeed::new(&mut sink, &caps);
+        let offered = feed_all(&mut feed, 10);
+        let summary = feed.finish();
+
+        assert_eq!(offered, 3);
+        assert_eq!(summary.rows_returned, 2);
+        assert!(summary.rows_truncated);
+        assert_eq!(summary.truncated_by.as_deref(), Some("bytes"));
+        assert_eq!(sink.rows.len(), 2);
+    }
+}
@@ -0,0 +1,980 @@
+//! Query result export to local files (`plenum query --output-file`)
+//!
+//! Rows are pulled through `DatabaseEngine::execute_stream` like `--format ndjson`,
+//! converted to Arrow record batches and written as Parquet, Arrow IPC or CSV, so
+//! result sets of any size are written in bounded memory. The database side is
+//! untouched: only the local file is created.
+//!
+//! Column types come from the engine's reported type name where it is unambiguous
+//! (integers, floats, booleans, decimals, dates, timestamps, binary, text). Columns
+//! without a declared type (e.g. `SQLite` expressions) are inferred from the first
+//! batch of values. Decimals keep every digit: their scale is the widest fraction in
+//! the first batch. Binary columns are decoded from their `--binary` rendering, so
+//! they are only typed as binary with `base64` or `hex`. 128-bit integers, times,
+//! intervals and nested values are written as their text form, exactly as they
+//! appear in the JSON output.
+
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "export")]
+pub use writer::ExportWriter;
+
+/// File format for `--output-file`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Apache Parquet (Snappy-compressed)
+    Parquet,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Arrow IPC file format (a.k.a. Feather v2)
+    Arrow,
+}
+
+impl ExportFormat {
+    /// Parse a `--format` value; `None` for non-file formats such as `json`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "parquet" => Some(Self::Parquet),
+            "csv" => Some(Self::Csv),
+            "arrow" => Some(Self::Arrow),
+            _ => None,
+        }
+    }
+
+    /// Infer the format from a file extension (`.parquet`, `.csv`, `.arrow`, `.ipc`, `.feather`).
+    #[must_use]
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "parquet" => Some(Self::Parquet),
+            "csv" => Some(Self::Csv),
+            "arrow" | "ipc" | "feather" => Some(Self::Arrow),
+            _ => None,
+        }
+    }
+
+    /// Format name as accepted by `--format`
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Parquet => "parquet",
+            Self::Csv => "csv",
+            Self::Arrow => "arrow",
+        }
+    }
+}
+
+/// Column type written to the export file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportType {
+    /// Boolean
+    Boolean,
+    /// 64-bit signed integer
+    Int64,
+    /// 64-bit float
+    Float64,
+    /// 128-bit decimal, with the scale taken from the values
+    Decimal128,
+    /// Calendar date
+    Date32,
+    /// Microsecond timestamp without a time zone
+    Timestamp,
+    /// Microsecond timestamp normalized to UTC
+    TimestampTz,
+    /// Raw bytes
+    Binary,
+    /// UTF-8 text (also used for 128-bit integers, times, intervals and nested values)
+    Utf8,
+}
+
+/// One column of an exported file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ExportColumn {
+    /// Column name
+    pub name: String,
+
+    /// Type name reported by the engine (`null` when the engine declares none)
+    pub source_type: Option<String>,
+
+    /// Type the column was written with
+    #[serde(rename = "type")]
+    pub export_type: ExportType,
+}
+
+/// Result of `plenum query --output-file`: where the rows went, not the rows themselves
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ExportResult {
+    /// Path of the written file, as given on the command line
+    pub path: String,
+
+    /// File format
+    pub format: ExportFormat,
+
+    /// Rows written to the file
+    pub rows_written: usize,
+
+    /// Size of the written file in bytes
+    pub bytes_written: u64,
+
+    /// File schema, in result column order
+    pub columns: Vec<ExportColumn>,
+}
+
+#[cfg(feature = "export")]
+mod writer {
+    use std::fs::File;
+    use std::io::BufWriter;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use arrow::array::{
+        ArrayRef, BinaryBuilder, BooleanBuilder, Date32Builder, Decimal128Builder, Float64Builder,
+        Int64Builder, StringBuilder, TimestampMicrosecondBuilder,
+    };
+    use arrow::compute::kernels::cast_utils::{parse_decimal, string_to_datetime};
+    use arrow::datatypes::{
+        DataType, Date32Type, Decimal128Type, Field, Schema, SchemaRef, TimeUnit,
+    };
+    use arrow::record_batch::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+    use parquet::basic::Compression;
+    use parquet::file::properties::WriterProperties;
+    use serde_json::Value;
+
+    use super::{ExportColumn, ExportFormat, ExportResult, ExportType};
+    use crate::engine::{BinaryFormat, DatabaseType, ResultColumn, RowSink};
+    use crate::error::{PlenumError, Result};
+
+    /// Rows buffered per record batch (and per Parquet row group flush)
+    const BATCH_ROWS: usize = 8192;
+
+    /// Decimal columns use the widest Decimal128 precision; only the scale varies
+    const DECIMAL_PRECISION: u8 = 38;
+
+    /// Time zone of `timestamptz` columns, whose values are normalized to UTC
+    const UTC: &str = "+00:00";
+
+    enum FileSink {
+        Parquet(ArrowWriter<BufWriter<File>>),
+        Arrow(arrow::ipc::writer::FileWriter<BufWriter<File>>),
+        Csv(Box<arrow::csv::Writer<BufWriter<File>>>),
+    }
+
+    /// `RowSink` that writes a query result to a Parquet, Arrow IPC or CSV file.
+    ///
+    /// Rows go to a temporary file next to the target, created by
+    /// [`ExportWriter::create`] so an unwritable directory fails before the query
+    /// runs. [`ExportWriter::finish`] renames it onto the target after a successful
+    /// stream; [`ExportWriter::abort`] removes it and leaves an existing target alone.
+    pub struct ExportWriter {
+        path: PathBuf,
+        temp: PathBuf,
+        format: ExportFormat,
+        engine: DatabaseType,
+        binary: BinaryFormat,
+        max_bytes: Option<usize>,
+        bytes: usize,
+        file: Option<BufWriter<File>>,
+        columns: Vec<ResultColumn>,
+        export_types: Vec<Option<ExportType>>,
+        buffer: Vec<Vec<Value>>,
+        sink: Option<(SchemaRef, FileSink)>,
+        rows_written: usize,
+    }
+
+    impl ExportWriter {
+        /// Prepare to write rows from `engine` in `format` to `path`, which is only
+        /// replaced once the export completes.
+        ///
+        /// `binary` is the `--binary` rendering of the streamed rows, which binary
+        /// columns are decoded from. `max_bytes` is measured per row exactly as
+        /// `apply_byte_budget` measures it for the JSON envelope, so every output
+        /// stops at the same row.
+        pub fn create(
+            path: &Path,
+            format: ExportFormat,
+            engine: DatabaseType,
+            binary: BinaryFormat,
+            max_bytes: Option<usize>,
+        ) -> Result<Self> {
+            let cannot_create = |e: &dyn std::fmt::Display| {
+                PlenumError::invalid_input(format!(
+                    "Cannot create output file {}: {e}",
+                    path.display()
+                ))
+            };
+            let name = path.file_name().ok_or_else(|| cannot_create(&"not a file path"))?;
+            let temp = path.with_file_name(format!(
+                ".{}.plenum-{}.tmp",
+                name.to_string_lossy(),
+                std::process::id()
+            ));
+            let file = File::create(&temp).map_err(|e| cannot_create(&e))?;
+            Ok(Self {
+                path: path.to_path_buf(),
+                temp,
+                format,
+                engine,
+                binary,
+                max_bytes,
+                bytes: 0,
+                file: Some(BufWriter::new(file)),
+                columns: Vec::new(),
+                export_types: Vec::new(),
+                buffer: Vec::new(),
+                sink: None,
+                rows_written: 0,
+            })
+        }
+
+        /// Flush the remaining rows, close the file and describe what was written.
+        pub fn finish(mut self) -> Result<ExportResult> {
+            if self.columns.is_empty() {
+                self.abort();
+                return Err(PlenumError::invalid_input(
+                    "Statement returned no result set to export",
+                ));
+            }
+            if let Err(e) = self.flush().and_then(|()| self.close()) {
+                self.abort();
+                return Err(e);
+            }
+            if let Err(e) = std::fs::rename(&self.temp, &self.path) {
+                self.abort();
+                return Err(self.write_error(&e.to_string()));
+            }
+            let bytes_written = std::fs::metadata(&self.path).map_or(0, |m| m.len());
+            let columns = self
+                .columns
+                .iter()
+                .zip(&self.export_types)
+                .map(|(column, export_type)| ExportColumn {
+                    name: column.name.clone(),
+                    source_type: column.type_name.clone(),
+                    export_type: export_type.unwrap_or(ExportType::Utf8),
+                })
+                .collect();
+            Ok(ExportResult {
+                path: self.path.display().to_string(),
+                format: self.format,
+                rows_written: self.rows_written,
+                bytes_written,
+                columns,
+            })
+        }
+
+        /// Discard the output: close and remove the temporary file. An existing
+        /// file at the target path is left untouched.
+        pub fn abort(&mut self) {
+            self.sink = None;
+            self.file = None;
+            let _ = std::fs::remove_file(&self.temp);
+        }
+
+        /// Write the buffered rows as one record batch, opening the file writer on first use.
+        fn flush(&mut self) -> Result<()> {
+            if self.sink.is_none() {
+                self.open_sink()?;
+            }
+            let Some((schema, sink)) = self.sink.as_mut() else {
+                return Ok(());
+            };
+            let rows = std::mem::take(&mut self.buffer);
+            if rows.is_empty() && (self.rows_written > 0 || !matches!(sink, FileSink::Csv(_))) {
+                return Ok(());
+            }
+            // An empty batch still makes the CSV writer emit its header row.
+            let batch = build_batch(schema, &self.columns, &rows, self.rows_written, self.binary)?;
+            let written = match sink {
+                FileSink::Parquet(w) => w.write(&batch).map_err(|e| e.to_string()),
+                FileSink::Arrow(w) => w.write(&batch).map_err(|e| e.to_string()),
+                FileSink::Csv(w) => w.write(&batch).map_err(|e| e.to_string()),
+            };
+            written.map_err(|e| self.write_error(&e))?;
+            self.rows_written += rows.len();
+            Ok(())
+        }
+
+        /// Fix the file schema (inferring undeclared column types and decimal scales
+        /// from the buffered rows) and open the format writer.
+        fn open_sink(&mut self) -> Result<()> {
+            let mut fields = Vec::with_capacity(self.columns.len());
+            for (idx, (column, export_type)) in
+                self.columns.iter().zip(&mut self.export_types).enumerate()
+            {
+                let values = || self.buffer.iter().map(|row| &row[idx]);
+                let resolved = export_type.unwrap_or_else(|| infer_type(values()));
+                let data_type = match resolved {
+                    ExportType::Boolean => Some(DataType::Boolean),
+                    ExportType::Int64 => Some(DataType::Int64),
+                    ExportType::Float64 => Some(DataType::Float64),
+                    ExportType::Decimal128 => decimal_scale(values())
+                        .map(|scale| DataType::Decimal128(DECIMAL_PRECISION, scale)),
+                    ExportType::Date32 => Some(DataType::Date32),
+                    ExportType::Timestamp => Some(DataType::Timestamp(TimeUnit::Microsecond, None)),
+                    ExportType::TimestampTz => {
+                        Some(DataType::Timestamp(TimeUnit::Microsecond, Some(UTC.into())))
+                    }
+                    ExportType::Binary => Some(DataType::Binary),
+                    ExportType::Utf8 => None,
+                };
+                // Decimals without a usable value to size the scale fall back to text
+                let (resolved, data_type) =
+                    data_type.map_or((ExportType::Utf8, DataType::Utf8), |dt| (resolved, dt));
+                *export_type = Some(resolved);
+                fields.push(Field::new(&column.name, data_type, true));
+            }
+            let schema: SchemaRef = Arc::new(Schema::new(fields));
+            let Some(file) = self.file.take() else {
+                return Err(self.write_error("file already closed"));
+            };
+            let sink = match self.format {
+                ExportFormat::Parquet => {
+                    let props =
+                        WriterProperties::builder().set_compression(Compression::SNAPPY).build();
+                    ArrowWriter::try_new(file, Arc::clone(&schema), Some(props))
+                        .map(FileSink::Parquet)
+                        .map_err(|e| e.to_string())
+                }
+                ExportFormat::Arrow => arrow::ipc::writer::FileWriter::try_new(file, &schema)
+                    .map(FileSink::Arrow)
+                    .map_err(|e| e.to_string()),
+                ExportFormat::Csv => Ok(FileSink::Csv(Box::new(
+                    arrow::csv::WriterBuilder::new().with_header(true).build(file),
+                ))),
+            }
+            .map_err(|e| self.write_error(&e))?;
+            self.sink = Some((schema, sink));
+            Ok(())
+        }
+
+        /// Write format footers and flush the file to disk.
+        fn close(&mut self) -> Result<()> {
+            let Some((_, sink)) = self.sink.take() else {
+                return Ok(());
+            };
+            let closed = match sink {
+                FileSink::Parquet(w) => w.close().map(drop).map_err(|e| e.to_string()),
+                FileSink::Arrow(mut w) => w.finish().map_err(|e| e.to_string()),
+                FileSink::Csv(w) => {
+                    use std::io::Write;
+                    w.into_inner().flush().map_err(|e| e.to_string())
+                }
+            };
+            closed.map_err(|e| self.write_error(&e))
+        }
+
+        fn write_error(&self, message: &str) -> PlenumError {
+            PlenumError::query_failed(format!(
+                "Failed to write {} file {}: {message}",
+                self.format.as_str(),
+                self.path.display()
+            ))
+        }
+    }
+
+    impl RowSink for ExportWriter {
+        fn columns(&mut self, columns: &[ResultColumn]) -> Result<()> {
+            self.export_types = columns
+                .iter()
+                .map(|c| {
+                    c.type_name.as_deref().and_then(|t| declared_type(self.engine, t, self.binary))
+                })
+                .collect();
+            self.columns = columns.to_vec();
+            Ok(())
+        }
+
+        fn row(&mut self, row: Vec<Value>) -> Result<bool> {
+            if let Some(max_bytes) = self.max_bytes {
+                let row_bytes = serde_json::to_string(&row).map_or(0, |s| s.len());
+                if self.bytes + row_bytes > max_bytes {
+                    return Ok(false);
+                }
+                self.bytes += row_bytes;
+            }
+            self.buffer.push(row);
+            if self.buffer.len() >= BATCH_ROWS {
+                self.flush()?;
+            }
+            Ok(true)
+        }
+    }
+
+    impl Drop for ExportWriter {
+        fn drop(&mut self) {
+            // A writer dropped without finish() or abort() (e.g. on a panic) must not
+            // leave its temporary file behind.
+            if self.sink.is_some() || self.file.is_some() {
+                self.abort();
+            }
+        }
+    }
+
+    /// Map an engine-reported type name to an export type; `None` means "infer from values".
+    ///
+    /// Binary columns are only typed as binary when `binary` renders them as
+    /// decodable text; `omit` and `length-only` leave nothing to decode.
+    fn declared_type(
+        engine: DatabaseType,
+        type_name: &str,
+        binary: BinaryFormat,
+    ) -> Option<ExportType> {
+        let upper = type_name.to_ascii_uppercase();
+        let bytes = match binary {
+            BinaryFormat::Base64 | BinaryFormat::Hex => ExportType::Binary,
+            BinaryFormat::Omit | BinaryFormat::LengthOnly => ExportType::Utf8,
+        };
+        match engine {
+            DatabaseType::Postgres => match upper.as_str() {
+                "BOOL" => Some(ExportType::Boolean),
+                "INT2" | "INT4" | "INT8" | "OID" => Some(ExportType::Int64),
+                "FLOAT4" | "FLOAT8" => Some(ExportType::Float64),
+                "NUMERIC" => Some(ExportType::Decimal128),
+                "DATE" => Some(ExportType::Date32),
+                "TIMESTAMP" => Some(ExportType::Timestamp),
+                "TIMESTAMPTZ" => Some(ExportType::TimestampTz),
+                "BYTEA" => Some(bytes),
+                _ => Some(ExportType::Utf8),
+            },
+            // MySQL binary strings share their type names with text strings and are
+            // passed through as text when valid UTF-8, so they stay text here.
+            DatabaseType::MySQL => match upper.as_str() {
+                "TINY" | "SHORT" | "LONG" | "INT24" | "LONGLONG" | "YEAR" => {
+                    Some(ExportType::Int64)
+                }
+                "FLOAT" | "DOUBLE" => Some(ExportType::Float64),
+                "DECIMAL" | "NEWDECIMAL" => Some(ExportType::Decimal128),
+                "DATE" | "NEWDATE" => Some(ExportType::Date32),
+                "DATETIME" | "DATETIME2" | "TIMESTAMP" | "TIMESTAMP2" => {
+                    Some(ExportType::Timestamp)
+                }
+                _ => Some(ExportType::Utf8),
+            },
+            DatabaseType::DuckDB => match upper.as_str() {
+                "BOOLEAN" => Some(ExportType::Boolean),
+                "TINYINT" | "SMALLINT" | "INTEGER" | "BIGINT" | "UTINYINT" | "USMALLINT"
+                | "UINTEGER" => Some(ExportType::Int64),
+                "FLOAT" | "DOUBLE" => Some(ExportType::Float64),
+                "DECIMAL" => Some(ExportType::Decimal128),
+                "DATE" => Some(ExportType::Date32),
+                "TIMESTAMP" | "TIMESTAMPS" | "TIMESTAMPMS" | "TIMESTAMPNS" => {
+                    Some(ExportType::Timestamp)
+                }
+                "TIMESTAMPTZ" => Some(ExportType::TimestampTz),
+                "BLOB" => Some(bytes),
+                _ => Some(ExportType::Utf8),
+            },
+            // SQLite type affinity rules (https://www.sqlite.org/datatype3.html §3.1);
+            // NUMERIC-affinity and undeclared columns can hold anything, so infer those.
+            DatabaseType::SQLite => {
+                if upper.contains("INT") {
+                    Some(ExportType::Int64)
+                } else if upper.contains("CHAR") || upper.contains("CLOB") || upper.contains("TEXT")
+                {
+                    Some(ExportType::Utf8)
+                } else if upper.contains("BLOB") {
+                    Some(bytes)
+                } else if upper.contains("REAL") || upper.contains("FLOA") || upper.contains("DOUB")
+                {
+                    Some(ExportType::Float64)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Infer a column type from sample values: the narrowest type every non-null value fits.
+    fn infer_type<'a>(values: impl Iterator<Item = &'a Value>) -> ExportType {
+        let mut inferred: Option<ExportType> = None;
+        for value in values {
+            let kind = match value {
+                Value::Null => continue,
+                Value::Bool(_) => ExportType::Boolean,
+                Value::Number(n) if n.is_i64() => ExportType::Int64,
+                Value::Number(_) => ExportType::Float64,
+                _ => return ExportType::Utf8,
+            };
+            inferred = match (inferred, kind) {
+                (None, kind) => Some(kind),
+                (Some(a), b) if a == b => Some(a),
+                (
+                    Some(ExportType::Int64 | ExportType::Float64),
+                    ExportType::Int64 | ExportType::Float64,
+                ) => Some(ExportType::Float64),
+                _ => return ExportType::Utf8,
+            };
+        }
+        inferred.unwrap_or(ExportType::Utf8)
+    }
+
+    /// Decimal scale for a column: the widest fraction among the sample values.
+    ///
+    /// `None` when there is no value to size it with or a value is not a plain
+    /// decimal (e.g. `NaN`), in which case the column is written as text.
+    fn decimal_scale<'a>(values: impl Iterator<Item = &'a Value>) -> Option<i8> {
+        let mut scale = None;
+        for value in values {
+            let digits = match value {
+                Value::Null => continue,
+                Value::String(s) => fraction_digits(s)?,
+                Value::Number(n) => fraction_digits(&n.to_string())?,
+                _ => return None,
+            };
+            scale = scale.max(Some(digits));
+        }
+        i8::try_from(scale?).ok().filter(|scale| scale.unsigned_abs() <= DECIMAL_PRECISION)
+    }
+
+    /// Number of fraction digits of a plain decimal literal (`-12.50` → 2).
+    fn fraction_digits(text: &str) -> Option<usize> {
+        let unsigned = text.strip_prefix('-').unwrap_or(text);
+        let (int, frac) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        ((!int.is_empty() || !frac.is_empty()) && digits(int) && digits(frac)).then_some(frac.len())
+    }
+
+    /// Parse a decimal cell at `scale`; `None` if it would need rounding or overflows.
+    fn decimal_value(text: &str, precision: u8, scale: i8) -> Option<i128> {
+        if fraction_digits(text)? > usize::try_from(scale).ok()? {
+            return None;
+        }
+        parse_decimal::<Decimal128Type>(text, precision, scale).ok()
+    }
+
+    /// Days since the epoch of a date cell. `MySQL`'s binary protocol renders dates
+    /// with a midnight time part, which is accepted; any other time is not.
+    fn date_value(text: &str) -> Option<i32> {
+        let datetime = string_to_datetime(&chrono::Utc, text).ok()?.naive_utc();
+        (datetime.time() == chrono::NaiveTime::MIN)
+            .then(|| Date32Type::from_naive_date(datetime.date()))
+    }
+
+    /// Microseconds since the epoch of a timestamp cell; values without an offset
+    /// are taken as they are, values with one are normalized to UTC.
+    fn timestamp_value(text: &str) -> Option<i64> {
+        string_to_datetime(&chrono::Utc, text).ok().map(|dt| dt.timestamp_micros())
+    }
+
+    /// Bytes of a binary cell rendered as `--binary base64` or `--binary hex`.
+    fn binary_value(text: &str, binary: BinaryFormat) -> Option<Vec<u8>> {
+        use base64::Engine;
+        match binary {
+            BinaryFormat::Base64 => base64::engine::general_purpose::STANDARD.decode(text).ok(),
+            BinaryFormat::Hex if text.len().is_multiple_of(2) => (0..text.len())
+                .step_by(2)
+                .map(|i| text.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+                .collect(),
+            BinaryFormat::Hex | BinaryFormat::Omit | BinaryFormat::LengthOnly => None,
+        }
+    }
+
+    /// Convert buffered JSON rows into a record batch matching `schema`.
+    ///
+    /// `first_row` is the zero-based index of `rows[0]` in the result, for error messages.
+    fn build_batch(
+        schema: &SchemaRef,
+        columns: &[ResultColumn],
+        rows: &[Vec<Value>],
+        first_row: usize,
+        binary: BinaryFormat,
+    ) -> Result<RecordBatch> {
+        let mut arrays: Vec<ArrayRef> = Vec::with_capacity(columns.len());
+        for (idx, field) in schema.fields().iter().enumerate() {
+            let values = rows.iter().map(|row| &row[idx]);
+            let mismatch = |row: usize, value: &Value| {
+                PlenumError::query_failed(format!(
+                    "Column '{}' is exported as {} but row {} holds {value}; CAST it in the query \
+                     to export it",
+                    columns[idx].name,
+                    field.data_type(),
+                    first_row + row + 1
+                ))
+            };
+            let array: ArrayRef = match field.data_type() {
+                DataType::Boolean => {
+                    let mut builder = BooleanBuilder::with_capacity(rows.len());
+                    for (row, value) in values.enumerate() {
+                        match value {
+                            Value::Null => builder.append_null(),
+                            Value::Bool(b) => builder.append_value(*b),
+                            Value::Number(n) if n.as_i64() == Some(0) => {
+                                builder.append_value(false);
+                            }
+                            Value::Number(n) if n.as_i64() == Some(1) => builder.append_value(true),
+                            _ => return Err(mismatch(row, value)),
+                        }
+                    }
+                    Arc::new(builder.finish())
+                }
+                DataType::Int64 => {
+                    let mut builder = Int64Builder::with_capacity(rows.len());
+                    for (row, value) in values.enumerate() {
+                        match value {
+                            Value::Null => builder.append_null(),
+                            Value::Number(n) if n.is_i64() => builder.append_option(n.as_i64()),
+                            // MySQL's text protocol delivers integers as strings
+                            Value::String(s) => match s.parse::<i64>() {
+                                Ok(v) => builder.append_value(v),
+                                Err(_) => return Err(mismatch(row, value)),
+                            },
+                            _ => return Err(mismatch(row, value)),
+                        }
+                    }
+                    Arc::new(builder.finish())
+                }
+                DataType::Float64 => {
+                    let mut builder = Float64Builder::with_capacity(rows.len());
+                    for (row, value) in values.enumerate() {
+                        match value {
+                            Value::Null => builder.append_null(),
+                            Value::Number(n) => builder.append_option(n.as_f64()),
+                            // Non-finite floats and MySQL text-protocol values arrive as strings
+                            Value::String(s) => match s.parse::<f64>() {
+                                Ok(v) => builder.append_value(v),
+                                Err(_) => return Err(mismatch(row, value)),
+                            },
+                            _ => return Err(mismatch(row, value)),
+                        }
+                    }
+                    Arc::new(builder.finish())
+                }
+                DataType::Decimal128(precision, scale) => {
+                    let mut builder = Decimal128Builder::with_capacity(rows.len())
+                        .with_precision_and_scale(*precision, *scale)
+                        .map_err(|e| {
+                            PlenumError::query_failed(format!("Failed to build record batch: {e}"))
+                        })?;
+                    for (row, value) in values.enumerate() {
+                        let parsed = match value {
+                            Value::Null => {
+                                builder.append_null();
+                                continue;
+                            }
+                            Value::String(s) => decimal_value(s, *precision, *scale),
+                            Value::Number(n) => decimal_value(&n.to_string(), *precision, *scale),
+                            _ => None,
+                        };
+                        builder.append_value(parsed.ok_or_else(|| mismatch(row, value))?);
+                    }
+                    Arc::new(builder.finish())
+                }
+                DataType::Date32 => {
+                    let mut builder = Date32Builder::with_capacity(rows.len());
+                    for (row, value) in values.enumerate() {
+                        match value {
+                            Value::Null => builder.append_null(),
+                            Value::String(s) => builder
+                                .append_value(date_value(s).ok_or_else(|| mismatch(row, value))?),
+                            _ => return Err(mismatch(row, value)),
+                        }
+                    }
+                    Arc::new(builder.finish())
+                }
+                DataType::Timestamp(_, tz) => {
+                    let mut builder = TimestampMicrosecondBuilder::with_capacity(rows.len())
+                        .with_timezone_opt(tz.clone());
+                    for (row, value) in values.enumerate() {
+                        match value {
+                            Value::Null => builder.append_null(),
+                            Value::String(s) => builder.append_value(
+                                timestamp_value(s).ok_or_else(|| mismatch(row, value))?,
+                            ),
+                            _ => return Err(mismatch(row, value)),
+                        }
+                    }
+                    Arc::new(builder.finish())
+                }
+                DataType::Binary => {
+                    let mut builder = BinaryBuilder::with_capacity(rows.len(), rows.len() * 16);
+                    for (row, value) in values.enumerate() {
+                        match value {
+                            Value::Null => builder.append_null(),
+                            Value::String(s) => builder.append_value(
+                                binary_value(s, binary).ok_or_else(|| mismatch(row, value))?,
+                            ),
+                            _ => return Err(mismatch(row, value)),
+                        }
+                    }
+                    Arc::new(builder.finish())
+                }
+                _ => {
+                    let mut builder = StringBuilder::with_capacity(rows.len(), rows.len() * 16);
+                    for value in values {
+                        match value {
+                            Value::Null => builder.append_null(),
+                            Value::String(s) => builder.append_value(s),
+                            other => builder.append_value(other.to_string()),
+                        }
+                    }
+                    Arc::new(builder.finish())
+                }
+            };
+            arrays.push(array);
+        }
+        RecordBatch::try_new(Arc::clone(schema), arrays)
+            .map_err(|e| PlenumError::query_failed(format!("Failed to build record batch: {e}")))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use arrow::array::Array;
+        use serde_json::json;
+
+        fn column(name: &str, type_name: Option<&str>) -> ResultColumn {
+            ResultColumn { name: name.to_string(), type_name: type_name.map(str::to_string) }
+        }
+
+        fn temp_path(ext: &str) -> PathBuf {
+            std::env::temp_dir().join(format!("plenum_export_{}_{ext}.{ext}", std::process::id()))
+        }
+
+        /// Export `values` of a column declared as `type_name` to Parquet and read it back.
+        fn round_trip(
+            engine: DatabaseType,
+            type_name: &str,
+            binary: BinaryFormat,
+            values: &[Value],
+        ) -> (ExportType, ArrayRef) {
+            use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+            let path = temp_path(&format!("{}.parquet", type_name.replace(' ', "_")));
+            let mut writer =
+                ExportWriter::create(&path, ExportFormat::Parquet, engine, binary, None).unwrap();
+            writer.columns(&[column("v", Some(type_name))]).unwrap();
+            for value in values {
+                writer.row(vec![value.clone()]).unwrap();
+            }
+            let result = writer.finish().unwrap();
+            let file = File::open(&path).unwrap();
+            let reader = ParquetRecordBatchReaderBuilder::try_new(file).unwrap().build().unwrap();
+            let batches: Vec<RecordBatch> = reader.collect::<std::result::Result<_, _>>().unwrap();
+            let _ = std::fs::remove_file(&path);
+            (result.columns[0].export_type, Arc::clone(batches[0].column(0)))
+        }
+
+        #[test]
+        fn test_declared_types_per_engine() {
+            let base64 = BinaryFormat::Base64;
+            let declared = |engine, name| declared_type(engine, name, base64);
+            assert_eq!(declared(DatabaseType::Postgres, "int8"), Some(ExportType::Int64));
+            assert_eq!(declared(DatabaseType::Postgres, "numeric"), Some(ExportType::Decimal128));
+            assert_eq!(
+                declared(DatabaseType::Postgres, "timestamptz"),
+                Some(ExportType::TimestampTz)
+            );
+            assert_eq!(declared(DatabaseType::Postgres, "bytea"), Some(ExportType::Binary));
+            assert_eq!(declared(DatabaseType::MySQL, "longlong"), Some(ExportType::Int64));
+            assert_eq!(declared(DatabaseType::MySQL, "newdecimal"), Some(ExportType::Decimal128));
+            assert_eq!(declared(DatabaseType::MySQL, "datetime"), Some(ExportType::Timestamp));
+            assert_eq!(declared(DatabaseType::MySQL, "blob"), Some(ExportType::Utf8));
+            assert_eq!(declared(DatabaseType::DuckDB, "HUGEINT"), Some(ExportType::Utf8));
+            assert_eq!(declared(DatabaseType::DuckDB, "TIMESTAMPNS"), Some(ExportType::Timestamp));
+            assert_eq!(declared(DatabaseType::SQLite, "BIGINT"), Some(ExportType::Int64));
+            assert_eq!(declared(DatabaseType::SQLite, "VARCHAR(20)"), Some(ExportType::Utf8));
+            assert_eq!(declared(DatabaseType::SQLite, "BLOB"), Some(ExportType::Binary));
+            assert_eq!(declared(DatabaseType::SQLite, "NUMERIC"), None);
+            assert_eq!(
+                declared_type(DatabaseType::Postgres, "bytea", BinaryFormat::LengthOnly),
+                Some(ExportType::Utf8),
+                "nothing to decode"
+            );
+        }
+
+        #[test]
+        fn test_decimal_round_trip_keeps_every_digit() {
+            let values = [json!("12.5"), Value::Null, json!("-0.0001"), json!(7)];
+            let (export_type, array) =
+                round_trip(DatabaseType::Postgres, "numeric", BinaryFormat::Base64, &values);
+            assert_eq!(export_type, ExportType::Decimal128);
+            assert_eq!(array.data_type(), &DataType::Decimal128(DECIMAL_PRECISION, 4));
+            let decimals = array.as_any().downcast_ref::<arrow::array::Decimal128Array>().unwrap();
+            assert_eq!(decimals.value_as_string(0), "12.5000");
+            assert!(decimals.is_null(1));
+            assert_eq!(decimals.value_as_string(2), "-0.0001");
+            assert_eq!(decimals.value_as_string(3), "7.0000");
+
+            assert_eq!(decimal_value("1.005", DECIMAL_PRECISION, 2), None, "never rounds");
+            let nan = [json!("NaN"), json!("1.5")];
+            let (export_type, _) =
+                round_trip(DatabaseType::Postgres, "numeric", BinaryFormat::Base64, &nan);
+            assert_eq!(export_type, ExportType::Utf8, "non-decimal values keep the text form");
+        }
+
+        #[test]
+        fn test_date_round_trip() {
+            // MySQL's binary protocol renders DATE values with a midnight time part
+            let values = [json!("2024-01-31T00:00:00.000000"), json!("1969-12-31"), Value::Null];
+            let (export_type, array) =
+                round_trip(DatabaseType::MySQL, "date", BinaryFormat::Base64, &values);
+            assert_eq!(export_type, ExportType::Date32);
+            let dates = array.as_any().downcast_ref::<arrow::array::Date32Array>().unwrap();
+            assert_eq!(dates.value(0), 19_753);
+            assert_eq!(dates.value(1), -1);
+            assert!(dates.is_null(2));
+            assert_eq!(date_value("2024-01-31 10:20:30"), None, "a time part is not a date");
+        }
+
+        #[test]
+        fn test_timestamp_round_trip() {
+            let values = [json!("2024-01-31T10:20:30.123456"), json!("1969-12-31 23:59:59")];
+            let (export_type, array) =
+                round_trip(DatabaseType::Postgres, "timestamp", BinaryFormat::Base64, &values);
+            assert_eq!(export_type, ExportType::Timestamp);
+            assert_eq!(array.data_type(), &DataType::Timestamp(TimeUnit::Microsecond, None));
+            let stamps =
+                array.as_any().downcast_ref::<arrow::array::TimestampMicrosecondArray>().unwrap();
+            assert_eq!(stamps.value(0), 1_706_696_430_123_456);
+            assert_eq!(stamps.value(1), -1_000_000);
+        }
+
+        #[test]
+        fn test_timestamptz_round_trip_normalizes_to_utc() {
+            let values = [json!("2024-01-31T10:20:30.500+02:00"), json!("2024-01-31 08:20:30")];
+            let (export_type, array) =
+                round_trip(DatabaseType::Postgres, "timestamptz", BinaryFormat::Base64, &values);
+            assert_eq!(export_type, ExportType::TimestampTz);
+            assert_eq!(
+                array.data_type(),
+                &DataType::Timestamp(TimeUnit::Microsecond, Some(UTC.into()))
+            );
+            let stamps =
+                array.as_any().downcast_ref::<arrow::array::TimestampMicrosecondArray>().unwrap();
+            assert_eq!(stamps.value(0), 1_706_689_230_500_000);
+            assert_eq!(stamps.value(1), 1_706_689_230_000_000);
+        }
+
+        #[test]
+        fn test_binary_round_trip_decodes_base64_and_hex() {
+            for (binary, text) in
+                [(BinaryFormat::Base64, "3q2+7w=="), (BinaryFormat::Hex, "deadbeef")]
+            {
+                let values = [json!(text), Value::Null];
+                let (export_type, array) =
+                    round_trip(DatabaseType::Postgres, "bytea", binary, &values);
+                assert_eq!(export_type, ExportType::Binary);
+                let bytes = array.as_any().downcast_ref::<arrow::array::BinaryArray>().unwrap();
+                assert_eq!(bytes.value(0), [0xDE, 0xAD, 0xBE, 0xEF]);
+                assert!(bytes.is_null(1));
+            }
+            assert_eq!(binary_value("abc", BinaryFormat::Hex), None);
+        }
+
+        #[test]
+        fn test_infer_type_widens_and_falls_back_to_text() {
+            let ints = [json!(1), Value::Null, json!(2)];
+            assert_eq!(infer_type(ints.iter()), ExportType::Int64);
+            let mixed = [json!(1), json!(2.5)];
+            assert_eq!(infer_type(mixed.iter()), ExportType::Float64);
+            let text = [json!(1), json!("x")];
+            assert_eq!(infer_type(text.iter()), ExportType::Utf8);
+            assert_eq!(infer_type([Value::Null].iter()), ExportType::Utf8);
+        }
+
+        #[test]
+        fn test_csv_export_writes_header_and_typed_values() {
+            let path = temp_path("csv");
+            let mut writer = ExportWriter::create(
+                &path,
+                ExportFormat::Csv,
+                DatabaseType::MySQL,
+                BinaryFormat::Base64,
+                None,
+            )
+            .unwrap();
+            writer
+                .columns(&[column("id", Some("long")), column("name", Some("var_string"))])
+                .unwrap();
+            assert!(writer.row(vec![json!("1"), json!("Ada")]).unwrap());
+            assert!(writer.row(vec![json!("2"), Value::Null]).unwrap());
+            let result = writer.finish().unwrap();
+
+            assert_eq!(result.rows_written, 2);
+            assert_eq!(result.columns[0].export_type, ExportType::Int64);
+            assert_eq!(result.columns[1].export_type, ExportType::Utf8);
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), "id,name\n1,Ada\n2,\n");
+            assert_eq!(result.bytes_written, 17);
+            let _ = std::fs::remove_file(&path);
+        }
+
+        #[test]
+        fn test_type_mismatch_fails_and_removes_file() {
+            let path = temp_path("arrow");
+            let mut writer = ExportWriter::create(
+                &path,
+                ExportFormat::Arrow,
+                DatabaseType::SQLite,
+                BinaryFormat::Base64,
+                None,
+            )
+            .unwrap();
+            writer.columns(&[column("n", Some("INTEGER"))]).unwrap();
+            writer.row(vec![json!("not a number")]).unwrap();
+            let err = writer.finish().unwrap_err();
+            assert!(err.message().contains("row 1"), "{}", err.message());
+            assert!(!path.exists(), "partial file must be removed");
+        }
+
+        #[test]
+        fn test_failed_export_keeps_existing_target() {
+            let path = temp_path("keep.csv");
+            std::fs::write(&path, "previous export\n").unwrap();
+            let mut writer = ExportWriter::create(
+                &path,
+                ExportFormat::Csv,
+                DatabaseType::SQLite,
+                BinaryFormat::Base64,
+                None,
+            )
+            .unwrap();
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), "previous export\n");
+            assert!(writer.temp.exists());
+            writer.abort();
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), "previous export\n");
+            assert!(!writer.temp.exists(), "the temporary file is removed");
+
+            let mut writer = ExportWriter::create(
+                &path,
+                ExportFormat::Csv,
+                DatabaseType::SQLite,
+                BinaryFormat::Base64,
+                None,
+            )
+            .unwrap();
+            writer.columns(&[column("n", Some("INTEGER"))]).unwrap();
+            writer.row(vec![json!(1)]).unwrap();
+            writer.finish().unwrap();
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), "n\n1\n");
+            let _ = std::fs::remove_file(&path);
+        }
+
+        #[test]
+        fn test_byte_budget_refuses_rows() {
+            let path = temp_path("parquet");
+            let mut writer = ExportWriter::create(
+                &path,
+                ExportFormat::Parquet,
+                DatabaseType::DuckDB,
+                BinaryFormat::Base64,
+                Some(8),
+            )
+            .unwrap();
+            writer.columns(&[column("n", Some("INTEGER"))]).unwrap();
+            assert!(writer.row(vec![json!(1)]).unwrap());
+            assert!(writer.row(vec![json!(2)]).unwrap());
+            assert!(!writer.row(vec![json!(3)]).unwrap(), "third row exceeds the budget");
+            let result = writer.finish().unwrap();
+            assert_eq!(result.rows_written, 2);
+            assert!(result.bytes_written > 0);
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
@@ -20,6 +20,9 @@
 //! - [`engine`] - Database engine trait and core types
 //! - [`capability`] - Capability validation and SQL categorization
 //! - [`config`] - Configuration management
+//! - [`cursor`] - Keyset cursor pagination
+//! - [`export`] - Query result export to local files
+//! - [`params`] - Positional and `:name` query parameters
 //!
 //! # Public API
 //! This library exports types and functions for use by both CLI and MCP interfaces:
@@ -28,15 +31,26 @@
 //! - Errors: [`PlenumError`]
 //! - Functions: Configuration resolution and validation
 
+// The MCP tool catalog in `mcp::handle_list_tools` is a single `json!` literal deep
+// enough to exceed the default macro recursion limit.
+#![recursion_limit = "256"]
+
 // Core modules (Phase 1)
+pub mod advise; // Heuristic index advice from structured plans (plenum advise)
 pub mod capability; // Capability validation and enforcement (Phase 1.4)
 pub mod config; // Configuration management (Phase 1.5)
-pub mod diff; // Schema diff computation (REF-281)
+pub mod cursor; // Keyset cursor pagination for query results
+pub mod diff; // Schema and data diff computation (REF-281)
 pub mod dsn; // DSN/URL connection string parsing
 pub mod engine; // Database engine trait and implementations (Phase 1.1, 3-5)
 pub mod error; // Error handling infrastructure (Phase 1.3)
+pub mod export; // Query result export to Parquet / Arrow IPC / CSV files
 pub mod mcp;
+pub mod ordering; // Deterministic result ordering (--deterministic)
 pub mod output; // JSON output envelopes (Phase 1.2) // MCP server (Phase 7) - Manual JSON-RPC 2.0 implementation
+pub mod params; // Positional and :name query parameters
+pub mod plans; // Plan baselines for saved queries (.plenum/plans)
+pub mod saved; // Project-level saved queries (.plenum/queries)
 
 // Re-export commonly used types for convenience
 pub use capability::validate_query;
@@ -46,13 +60,27 @@ pub use config::{
 };
 pub use dsn::{parse_dsn, redact_dsn};
 pub use engine::{
-    apply_byte_budget, Capabilities, ColumnChange, ColumnInfo, ConnectionConfig, ConnectionInfo,
-    DatabaseEngine, DatabaseType, DefinitionChange, ExplainFormat, ExplainPlanNode, ForeignKeyInfo,
-    IndexInfo, IndexSummary, IntrospectResult, PrimaryKeyChange, QueryResult, SchemaDiff,
-    SchemaInfo, TableDiff, TableInfo, TimeOnlyResult, ViewDiff, ViewInfo,
+    apply_byte_budget, apply_byte_budget_with_cell_limit, filter_settings, glob_match, limit_cells,
+    ActivityReport, AttachedDatabaseInfo, BinaryFormat, Capabilities, CellChange, ColumnChange,
+    ColumnInfo, ColumnPrivileges, ColumnProfile, ColumnSummary, ConnectionConfig, ConnectionInfo,
+    ConstraintInfo, CustomTypeInfo, DataDiff, DataDiffCounts, DatabaseEngine, DatabaseType,
+    DefinitionChange, ExplainFormat, ExplainPlanNode, FileAccess, ForeignKeyInfo, GrantInfo,
+    IndexAdvice, IndexFinding, IndexHealthReport, IndexInfo, IndexSuggestion, IndexSummary,
+    InspectOperation, InspectResult, IntrospectResult, LockWait, MacroInfo, PlanBuffers,
+    PlanChange, PlanCheck, PlanOperation, PlanReport, PrimaryKeyChange, PrivilegeReport,
+    PrivilegeWarning, ProfileOptions, QueryControl, QueryRanking, QueryResult, ResultColumn,
+    ResultSummary, RlsPolicy, RowChange, RowSink, SampleOptions, SchemaDiff, SchemaInfo,
+    SequenceInfo, SessionActivity, SettingInfo, SizeOptions, SizeReport, StatementStats,
+    StreamSummary, TableDiff, TableInfo, TablePrivileges, TableProfile, TableSize, TimeOnlyResult,
+    TopQueriesReport, ValueFrequency, ViewDiff, ViewInfo,
 };
 pub use error::{PlenumError, Result};
-pub use output::{ErrorEnvelope, ErrorInfo, Metadata, SuccessEnvelope, CONTRACT_VERSION};
+#[cfg(feature = "export")]
+pub use export::ExportWriter;
+pub use export::{ExportColumn, ExportFormat, ExportResult, ExportType};
+pub use output::{
+    ErrorEnvelope, ErrorInfo, Metadata, NdjsonLine, SuccessEnvelope, CONTRACT_VERSION,
+};
 
 #[cfg(test)]
 mod tests {
@@ -1,23 +1,32 @@
 //! Plenum CLI Entry Point
 //!
 //! This is the main binary entry point for the Plenum CLI.
-//! It provides four subcommands:
+//! It provides five subcommands:
 //! - `connect` - Database connection configuration management
 //! - `introspect` - Schema introspection
+//! - `inspect` - Live server state (sessions, lock waits)
 //! - `query` - Constrained query execution
 //! - `mcp` - MCP server mode (hidden, for AI agent integration)
 //!
 //! All output to stdout is JSON-only. Logs go to stderr.
 
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-use plenum::engine::{SslMode, TlsConfig};
+use plenum::engine::{
+    InspectOperation, ProfileOptions, QueryRanking, SampleOptions, SizeOptions, SslMode, TlsConfig,
+    DEFAULT_PROFILE_SAMPLE_ROWS, DEFAULT_PROFILE_TOP_N, DEFAULT_SAMPLE_SIZE, DEFAULT_SIZES_LIMIT,
+    DEFAULT_TOP_QUERIES_LIMIT,
+};
+use plenum::params::QueryParams;
+use plenum::saved::SavedQuery;
 use plenum::{
-    parse_dsn, redact_dsn, Capabilities, ConfigLocation, ConnectionConfig, DatabaseEngine,
-    DatabaseType, ErrorEnvelope, ExplainFormat, KeychainEntry, Metadata, PlenumError, Result,
-    SuccessEnvelope,
+    parse_dsn, redact_dsn, BinaryFormat, Capabilities, ConfigLocation, ConnectionConfig,
+    DatabaseEngine, DatabaseType, ErrorEnvelope, ExplainFormat, ExportFormat, ExportResult,
+    KeychainEntry, Metadata, NdjsonLine, PlenumError, PrivilegeWarning, Result, ResultColumn,
+    ResultSummary, RowSink, StreamSummary, SuccessEnvelope,
 };
 
 // Import database engines
@@ -29,6 +38,8 @@ use plenum::engine::mysql::MySqlEngine;
 use plenum::engine::postgres::PostgresEngine;
 #[cfg(feature = "sqlite")]
 use plenum::engine::sqlite::SqliteEngine;
+#[cfg(feature = "export")]
+use plenum::ExportWriter;
 
 /// Resolved arguments for `plenum connect`:
 /// connection name, project path, config, `password_env`, `password_command`, `keychain_entry`, save location.
@@ -52,6 +63,8 @@ struct Cli {
     command: Option<Commands>,
 }
 
+// Parsed once per process; boxing the flag-heavy variants would only complicate clap.
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Configure and validate database connections
@@ -132,12 +145,15 @@ enum Commands {
         #[arg(long)]
         ssl_key: Option<PathBuf>,
 
-        /// Test connection liveness and return server metadata without saving config
+        /// Test connection liveness and return server metadata without saving config.
+        /// Also reports least-privilege warnings (write/DDL grants, superuser, table
+        /// ownership, read replica) for the configured user.
         #[arg(long, conflicts_with = "save")]
         test: bool,
     },
 
     /// Introspect database schema
+    #[command(group(clap::ArgGroup::new("sampling").args(["profile", "sample"])))]
     Introspect {
         /// One-off connection DSN/URL (mutually exclusive with --name and explicit connection flags).
         /// Accepted schemes: postgres://, postgresql://, mysql://, sqlite:
@@ -198,38 +214,89 @@ enum Commands {
 
         // ===== OPERATIONS (mutually exclusive) =====
         /// List all databases (requires wildcard database connection)
-        #[arg(long, conflicts_with_all = ["list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "diff_against"])]
+        #[arg(long, conflicts_with_all = ["list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
         list_databases: bool,
 
         /// List all schemas (`PostgreSQL` only)
-        #[arg(long, conflicts_with_all = ["list_databases", "list_tables", "list_views", "list_indexes", "table", "view", "diff_against"])]
+        #[arg(long, conflicts_with_all = ["list_databases", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
         list_schemas: bool,
 
         /// List all table names
-        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_views", "list_indexes", "table", "view", "diff_against"])]
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
         list_tables: bool,
 
         /// List all view names
-        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_indexes", "table", "view", "diff_against"])]
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
         list_views: bool,
 
         /// List all indexes (optionally filtered by table name)
-        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "table", "view", "diff_against"])]
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
         list_indexes: Option<String>,
 
         /// Get full details for a specific table
-        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "view", "diff_against"])]
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
         table: Option<String>,
 
         /// Get details for a specific view
-        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "diff_against"])]
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
         view: Option<String>,
 
+        /// List all sequences (`DuckDB` only)
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
+        list_sequences: bool,
+
+        /// List all scalar and table macros (`DuckDB` only)
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
+        list_macros: bool,
+
+        /// List user-defined types such as enums and structs (`DuckDB` only)
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
+        list_types: bool,
+
+        /// List CHECK and UNIQUE constraints, optionally filtered by table name (`DuckDB` only)
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
+        list_constraints: Option<String>,
+
+        /// List attached databases with path, storage type and access mode (`DuckDB` only)
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_privileges", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
+        list_attached: bool,
+
+        /// Report the connected user's effective privileges (optionally filtered by table name):
+        /// grants and row-level security on `PostgreSQL`, `SHOW GRANTS` on `MySQL`,
+        /// file access on `SQLite` / `DuckDB`
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "index_health", "sizes", "settings", "profile", "sample", "diff_against"])]
+        list_privileges: Option<String>,
+
+        /// Diagnose index health (optionally filtered by table name): unused, duplicate and
+        /// redundant indexes and bloated tables. Not supported on `DuckDB`.
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "sizes", "settings", "profile", "sample", "diff_against"])]
+        index_health: Option<String>,
+
+        /// Report table, index and TOAST sizes in bytes plus the database size, largest
+        /// tables first (see --limit, --offset)
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "settings", "profile", "sample", "diff_against"])]
+        sizes: bool,
+
+        /// Report server/session settings: name, value, unit, source and whether the session
+        /// may change it. Optional case-insensitive glob on the name (e.g. `max_conn*`).
+        #[arg(long, num_args = 0..=1, default_missing_value = "", value_name = "PATTERN", conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "profile", "sample", "diff_against"])]
+        settings: Option<String>,
+
+        /// Profile a table's columns: null fraction, distinct count, min/max and top values.
+        /// `PostgreSQL` reads fresh `pg_stats`; otherwise a bounded sample is read.
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "sample", "diff_against"])]
+        profile: Option<String>,
+
+        /// Return a reproducible random sample of a table's rows (see --size, --seed).
+        /// Same table contents, size and seed → same rows.
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "diff_against"])]
+        sample: Option<String>,
+
         /// Compare the current connection against this named connection (structural schema diff).
         /// Mutually exclusive with all other operation flags.
         /// Returns a full structural diff: tables/views added, removed, and changed (columns,
         /// indexes, foreign keys, primary keys).
-        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view"])]
+        #[arg(long, conflicts_with_all = ["list_databases", "list_schemas", "list_tables", "list_views", "list_indexes", "table", "view", "list_sequences", "list_macros", "list_types", "list_constraints", "list_attached", "list_privileges", "index_health", "sizes", "settings", "profile", "sample"])]
         diff_against: Option<String>,
 
         /// Project path for the --diff-against connection (defaults to the current project path).
@@ -262,6 +329,120 @@ enum Commands {
         /// Include indexes in table details (default: true)
         #[arg(long, requires = "table")]
         indexes: Option<bool>,
+
+        // ===== PROFILING MODIFIERS (for --profile operation) =====
+        /// Maximum rows to sample when profiling (default: 10000)
+        #[arg(long, requires = "profile")]
+        max_rows: Option<usize>,
+
+        /// Number of most frequent values to report per column (default: 5)
+        #[arg(long, requires = "profile")]
+        top_values: Option<usize>,
+
+        // ===== SAMPLING MODIFIERS (for --sample operation) =====
+        /// Number of rows to sample (default: 10)
+        #[arg(long, requires = "sample")]
+        size: Option<usize>,
+
+        /// Sampling seed (default: 0)
+        #[arg(long, requires = "sample")]
+        seed: Option<u32>,
+
+        // ===== SIZE INVENTORY MODIFIERS (for --sizes operation) =====
+        /// Maximum tables per page (default: 50)
+        #[arg(long, requires = "sizes")]
+        limit: Option<usize>,
+
+        /// Number of tables to skip, after sorting by size (default: 0)
+        #[arg(long, requires = "sizes")]
+        offset: Option<usize>,
+
+        /// Timeout in milliseconds for the --profile / --sample query
+        #[arg(long, requires = "sampling")]
+        timeout_ms: Option<u64>,
+    },
+
+    /// Inspect live server state: sessions and lock waits (`PostgreSQL` / `MySQL`)
+    Inspect {
+        /// One-off connection DSN/URL (mutually exclusive with --name and explicit connection flags).
+        /// Accepted schemes: postgres://, postgresql://, mysql://, sqlite:
+        #[arg(long, conflicts_with_all = ["name", "engine", "host", "port", "user", "password", "database", "file"])]
+        dsn: Option<String>,
+
+        /// Connection name (optional, defaults to "default")
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Project path (optional, defaults to current directory)
+        #[arg(long)]
+        project_path: Option<String>,
+
+        /// Engine override
+        #[arg(long, value_parser = ["postgres", "mysql", "sqlite"])]
+        engine: Option<String>,
+
+        /// Host override
+        #[arg(long)]
+        host: Option<String>,
+
+        /// Port override
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// Username override
+        #[arg(long)]
+        user: Option<String>,
+
+        /// Password override
+        #[arg(long)]
+        password: Option<String>,
+
+        /// Database override
+        #[arg(long)]
+        database: Option<String>,
+
+        /// `SQLite` file override
+        #[arg(long)]
+        file: Option<PathBuf>,
+
+        /// TLS/SSL mode (postgres/mysql only): disable, require, verify-ca, or verify-full
+        #[arg(long, value_parser = ["disable", "require", "verify-ca", "verify-full"])]
+        ssl_mode: Option<String>,
+
+        /// Path to PEM CA certificate for TLS verification (required for verify-ca / verify-full)
+        #[arg(long)]
+        ssl_ca: Option<PathBuf>,
+
+        /// Path to PEM client certificate for mTLS (must be paired with --ssl-key)
+        #[arg(long)]
+        ssl_cert: Option<PathBuf>,
+
+        /// Path to PEM client private key for mTLS (must be paired with --ssl-cert)
+        #[arg(long)]
+        ssl_key: Option<PathBuf>,
+
+        // ===== OPERATIONS =====
+        /// Report sessions (pid, user, state, query, duration, wait event) and
+        /// lock-wait edges between them
+        #[arg(long, conflicts_with = "top_queries")]
+        activity: bool,
+
+        /// Report the most expensive normalized statements (`pg_stat_statements` /
+        /// `performance_schema` digests)
+        #[arg(long)]
+        top_queries: bool,
+
+        /// Metric to rank --top-queries by: total-time, mean-time, calls, or rows
+        #[arg(long, requires = "top_queries", value_parser = ["total-time", "mean-time", "calls", "rows"])]
+        order_by: Option<String>,
+
+        /// Maximum statements returned by --top-queries (default: 10)
+        #[arg(long, requires = "top_queries")]
+        limit: Option<usize>,
+
+        /// Replace string and numeric literals in session query text with `?` (--activity only)
+        #[arg(long, requires = "activity")]
+        redact_queries: bool,
     },
 
     /// Execute constrained SQL queries
@@ -331,6 +512,11 @@ enum Commands {
         #[arg(long)]
         sql_file: Option<PathBuf>,
 
+        /// Run a saved query from `.plenum/queries/` by name. Its header supplies the
+        /// connection, typed parameters and default caps; flags override them.
+        #[arg(long, conflicts_with_all = ["sql", "sql_file"])]
+        saved: Option<String>,
+
         /// Max rows to return per page
         #[arg(long)]
         max_rows: Option<usize>,
@@ -339,10 +525,31 @@ enum Commands {
         #[arg(long)]
         max_bytes: Option<usize>,
 
+        /// Longest value kept in a single cell of the JSON / NDJSON output, in bytes; longer
+        /// text, JSON and encoded binary values become a marker object with the original
+        /// length, a SHA-256 and a prefix (exports keep the real values)
+        #[arg(long)]
+        max_cell_bytes: Option<usize>,
+
+        /// Rendering of BLOB / BYTEA / binary values: "base64" (default), "hex",
+        /// "omit" (null) or "length-only" (byte length)
+        #[arg(long, value_parser = ["base64", "hex", "omit", "length-only"])]
+        binary: Option<String>,
+
         /// Number of rows to skip before collecting results (for pagination)
         #[arg(long)]
         offset: Option<usize>,
 
+        /// Issue `metadata.next_cursor` for keyset pagination when a page is truncated;
+        /// a single-table SELECT without ORDER BY is then ordered by its primary key
+        #[arg(long, conflicts_with_all = ["offset", "format", "output_file"])]
+        paginate: bool,
+
+        /// Resume after the last row of a previous page: pass `metadata.next_cursor`
+        /// from that response together with the same SQL and params (implies --paginate)
+        #[arg(long, conflicts_with_all = ["offset", "format", "output_file"])]
+        cursor: Option<String>,
+
         /// Query timeout in milliseconds
         #[arg(long)]
         timeout_ms: Option<u64>,
@@ -350,7 +557,10 @@ enum Commands {
         /// Bound query parameters, one per flag invocation.
         /// Parse rules: numeric literals bind as integers or floats, "true"/"false" as
         /// booleans, "null" as NULL, JSON strings as strings, everything else as text.
-        /// Use $1/$2/… placeholders for `PostgreSQL`, ? for MySQL/SQLite.
+        /// Append a type to bind it natively: `…::uuid`, `12.50::decimal`, `2024-01-31::date`,
+        /// `…::timestamp`, `…::json`, `<base64>::bytes`, or an array such as `[1,2,3]::int[]`.
+        /// Write :name placeholders in the SQL and pass name=value on every engine, or
+        /// use native positional placeholders ($1/$2/… for `PostgreSQL`, ? elsewhere).
         #[arg(long = "param", action = clap::ArgAction::Append)]
         param: Vec<String>,
 
@@ -364,9 +574,139 @@ enum Commands {
 
         /// EXPLAIN output format: "native" (default) returns raw engine rows unchanged;
         /// "structured" requires an EXPLAIN statement and returns data.plan — a normalized,
-        /// engine-stable plan tree. Non-EXPLAIN queries with "structured" are rejected.
+        /// engine-stable plan tree whose nodes share one operation taxonomy (`full_scan`,
+        /// `index_scan`, `hash_join`, ...). Non-EXPLAIN queries with "structured" are rejected.
+        /// With EXPLAIN ANALYZE of a SELECT (`PostgreSQL`, `MySQL` 8, `DuckDB`) the query runs
+        /// within --timeout-ms and each node also carries actual rows, time and loops.
         #[arg(long)]
         explain_format: Option<String>,
+
+        /// Output format: "json" (default) prints one envelope once the query finishes;
+        /// "ndjson" streams a header line (columns and types), one line per row as it is
+        /// fetched, and a trailer line with the metadata; "parquet", "csv" and "arrow"
+        /// select the --output-file format
+        #[arg(long, value_parser = ["json", "ndjson", "parquet", "csv", "arrow"], conflicts_with_all = ["time_only", "check_only", "explain_format"])]
+        format: Option<String>,
+
+        /// Write the result rows to this local file instead of stdout; stdout gets an
+        /// envelope with the path, row count and file schema. The format comes from
+        /// --format or the file extension (.parquet, .csv, .arrow/.ipc/.feather).
+        /// An existing file is overwritten.
+        #[arg(long, conflicts_with_all = ["time_only", "check_only", "explain_format"])]
+        output_file: Option<PathBuf>,
+
+        /// Run the same SQL on this named connection too and return a data diff: rows
+        /// added, removed and changed (per column), matched by --key. --max-rows caps
+        /// each reported row list; the counts are always complete.
+        #[arg(long, requires = "key", conflicts_with_all = ["offset", "paginate", "cursor", "time_only", "check_only", "explain_format", "format", "output_file"])]
+        diff_against: Option<String>,
+
+        /// Project path for the --diff-against connection (defaults to the current project path)
+        #[arg(long, requires = "diff_against")]
+        diff_against_project_path: Option<String>,
+
+        /// Key column(s) matching rows for --diff-against; repeat or comma-separate for a
+        /// composite key. The key must be unique in both results.
+        #[arg(long, requires = "diff_against", value_delimiter = ',', action = clap::ArgAction::Append)]
+        key: Vec<String>,
+
+        /// Return per-column statistics instead of rows: count, null count, distinct
+        /// count (exact up to 10,000 values), min, max and a few example values.
+        /// Every row is read; --max-rows does not apply.
+        #[arg(long, conflicts_with_all = ["max_rows", "max_bytes", "offset", "paginate", "cursor", "time_only", "check_only", "explain_format", "format", "output_file", "diff_against"])]
+        summarize: bool,
+
+        /// Guarantee a stable row order: when the outer query has no ORDER BY, rows are
+        /// ordered by the primary key (with --paginate) or by every column. `metadata.ordering`
+        /// reports "engine" (the query's own ORDER BY), "imposed" (ordered by Plenum) or
+        /// "unordered" (the statement could not be wrapped in an ordered subquery).
+        #[arg(long, conflicts_with_all = ["explain_format", "format", "output_file", "diff_against", "summarize"])]
+        deterministic: bool,
+    },
+
+    /// Suggest indexes for a SELECT from its structured plan and the tables it scans.
+    /// Prints `CREATE INDEX` statements with a rationale each; nothing is executed.
+    Advise {
+        /// One-off connection DSN/URL (mutually exclusive with --name and explicit connection flags).
+        /// Accepted schemes: postgres://, postgresql://, mysql://, sqlite:
+        #[arg(long, conflicts_with_all = ["name", "engine", "host", "port", "user", "password", "database", "file"])]
+        dsn: Option<String>,
+
+        /// Connection name (optional, defaults to "default")
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Project path (optional, defaults to current directory)
+        #[arg(long)]
+        project_path: Option<String>,
+
+        /// Engine override
+        #[arg(long, value_parser = ["postgres", "mysql", "sqlite"])]
+        engine: Option<String>,
+
+        /// Host override
+        #[arg(long)]
+        host: Option<String>,
+
+        /// Port override
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// Username override
+        #[arg(long)]
+        user: Option<String>,
+
+        /// Password override
+        #[arg(long)]
+        password: Option<String>,
+
+        /// Database override
+        #[arg(long)]
+        database: Option<String>,
+
+        /// `SQLite` file override
+        #[arg(long)]
+        file: Option<PathBuf>,
+
+        /// TLS/SSL mode (postgres/mysql only): disable, require, verify-ca, or verify-full
+        #[arg(long, value_parser = ["disable", "require", "verify-ca", "verify-full"])]
+        ssl_mode: Option<String>,
+
+        /// Path to PEM CA certificate for TLS verification (required for verify-ca / verify-full)
+        #[arg(long)]
+        ssl_ca: Option<PathBuf>,
+
+        /// Path to PEM client certificate for mTLS (must be paired with --ssl-key)
+        #[arg(long)]
+        ssl_cert: Option<PathBuf>,
+
+        /// Path to PEM client private key for mTLS (must be paired with --ssl-cert)
+        #[arg(long)]
+        ssl_key: Option<PathBuf>,
+
+        /// SELECT statement to advise on, without EXPLAIN; it is planned, never run
+        #[arg(long)]
+        sql: String,
+
+        /// Bound query parameters, one per flag invocation (same rules as `query --param`)
+        #[arg(long = "param", action = clap::ArgAction::Append)]
+        param: Vec<String>,
+
+        /// Planning timeout in milliseconds
+        #[arg(long)]
+        timeout_ms: Option<u64>,
+
+        /// Smallest table, by estimated row count, worth an index suggestion.
+        /// Tables without a row estimate are always considered.
+        #[arg(long, default_value_t = plenum::advise::DEFAULT_MIN_ROWS)]
+        min_rows: i64,
+    },
+
+    /// Record and check plan baselines of saved queries (`.plenum/plans/<name>.json`).
+    /// `check` reports plan shape changes and estimated cost increases since `record`.
+    Plan {
+        #[command(subcommand)]
+        action: PlanAction,
     },
 
     /// Start MCP server (hidden from help, for AI agent integration)
@@ -374,6 +714,44 @@ enum Commands {
     Mcp,
 }
 
+#[derive(Subcommand)]
+enum PlanAction {
+    /// Explain saved queries with their parameter defaults and write the structured
+    /// plans as baselines
+    Record {
+        /// Saved query to record, one per flag invocation (defaults to every saved query)
+        #[arg(long = "saved", action = clap::ArgAction::Append)]
+        saved: Vec<String>,
+
+        /// Connection name overriding each saved query's own connection
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Project path (optional, defaults to current directory)
+        #[arg(long)]
+        project_path: Option<String>,
+    },
+
+    /// Explain saved queries again and compare the plans with their baselines
+    Check {
+        /// Saved query to check, one per flag invocation (defaults to every saved query)
+        #[arg(long = "saved", action = clap::ArgAction::Append)]
+        saved: Vec<String>,
+
+        /// Connection name overriding each saved query's own connection
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Project path (optional, defaults to current directory)
+        #[arg(long)]
+        project_path: Option<String>,
+
+        /// Root estimated cost ratio (current / baseline) above which the cost counts as increased
+        #[arg(long, default_value_t = plenum::plans::DEFAULT_COST_THRESHOLD)]
+        cost_threshold: f64,
+    },
+}
+
 #[tokio::main]
 async fn main() {
     // Parse CLI arguments
@@ -479,6 +857,17 @@ async fn main() {
             list_indexes,
             table,
             view,
+            list_sequences,
+            list_macros,
+            list_types,
+            list_constraints,
+            list_attached,
+            list_privileges,
+            index_health,
+            sizes,
+            settings,
+            profile,
+            sample,
             diff_against,
             diff_against_project_path,
             target_database,
@@ -487,6 +876,13 @@ async fn main() {
             primary_key,
             foreign_keys,
             indexes,
+            max_rows,
+            top_values,
+            size,
+            seed,
+            limit,
+            offset,
+            timeout_ms,
         }) => {
             let tls = build_tls_config(ssl_mode.as_deref(), ssl_ca, ssl_cert, ssl_key);
             handle_introspect(
@@ -508,6 +904,17 @@ async fn main() {
                 list_indexes,
                 table,
                 view,
+                list_sequences,
+                list_macros,
+                list_types,
+                list_constraints,
+                list_attached,
+                list_privileges,
+                index_health,
+                sizes,
+                settings,
+                profile,
+                sample,
                 diff_against,
                 diff_against_project_path,
                 target_database,
@@ -516,6 +923,72 @@ async fn main() {
                 primary_key,
                 foreign_keys,
                 indexes,
+                ProfileOptions {
+                    sample_rows: max_rows.unwrap_or(DEFAULT_PROFILE_SAMPLE_ROWS),
+                    top_n: top_values.unwrap_or(DEFAULT_PROFILE_TOP_N),
+                    timeout_ms,
+                },
+                SampleOptions {
+                    size: size.unwrap_or(DEFAULT_SAMPLE_SIZE),
+                    seed: seed.unwrap_or(0),
+                    timeout_ms,
+                },
+                SizeOptions {
+                    limit: limit.unwrap_or(DEFAULT_SIZES_LIMIT),
+                    offset: offset.unwrap_or(0),
+                },
+            )
+            .await
+        }
+        Some(Commands::Inspect {
+            dsn,
+            name,
+            project_path,
+            engine,
+            host,
+            port,
+            user,
+            password,
+            database,
+            file,
+            ssl_mode,
+            ssl_ca,
+            ssl_cert,
+            ssl_key,
+            activity,
+            top_queries,
+            order_by,
+            limit,
+            redact_queries,
+        }) => {
+            let tls = build_tls_config(ssl_mode.as_deref(), ssl_ca, ssl_cert, ssl_key);
+            let operation = if activity {
+                Some(InspectOperation::Activity)
+            } else if top_queries {
+                Some(InspectOperation::TopQueries {
+                    order_by: order_by
+                        .as_deref()
+                        .and_then(QueryRanking::parse)
+                        .unwrap_or(QueryRanking::TotalTime),
+                    limit: limit.unwrap_or(DEFAULT_TOP_QUERIES_LIMIT),
+                })
+            } else {
+                None
+            };
+            handle_inspect(
+                dsn,
+                name,
+                project_path,
+                engine,
+                host,
+                port,
+                user,
+                password,
+                database,
+                file,
+                tls,
+                operation,
+                redact_queries,
             )
             .await
         }
@@ -536,14 +1009,26 @@ async fn main() {
             ssl_key,
             sql,
             sql_file,
+            saved,
             max_rows,
             max_bytes,
+            max_cell_bytes,
+            binary,
             offset,
+            paginate,
+            cursor,
             timeout_ms,
             param,
             time_only,
             check_only,
             explain_format,
+            format,
+            output_file,
+            diff_against,
+            diff_against_project_path,
+            key,
+            summarize,
+            deterministic,
         }) => {
             let tls = build_tls_config(ssl_mode.as_deref(), ssl_ca, ssl_cert, ssl_key);
             handle_query(
@@ -560,17 +1045,79 @@ async fn main() {
                 tls,
                 sql,
                 sql_file,
+                saved,
                 max_rows,
                 max_bytes,
+                max_cell_bytes,
+                binary.as_deref().and_then(BinaryFormat::from_name),
                 offset,
+                paginate,
+                cursor,
                 timeout_ms,
                 param,
                 time_only,
                 check_only,
                 explain_format,
+                format,
+                output_file,
+                diff_against.map(|name| DataDiffTarget {
+                    name,
+                    project_path: diff_against_project_path,
+                    key,
+                }),
+                summarize,
+                deterministic,
+            )
+            .await
+        }
+        Some(Commands::Advise {
+            dsn,
+            name,
+            project_path,
+            engine,
+            host,
+            port,
+            user,
+            password,
+            database,
+            file,
+            ssl_mode,
+            ssl_ca,
+            ssl_cert,
+            ssl_key,
+            sql,
+            param,
+            timeout_ms,
+            min_rows,
+        }) => {
+            let tls = build_tls_config(ssl_mode.as_deref(), ssl_ca, ssl_cert, ssl_key);
+            handle_advise(
+                dsn,
+                name,
+                project_path,
+                engine,
+                host,
+                port,
+                user,
+                password,
+                database,
+                file,
+                tls,
+                &sql,
+                &param,
+                timeout_ms,
+                min_rows,
             )
             .await
         }
+        Some(Commands::Plan { action }) => match action {
+            PlanAction::Record { saved, name, project_path } => {
+                handle_plan(project_path, name, &saved, None).await
+            }
+            PlanAction::Check { saved, name, project_path, cost_threshold } => {
+                handle_plan(project_path, name, &saved, Some(cost_threshold)).await
+            }
+        },
         Some(Commands::Mcp) => handle_mcp().await,
         None => {
             // No subcommand provided
@@ -934,11 +1481,17 @@ async fn handle_connect_test(
         )),
     };
 
-    let elapsed_ms = start.elapsed().as_millis() as u64;
-
     match result {
-        Ok(connection_info) => {
-            let envelope = SuccessEnvelope::new(
+        Ok(mut connection_info) => {
+            // Least-privilege advisor: a failed audit is reported, never fatal to --test
+            connection_info.warnings = audit_privileges(&config).await.unwrap_or_else(|e| {
+                vec![PrivilegeWarning::info(
+                    "AUDIT_INCOMPLETE",
+                    format!("Least-privilege checks could not run: {e}"),
+                )]
+            });
+            let elapsed_ms = start.elapsed().as_millis() as u64;
+            let envelope = SuccessEnvelope::new(
                 config.engine.as_str(),
                 "connect",
                 connection_info,
@@ -955,6 +1508,31 @@ async fn handle_connect_test(
     }
 }
 
+/// Audit the configured credentials against the least-privilege model
+async fn audit_privileges(config: &ConnectionConfig) -> Result<Vec<PrivilegeWarning>> {
+    match config.engine {
+        #[cfg(feature = "sqlite")]
+        DatabaseType::SQLite => SqliteEngine::audit_privileges(config).await,
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseType::SQLite => Ok(Vec::new()),
+
+        #[cfg(feature = "postgres")]
+        DatabaseType::Postgres => PostgresEngine::audit_privileges(config).await,
+        #[cfg(not(feature = "postgres"))]
+        DatabaseType::Postgres => Ok(Vec::new()),
+
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => MySqlEngine::audit_privileges(config).await,
+        #[cfg(not(feature = "mysql"))]
+        DatabaseType::MySQL => Ok(Vec::new()),
+
+        #[cfg(feature = "duckdb")]
+        DatabaseType::DuckDB => DuckDbEngine::audit_privileges(config).await,
+        #[cfg(not(feature = "duckdb"))]
+        DatabaseType::DuckDB => Ok(Vec::new()),
+    }
+}
+
 /// Interactive connection picker (when no args provided)
 async fn interactive_connect_picker(
 ) -> Result<(String, Option<String>, ConnectionConfig, ConfigLocation)> {
@@ -1298,6 +1876,17 @@ async fn handle_introspect(
     list_indexes: Option<String>,
     table: Option<String>,
     view: Option<String>,
+    list_sequences: bool,
+    list_macros: bool,
+    list_types: bool,
+    list_constraints: Option<String>,
+    list_attached: bool,
+    list_privileges: Option<String>,
+    index_health: Option<String>,
+    sizes: bool,
+    settings: Option<String>,
+    profile: Option<String>,
+    sample: Option<String>,
     diff_against: Option<String>,
     diff_against_project_path: Option<String>,
     target_database: Option<String>,
@@ -1306,6 +1895,9 @@ async fn handle_introspect(
     primary_key: Option<bool>,
     foreign_keys: Option<bool>,
     indexes: Option<bool>,
+    profile_options: ProfileOptions,
+    sample_options: SampleOptions,
+    size_options: SizeOptions,
 ) -> std::result::Result<(), i32> {
     use plenum::engine::{IntrospectOperation, TableFields};
 
@@ -1403,6 +1995,17 @@ async fn handle_introspect(
                 list_indexes.is_some(),
                 table.is_some(),
                 view.is_some(),
+                list_sequences,
+                list_macros,
+                list_types,
+                list_constraints.is_some(),
+                list_attached,
+                list_privileges.is_some(),
+                index_health.is_some(),
+                sizes,
+                settings.is_some(),
+                profile.is_some(),
+                sample.is_some(),
             ];
             let op_count = ops.iter().filter(|&&x| x).count();
 
@@ -1414,7 +2017,11 @@ async fn handle_introspect(
                         "INVALID_INPUT",
                         "No introspect operation specified. Must provide exactly one of: \
                          --list-databases, --list-schemas, --list-tables, --list-views, \
-                         --list-indexes, --table, --view, or --diff-against. \
+                         --list-indexes, --table, --view, --list-sequences, --list-macros, \
+                         --list-types, --list-constraints, --list-attached, \
+                         --list-privileges, --index-health, --sizes, --settings, --profile, \
+                         --sample, or \
+                         --diff-against. \
                          Use --help for more information.",
                     ),
                 );
@@ -1456,6 +2063,32 @@ async fn handle_introspect(
                 IntrospectOperation::TableDetails { name: table_name, fields }
             } else if let Some(view_name) = view {
                 IntrospectOperation::ViewDetails { name: view_name }
+            } else if list_sequences {
+                IntrospectOperation::ListSequences
+            } else if list_macros {
+                IntrospectOperation::ListMacros
+            } else if list_types {
+                IntrospectOperation::ListTypes
+            } else if let Some(table_filter) = list_constraints {
+                let filter = if table_filter.is_empty() { None } else { Some(table_filter) };
+                IntrospectOperation::ListConstraints { table: filter }
+            } else if list_attached {
+                IntrospectOperation::ListAttachedDatabases
+            } else if let Some(table_filter) = list_privileges {
+                let filter = if table_filter.is_empty() { None } else { Some(table_filter) };
+                IntrospectOperation::ListPrivileges { table: filter }
+            } else if let Some(table_filter) = index_health {
+                let filter = if table_filter.is_empty() { None } else { Some(table_filter) };
+                IntrospectOperation::IndexHealth { table: filter }
+            } else if sizes {
+                IntrospectOperation::Sizes { options: size_options }
+            } else if let Some(pattern) = settings {
+                let pattern = if pattern.is_empty() { None } else { Some(pattern) };
+                IntrospectOperation::Settings { pattern }
+            } else if let Some(table_name) = profile {
+                IntrospectOperation::ProfileTable { name: table_name, options: profile_options }
+            } else if let Some(table_name) = sample {
+                IntrospectOperation::SampleTable { name: table_name, options: sample_options }
             } else {
                 unreachable!("Operation validation above ensures we have exactly one operation")
             }
@@ -1544,6 +2177,405 @@ async fn handle_introspect(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+async fn handle_inspect(
+    dsn: Option<String>,
+    name: Option<String>,
+    project_path: Option<String>,
+    engine: Option<String>,
+    host: Option<String>,
+    port: Option<u16>,
+    user: Option<String>,
+    password: Option<String>,
+    database: Option<String>,
+    file: Option<PathBuf>,
+    tls: Option<TlsConfig>,
+    operation: Option<InspectOperation>,
+    redact_queries: bool,
+) -> std::result::Result<(), i32> {
+    use plenum::InspectResult;
+
+    let start = Instant::now();
+
+    // Resolve connection config — DSN path bypasses saved config entirely
+    let config = if let Some(ref dsn_str) = dsn {
+        match parse_dsn(dsn_str) {
+            Ok(cfg) => cfg,
+            Err(e) => {
+                let envelope = ErrorEnvelope::new(
+                    "",
+                    "inspect",
+                    plenum::ErrorInfo::new(
+                        e.error_code(),
+                        format!("{} (DSN: {})", e.message(), redact_dsn(dsn_str)),
+                    ),
+                );
+                output_error(&envelope);
+                return Err(1);
+            }
+        }
+    } else {
+        match build_connection_config(
+            name.as_deref(),
+            project_path.as_deref(),
+            engine,
+            host,
+            port,
+            user,
+            password,
+            database,
+            file,
+            tls,
+        ) {
+            Ok((cfg, _is_readonly)) => cfg,
+            Err(e) => {
+                let envelope = ErrorEnvelope::from_error("", "inspect", &e);
+                output_error(&envelope);
+                return Err(1);
+            }
+        }
+    };
+
+    let Some(operation) = operation else {
+        let envelope = ErrorEnvelope::new(
+            config.engine.as_str(),
+            "inspect",
+            plenum::ErrorInfo::new(
+                "INVALID_INPUT",
+                "No inspect operation specified. Must provide one of: --activity, --top-queries. \
+                 Use --help for more information.",
+            ),
+        );
+        output_error(&envelope);
+        return Err(1);
+    };
+
+    let inspect_result = match config.engine {
+        #[cfg(feature = "sqlite")]
+        DatabaseType::SQLite => SqliteEngine::inspect(&config, &operation).await,
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseType::SQLite => Err(PlenumError::invalid_input(
+            "SQLite engine not enabled. Build with --features sqlite to enable SQLite support.",
+        )),
+
+        #[cfg(feature = "postgres")]
+        DatabaseType::Postgres => PostgresEngine::inspect(&config, &operation).await,
+        #[cfg(not(feature = "postgres"))]
+        DatabaseType::Postgres => Err(PlenumError::invalid_input(
+            "PostgreSQL engine not enabled. Build with --features postgres to enable PostgreSQL support.",
+        )),
+
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => MySqlEngine::inspect(&config, &operation).await,
+        #[cfg(not(feature = "mysql"))]
+        DatabaseType::MySQL => Err(PlenumError::invalid_input(
+            "MySQL engine not enabled. Build with --features mysql to enable MySQL support.",
+        )),
+
+        #[cfg(feature = "duckdb")]
+        DatabaseType::DuckDB => DuckDbEngine::inspect(&config, &operation).await,
+        #[cfg(not(feature = "duckdb"))]
+        DatabaseType::DuckDB => Err(PlenumError::invalid_input(
+            "DuckDB engine not enabled. Build with --features duckdb to enable DuckDB support.",
+        )),
+    };
+
+    match inspect_result {
+        Ok(mut inspect_result) => {
+            if let InspectResult::Activity { activity } = &mut inspect_result {
+                if redact_queries {
+                    activity.redact_queries(config.engine);
+                }
+            }
+            let elapsed_ms = start.elapsed().as_millis() as u64;
+            let envelope = SuccessEnvelope::new(
+                config.engine.as_str(),
+                "inspect",
+                inspect_result,
+                Metadata::new(elapsed_ms),
+            );
+            output_success(&envelope);
+            Ok(())
+        }
+        Err(e) => {
+            let envelope = ErrorEnvelope::from_error(config.engine.as_str(), "inspect", &e);
+            output_error(&envelope);
+            Err(1)
+        }
+    }
+}
+
+async fn handle_advise(
+    dsn: Option<String>,
+    name: Option<String>,
+    project_path: Option<String>,
+    engine: Option<String>,
+    host: Option<String>,
+    port: Option<u16>,
+    user: Option<String>,
+    password: Option<String>,
+    database: Option<String>,
+    file: Option<PathBuf>,
+    tls: Option<TlsConfig>,
+    sql: &str,
+    raw_params: &[String],
+    timeout_ms: Option<u64>,
+    min_rows: i64,
+) -> std::result::Result<(), i32> {
+    let start = Instant::now();
+
+    // Resolve connection config — DSN path bypasses saved config entirely
+    let config = if let Some(ref dsn_str) = dsn {
+        match parse_dsn(dsn_str) {
+            Ok(cfg) => cfg,
+            Err(e) => {
+                let envelope = ErrorEnvelope::new(
+                    "",
+                    "advise",
+                    plenum::ErrorInfo::new(
+                        e.error_code(),
+                        format!("{} (DSN: {})", e.message(), redact_dsn(dsn_str)),
+                    ),
+                );
+                output_error(&envelope);
+                return Err(1);
+            }
+        }
+    } else {
+        match build_connection_config(
+            name.as_deref(),
+            project_path.as_deref(),
+            engine,
+            host,
+            port,
+            user,
+            password,
+            database,
+            file,
+            tls,
+        ) {
+            Ok((cfg, _is_readonly)) => cfg,
+            Err(e) => {
+                let envelope = ErrorEnvelope::from_error("", "advise", &e);
+                output_error(&envelope);
+                return Err(1);
+            }
+        }
+    };
+
+    let bound = QueryParams::from_cli(raw_params, sql, config.engine)
+        .and_then(|query_params| query_params.bind(sql, config.engine));
+    let (sql, params) = match bound {
+        Ok(bound) => bound,
+        Err(e) => {
+            let envelope = ErrorEnvelope::from_error(config.engine.as_str(), "advise", &e);
+            output_error(&envelope);
+            return Err(1);
+        }
+    };
+
+    let advice = match config.engine {
+        #[cfg(feature = "sqlite")]
+        DatabaseType::SQLite => {
+            plenum::advise::advise::<SqliteEngine>(&config, &sql, &params, timeout_ms, min_rows)
+                .await
+        }
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseType::SQLite => Err(PlenumError::invalid_input(
+            "SQLite engine not enabled. Build with --features sqlite to enable SQLite support.",
+        )),
+
+        #[cfg(feature = "postgres")]
+        DatabaseType::Postgres => {
+            plenum::advise::advise::<PostgresEngine>(&config, &sql, &params, timeout_ms, min_rows)
+                .await
+        }
+        #[cfg(not(feature = "postgres"))]
+        DatabaseType::Postgres => Err(PlenumError::invalid_input(
+            "PostgreSQL engine not enabled. Build with --features postgres to enable PostgreSQL support.",
+        )),
+
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => {
+            plenum::advise::advise::<MySqlEngine>(&config, &sql, &params, timeout_ms, min_rows)
+                .await
+        }
+        #[cfg(not(feature = "mysql"))]
+        DatabaseType::MySQL => Err(PlenumError::invalid_input(
+            "MySQL engine not enabled. Build with --features mysql to enable MySQL support.",
+        )),
+
+        #[cfg(feature = "duckdb")]
+        DatabaseType::DuckDB => {
+            plenum::advise::advise::<DuckDbEngine>(&config, &sql, &params, timeout_ms, min_rows)
+                .await
+        }
+        #[cfg(not(feature = "duckdb"))]
+        DatabaseType::DuckDB => Err(PlenumError::invalid_input(
+            "DuckDB engine not enabled. Build with --features duckdb to enable DuckDB support.",
+        )),
+    };
+
+    match advice {
+        Ok(advice) => {
+            let elapsed_ms = start.elapsed().as_millis() as u64;
+            let envelope = SuccessEnvelope::new(
+                config.engine.as_str(),
+                "advise",
+                advice,
+                Metadata::new(elapsed_ms),
+            );
+            output_success(&envelope);
+            Ok(())
+        }
+        Err(e) => {
+            let envelope = ErrorEnvelope::from_error(config.engine.as_str(), "advise", &e);
+            output_error(&envelope);
+            Err(1)
+        }
+    }
+}
+
+/// Record plan baselines of saved queries, or check them when `cost_threshold` is set.
+async fn handle_plan(
+    project_path: Option<String>,
+    name: Option<String>,
+    saved: &[String],
+    cost_threshold: Option<f64>,
+) -> std::result::Result<(), i32> {
+    let start = Instant::now();
+
+    if cost_threshold.is_some_and(|t| t.is_nan() || t < 1.0) {
+        let e = PlenumError::invalid_input("--cost-threshold must be at least 1 (a cost ratio)");
+        output_error(&ErrorEnvelope::from_error("", "plan", &e));
+        return Err(1);
+    }
+    let selected = match plan_selection(project_path, saved) {
+        Ok(selected) => selected,
+        Err(e) => {
+            let envelope = ErrorEnvelope::from_error("", "plan", &e);
+            output_error(&envelope);
+            return Err(1);
+        }
+    };
+    let (project_path, queries) = selected;
+
+    let mut engines = Vec::new();
+    let mut entries = Vec::new();
+    for query in &queries {
+        let entry =
+            match plan_saved_query(&project_path, name.as_deref(), query, cost_threshold).await {
+                Ok((engine, entry)) => {
+                    engines.push(engine);
+                    entry
+                }
+                Err(e) => plenum::plans::failed(&query.name, &e),
+            };
+        entries.push(entry);
+    }
+
+    let engine = match engines.first() {
+        Some(first) if engines.iter().all(|e| e == first) => first.as_str(),
+        _ => "",
+    };
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+    let report = plenum::plans::report(entries);
+    let changed = report.changed;
+    let mut envelope = SuccessEnvelope::new(engine, "plan", report, Metadata::new(elapsed_ms));
+    // A changed plan fails the check, keeping the report, so CI can gate on the exit code
+    envelope.ok = changed == 0;
+    output_success(&envelope);
+    if changed > 0 {
+        return Err(1);
+    }
+    Ok(())
+}
+
+/// The project path and the saved queries named by `--saved` (all when none are named).
+fn plan_selection(
+    project_path: Option<String>,
+    saved: &[String],
+) -> Result<(String, Vec<SavedQuery>)> {
+    let project_path = match project_path {
+        Some(path) => path,
+        None => plenum::config::get_current_project_path()?,
+    };
+    let queries = plenum::saved::load_saved_queries(Path::new(&project_path))?;
+    if let Some(missing) = saved.iter().find(|n| !queries.iter().any(|q| &q.name == *n)) {
+        plenum::saved::find_saved_query(Path::new(&project_path), missing)?;
+    }
+    let queries: Vec<SavedQuery> =
+        queries.into_iter().filter(|q| saved.is_empty() || saved.contains(&q.name)).collect();
+    if queries.is_empty() {
+        return Err(PlenumError::invalid_input(format!(
+            "No saved queries in {}",
+            plenum::saved::queries_dir(Path::new(&project_path)).display()
+        )));
+    }
+    Ok((project_path, queries))
+}
+
+/// Explain one saved query on its connection (or `name`) and record or check its baseline.
+async fn plan_saved_query(
+    project_path: &str,
+    name: Option<&str>,
+    query: &SavedQuery,
+    cost_threshold: Option<f64>,
+) -> Result<(DatabaseType, plenum::PlanCheck)> {
+    let (config, _is_readonly) = build_connection_config(
+        name.or(query.connection.as_deref()),
+        Some(project_path),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )?;
+
+    let plan = match config.engine {
+        #[cfg(feature = "sqlite")]
+        DatabaseType::SQLite => plenum::plans::explain_saved::<SqliteEngine>(&config, query).await,
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseType::SQLite => Err(PlenumError::invalid_input(
+            "SQLite engine not enabled. Build with --features sqlite to enable SQLite support.",
+        )),
+
+        #[cfg(feature = "postgres")]
+        DatabaseType::Postgres => {
+            plenum::plans::explain_saved::<PostgresEngine>(&config, query).await
+        }
+        #[cfg(not(feature = "postgres"))]
+        DatabaseType::Postgres => Err(PlenumError::invalid_input(
+            "PostgreSQL engine not enabled. Build with --features postgres to enable PostgreSQL support.",
+        )),
+
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => plenum::plans::explain_saved::<MySqlEngine>(&config, query).await,
+        #[cfg(not(feature = "mysql"))]
+        DatabaseType::MySQL => Err(PlenumError::invalid_input(
+            "MySQL engine not enabled. Build with --features mysql to enable MySQL support.",
+        )),
+
+        #[cfg(feature = "duckdb")]
+        DatabaseType::DuckDB => plenum::plans::explain_saved::<DuckDbEngine>(&config, query).await,
+        #[cfg(not(feature = "duckdb"))]
+        DatabaseType::DuckDB => Err(PlenumError::invalid_input(
+            "DuckDB engine not enabled. Build with --features duckdb to enable DuckDB support.",
+        )),
+    }?;
+
+    let project = Path::new(project_path);
+    let entry = match cost_threshold {
+        Some(threshold) => plenum::plans::check(project, query, config.engine, &plan, threshold)?,
+        None => plenum::plans::record(project, query, config.engine, plan)?,
+    };
+    Ok((config.engine, entry))
+}
+
+#[allow(clippy::fn_params_excessive_bools)]
 async fn handle_query(
     dsn: Option<String>,
     name: Option<String>,
@@ -1558,17 +2590,51 @@ async fn handle_query(
     tls: Option<TlsConfig>,
     sql: Option<String>,
     sql_file: Option<PathBuf>,
+    saved: Option<String>,
     max_rows: Option<usize>,
     max_bytes: Option<usize>,
+    max_cell_bytes: Option<usize>,
+    binary_format: Option<BinaryFormat>,
     offset: Option<usize>,
+    paginate: bool,
+    cursor: Option<String>,
     timeout_ms: Option<u64>,
     raw_params: Vec<String>,
     time_only: bool,
     check_only: bool,
     explain_format: Option<String>,
+    format: Option<String>,
+    output_file: Option<PathBuf>,
+    diff_against: Option<DataDiffTarget>,
+    summarize: bool,
+    deterministic: bool,
 ) -> std::result::Result<(), i32> {
     let start = Instant::now();
 
+    // Saved query: its header supplies the SQL, connection and default caps
+    let saved = match saved.map(|n| find_saved_query(project_path.as_deref(), &n)).transpose() {
+        Ok(saved) => saved,
+        Err(e) => {
+            let envelope = ErrorEnvelope::from_error("", "query", &e);
+            output_error(&envelope);
+            return Err(1);
+        }
+    };
+    let (sql, name, max_rows, max_bytes, timeout_ms) = match &saved {
+        Some(q) => (
+            Some(q.sql.clone()),
+            if dsn.is_none() && engine.is_none() {
+                name.or_else(|| q.connection.clone())
+            } else {
+                name
+            },
+            max_rows.or(q.max_rows),
+            max_bytes.or(q.max_bytes),
+            timeout_ms.or(q.timeout_ms),
+        ),
+        None => (sql, name, max_rows, max_bytes, timeout_ms),
+    };
+
     // Resolve SQL input
     let sql_text = match (sql, sql_file) {
         (Some(s), None) => s,
@@ -1601,7 +2667,10 @@ async fn handle_query(
             let envelope = ErrorEnvelope::new(
                 "",
                 "query",
-                plenum::ErrorInfo::new("INVALID_INPUT", "Either --sql or --sql-file is required"),
+                plenum::ErrorInfo::new(
+                    "INVALID_INPUT",
+                    "One of --sql, --sql-file or --saved is required",
+                ),
             );
             output_error(&envelope);
             return Err(1);
@@ -1658,16 +2727,50 @@ async fn handle_query(
         timeout_ms,
         offset,
         explain_format: explain_format_parsed,
+        max_cell_bytes,
+        binary_format,
+        control: None,
     };
     // max_bytes is applied post-engine as a post-processing step (see apply_byte_budget call below)
 
-    // Parse --param strings into typed JSON values.
-    // Rule: try serde_json parsing first so that 5 → Number, true → Bool, null → Null,
-    // "text" → String. If parsing fails, treat as a plain text string.
-    let params: Vec<serde_json::Value> = raw_params
-        .iter()
-        .map(|s| serde_json::from_str(s).unwrap_or_else(|_| serde_json::Value::String(s.clone())))
-        .collect();
+    // Parse --param strings into typed JSON values (typed by the header for a
+    // saved query; see plenum::params and plenum::saved).
+    let query_params = match &saved {
+        Some(q) => q.params_from_cli(&raw_params),
+        None => QueryParams::from_cli(&raw_params, &sql_text, config.engine),
+    };
+    let query_params = match query_params {
+        Ok(query_params) => query_params,
+        Err(e) => {
+            let envelope = ErrorEnvelope::from_error(config.engine.as_str(), "query", &e);
+            output_error(&envelope);
+            return Err(1);
+        }
+    };
+
+    if let Some(target) = diff_against {
+        return diff_query(
+            &config,
+            project_path.as_deref(),
+            target,
+            &sql_text,
+            &query_params,
+            &capabilities,
+            start,
+        )
+        .await;
+    }
+
+    // Rewrite any :name placeholders to the engine's native syntax
+    let bound = query_params.bind(&sql_text, config.engine);
+    let (sql_text, params) = match bound {
+        Ok(bound) => bound,
+        Err(e) => {
+            let envelope = ErrorEnvelope::from_error(config.engine.as_str(), "query", &e);
+            output_error(&envelope);
+            return Err(1);
+        }
+    };
 
     // Validate query is read-only
     match plenum::validate_query(&sql_text, &capabilities, config.engine) {
@@ -1692,11 +2795,59 @@ async fn handle_query(
         }
     }
 
+    if summarize {
+        return match summarize_query(&config, &sql_text, &params, timeout_ms).await {
+            Ok(summary) => {
+                let meta = Metadata::new(summary.execution_ms);
+                let envelope = SuccessEnvelope::new(config.engine.as_str(), "query", summary, meta);
+                output_success(&envelope);
+                Ok(())
+            }
+            Err(e) => {
+                let envelope = ErrorEnvelope::from_error(config.engine.as_str(), "query", &e);
+                output_error(&envelope);
+                Err(1)
+            }
+        };
+    }
+
+    if let Some(path) = output_file {
+        return export_query(
+            &config,
+            &sql_text,
+            &params,
+            &capabilities,
+            max_bytes,
+            &path,
+            format.as_deref(),
+        )
+        .await;
+    }
+    if let Some(file_format) = format.as_deref().filter(|f| ExportFormat::from_name(f).is_some()) {
+        let e =
+            PlenumError::invalid_input(format!("--format {file_format} requires --output-file"));
+        let envelope = ErrorEnvelope::from_error(config.engine.as_str(), "query", &e);
+        output_error(&envelope);
+        return Err(1);
+    }
+    if format.as_deref() == Some("ndjson") {
+        return stream_query_ndjson(&config, &sql_text, &params, &capabilities, max_bytes).await;
+    }
+
     // Call appropriate database engine for query execution
     let execute_result = match config.engine {
         #[cfg(feature = "sqlite")]
         DatabaseType::SQLite => {
-            SqliteEngine::execute(&config, &sql_text, &params, &capabilities).await
+            plenum::ordering::execute_with_ordering::<SqliteEngine>(
+                &config,
+                &sql_text,
+                &params,
+                &capabilities,
+                cursor.as_deref(),
+                paginate,
+                deterministic,
+            )
+            .await
         }
         #[cfg(not(feature = "sqlite"))]
         DatabaseType::SQLite => {
@@ -1707,7 +2858,16 @@ async fn handle_query(
 
         #[cfg(feature = "postgres")]
         DatabaseType::Postgres => {
-            PostgresEngine::execute(&config, &sql_text, &params, &capabilities).await
+            plenum::ordering::execute_with_ordering::<PostgresEngine>(
+                &config,
+                &sql_text,
+                &params,
+                &capabilities,
+                cursor.as_deref(),
+                paginate,
+                deterministic,
+            )
+            .await
         }
         #[cfg(not(feature = "postgres"))]
         DatabaseType::Postgres => {
@@ -1718,7 +2878,16 @@ async fn handle_query(
 
         #[cfg(feature = "mysql")]
         DatabaseType::MySQL => {
-            MySqlEngine::execute(&config, &sql_text, &params, &capabilities).await
+            plenum::ordering::execute_with_ordering::<MySqlEngine>(
+                &config,
+                &sql_text,
+                &params,
+                &capabilities,
+                cursor.as_deref(),
+                paginate,
+                deterministic,
+            )
+            .await
         }
         #[cfg(not(feature = "mysql"))]
         DatabaseType::MySQL => {
@@ -1729,7 +2898,16 @@ async fn handle_query(
 
         #[cfg(feature = "duckdb")]
         DatabaseType::DuckDB => {
-            DuckDbEngine::execute(&config, &sql_text, &params, &capabilities).await
+            plenum::ordering::execute_with_ordering::<DuckDbEngine>(
+                &config,
+                &sql_text,
+                &params,
+                &capabilities,
+                cursor.as_deref(),
+                paginate,
+                deterministic,
+            )
+            .await
         }
         #[cfg(not(feature = "duckdb"))]
         DatabaseType::DuckDB => {
@@ -1740,10 +2918,18 @@ async fn handle_query(
     };
 
     match execute_result {
-        Ok(mut query_result) => {
-            // Apply byte budget post-engine (row-boundary truncation)
+        Ok((mut query_result, keyset)) => {
+            // Apply byte budget post-engine (row-boundary truncation), measuring rows
+            // as they are printed once --max-cell-bytes has marked them
             if let Some(max_b) = max_bytes {
-                plenum::engine::apply_byte_budget(&mut query_result, max_b);
+                plenum::apply_byte_budget_with_cell_limit(&mut query_result, max_b, max_cell_bytes);
+            }
+            // The cursor points after the last row actually returned, so it is
+            // issued only once the byte budget has trimmed the page. It encodes the
+            // real key values, so cells are marked only afterwards.
+            query_result.next_cursor = keyset.and_then(|k| k.next_cursor(&query_result));
+            if let Some(max) = max_cell_bytes {
+                plenum::limit_cells(&mut query_result.rows, max);
             }
 
             let execution_ms = query_result.execution_ms;
@@ -1757,7 +2943,9 @@ async fn handle_query(
                 rows_truncated,
                 effective_offset,
                 truncated_by,
-            );
+            )
+            .with_next_cursor(query_result.next_cursor.clone(), cursor.is_some())
+            .with_ordering(query_result.ordering.clone());
 
             if time_only {
                 // Return only timing information (for benchmarking)
@@ -1786,6 +2974,322 @@ async fn handle_query(
     }
 }
 
+/// The `--diff-against` connection and `--key` columns of a data diff query
+struct DataDiffTarget {
+    name: String,
+    project_path: Option<String>,
+    key: Vec<String>,
+}
+
+/// Run a query with `--diff-against`: the same SQL on both connections, returning
+/// the data diff. Parameters are bound separately for each connection's engine.
+async fn diff_query(
+    config: &ConnectionConfig,
+    project_path: Option<&str>,
+    target: DataDiffTarget,
+    sql: &str,
+    params: &QueryParams,
+    caps: &Capabilities,
+    start: Instant,
+) -> std::result::Result<(), i32> {
+    // Resolve the diff-against connection like `introspect --diff-against`:
+    // --diff-against-project-path overrides, else the primary project path.
+    let target_proj = target.project_path.as_deref().or(project_path);
+    let target_config = match plenum::resolve_connection(target_proj, Some(&target.name)) {
+        Ok((cfg, _)) => cfg,
+        Err(e) => {
+            let envelope = ErrorEnvelope::from_error(config.engine.as_str(), "query", &e);
+            output_error(&envelope);
+            return Err(1);
+        }
+    };
+
+    let result =
+        plenum::diff::compute_data_diff(config, &target_config, sql, params, &target.key, caps)
+            .await;
+
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+    match result {
+        Ok(diff) => {
+            let envelope = SuccessEnvelope::new(
+                config.engine.as_str(),
+                "query",
+                diff,
+                Metadata::new(elapsed_ms),
+            );
+            output_success(&envelope);
+            Ok(())
+        }
+        Err(e) => {
+            let envelope = ErrorEnvelope::from_error(config.engine.as_str(), "query", &e);
+            output_error(&envelope);
+            Err(1)
+        }
+    }
+}
+
+/// Run a query with `--format ndjson`, writing each line to stdout as it is produced.
+///
+/// A failure after the header has been written still ends the stream with an
+/// error envelope line, so the last line is always either the trailer or an error.
+async fn stream_query_ndjson(
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[serde_json::Value],
+    caps: &Capabilities,
+    max_bytes: Option<usize>,
+) -> std::result::Result<(), i32> {
+    let mut writer = NdjsonWriter {
+        engine: config.engine.as_str(),
+        max_bytes,
+        max_cell_bytes: caps.max_cell_bytes,
+        bytes: 0,
+    };
+    let stream_result = execute_stream(config, sql, params, caps, &mut writer).await;
+
+    let trailer = stream_result.and_then(|summary| {
+        let meta = Metadata::with_query(
+            summary.execution_ms,
+            summary.rows_returned,
+            summary.rows_truncated,
+            caps.offset.unwrap_or(0),
+            summary.truncated_by,
+        );
+        NdjsonWriter::write_line(&NdjsonLine::Trailer {
+            ok: true,
+            rows_affected: summary.rows_affected,
+            meta,
+        })
+    });
+    match trailer {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let envelope = ErrorEnvelope::from_error(config.engine.as_str(), "query", &e);
+            output_error(&envelope);
+            Err(1)
+        }
+    }
+}
+
+/// Run a query with `--output-file`, writing the rows to a local Parquet, Arrow IPC or
+/// CSV file and printing an envelope that describes the file.
+async fn export_query(
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[serde_json::Value],
+    caps: &Capabilities,
+    max_bytes: Option<usize>,
+    path: &std::path::Path,
+    format: Option<&str>,
+) -> std::result::Result<(), i32> {
+    let result = run_export(config, sql, params, caps, max_bytes, path, format).await;
+    match result {
+        Ok((export, summary)) => {
+            let meta = Metadata::with_query(
+                summary.execution_ms,
+                summary.rows_returned,
+                summary.rows_truncated,
+                caps.offset.unwrap_or(0),
+                summary.truncated_by,
+            );
+            let envelope = SuccessEnvelope::new(config.engine.as_str(), "query", export, meta);
+            output_success(&envelope);
+            Ok(())
+        }
+        Err(e) => {
+            let envelope = ErrorEnvelope::from_error(config.engine.as_str(), "query", &e);
+            output_error(&envelope);
+            Err(1)
+        }
+    }
+}
+
+#[cfg(feature = "export")]
+async fn run_export(
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[serde_json::Value],
+    caps: &Capabilities,
+    max_bytes: Option<usize>,
+    path: &std::path::Path,
+    format: Option<&str>,
+) -> Result<(ExportResult, StreamSummary)> {
+    let export_format = match format {
+        Some(name) => ExportFormat::from_name(name).ok_or_else(|| {
+            PlenumError::invalid_input(format!(
+                "--format {name} cannot be written to --output-file; use parquet, csv or arrow"
+            ))
+        })?,
+        None => ExportFormat::from_extension(path).ok_or_else(|| {
+            PlenumError::invalid_input(format!(
+                "Cannot infer the export format from {}; pass --format parquet, csv or arrow",
+                path.display()
+            ))
+        })?,
+    };
+    let binary = caps.binary_format.unwrap_or_default();
+    let mut writer = ExportWriter::create(path, export_format, config.engine, binary, max_bytes)?;
+    match execute_stream(config, sql, params, caps, &mut writer).await {
+        Ok(summary) => Ok((writer.finish()?, summary)),
+        Err(e) => {
+            writer.abort();
+            Err(e)
+        }
+    }
+}
+
+#[cfg(not(feature = "export"))]
+#[allow(clippy::unused_async)]
+async fn run_export(
+    _config: &ConnectionConfig,
+    _sql: &str,
+    _params: &[serde_json::Value],
+    _caps: &Capabilities,
+    _max_bytes: Option<usize>,
+    _path: &std::path::Path,
+    _format: Option<&str>,
+) -> Result<(ExportResult, StreamSummary)> {
+    Err(PlenumError::invalid_input(
+        "File export not enabled. Build with --features export to enable --output-file.",
+    ))
+}
+
+/// Dispatch `summarize_query` to the configured engine.
+async fn summarize_query(
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[serde_json::Value],
+    timeout_ms: Option<u64>,
+) -> Result<ResultSummary> {
+    match config.engine {
+        #[cfg(feature = "sqlite")]
+        DatabaseType::SQLite => {
+            plenum::engine::summarize_query::<SqliteEngine>(config, sql, params, timeout_ms).await
+        }
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseType::SQLite => Err(PlenumError::invalid_input(
+            "SQLite engine not enabled. Build with --features sqlite to enable SQLite support.",
+        )),
+
+        #[cfg(feature = "postgres")]
+        DatabaseType::Postgres => {
+            plenum::engine::summarize_query::<PostgresEngine>(config, sql, params, timeout_ms).await
+        }
+        #[cfg(not(feature = "postgres"))]
+        DatabaseType::Postgres => Err(PlenumError::invalid_input(
+            "PostgreSQL engine not enabled. Build with --features postgres to enable PostgreSQL support.",
+        )),
+
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => {
+            plenum::engine::summarize_query::<MySqlEngine>(config, sql, params, timeout_ms).await
+        }
+        #[cfg(not(feature = "mysql"))]
+        DatabaseType::MySQL => Err(PlenumError::invalid_input(
+            "MySQL engine not enabled. Build with --features mysql to enable MySQL support.",
+        )),
+
+        #[cfg(feature = "duckdb")]
+        DatabaseType::DuckDB => {
+            plenum::engine::summarize_query::<DuckDbEngine>(config, sql, params, timeout_ms).await
+        }
+        #[cfg(not(feature = "duckdb"))]
+        DatabaseType::DuckDB => Err(PlenumError::invalid_input(
+            "DuckDB engine not enabled. Build with --features duckdb to enable DuckDB support.",
+        )),
+    }
+}
+
+/// Dispatch `execute_stream` to the configured engine.
+async fn execute_stream(
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[serde_json::Value],
+    caps: &Capabilities,
+    sink: &mut dyn RowSink,
+) -> Result<StreamSummary> {
+    match config.engine {
+        #[cfg(feature = "sqlite")]
+        DatabaseType::SQLite => SqliteEngine::execute_stream(config, sql, params, caps, sink).await,
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseType::SQLite => Err(PlenumError::invalid_input(
+            "SQLite engine not enabled. Build with --features sqlite to enable SQLite support.",
+        )),
+
+        #[cfg(feature = "postgres")]
+        DatabaseType::Postgres => {
+            PostgresEngine::execute_stream(config, sql, params, caps, sink).await
+        }
+        #[cfg(not(feature = "postgres"))]
+        DatabaseType::Postgres => Err(PlenumError::invalid_input(
+            "PostgreSQL engine not enabled. Build with --features postgres to enable PostgreSQL support.",
+        )),
+
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => MySqlEngine::execute_stream(config, sql, params, caps, sink).await,
+        #[cfg(not(feature = "mysql"))]
+        DatabaseType::MySQL => Err(PlenumError::invalid_input(
+            "MySQL engine not enabled. Build with --features mysql to enable MySQL support.",
+        )),
+
+        #[cfg(feature = "duckdb")]
+        DatabaseType::DuckDB => DuckDbEngine::execute_stream(config, sql, params, caps, sink).await,
+        #[cfg(not(feature = "duckdb"))]
+        DatabaseType::DuckDB => Err(PlenumError::invalid_input(
+            "DuckDB engine not enabled. Build with --features duckdb to enable DuckDB support.",
+        )),
+    }
+}
+
+/// `RowSink` that prints `--format ndjson` lines to stdout as rows arrive.
+///
+/// Cells are marked with `max_cell_bytes` before printing, and `max_bytes` is
+/// measured per marked row exactly as the JSON envelope measures it, so both
+/// formats stop at the same row.
+struct NdjsonWriter {
+    engine: &'static str,
+    max_bytes: Option<usize>,
+    max_cell_bytes: Option<usize>,
+    bytes: usize,
+}
+
+impl NdjsonWriter {
+    fn write_line(line: &NdjsonLine) -> Result<()> {
+        let mut stdout = std::io::stdout().lock();
+        serde_json::to_writer(&mut stdout, line)
+            .map_err(std::io::Error::from)
+            .and_then(|()| stdout.write_all(b"\n"))
+            .and_then(|()| stdout.flush())
+            .map_err(|e| PlenumError::query_failed(format!("Failed to write NDJSON output: {e}")))
+    }
+}
+
+impl RowSink for NdjsonWriter {
+    fn columns(&mut self, columns: &[ResultColumn]) -> Result<()> {
+        Self::write_line(&NdjsonLine::Header {
+            contract_version: plenum::CONTRACT_VERSION,
+            engine: self.engine.to_string(),
+            command: "query".to_string(),
+            columns: columns.to_vec(),
+        })
+    }
+
+    fn row(&mut self, mut row: Vec<serde_json::Value>) -> Result<bool> {
+        if let Some(max) = self.max_cell_bytes {
+            plenum::limit_cells(std::slice::from_mut(&mut row), max);
+        }
+        if let Some(max_bytes) = self.max_bytes {
+            let row_bytes = plenum::engine::row_bytes(&row, None);
+            if self.bytes + row_bytes > max_bytes {
+                return Ok(false);
+            }
+            self.bytes += row_bytes;
+        }
+        Self::write_line(&NdjsonLine::Row { values: row })?;
+        Ok(true)
+    }
+}
+
 #[allow(clippy::future_not_send)]
 async fn handle_mcp() -> std::result::Result<(), i32> {
     // Phase 7: MCP server using manual JSON-RPC 2.0 implementation
@@ -1862,6 +3366,15 @@ fn build_tls_config(
 /// This helper resolves a connection from config or builds one from CLI arguments.
 /// Precedence: Named connection at project path → CLI arguments only
 /// Returns a tuple of (`ConnectionConfig`, `is_readonly`).
+/// Load a saved query from the project's `.plenum/queries/` directory.
+fn find_saved_query(project_path: Option<&str>, name: &str) -> Result<SavedQuery> {
+    let project_path = match project_path {
+        Some(path) => path.to_string(),
+        None => plenum::config::get_current_project_path()?,
+    };
+    plenum::saved::find_saved_query(Path::new(&project_path), name)
+}
+
 fn build_connection_config(
     name: Option<&str>,
     project_path: Option<&str>,
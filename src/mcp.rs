@@ -20,6 +20,15 @@
 //!
 //! - `introspect` - Introspect database schema
 //! - `query` - Execute constrained SQL queries
+//! - `saved_<name>` - One tool per saved query in `.plenum/queries/` (see [`crate::saved`])
+//!
+//! # Cancellation and Progress
+//!
+//! Requests run one at a time, but stdin is read while a tool call runs.
+//! `notifications/cancelled` stops its query through the engine's cancel mechanism
+//! (see [`crate::QueryControl`]) and the call gets no response. A call that sends
+//! `_meta.progressToken` receives `notifications/progress` every second with the
+//! elapsed seconds and the rows read so far.
 //!
 //! Connection management is handled via the `plenum connect` CLI command or by
 //! directly editing configuration files (`.plenum/config.json` or `~/.config/plenum/connections.json`).
@@ -43,10 +52,20 @@
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::VecDeque;
 use std::io::{self, BufRead, Write};
-use std::path::PathBuf;
-
-use crate::{parse_dsn, redact_dsn, Capabilities, ConnectionConfig, DatabaseEngine, DatabaseType};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::cursor::Keyset;
+use crate::ordering::execute_with_ordering;
+use crate::params::QueryParams;
+use crate::{
+    parse_dsn, redact_dsn, Capabilities, ConnectionConfig, DatabaseEngine, DatabaseType,
+    QueryControl,
+};
 
 // Import database engines
 #[cfg(feature = "duckdb")]
@@ -88,6 +107,14 @@ struct JsonRpcResponse {
     error: Option<JsonRpcError>,
 }
 
+/// JSON-RPC 2.0 Notification sent by the server (no id, no response expected)
+#[derive(Debug, Serialize)]
+struct JsonRpcNotification {
+    jsonrpc: String,
+    method: String,
+    params: Value,
+}
+
 /// JSON-RPC 2.0 Error
 #[derive(Debug, Serialize)]
 struct JsonRpcError {
@@ -140,6 +167,10 @@ impl CallToolResult {
 // MCP Server
 // ============================================================================
 
+/// Interval between `notifications/progress` messages for a tool call that supplied
+/// a progress token
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Start the MCP server
 ///
 /// This function runs the main MCP server loop, reading JSON-RPC requests
@@ -152,54 +183,149 @@ impl CallToolResult {
 /// - Each response is a single line of JSON
 /// - Errors are returned as JSON-RPC error responses
 ///
+/// Requests are handled one at a time. While a request runs, stdin is still read:
+/// `notifications/cancelled` for it cancels the running query (see
+/// [`crate::QueryControl`]) and suppresses its response, and other requests queue
+/// behind it. A request whose `params._meta.progressToken` is set receives a
+/// `notifications/progress` every second until it completes.
+///
 /// # Errors
 ///
 /// Returns an error if stdio communication fails or if there's a fatal error.
-#[allow(clippy::future_not_send)]
 pub async fn serve() -> Result<()> {
-    let stdin = io::stdin();
-    let reader = stdin.lock();
-    let mut stdout = io::stdout();
-
-    for line in reader.lines() {
-        let line = line?;
-
-        // Skip empty lines
-        if line.trim().is_empty() {
-            continue;
+    // stdin is read on its own thread so cancellations arrive while a query runs
+    let (lines_tx, mut lines) = mpsc::unbounded_channel();
+    std::thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if lines_tx.send(line).is_err() {
+                break;
+            }
         }
+    });
 
-        // Parse JSON-RPC request. Unparseable input is silently skipped: we have
-        // no id to attach to a response, and emitting one with id: null violates
-        // strict JSON-RPC 2.0 validators (e.g. the MCP TypeScript SDK's Zod schemas).
-        let Ok(request) = serde_json::from_str::<JsonRpcRequest>(&line) else {
-            continue;
+    let mut stdout = io::stdout();
+    let mut queued: VecDeque<JsonRpcRequest> = VecDeque::new();
+    loop {
+        let request = match queued.pop_front() {
+            Some(request) => request,
+            None => match lines.recv().await {
+                Some(line) => match parse_request(&line) {
+                    Some(request) => request,
+                    None => continue,
+                },
+                None => break,
+            },
         };
 
         // JSON-RPC 2.0: a Notification has no id and MUST NOT receive a response.
         // The MCP handshake sends `notifications/initialized` between `initialize`
-        // and `tools/list`; replying to it breaks strict clients.
+        // and `tools/list`; replying to it breaks strict clients. A cancellation
+        // arriving here names a request that already finished.
         if request.id.is_none() {
             continue;
         }
 
-        let response = handle_request(request).await;
-        let response_json = serde_json::to_string(&response)?;
-        writeln!(stdout, "{response_json}")?;
-        stdout.flush()?;
+        if let Some(response) = run_request(request, &mut lines, &mut queued, &mut stdout).await? {
+            write_message(&mut stdout, &response)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse one line of input. Unparseable input is silently skipped: we have no id
+/// to attach to a response, and emitting one with id: null violates strict
+/// JSON-RPC 2.0 validators (e.g. the MCP TypeScript SDK's Zod schemas).
+fn parse_request(line: &str) -> Option<JsonRpcRequest> {
+    if line.trim().is_empty() {
+        return None;
+    }
+    serde_json::from_str(line).ok()
+}
+
+/// Run `request` while reading further input: a cancellation of `request` cancels
+/// it, a cancellation of a queued request drops that request, and other requests
+/// are queued. Returns `None` when the request was cancelled, since MCP forbids
+/// answering a cancelled request.
+async fn run_request(
+    request: JsonRpcRequest,
+    lines: &mut mpsc::UnboundedReceiver<String>,
+    queued: &mut VecDeque<JsonRpcRequest>,
+    stdout: &mut io::Stdout,
+) -> Result<Option<JsonRpcResponse>> {
+    let id = request.id.clone();
+    let progress_token =
+        request.params.as_ref().and_then(|p| p.pointer("/_meta/progressToken")).cloned();
+    let control = QueryControl::new();
+    // SQLite and DuckDB run synchronously; a blocking thread keeps the runtime's
+    // workers free for this loop's timer and the PostgreSQL connection tasks.
+    let runtime = tokio::runtime::Handle::current();
+    let handler_control = control.clone();
+    let mut call = tokio::task::spawn_blocking(move || {
+        runtime.block_on(handle_request(request, handler_control))
+    });
+
+    let started = Instant::now();
+    let mut progress = tokio::time::interval_at(started + PROGRESS_INTERVAL, PROGRESS_INTERVAL);
+    let mut input_open = true;
+    loop {
+        tokio::select! {
+            response = &mut call => {
+                let response = response.map_err(|e| anyhow!("Request handler failed: {e}"))?;
+                return Ok((!control.is_cancelled()).then_some(response));
+            }
+            line = lines.recv(), if input_open => {
+                let Some(line) = line else {
+                    // stdin closed: finish the running request, then exit
+                    input_open = false;
+                    continue;
+                };
+                let Some(message) = parse_request(&line) else { continue };
+                if message.id.is_some() {
+                    queued.push_back(message);
+                } else if message.method == "notifications/cancelled" {
+                    let cancelled = message.params.as_ref().and_then(|p| p.get("requestId"));
+                    if cancelled.is_some() && cancelled == id.as_ref() {
+                        control.cancel();
+                    } else if let Some(cancelled) = cancelled {
+                        queued.retain(|r| r.id.as_ref() != Some(cancelled));
+                    }
+                }
+            }
+            _ = progress.tick(), if progress_token.is_some() && !control.is_cancelled() => {
+                let elapsed = started.elapsed().as_secs();
+                let notification = JsonRpcNotification {
+                    jsonrpc: "2.0".to_string(),
+                    method: "notifications/progress".to_string(),
+                    params: serde_json::json!({
+                        "progressToken": progress_token,
+                        "progress": elapsed,
+                        "message": format!("Running for {elapsed}s, {} rows read", control.rows_read()),
+                    }),
+                };
+                write_message(stdout, &notification)?;
+            }
+        }
     }
+}
 
+/// Write one JSON-RPC message as a line and flush it
+fn write_message(stdout: &mut io::Stdout, message: &impl Serialize) -> Result<()> {
+    let json = serde_json::to_string(message)?;
+    writeln!(stdout, "{json}")?;
+    stdout.flush()?;
     Ok(())
 }
 
 /// Handle a JSON-RPC request
 ///
 /// Routes the request to the appropriate handler based on the method name.
-async fn handle_request(request: JsonRpcRequest) -> JsonRpcResponse {
+async fn handle_request(request: JsonRpcRequest, control: QueryControl) -> JsonRpcResponse {
     let result = match request.method.as_str() {
         "initialize" => handle_initialize(request.params),
         "tools/list" => handle_list_tools(),
-        "tools/call" => handle_call_tool(request.params).await,
+        "tools/call" => handle_call_tool(request.params, &control).await,
         _ => Err(anyhow!("Unknown method: {}", request.method)),
     };
 
@@ -247,11 +373,11 @@ fn handle_initialize(_params: Option<Value>) -> Result<Value> {
 ///
 /// Returns the list of available MCP tools with their schemas.
 fn handle_list_tools() -> Result<Value> {
-    Ok(serde_json::json!({
+    let mut tools = serde_json::json!({
         "tools": [
             {
                 "name": "introspect",
-                "description": "Introspect database schema with granular operations. NEVER dumps entire schema - requires explicit operation. IMPORTANT CONNECTION WORKFLOW: (1) RECOMMENDED: Auto-resolve (omit all connection params) - uses project's default saved connection, (2) COMMON: Named connection (use 'connection' param only) - references saved connection by name, (3) DISCOURAGED: Explicit credentials (engine + host/user/password) - ONLY for one-off scenarios, NOT for regular use. DO NOT pass credentials repeatedly - use saved connections instead. Before using explicit credentials, check if a saved connection exists. Operations (EXACTLY ONE required, mutually exclusive): list_databases (list all DBs), list_schemas (Postgres only), list_tables (table names in schema/DB), list_views (view names), list_indexes (all or filtered by table), table (full details for specific table with optional field filtering), view (view definition + columns), diff_against (structural schema diff between two named connections - returns {data:{diff:{tables_added,tables_removed,tables_changed,views_added,views_removed,views_changed}}}). Optional modifiers: 'target_database' (switch to different DB before introspecting - Postgres/MySQL only), 'schema' (filter to specific schema - Postgres/MySQL only). Returns typed JSON specific to operation (DatabaseList, SchemaList, TableList, ViewList, IndexList, TableDetails, or ViewDetails). Stateless - connection opened, operation executed, connection closed.",
+                "description": "Introspect database schema with granular operations. NEVER dumps entire schema - requires explicit operation. IMPORTANT CONNECTION WORKFLOW: (1) RECOMMENDED: Auto-resolve (omit all connection params) - uses project's default saved connection, (2) COMMON: Named connection (use 'connection' param only) - references saved connection by name, (3) DISCOURAGED: Explicit credentials (engine + host/user/password) - ONLY for one-off scenarios, NOT for regular use. DO NOT pass credentials repeatedly - use saved connections instead. Before using explicit credentials, check if a saved connection exists. Operations (EXACTLY ONE required, mutually exclusive): list_databases (list all DBs), list_schemas (Postgres only), list_tables (table names in schema/DB), list_views (view names), list_indexes (all or filtered by table), table (full details for specific table with optional field filtering), view (view definition + columns), list_sequences / list_macros / list_types / list_constraints / list_attached (DuckDB catalog objects), list_privileges (what the connected user may read/write, incl. row-level security - check before querying unfamiliar tables to avoid permission errors), index_health (unused, duplicate and redundant indexes and bloated tables - check before proposing a new index), sizes (table, index and TOAST bytes plus database size, largest first, paginated), settings (server/session settings with value, unit, source and whether the session may change them, optional glob filter), profile (per-column null fraction, distinct count, min/max and top values for a table), sample (reproducible random sample of a table's rows), diff_against (structural schema diff between two named connections - returns {data:{diff:{tables_added,tables_removed,tables_changed,views_added,views_removed,views_changed}}}). Optional modifiers: 'target_database' (switch to different DB before introspecting - Postgres/MySQL only), 'schema' (filter to specific schema - Postgres/MySQL only), 'max_rows' / 'top_values' (bound the profile operation), 'size' / 'seed' (sample operation), 'limit' / 'offset' (sizes operation), 'timeout_ms' (profile and sample). Returns typed JSON specific to operation (DatabaseList, SchemaList, TableList, ViewList, IndexList, TableDetails, ViewDetails, SequenceList, MacroList, TypeList, ConstraintList, AttachedDatabaseList, Privileges, IndexHealth, Sizes, Settings, TableProfile, or TableSample). Stateless - connection opened, operation executed, connection closed.",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
@@ -320,6 +446,78 @@ fn handle_list_tools() -> Result<Value> {
                             "type": "string",
                             "description": "Operation: Get view definition and columns (name as value). Returns {\"type\": \"view_details\", \"view\": {\"name\": \"...\", \"definition\": \"CREATE VIEW ...\", \"columns\": [...]}}. Mutually exclusive with other operations."
                         },
+                        "list_sequences": {
+                            "type": "boolean",
+                            "description": "Operation: List sequences with start/min/max/increment, cycle flag and last value. Returns {\"type\": \"sequence_list\", \"sequences\": [...]}. DuckDB only. Mutually exclusive with other operations."
+                        },
+                        "list_macros": {
+                            "type": "boolean",
+                            "description": "Operation: List user-defined scalar and table macros with parameters and body. Returns {\"type\": \"macro_list\", \"macros\": [{\"name\": \"...\", \"kind\": \"macro\", \"parameters\": [...], \"definition\": \"...\"}, ...]}. DuckDB only. Mutually exclusive with other operations."
+                        },
+                        "list_types": {
+                            "type": "boolean",
+                            "description": "Operation: List user-defined types (enums, structs, aliases). Returns {\"type\": \"type_list\", \"types\": [{\"name\": \"mood\", \"logical_type\": \"ENUM\", \"definition\": \"ENUM('sad', 'happy')\", \"labels\": [\"sad\", \"happy\"]}, ...]}. DuckDB only. Mutually exclusive with other operations."
+                        },
+                        "list_constraints": {
+                            "type": "string",
+                            "description": "Operation: List CHECK and UNIQUE constraints (all tables or filtered by table name). Pass table name as value to filter, or empty string for all. Returns {\"type\": \"constraint_list\", \"constraints\": [{\"name\": \"...\", \"table\": \"...\", \"constraint_type\": \"CHECK\", \"columns\": [...], \"definition\": \"CHECK(...)\"}, ...]}. Primary/foreign keys are reported by the 'table' operation. DuckDB only. Mutually exclusive with other operations."
+                        },
+                        "list_attached": {
+                            "type": "boolean",
+                            "description": "Operation: List attached databases with file path, storage type and read-only flag. Returns {\"type\": \"attached_database_list\", \"databases\": [{\"name\": \"...\", \"path\": \"...\", \"database_type\": \"duckdb\", \"readonly\": true}, ...]}. DuckDB only. Mutually exclusive with other operations."
+                        },
+                        "list_privileges": {
+                            "type": "string",
+                            "description": "Operation: Report the connected user's effective privileges (all tables or filtered by table name). Pass table name as value to filter, or empty string for all. Returns {\"type\": \"privileges\", \"privileges\": {\"user\": \"app\", \"scope\": \"database\" | \"file\", \"roles\": [...], \"tables\": [{\"table\": \"orders\", \"schema\": \"public\", \"privileges\": [\"SELECT\"], \"column_privileges\": [...], \"rls_enabled\": true, \"rls_forced\": false, \"policies\": [{\"name\": \"...\", \"command\": \"SELECT\", \"permissive\": true, \"roles\": [...], \"applies_to_user\": true, \"using\": \"...\", \"with_check\": null}]}], \"grants\": [{\"privileges\": [\"SELECT\"], \"column_privileges\": [...], \"database\": \"shop\", \"object\": \"*\", \"with_grant_option\": false}], \"file\": {\"path\": \"...\", \"writable\": true}}}. PostgreSQL fills 'tables' (has_table_privilege + RLS policies), MySQL fills 'grants' (parsed SHOW GRANTS), SQLite/DuckDB report file-level access only. Mutually exclusive with other operations."
+                        },
+                        "index_health": {
+                            "type": "string",
+                            "description": "Operation: Diagnose index health (all tables or filtered by table name). Pass table name as value to filter, or empty string for all. Returns {\"type\": \"index_health\", \"health\": {\"findings\": [{\"table\": \"orders\", \"schema\": \"public\", \"index\": \"idx_orders_customer\", \"reason\": \"unused\" | \"duplicate\" | \"redundant\" | \"unselective\" | \"bloat\", \"metric\": \"idx_scan\", \"value\": 0, \"detail\": \"...\"}], \"notes\": [\"...\"]}}. 'index' is null for table-level (bloat) findings. PostgreSQL reads pg_stat_user_indexes / pg_stat_user_tables, MySQL the sys schema unused/redundant index views, SQLite index definitions and sqlite_stat1 (no usage counters). Not supported on DuckDB. Mutually exclusive with other operations."
+                        },
+                        "profile": {
+                            "type": "string",
+                            "description": "Operation: Profile a table's columns before writing a WHERE clause (table name as value). Returns {\"type\": \"table_profile\", \"profile\": {\"table\": \"users\", \"source\": \"statistics\" | \"sample\", \"row_count\": 1000, \"exhaustive\": false, \"columns\": [{\"name\": \"status\", \"null_fraction\": 0.0, \"distinct_count\": 3, \"min\": \"active\", \"max\": \"pending\", \"top_values\": [{\"value\": \"active\", \"count\": 900, \"frequency\": 0.9}, ...]}, ...]}}. PostgreSQL reads pg_stats when it is fresh (values rendered as text); otherwise and on other engines a bounded sample of max_rows rows is read. Mutually exclusive with other operations."
+                        },
+                        "max_rows": {
+                            "type": "number",
+                            "description": "Optional modifier for profile: Maximum rows to sample. Default: 10000."
+                        },
+                        "sizes": {
+                            "type": "boolean",
+                            "description": "Operation: Report storage sizes, largest tables first. Returns {\"type\": \"sizes\", \"sizes\": {\"database_bytes\": 73400320, \"tables\": [{\"name\": \"orders\", \"schema\": \"public\", \"table_bytes\": 40960000, \"index_bytes\": 12288000, \"toast_bytes\": 8192, \"total_bytes\": 53256192, \"row_estimate\": 500000}], \"total_tables\": 12, \"offset\": 0, \"has_more\": false, \"notes\": []}}. PostgreSQL uses pg_total_relation_size and friends, MySQL information_schema.TABLES estimates, SQLite the dbstat virtual table (database size only without it), DuckDB pragma_database_size with per-table row estimates only (byte fields null). Page with 'limit' / 'offset'. Mutually exclusive with other operations."
+                        },
+                        "settings": {
+                            "type": "string",
+                            "description": "Operation: Report server/session settings. Pass a case-insensitive glob on the setting name as value (e.g. \"max_conn*\", \"*timeout*\"), or empty string for all. Returns {\"type\": \"settings\", \"settings\": [{\"name\": \"work_mem\", \"value\": \"4096\", \"unit\": \"kB\", \"source\": \"default\", \"session_changeable\": true}]}. PostgreSQL reads pg_settings, MySQL SHOW SESSION VARIABLES (source and scope from performance_schema when readable, no units), SQLite a fixed list of setting pragmas, DuckDB duckdb_settings(). Fields an engine does not expose are null. Mutually exclusive with other operations."
+                        },
+                        "limit": {
+                            "type": "number",
+                            "description": "Optional modifier for sizes: Maximum tables per page. Default: 50."
+                        },
+                        "offset": {
+                            "type": "number",
+                            "description": "Optional modifier for sizes: Number of tables to skip after sorting by size. Use with has_more to page. Default: 0."
+                        },
+                        "sample": {
+                            "type": "string",
+                            "description": "Operation: Return a reproducible random sample of a table's rows (table name as value) - more representative than SELECT * LIMIT n. Returns {\"type\": \"table_sample\", \"sample\": {\"columns\": [...], \"rows\": [[...], ...], \"execution_ms\": 3}}. The same table contents, size and seed always return the same rows. Uses TABLESAMPLE BERNOULLI ... REPEATABLE on PostgreSQL, USING SAMPLE (reservoir, seed) on DuckDB and a seeded hash ordering on MySQL/SQLite. Mutually exclusive with other operations."
+                        },
+                        "size": {
+                            "type": "number",
+                            "description": "Optional modifier for sample: Number of rows to return. Default: 10."
+                        },
+                        "seed": {
+                            "type": "number",
+                            "description": "Optional modifier for sample: Sampling seed (0 - 4294967295). Change it to draw a different sample. Default: 0."
+                        },
+                        "timeout_ms": {
+                            "type": "number",
+                            "description": "Optional modifier for profile and sample: Timeout for the sampling query in milliseconds."
+                        },
+                        "top_values": {
+                            "type": "number",
+                            "description": "Optional modifier for profile: Number of most frequent values reported per column. Default: 5."
+                        },
                         "target_database": {
                             "type": "string",
                             "description": "Optional modifier: Switch to different database before introspecting. Reconnects with different DB. Postgres/MySQL only (SQLite uses different files). Example: introspect 'production' DB tables while default connection points to 'staging'."
@@ -410,7 +608,48 @@ fn handle_list_tools() -> Result<Value> {
                             "type": "number",
                             "description": "Optional: Maximum serialized byte size of the rows array. Truncates at row boundaries so partial rows are never returned. When triggered, the response includes rows_truncated:true and truncated_by:'bytes' in the meta section. Useful for tables with wide columns (BLOBs, large JSON) where max_rows alone may not bound the response size. Example: 50000 (50 KB)."
                         },
-                        "timeout_ms": {
+                        "max_cell_bytes": {
+                            "type": "number",
+                            "description": "Optional: Longest value kept in a single cell, in bytes. A longer text, JSON or encoded binary value is replaced by a marker object {truncated: true, original_bytes, sha256, prefix} holding the first max_cell_bytes bytes, its full length and a SHA-256 of the full value. Numbers, booleans and NULL are never truncated. Use with max_bytes on tables with large JSON documents or BLOBs so one giant value does not use up the whole budget. Example: 2000."
+                        },
+                        "binary": {
+                            "type": "string",
+                            "enum": ["base64", "hex", "omit", "length-only"],
+                            "description": "Optional: How BLOB / BYTEA / binary values are returned: 'base64' (default), 'hex', 'omit' (null) or 'length-only' (the byte length as a number). Use 'length-only' or 'omit' when you only need to know whether binary data is present."
+                        },
+                        "params": {
+                            "description": "Optional: Bound query parameters. An OBJECT binds by name to :name placeholders in the SQL, on every engine (e.g. sql 'SELECT * FROM orders WHERE customer_id = :cid AND status = :status' with params {\"cid\": 42, \"status\": \"open\"}); every placeholder needs a value and every value a placeholder. An ARRAY binds positionally to the engine's native placeholders ($1, $2 for postgres; ? for mysql/sqlite/duckdb). Prefer named parameters over inlining values into the SQL. Placeholders inside string literals, comments and ::type casts are ignored. To bind an exact native type, pass a typed value object {\"$type\": T, \"value\": V} with T one of bool, int, float, decimal, text, uuid, date, time, timestamp, json, bytes (base64), or T[] for an array, e.g. {\"id\": {\"$type\": \"uuid\", \"value\": \"a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11\"}, \"ids\": {\"$type\": \"int[]\", \"value\": [1, 2, 3]}}. Any other object binds as JSON. Invalid typed values fail with INVALID_INPUT before execution."
+                        },
+                        "paginate": {
+                            "type": "boolean",
+                            "description": "Optional: Issue a 'next_cursor' for keyset pagination when the page is truncated (requires max_rows). next_cursor is returned for single-table SELECTs ordered by unique, non-null columns, or with no ORDER BY on a table with a primary key (the query is then ordered by the primary key). Without paginate or cursor the SQL runs exactly as written. Default: false."
+                        },
+                        "cursor": {
+                            "type": "string",
+                            "description": "Optional: Resume after the last row of a previous page (implies paginate). Pass the 'next_cursor' value from that response together with the SAME sql and params. Keyset pagination: each page costs the same regardless of depth. A cursor replayed against different SQL is rejected with INVALID_INPUT."
+                        },
+                        "diff_against": {
+                            "type": "string",
+                            "description": "Optional: Name of a second saved connection. Runs the SAME sql (and params, bound per engine) on both connections and returns a data diff instead of rows: {key, columns, columns_added, columns_removed, base_rows, target_rows, counts:{added,removed,changed,unchanged}, rows_added, rows_removed, rows_changed:[{key, changes:[{column, from, to}]}], truncated}. 'added' rows exist only on diff_against, 'removed' rows only on the base connection. Requires 'key'. max_rows caps each row list (counts stay complete). Use to check that staging and prod, or a source and its copy, hold the same data."
+                        },
+                        "diff_against_project_path": {
+                            "type": "string",
+                            "description": "Optional modifier for diff_against: project path to look up the diff-against connection in. Defaults to the current project path."
+                        },
+                        "key": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Required with diff_against: column(s) that identify a row in the query result, e.g. [\"id\"] or [\"customer_id\", \"order_no\"]. Must be unique in both results."
+                        },
+                        "summarize": {
+                            "type": "boolean",
+                            "description": "Optional: Return per-column statistics INSTEAD of rows: {row_count, execution_ms, columns:[{name, type, count, null_count, distinct_count, distinct_exact, min, max, examples}]}. count is non-NULL values; distinct_count is exact up to 10,000 values (distinct_exact:false beyond that); examples holds the first 3 distinct values. Every row is read, so max_rows, max_bytes and cursor do not apply. Use to learn the shape of a result (size, value ranges, NULLs) without spending tokens on rows or exposing them. Default: false."
+                        },
+    "deterministic": {
+                            "type": "boolean",
+                            "description": "Optional: Guarantee a stable row order across runs. When the outer query has no ORDER BY, rows are ordered by the table's primary key (with paginate) or by every column (the query runs wrapped in ORDER BY 1, 2, ...). The result's 'ordering' field reports 'engine' (the query's own ORDER BY fixed the order), 'imposed' (Plenum ordered the rows) or 'unordered' (the statement could not be wrapped: non-SELECT, duplicate column names on MySQL, or unorderable types such as postgres json). Use when results are compared across runs, hashed or cached. Default: false."
+                        },
+                                                "timeout_ms": {
                             "type": "number",
                             "description": "Optional: Query execution timeout in milliseconds. Recommended for potentially expensive queries to prevent long-running operations. Example: 5000 (5 seconds). No timeout if omitted."
                         },
@@ -425,7 +664,7 @@ fn handle_list_tools() -> Result<Value> {
                         "explain_format": {
                             "type": "string",
                             "enum": ["native", "structured"],
-                            "description": "Optional: EXPLAIN output format. 'native' (default) returns raw engine rows unchanged. 'structured' requires the SQL to be an EXPLAIN statement and returns data.plan — a normalized, engine-stable JSON tree with node_type, relation, estimated_rows, estimated_cost, and children. Engine-absent fields are explicit null. Non-EXPLAIN queries with 'structured' are rejected with INVALID_INPUT."
+                            "description": "Optional: EXPLAIN output format. 'native' (default) returns raw engine rows unchanged. 'structured' requires the SQL to be an EXPLAIN statement and returns data.plan — a normalized, engine-stable JSON tree with node_type, operation (full_scan, index_scan, index_only_scan, hash_join, nested_loop, merge_join, hash, sort, aggregate, window, limit, filter, projection, materialize, union, distinct, subquery_scan or other), relation, index, filter, join_condition, estimated_rows, estimated_cost, and children. Engine-absent fields are explicit null. With 'EXPLAIN ANALYZE SELECT ...' (Postgres, MySQL 8, DuckDB) the query runs within timeout_ms and each node also carries actual_rows, actual_time_ms, loops and (Postgres) buffers. Non-EXPLAIN queries with 'structured' are rejected with INVALID_INPUT."
                         }
                     },
                     "required": ["sql"]
@@ -433,7 +672,7 @@ fn handle_list_tools() -> Result<Value> {
             },
             {
                 "name": "connect",
-                "description": "Test a database connection and return server metadata (version, database name, user). Opens a connection, verifies liveness, returns ConnectionInfo, then disconnects immediately. Stateless and read-only — no config is saved or mutated. Use to health-check a saved connection before running queries, or to verify credentials after setup. IMPORTANT CONNECTION WORKFLOW: (1) RECOMMENDED: Auto-resolve (omit all connection params) — uses project's default saved connection, (2) COMMON: Named connection (use 'connection' param only) — references saved connection by name, (3) DISCOURAGED: Explicit credentials (engine + host/user/password) — ONLY for one-off checks. Possible error codes: CONNECTION_FAILED (unreachable host, bad credentials, missing file), INVALID_INPUT (missing required params), CONFIG_ERROR (no saved connection found).",
+                "description": "Test a database connection and return server metadata (version, database name, user). Opens a connection, verifies liveness, returns ConnectionInfo, then disconnects immediately. ConnectionInfo also carries 'warnings' when the credential is not least-privilege (e.g. {\"code\": \"WRITE_PRIVILEGE\", \"severity\": \"warning\", \"message\": \"...\", \"objects\": [\"public.orders\"]}; codes: SUPERUSER, ELEVATED_ROLE, WRITE_PRIVILEGE, DDL_PRIVILEGE, TABLE_OWNERSHIP, GRANT_OPTION, READ_REPLICA (info), AUDIT_INCOMPLETE (info)). Stateless and read-only — no config is saved or mutated. Use to health-check a saved connection before running queries, or to verify credentials after setup. IMPORTANT CONNECTION WORKFLOW: (1) RECOMMENDED: Auto-resolve (omit all connection params) — uses project's default saved connection, (2) COMMON: Named connection (use 'connection' param only) — references saved connection by name, (3) DISCOURAGED: Explicit credentials (engine + host/user/password) — ONLY for one-off checks. Possible error codes: CONNECTION_FAILED (unreachable host, bad credentials, missing file), INVALID_INPUT (missing required params), CONFIG_ERROR (no saved connection found).",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
@@ -472,15 +711,182 @@ fn handle_list_tools() -> Result<Value> {
                         }
                     }
                 }
+            },
+            {
+                "name": "inspect",
+                "description": "Inspect live server state (read-only). Use when the application stalls to see what is running and who is blocking whom. Operations (EXACTLY ONE required): activity (sessions and lock waits), top_queries (most expensive normalized statements). activity returns {\"type\": \"activity\", \"activity\": {\"sessions\": [{\"pid\": 4242, \"user\": \"app\", \"database\": \"shop\", \"application\": \"psql\", \"state\": \"active\", \"query\": \"UPDATE ...\", \"duration_ms\": 1500, \"wait_event\": \"Lock:transactionid\"}], \"lock_waits\": [{\"waiting_pid\": 4242, \"blocking_pid\": 4100, \"lock_type\": \"transactionid\", \"mode\": \"ShareLock\", \"object\": null}]}}. PostgreSQL reads pg_stat_activity and pg_locks; MySQL reads performance_schema.threads and data_lock_waits. top_queries returns {\"type\": \"top_queries\", \"top_queries\": {\"order_by\": \"total_time\", \"statements\": [{\"digest\": \"-4535282926430436745\", \"query\": \"SELECT * FROM orders WHERE customer_id = $1\", \"database\": \"shop\", \"calls\": 1200, \"total_time_ms\": 5400.2, \"mean_time_ms\": 4.5, \"rows\": 36000}], \"notes\": [...]}} from pg_stat_statements (PostgreSQL) or performance_schema.events_statements_summary_by_digest (MySQL); when statistics are unavailable, statements is empty and notes explain why. SQLite/DuckDB have no server sessions and are rejected. Set redact_queries to mask literals in query text. Connection resolution works as for 'introspect' (prefer saved connections). Stateless - connection opened, operation executed, connection closed.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "dsn": {
+                            "type": "string",
+                            "description": "One-off connection DSN/URL. Mutually exclusive with 'connection' and 'engine'. Accepted schemes: postgres://, postgresql://, mysql://. Credentials are redacted from any error output. Config is never written."
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "RECOMMENDED: Name of saved connection to inspect. If omitted along with 'engine', auto-resolves project's default connection (BEST PRACTICE)."
+                        },
+                        "engine": {
+                            "type": "string",
+                            "enum": ["postgres", "mysql", "sqlite", "duckdb"],
+                            "description": "DISCOURAGED: Database engine type for explicit one-off connections. Only use if no saved connection exists."
+                        },
+                        "host": {
+                            "type": "string",
+                            "description": "DISCOURAGED: Database host (postgres/mysql). Only for explicit one-off connections."
+                        },
+                        "port": {
+                            "type": "number",
+                            "description": "DISCOURAGED: Database port (postgres/mysql). Defaults: postgres=5432, mysql=3306."
+                        },
+                        "user": {
+                            "type": "string",
+                            "description": "DISCOURAGED: Database username (postgres/mysql). Only for explicit one-off connections."
+                        },
+                        "password": {
+                            "type": "string",
+                            "description": "DISCOURAGED: Database password (postgres/mysql). Only for explicit one-off connections."
+                        },
+                        "database": {
+                            "type": "string",
+                            "description": "DISCOURAGED: Database name (postgres/mysql). Only for explicit one-off connections."
+                        },
+                        "activity": {
+                            "type": "boolean",
+                            "description": "Operation: Report sessions (pid, user, database, application, state, query, duration_ms, wait_event) and lock-wait edges (waiting_pid blocked by blocking_pid). Other users' query text may be hidden without monitoring privileges (pg_read_all_stats on PostgreSQL, performance_schema access on MySQL)."
+                        },
+                        "top_queries": {
+                            "type": "boolean",
+                            "description": "Operation: Report the most expensive normalized statements (digest, query, database, calls, total_time_ms, mean_time_ms, rows). Use to find real hot spots before proposing indexes or rewrites."
+                        },
+                        "order_by": {
+                            "type": "string",
+                            "enum": ["total_time", "mean_time", "calls", "rows"],
+                            "description": "Optional (top_queries only): Metric to rank statements by, descending. Default: total_time."
+                        },
+                        "limit": {
+                            "type": "number",
+                            "description": "Optional (top_queries only): Maximum number of statements to return. Default: 10."
+                        },
+                        "redact_queries": {
+                            "type": "boolean",
+                            "description": "Optional (activity only): Replace string and numeric literals in session query text with '?'. Default: false. top_queries text is already normalized."
+                        }
+                    }
+                }
+            },
+            {
+                "name": "advise",
+                "description": "Suggest indexes for a SELECT (read-only; NOTHING is executed or created). Plans the statement with the structured EXPLAIN and reads the scanned tables' indexes and row estimates, then returns {\"suggestions\": [{\"table\": \"orders\", \"index_name\": \"idx_orders_status_placed_at\", \"columns\": [\"status\", \"placed_at\"], \"reason\": \"sort\", \"statement\": \"CREATE INDEX CONCURRENTLY ...\", \"rationale\": \"...\", \"table_rows\": 250000, \"estimated_rows\": 1200}], \"plan\": {...}}. Reasons: full_scan_filter (a full scan of a large table whose filter compares columns with =, IN, ranges or BETWEEN) and sort (a single-table ORDER BY of plain columns an index could return in order). Suggestions an existing index already serves are left out; an empty list means nothing qualified. statement is vendor-specific DDL (CONCURRENTLY on PostgreSQL, ALGORITHM=INPLACE, LOCK=NONE on MySQL): present it to the user to review and run - never try to run it through Plenum. DuckDB only gets equality-filter advice. Connection resolution works as for 'query' (prefer saved connections).",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "sql": {
+                            "type": "string",
+                            "description": "SELECT statement to advise on, without EXPLAIN. REQUIRED. It is planned, never run."
+                        },
+                        "dsn": {
+                            "type": "string",
+                            "description": "One-off connection DSN/URL. Mutually exclusive with 'connection' and 'engine'. Accepted schemes: postgres://, postgresql://, mysql://, sqlite:. Credentials are redacted from any error output. Config is never written."
+                        },
+                        "connection": {
+                            "type": "string",
+                            "description": "RECOMMENDED: Name of saved connection to use. If omitted along with 'engine', auto-resolves project's default connection (BEST PRACTICE)."
+                        },
+                        "engine": {
+                            "type": "string",
+                            "enum": ["postgres", "mysql", "sqlite", "duckdb"],
+                            "description": "DISCOURAGED: Database engine type for explicit one-off connections. Only use if no saved connection exists."
+                        },
+                        "host": {
+                            "type": "string",
+                            "description": "DISCOURAGED: Database host (postgres/mysql). Only for explicit one-off connections."
+                        },
+                        "port": {
+                            "type": "number",
+                            "description": "DISCOURAGED: Database port (postgres/mysql). Defaults: postgres=5432, mysql=3306."
+                        },
+                        "user": {
+                            "type": "string",
+                            "description": "DISCOURAGED: Database username (postgres/mysql). Only for explicit one-off connections."
+                        },
+                        "password": {
+                            "type": "string",
+                            "description": "DISCOURAGED: Database password (postgres/mysql). Only for explicit one-off connections."
+                        },
+                        "database": {
+                            "type": "string",
+                            "description": "DISCOURAGED: Database name (postgres/mysql). Only for explicit one-off connections."
+                        },
+                        "file": {
+                            "type": "string",
+                            "description": "DISCOURAGED: File path to SQLite/DuckDB database file. Only for one-off sqlite/duckdb explicit connections."
+                        },
+                        "params": {
+                            "description": "Optional: Bound query parameters, as for the 'query' tool (an object for :name placeholders, an array for native positional placeholders). Planners may pick different plans for different values."
+                        },
+                        "timeout_ms": {
+                            "type": "number",
+                            "description": "Optional: Planning timeout in milliseconds."
+                        },
+                        "min_rows": {
+                            "type": "number",
+                            "description": "Optional: Smallest table, by estimated row count, worth an index suggestion. Tables without a row estimate (e.g. SQLite before ANALYZE) are always considered. Default: 10000."
+                        }
+                    },
+                    "required": ["sql"]
+                }
             }
         ]
-    }))
+    });
+    if let Some(list) = tools["tools"].as_array_mut() {
+        list.extend(saved_query_tools());
+    }
+    Ok(tools)
+}
+
+/// Tool definitions for the project's saved queries.
+///
+/// A file that fails to load is skipped with a warning on stderr, so one malformed
+/// file hides neither the built-in tools nor the other saved queries.
+fn saved_query_tools() -> Vec<Value> {
+    let Ok(project_path) = crate::config::get_current_project_path() else {
+        return Vec::new();
+    };
+    let (queries, skipped) =
+        crate::saved::load_saved_queries_skipping_errors(Path::new(&project_path));
+    for error in &skipped {
+        eprintln!("Warning: Skipping saved query: {}", error.message());
+    }
+    queries
+        .iter()
+        .map(|query| {
+            let purpose = query.description.as_deref().unwrap_or("Vetted project query");
+            let connection = query.connection.as_deref().map_or_else(
+                || "the project's default connection".to_string(),
+                |c| format!("connection '{c}'"),
+            );
+            serde_json::json!({
+                "name": query.tool_name(),
+                "description": format!(
+                    "{purpose}. Saved query '{}' ({}), READ-ONLY, runs on {connection}. \
+                     Pass its parameters as arguments; they are typed and validated before \
+                     execution. Returns the same result as the query tool: \
+                     {{columns, rows, rows_truncated, ...}}. Prefer this over writing \
+                     equivalent SQL.",
+                    query.name,
+                    query.path.display()
+                ),
+                "inputSchema": query.input_schema(),
+            })
+        })
+        .collect()
 }
 
 /// Handle tools/call request
 ///
 /// Routes the tool call to the appropriate tool implementation.
-async fn handle_call_tool(params: Option<Value>) -> Result<Value> {
+async fn handle_call_tool(params: Option<Value>, control: &QueryControl) -> Result<Value> {
     let params = params.ok_or_else(|| anyhow!("Missing params"))?;
     let name = params["name"].as_str().ok_or_else(|| anyhow!("Missing tool name"))?;
     let arguments = &params["arguments"];
@@ -488,8 +894,13 @@ async fn handle_call_tool(params: Option<Value>) -> Result<Value> {
     match name {
         "connect" => tool_connect(arguments).await,
         "introspect" => tool_introspect(arguments).await,
-        "query" => tool_query(arguments).await,
-        _ => Err(anyhow!("Unknown tool: {name}")),
+        "query" => tool_query(arguments, control).await,
+        "inspect" => tool_inspect(arguments).await,
+        "advise" => tool_advise(arguments).await,
+        _ => match name.strip_prefix(crate::saved::SAVED_TOOL_PREFIX) {
+            Some(saved) => tool_saved_query(saved, arguments, control).await,
+            None => Err(anyhow!("Unknown tool: {name}")),
+        },
     }
 }
 
@@ -504,7 +915,7 @@ async fn handle_call_tool(params: Option<Value>) -> Result<Value> {
 async fn tool_connect(args: &Value) -> Result<Value> {
     let (config, _is_readonly) = resolve_connection_from_args(args)?;
 
-    let connection_info = match config.engine {
+    let mut connection_info = match config.engine {
         #[cfg(feature = "sqlite")]
         DatabaseType::SQLite => SqliteEngine::validate_connection(&config)
             .await
@@ -542,6 +953,13 @@ async fn tool_connect(args: &Value) -> Result<Value> {
         }
     };
 
+    connection_info.warnings = audit_privileges(&config).await.unwrap_or_else(|e| {
+        vec![crate::PrivilegeWarning::info(
+            "AUDIT_INCOMPLETE",
+            format!("Least-privilege checks could not run: {e}"),
+        )]
+    });
+
     CallToolResult::success(connection_info)
 }
 
@@ -617,10 +1035,160 @@ async fn tool_introspect(args: &Value) -> Result<Value> {
     CallToolResult::success(result)
 }
 
+/// MCP Tool: inspect
+///
+/// Reports live server state (sessions, lock waits).
+async fn tool_inspect(args: &Value) -> Result<Value> {
+    use crate::engine::InspectResult;
+
+    let (config, _is_readonly) = resolve_connection_from_args(args)?;
+    let operation = parse_inspect_operation(args)?;
+
+    let mut result = match config.engine {
+        #[cfg(feature = "sqlite")]
+        DatabaseType::SQLite => SqliteEngine::inspect(&config, &operation)
+            .await
+            .map_err(|e| anyhow!("SQLite inspection failed: {e}"))?,
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseType::SQLite => {
+            return Err(anyhow!("SQLite engine not enabled. Build with --features sqlite"));
+        }
+
+        #[cfg(feature = "postgres")]
+        DatabaseType::Postgres => PostgresEngine::inspect(&config, &operation)
+            .await
+            .map_err(|e| anyhow!("PostgreSQL inspection failed: {e}"))?,
+        #[cfg(not(feature = "postgres"))]
+        DatabaseType::Postgres => {
+            return Err(anyhow!("PostgreSQL engine not enabled. Build with --features postgres"));
+        }
+
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => MySqlEngine::inspect(&config, &operation)
+            .await
+            .map_err(|e| anyhow!("MySQL inspection failed: {e}"))?,
+        #[cfg(not(feature = "mysql"))]
+        DatabaseType::MySQL => {
+            return Err(anyhow!("MySQL engine not enabled. Build with --features mysql"));
+        }
+
+        #[cfg(feature = "duckdb")]
+        DatabaseType::DuckDB => DuckDbEngine::inspect(&config, &operation)
+            .await
+            .map_err(|e| anyhow!("DuckDB inspection failed: {e}"))?,
+        #[cfg(not(feature = "duckdb"))]
+        DatabaseType::DuckDB => {
+            return Err(anyhow!("DuckDB engine not enabled. Build with --features duckdb"));
+        }
+    };
+
+    if args.get("redact_queries").and_then(Value::as_bool).unwrap_or(false) {
+        if let InspectResult::Activity { activity } = &mut result {
+            activity.redact_queries(config.engine);
+        }
+    }
+
+    CallToolResult::success(result)
+}
+
+/// MCP Tool: advise
+///
+/// Plans a SELECT and suggests indexes for it; the suggested DDL is never executed.
+async fn tool_advise(args: &Value) -> Result<Value> {
+    use crate::advise::{advise, DEFAULT_MIN_ROWS};
+
+    let sql = args["sql"].as_str().ok_or_else(|| anyhow!("Missing required field: sql"))?;
+    let (config, _is_readonly) = resolve_connection_from_args(args)?;
+    let timeout_ms = args.get("timeout_ms").and_then(Value::as_u64);
+    let min_rows = args.get("min_rows").and_then(Value::as_i64).unwrap_or(DEFAULT_MIN_ROWS);
+    let (sql, params) = QueryParams::from_json(args.get("params"))
+        .and_then(|p| p.bind(sql, config.engine))
+        .map_err(|e| anyhow!("{e}"))?;
+
+    let advice = match config.engine {
+        #[cfg(feature = "sqlite")]
+        DatabaseType::SQLite => {
+            advise::<SqliteEngine>(&config, &sql, &params, timeout_ms, min_rows).await
+        }
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseType::SQLite => {
+            return Err(anyhow!("SQLite engine not enabled. Build with --features sqlite"));
+        }
+
+        #[cfg(feature = "postgres")]
+        DatabaseType::Postgres => {
+            advise::<PostgresEngine>(&config, &sql, &params, timeout_ms, min_rows).await
+        }
+        #[cfg(not(feature = "postgres"))]
+        DatabaseType::Postgres => {
+            return Err(anyhow!("PostgreSQL engine not enabled. Build with --features postgres"));
+        }
+
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => {
+            advise::<MySqlEngine>(&config, &sql, &params, timeout_ms, min_rows).await
+        }
+        #[cfg(not(feature = "mysql"))]
+        DatabaseType::MySQL => {
+            return Err(anyhow!("MySQL engine not enabled. Build with --features mysql"));
+        }
+
+        #[cfg(feature = "duckdb")]
+        DatabaseType::DuckDB => {
+            advise::<DuckDbEngine>(&config, &sql, &params, timeout_ms, min_rows).await
+        }
+        #[cfg(not(feature = "duckdb"))]
+        DatabaseType::DuckDB => {
+            return Err(anyhow!("DuckDB engine not enabled. Build with --features duckdb"));
+        }
+    }
+    .map_err(|e| anyhow!("Index advice failed: {e}"))?;
+
+    CallToolResult::success(advice)
+}
+
+/// Parse inspect operation from MCP arguments.
+fn parse_inspect_operation(args: &Value) -> Result<crate::engine::InspectOperation> {
+    use crate::engine::{InspectOperation, QueryRanking, DEFAULT_TOP_QUERIES_LIMIT};
+
+    let flag = |key: &str| args.get(key).and_then(Value::as_bool).unwrap_or(false);
+    let mut operations = Vec::new();
+    if flag("activity") {
+        operations.push(InspectOperation::Activity);
+    }
+    if flag("top_queries") {
+        let order_by = match args.get("order_by").and_then(Value::as_str) {
+            Some(name) => QueryRanking::parse(name).ok_or_else(|| {
+                anyhow!("Invalid order_by '{name}': expected total_time, mean_time, calls or rows")
+            })?,
+            None => QueryRanking::TotalTime,
+        };
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .map_or(DEFAULT_TOP_QUERIES_LIMIT, |n| usize::try_from(n).unwrap_or(usize::MAX));
+        operations.push(InspectOperation::TopQueries { order_by, limit });
+    }
+
+    match operations.len() {
+        0 => Err(anyhow!(
+            "No inspect operation specified. Must provide one of: activity, top_queries"
+        )),
+        1 => Ok(operations.remove(0)),
+        _ => Err(anyhow!(
+            "Multiple inspect operations specified. Provide exactly one of: activity, top_queries"
+        )),
+    }
+}
+
 /// Parse introspect operation from MCP arguments.
 /// Called only on the standard path; `diff_against` is handled before this in `tool_introspect`.
 fn parse_introspect_operation(args: &Value) -> Result<crate::engine::IntrospectOperation> {
-    use crate::engine::{IntrospectOperation, TableFields};
+    use crate::engine::{
+        IntrospectOperation, ProfileOptions, SampleOptions, SizeOptions, TableFields,
+        DEFAULT_PROFILE_SAMPLE_ROWS, DEFAULT_PROFILE_TOP_N, DEFAULT_SAMPLE_SIZE,
+        DEFAULT_SIZES_LIMIT,
+    };
 
     // Check which operation is requested (mutually exclusive)
     let is_list_databases = args.get("list_databases").and_then(Value::as_bool).unwrap_or(false);
@@ -630,6 +1198,17 @@ fn parse_introspect_operation(args: &Value) -> Result<crate::engine::IntrospectO
     let is_list_indexes = args.get("list_indexes").is_some();
     let table_name = args.get("table").and_then(|v| v.as_str());
     let view_name = args.get("view").and_then(|v| v.as_str());
+    let is_list_sequences = args.get("list_sequences").and_then(Value::as_bool).unwrap_or(false);
+    let is_list_macros = args.get("list_macros").and_then(Value::as_bool).unwrap_or(false);
+    let is_list_types = args.get("list_types").and_then(Value::as_bool).unwrap_or(false);
+    let is_list_constraints = args.get("list_constraints").is_some();
+    let is_list_attached = args.get("list_attached").and_then(Value::as_bool).unwrap_or(false);
+    let is_list_privileges = args.get("list_privileges").is_some();
+    let is_index_health = args.get("index_health").is_some();
+    let is_sizes = args.get("sizes").and_then(Value::as_bool).unwrap_or(false);
+    let is_settings = args.get("settings").is_some();
+    let profile_table = args.get("profile").and_then(|v| v.as_str());
+    let sample_table = args.get("sample").and_then(|v| v.as_str());
 
     // Count how many operations were specified
     let op_count = [
@@ -640,6 +1219,17 @@ fn parse_introspect_operation(args: &Value) -> Result<crate::engine::IntrospectO
         is_list_indexes,
         table_name.is_some(),
         view_name.is_some(),
+        is_list_sequences,
+        is_list_macros,
+        is_list_types,
+        is_list_constraints,
+        is_list_attached,
+        is_list_privileges,
+        is_index_health,
+        is_sizes,
+        is_settings,
+        profile_table.is_some(),
+        sample_table.is_some(),
     ]
     .iter()
     .filter(|&&x| x)
@@ -649,7 +1239,8 @@ fn parse_introspect_operation(args: &Value) -> Result<crate::engine::IntrospectO
         return Err(anyhow!(
             "No introspect operation specified. Must provide one of: \
              list_databases, list_schemas, list_tables, list_views, list_indexes, table, view, \
-             or diff_against"
+             list_sequences, list_macros, list_types, list_constraints, list_attached, \
+             list_privileges, index_health, sizes, settings, profile, sample, or diff_against"
         ));
     }
 
@@ -697,13 +1288,108 @@ fn parse_introspect_operation(args: &Value) -> Result<crate::engine::IntrospectO
         return Ok(IntrospectOperation::ViewDetails { name: name.to_string() });
     }
 
+    if is_list_sequences {
+        return Ok(IntrospectOperation::ListSequences);
+    }
+
+    if is_list_macros {
+        return Ok(IntrospectOperation::ListMacros);
+    }
+
+    if is_list_types {
+        return Ok(IntrospectOperation::ListTypes);
+    }
+
+    if is_list_constraints {
+        let table_filter = args
+            .get("list_constraints")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from);
+        return Ok(IntrospectOperation::ListConstraints { table: table_filter });
+    }
+
+    if is_list_attached {
+        return Ok(IntrospectOperation::ListAttachedDatabases);
+    }
+
+    if is_list_privileges {
+        let table_filter = args
+            .get("list_privileges")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from);
+        return Ok(IntrospectOperation::ListPrivileges { table: table_filter });
+    }
+
+    if is_index_health {
+        let table_filter = args
+            .get("index_health")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from);
+        return Ok(IntrospectOperation::IndexHealth { table: table_filter });
+    }
+
+    if is_sizes {
+        let options = SizeOptions {
+            limit: args
+                .get("limit")
+                .and_then(Value::as_u64)
+                .map_or(DEFAULT_SIZES_LIMIT, |n| n as usize),
+            offset: args.get("offset").and_then(Value::as_u64).map_or(0, |n| n as usize),
+        };
+        return Ok(IntrospectOperation::Sizes { options });
+    }
+
+    if is_settings {
+        let pattern = args
+            .get("settings")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from);
+        return Ok(IntrospectOperation::Settings { pattern });
+    }
+
+    if let Some(name) = profile_table {
+        let options = ProfileOptions {
+            sample_rows: args
+                .get("max_rows")
+                .and_then(Value::as_u64)
+                .map_or(DEFAULT_PROFILE_SAMPLE_ROWS, |n| n as usize),
+            top_n: args
+                .get("top_values")
+                .and_then(Value::as_u64)
+                .map_or(DEFAULT_PROFILE_TOP_N, |n| n as usize),
+            timeout_ms: args.get("timeout_ms").and_then(Value::as_u64),
+        };
+        return Ok(IntrospectOperation::ProfileTable { name: name.to_string(), options });
+    }
+
+    if let Some(name) = sample_table {
+        let seed = match args.get("seed").and_then(Value::as_u64) {
+            Some(seed) => u32::try_from(seed)
+                .map_err(|_| anyhow!("seed must be between 0 and {}", u32::MAX))?,
+            None => 0,
+        };
+        let options = SampleOptions {
+            size: args
+                .get("size")
+                .and_then(Value::as_u64)
+                .map_or(DEFAULT_SAMPLE_SIZE, |n| n as usize),
+            seed,
+            timeout_ms: args.get("timeout_ms").and_then(Value::as_u64),
+        };
+        return Ok(IntrospectOperation::SampleTable { name: name.to_string(), options });
+    }
+
     Err(anyhow!("Failed to parse introspect operation"))
 }
 
 /// MCP Tool: query
 ///
 /// Executes a READ-ONLY SQL query.
-async fn tool_query(args: &Value) -> Result<Value> {
+async fn tool_query(args: &Value, control: &QueryControl) -> Result<Value> {
     // Extract SQL
     let sql = args["sql"].as_str().ok_or_else(|| anyhow!("Missing required field: sql"))?;
 
@@ -731,9 +1417,60 @@ async fn tool_query(args: &Value) -> Result<Value> {
         }
     };
 
+    let max_cell_bytes =
+        args.get("max_cell_bytes").and_then(serde_json::Value::as_u64).map(|n| n as usize);
+    let binary_format = match args.get("binary").and_then(serde_json::Value::as_str) {
+        None => None,
+        Some(name) => Some(crate::engine::BinaryFormat::from_name(name).ok_or_else(|| {
+            anyhow!("Invalid binary '{name}'. Valid values: base64, hex, omit, length-only")
+        })?),
+    };
+
     // Build capabilities (read-only only; max_bytes is post-processed below)
-    let capabilities =
-        Capabilities { max_rows, max_bytes: None, timeout_ms, offset: None, explain_format };
+    let capabilities = Capabilities {
+        max_rows,
+        max_bytes: None,
+        timeout_ms,
+        offset: None,
+        explain_format,
+        max_cell_bytes,
+        binary_format,
+        control: Some(control.clone()),
+    };
+
+    // Data diff: the same SQL on a second connection, params bound per engine
+    if let Some(target_name) = args.get("diff_against").and_then(|v| v.as_str()) {
+        let target_proj = args.get("diff_against_project_path").and_then(|v| v.as_str());
+        let (target_config, _) = crate::resolve_connection(target_proj, Some(target_name))
+            .map_err(|e| {
+                anyhow!("Failed to resolve diff-against connection '{target_name}': {e}")
+            })?;
+        let key: Vec<String> = match args.get("key") {
+            Some(Value::String(column)) => vec![column.clone()],
+            Some(Value::Array(columns)) => {
+                columns.iter().filter_map(Value::as_str).map(String::from).collect()
+            }
+            _ => Vec::new(),
+        };
+        let params = QueryParams::from_json(args.get("params")).map_err(|e| anyhow!("{e}"))?;
+        let diff = crate::diff::compute_data_diff(
+            &config,
+            &target_config,
+            sql,
+            &params,
+            &key,
+            &capabilities,
+        )
+        .await
+        .map_err(|e| anyhow!("Data diff failed: {e}"))?;
+        return CallToolResult::success(diff);
+    }
+
+    // Rewrite :name placeholders to the engine's native syntax
+    let (sql, params) = QueryParams::from_json(args.get("params"))
+        .and_then(|p| p.bind(sql, config.engine))
+        .map_err(|e| anyhow!("{e}"))?;
+    let sql = sql.as_str();
 
     // Validate query is read-only (pre-execution check)
     crate::validate_query(sql, &capabilities, config.engine).map_err(|e| anyhow!("{e}"))?;
@@ -745,12 +1482,30 @@ async fn tool_query(args: &Value) -> Result<Value> {
         );
     }
 
-    // Execute query (opens and closes connection)
-    let mut query_result = execute_query(&config, sql, &capabilities).await?;
+    // Summarize: per-column statistics over every row, no rows returned
+    if args.get("summarize").and_then(serde_json::Value::as_bool).unwrap_or(false) {
+        let summary = summarize_query(&config, sql, &params, timeout_ms).await?;
+        return CallToolResult::success(summary);
+    }
 
-    // Apply byte budget post-engine (row-boundary truncation)
+    // Execute query (opens and closes connection)
+    let cursor = args.get("cursor").and_then(serde_json::Value::as_str);
+    let paginate = args.get("paginate").and_then(serde_json::Value::as_bool).unwrap_or(false);
+    let deterministic =
+        args.get("deterministic").and_then(serde_json::Value::as_bool).unwrap_or(false);
+    let (mut query_result, keyset) =
+        execute_query(&config, sql, &params, &capabilities, cursor, paginate, deterministic)
+            .await?;
+
+    // Apply byte budget post-engine (row-boundary truncation), measuring rows as
+    // they are returned once max_cell_bytes has marked them
     if let Some(max_b) = max_bytes {
-        crate::engine::apply_byte_budget(&mut query_result, max_b);
+        crate::engine::apply_byte_budget_with_cell_limit(&mut query_result, max_b, max_cell_bytes);
+    }
+    // The cursor encodes the real key values, so cells are marked only afterwards
+    query_result.next_cursor = keyset.and_then(|k| k.next_cursor(&query_result));
+    if let Some(max) = max_cell_bytes {
+        crate::engine::limit_cells(&mut query_result.rows, max);
     }
 
     // Return time-only result if requested (for benchmarking)
@@ -765,6 +1520,44 @@ async fn tool_query(args: &Value) -> Result<Value> {
     }
 }
 
+/// MCP Tool: `saved_<name>`
+///
+/// Runs a saved query through the query tool: the header supplies the SQL, the
+/// connection and default caps, and the arguments supply the declared parameters.
+async fn tool_saved_query(name: &str, args: &Value, control: &QueryControl) -> Result<Value> {
+    let project_path = crate::config::get_current_project_path().map_err(|e| anyhow!("{e}"))?;
+    let saved = crate::saved::find_saved_query(Path::new(&project_path), name)
+        .map_err(|e| anyhow!("{e}"))?;
+
+    let mut query_args = serde_json::Map::new();
+    let mut values = std::collections::BTreeMap::new();
+    for (key, value) in args.as_object().into_iter().flatten() {
+        if crate::saved::RESERVED_ARGS.contains(&key.as_str()) {
+            query_args.insert(key.clone(), value.clone());
+        } else {
+            values.insert(key.clone(), value.clone());
+        }
+    }
+    let QueryParams::Named(params) = saved.bind_values(values).map_err(|e| anyhow!("{e}"))? else {
+        return Err(anyhow!("Saved query '{name}' must bind parameters by name"));
+    };
+
+    query_args.insert("sql".into(), Value::String(saved.sql.clone()));
+    query_args.insert("params".into(), Value::Object(params.into_iter().collect()));
+    let defaults = [
+        ("connection", saved.connection.clone().map(Value::from)),
+        ("max_rows", saved.max_rows.map(Value::from)),
+        ("max_bytes", saved.max_bytes.map(Value::from)),
+        ("timeout_ms", saved.timeout_ms.map(Value::from)),
+    ];
+    for (key, default) in defaults {
+        if let Some(default) = default {
+            query_args.entry(key).or_insert(default);
+        }
+    }
+    tool_query(&Value::Object(query_args), control).await
+}
+
 // ============================================================================
 // Helper Functions (Stateless)
 // ============================================================================
@@ -952,47 +1745,167 @@ async fn validate_connection(config: &ConnectionConfig) -> Result<crate::Connect
     }
 }
 
-/// Execute query
+/// Audit the configured credentials against the least-privilege model
+///
+/// Findings are attached to the `connect` result; an engine that is not compiled in
+/// has already failed `validate_connection`, so it has nothing to audit.
+async fn audit_privileges(config: &ConnectionConfig) -> Result<Vec<crate::PrivilegeWarning>> {
+    let warnings = match config.engine {
+        #[cfg(feature = "sqlite")]
+        DatabaseType::SQLite => SqliteEngine::audit_privileges(config).await?,
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseType::SQLite => Vec::new(),
+
+        #[cfg(feature = "postgres")]
+        DatabaseType::Postgres => PostgresEngine::audit_privileges(config).await?,
+        #[cfg(not(feature = "postgres"))]
+        DatabaseType::Postgres => Vec::new(),
+
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => MySqlEngine::audit_privileges(config).await?,
+        #[cfg(not(feature = "mysql"))]
+        DatabaseType::MySQL => Vec::new(),
+
+        #[cfg(feature = "duckdb")]
+        DatabaseType::DuckDB => DuckDbEngine::audit_privileges(config).await?,
+        #[cfg(not(feature = "duckdb"))]
+        DatabaseType::DuckDB => Vec::new(),
+    };
+    Ok(warnings)
+}
+
+/// Summarize every row of a query with bound `params` (see `summarize_query`)
+async fn summarize_query(
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[Value],
+    timeout_ms: Option<u64>,
+) -> Result<crate::ResultSummary> {
+    match config.engine {
+        #[cfg(feature = "sqlite")]
+        DatabaseType::SQLite => {
+            crate::engine::summarize_query::<SqliteEngine>(config, sql, params, timeout_ms)
+                .await
+                .map_err(|e| anyhow!("SQLite query failed: {e}"))
+        }
+        #[cfg(not(feature = "sqlite"))]
+        DatabaseType::SQLite => {
+            Err(anyhow!("SQLite engine not enabled. Build with --features sqlite"))
+        }
+
+        #[cfg(feature = "postgres")]
+        DatabaseType::Postgres => {
+            crate::engine::summarize_query::<PostgresEngine>(config, sql, params, timeout_ms)
+                .await
+                .map_err(|e| anyhow!("PostgreSQL query failed: {e}"))
+        }
+        #[cfg(not(feature = "postgres"))]
+        DatabaseType::Postgres => {
+            Err(anyhow!("PostgreSQL engine not enabled. Build with --features postgres"))
+        }
+
+        #[cfg(feature = "mysql")]
+        DatabaseType::MySQL => {
+            crate::engine::summarize_query::<MySqlEngine>(config, sql, params, timeout_ms)
+                .await
+                .map_err(|e| anyhow!("MySQL query failed: {e}"))
+        }
+        #[cfg(not(feature = "mysql"))]
+        DatabaseType::MySQL => {
+            Err(anyhow!("MySQL engine not enabled. Build with --features mysql"))
+        }
+
+        #[cfg(feature = "duckdb")]
+        DatabaseType::DuckDB => {
+            crate::engine::summarize_query::<DuckDbEngine>(config, sql, params, timeout_ms)
+                .await
+                .map_err(|e| anyhow!("DuckDB query failed: {e}"))
+        }
+        #[cfg(not(feature = "duckdb"))]
+        DatabaseType::DuckDB => {
+            Err(anyhow!("DuckDB engine not enabled. Build with --features duckdb"))
+        }
+    }
+}
+
+/// Execute query with bound `params`, resuming after `cursor` when given and
+/// paginating by keyset when `paginate` is set
 ///
 /// Opens a connection, executes query, and immediately closes it.
 /// This function is stateless - no connection persists after it returns.
 async fn execute_query(
     config: &ConnectionConfig,
     sql: &str,
+    params: &[Value],
     capabilities: &Capabilities,
-) -> Result<crate::QueryResult> {
+    cursor: Option<&str>,
+    paginate: bool,
+    deterministic: bool,
+) -> Result<(crate::QueryResult, Option<Keyset>)> {
     match config.engine {
         #[cfg(feature = "sqlite")]
-        DatabaseType::SQLite => SqliteEngine::execute(config, sql, &[], capabilities)
-            .await
-            .map_err(|e| anyhow!("SQLite query failed: {e}")),
+        DatabaseType::SQLite => execute_with_ordering::<SqliteEngine>(
+            config,
+            sql,
+            params,
+            capabilities,
+            cursor,
+            paginate,
+            deterministic,
+        )
+        .await
+        .map_err(|e| anyhow!("SQLite query failed: {e}")),
         #[cfg(not(feature = "sqlite"))]
         DatabaseType::SQLite => {
             Err(anyhow!("SQLite engine not enabled. Build with --features sqlite"))
         }
 
         #[cfg(feature = "postgres")]
-        DatabaseType::Postgres => PostgresEngine::execute(config, sql, &[], capabilities)
-            .await
-            .map_err(|e| anyhow!("PostgreSQL query failed: {e}")),
+        DatabaseType::Postgres => execute_with_ordering::<PostgresEngine>(
+            config,
+            sql,
+            params,
+            capabilities,
+            cursor,
+            paginate,
+            deterministic,
+        )
+        .await
+        .map_err(|e| anyhow!("PostgreSQL query failed: {e}")),
         #[cfg(not(feature = "postgres"))]
         DatabaseType::Postgres => {
             Err(anyhow!("PostgreSQL engine not enabled. Build with --features postgres"))
         }
 
         #[cfg(feature = "mysql")]
-        DatabaseType::MySQL => MySqlEngine::execute(config, sql, &[], capabilities)
-            .await
-            .map_err(|e| anyhow!("MySQL query failed: {e}")),
+        DatabaseType::MySQL => execute_with_ordering::<MySqlEngine>(
+            config,
+            sql,
+            params,
+            capabilities,
+            cursor,
+            paginate,
+            deterministic,
+        )
+        .await
+        .map_err(|e| anyhow!("MySQL query failed: {e}")),
         #[cfg(not(feature = "mysql"))]
         DatabaseType::MySQL => {
             Err(anyhow!("MySQL engine not enabled. Build with --features mysql"))
         }
 
         #[cfg(feature = "duckdb")]
-        DatabaseType::DuckDB => DuckDbEngine::execute(config, sql, &[], capabilities)
-            .await
-            .map_err(|e| anyhow!("DuckDB query failed: {e}")),
+        DatabaseType::DuckDB => execute_with_ordering::<DuckDbEngine>(
+            config,
+            sql,
+            params,
+            capabilities,
+            cursor,
+            paginate,
+            deterministic,
+        )
+        .await
+        .map_err(|e| anyhow!("DuckDB query failed: {e}")),
         #[cfg(not(feature = "duckdb"))]
         DatabaseType::DuckDB => {
             Err(anyhow!("DuckDB engine not enabled. Build with --features duckdb"))
@@ -0,0 +1,159 @@
+//! Deterministic result ordering (`--deterministic`)
+//!
+//! SQL gives no row order without ORDER BY, so the same query can return rows in a
+//! different order between runs, engines or after a vacuum. In deterministic mode a
+//! query whose outer SELECT has no ORDER BY is ordered by the database instead, and
+//! the ordering is chosen before the query runs, so it runs exactly once:
+//!
+//! - a query paged by cursor (`--paginate`, `--cursor`) is ordered by the table's
+//!   primary key, the same ordering its keyset predicates use;
+//! - otherwise the query is wrapped as
+//!   `SELECT * FROM (<query>) AS plenum_ordered ORDER BY 1, 2, ...`, so `max_rows`
+//!   and `offset` cut a well-defined prefix. The column count comes from a
+//!   `LIMIT 0` probe of the same wrapper.
+//!
+//! A statement that cannot be wrapped (`SHOW`, `PRAGMA`, a result with duplicate
+//! column names on `MySQL`, or a column type without an ordering such as
+//! `PostgreSQL` `json`) runs as written. The result's `ordering` field records which
+//! of these applied.
+
+use serde_json::Value;
+
+use crate::cursor::{execute_with_cursor, outer_query, primary_key_order, Keyset};
+use crate::engine::{
+    Capabilities, ConnectionConfig, DatabaseEngine, DatabaseType, QueryResult, ResultColumn,
+    RowSink,
+};
+use crate::error::Result;
+
+/// The query's own top-level ORDER BY fixed the row order
+pub const ORDERING_ENGINE: &str = "engine";
+
+/// Plenum ordered the rows (primary key or wrapped ORDER BY)
+pub const ORDERING_IMPOSED: &str = "imposed";
+
+/// The row order could not be fixed: a statement that cannot be wrapped in an
+/// ordered subquery (`SHOW`, `PRAGMA`, unorderable or duplicate columns)
+pub const ORDERING_UNORDERED: &str = "unordered";
+
+/// `PostgreSQL` types without a default btree ordering; `ORDER BY` on them fails
+const PG_UNORDERABLE: &[&str] =
+    &["json", "xml", "point", "line", "lseg", "box", "path", "polygon", "circle", "aclitem"];
+
+/// Execute `query` like [`execute_with_cursor`], imposing a row order when
+/// `deterministic` is set and the query has none of its own.
+///
+/// # Errors
+///
+/// Whatever [`execute_with_cursor`] or `E::execute` returns for the chosen query.
+pub async fn execute_with_ordering<E: DatabaseEngine>(
+    config: &ConnectionConfig,
+    query: &str,
+    params: &[Value],
+    caps: &Capabilities,
+    cursor: Option<&str>,
+    paginate: bool,
+    deterministic: bool,
+) -> Result<(QueryResult, Option<Keyset>)> {
+    if !deterministic || caps.explain_format.is_some() {
+        return execute_with_cursor::<E>(config, query, params, caps, cursor, paginate).await;
+    }
+
+    let outer = outer_query(query, config.engine);
+    if outer.ordered || cursor.is_some() {
+        // Either the query's own ORDER BY, or the keyset the cursor resumes
+        let (mut result, keyset) =
+            execute_with_cursor::<E>(config, query, params, caps, cursor, paginate).await?;
+        let ordering = if outer.ordered { ORDERING_ENGINE } else { ORDERING_IMPOSED };
+        result.ordering = Some(ordering.to_string());
+        return Ok((result, keyset));
+    }
+    if paginate && caps.max_rows.is_some() {
+        if let Some((sql, keyset)) = primary_key_order::<E>(config, query, params).await {
+            let mut result = E::execute(config, &sql, params, caps).await?;
+            result.ordering = Some(ORDERING_IMPOSED.to_string());
+            return Ok((result, Some(keyset)));
+        }
+    }
+
+    let wrapped = format!("SELECT * FROM (\n{}\n) AS plenum_ordered", outer.body);
+    let columns = if outer.is_select {
+        probe_columns::<E>(config, &wrapped, params, caps).await
+    } else {
+        None
+    };
+    let Some(columns) = columns else {
+        let mut result = E::execute(config, query, params, caps).await?;
+        result.ordering = Some(ORDERING_UNORDERED.to_string());
+        return Ok((result, None));
+    };
+
+    let positions: Vec<String> = (1..=columns).map(|i| i.to_string()).collect();
+    let sql = format!("{wrapped} ORDER BY {}", positions.join(", "));
+    crate::validate_query(&sql, caps, config.engine)?;
+    let mut ordered = E::execute(config, &sql, params, caps).await?;
+    ordered.ordering = Some(ORDERING_IMPOSED.to_string());
+    Ok((ordered, None))
+}
+
+/// Number of columns `wrapped` returns, from a `LIMIT 0` run that reads no rows;
+/// `None` when it cannot be ordered by every column.
+async fn probe_columns<E: DatabaseEngine>(
+    config: &ConnectionConfig,
+    wrapped: &str,
+    params: &[Value],
+    caps: &Capabilities,
+) -> Option<usize> {
+    let probe = format!("{wrapped} LIMIT 0");
+    crate::validate_query(&probe, caps, config.engine).ok()?;
+    let caps = Capabilities { max_rows: None, offset: None, ..caps.clone() };
+    let mut sink = ColumnProbe::default();
+    E::execute_stream(config, &probe, params, &caps, &mut sink).await.ok()?;
+    let orderable = !sink.columns.is_empty()
+        && sink.columns.iter().all(|c| is_orderable(config.engine, c.type_name.as_deref()));
+    orderable.then_some(sink.columns.len())
+}
+
+/// Whether `ORDER BY` accepts a column of engine type `type_name`
+fn is_orderable(engine: DatabaseType, type_name: Option<&str>) -> bool {
+    match (engine, type_name) {
+        (DatabaseType::Postgres, Some(name)) => {
+            let element = name.strip_prefix('_').unwrap_or(name);
+            !PG_UNORDERABLE.iter().any(|t| element.eq_ignore_ascii_case(t))
+        }
+        _ => true,
+    }
+}
+
+/// Records the result columns of a probe and refuses every row
+#[derive(Default)]
+struct ColumnProbe {
+    columns: Vec<ResultColumn>,
+}
+
+impl RowSink for ColumnProbe {
+    fn columns(&mut self, columns: &[ResultColumn]) -> Result<()> {
+        self.columns = columns.to_vec();
+        Ok(())
+    }
+
+    fn row(&mut self, _row: Vec<Value>) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_orderable_rejects_postgres_types_without_ordering() {
+        assert!(is_orderable(DatabaseType::Postgres, Some("int4")));
+        assert!(is_orderable(DatabaseType::Postgres, Some("jsonb")));
+        assert!(!is_orderable(DatabaseType::Postgres, Some("json")));
+        assert!(!is_orderable(DatabaseType::Postgres, Some("_xml")));
+        assert!(!is_orderable(DatabaseType::Postgres, Some("point")));
+        assert!(is_orderable(DatabaseType::MySQL, Some("json")));
+        assert!(is_orderable(DatabaseType::SQLite, None));
+    }
+}
@@ -6,12 +6,15 @@
 //! # Output Contract
 //! - Success: `{"ok": true, "engine": "...", "command": "...", "data": {...}, "meta": {...}}`
 //! - Error: `{"ok": false, "engine": "...", "command": "...", "error": {"code": "...", "message": "..."}, "meta": {...}}`
+//! - Streamed query (`--format ndjson`): a `header` line, one `row` line per row, then a
+//!   `trailer` line, each an `NdjsonLine`; a failure ends the stream with an error envelope line
 //!
 //! Output is stable, versioned, and suitable for programmatic parsing by agents.
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::engine::ResultColumn;
 use crate::error::PlenumError;
 
 /// The current output contract version. Bump this on any breaking change to the JSON envelope schema.
@@ -22,7 +25,7 @@ pub const CONTRACT_VERSION: &str = "1";
 /// Generic over the data type to support different operation return values.
 #[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct SuccessEnvelope<T> {
-    /// Always true for success envelopes
+    /// True for success envelopes, except a `plan check` that found changed plans
     pub ok: bool,
 
     /// Database engine used for this operation (postgres, mysql, sqlite)
@@ -142,6 +145,16 @@ pub struct Metadata {
     /// absent for row-count truncation or when rows are not truncated
     #[serde(skip_serializing_if = "Option::is_none")]
     pub truncated_by: Option<String>,
+
+    /// Opaque keyset cursor to pass as --cursor for the next page (present only when
+    /// `has_more` is true and the query is ordered by unique, non-null columns)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+
+    /// How the row order was fixed under --deterministic: "engine" (the query's own
+    /// ORDER BY), "imposed" (ordered by Plenum) or "unordered"; absent otherwise
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ordering: Option<String>,
 }
 
 impl Metadata {
@@ -156,6 +169,8 @@ impl Metadata {
             has_more: None,
             next_offset: None,
             truncated_by: None,
+            next_cursor: None,
+            ordering: None,
         }
     }
 
@@ -170,6 +185,8 @@ impl Metadata {
             has_more: None,
             next_offset: None,
             truncated_by: None,
+            next_cursor: None,
+            ordering: None,
         }
     }
 
@@ -194,8 +211,74 @@ impl Metadata {
             has_more: Some(truncated),
             next_offset,
             truncated_by: if truncated { truncated_by } else { None },
+            next_cursor: None,
+            ordering: None,
         }
     }
+
+    /// Attach the keyset cursor for the next page.
+    ///
+    /// A page fetched with `--cursor` has no meaningful row offset, so `next_offset`
+    /// is dropped when `from_cursor` is true.
+    #[must_use]
+    pub fn with_next_cursor(mut self, next_cursor: Option<String>, from_cursor: bool) -> Self {
+        self.next_cursor = next_cursor;
+        if from_cursor {
+            self.next_offset = None;
+        }
+        self
+    }
+
+    /// Record how the row order was fixed under --deterministic.
+    #[must_use]
+    pub fn with_ordering(mut self, ordering: Option<String>) -> Self {
+        self.ordering = ordering;
+        self
+    }
+}
+
+/// One line of a streamed query result (`plenum query --format ndjson`)
+///
+/// A successful stream is one `header`, one `row` per result row, then one `trailer`.
+/// If the query fails at any point the stream ends with an `ErrorEnvelope` line
+/// (`"ok": false`) instead of the trailer.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NdjsonLine {
+    /// First line: where the rows come from and what their columns are
+    Header {
+        /// Output contract version
+        #[schemars(with = "String")]
+        contract_version: &'static str,
+
+        /// Database engine used for this operation
+        engine: String,
+
+        /// Command that was executed (always "query")
+        command: String,
+
+        /// Result columns with engine-native type names (empty for statements without rows)
+        columns: Vec<ResultColumn>,
+    },
+
+    /// One result row
+    Row {
+        /// Values in column order
+        values: Vec<serde_json::Value>,
+    },
+
+    /// Last line of a successful stream
+    Trailer {
+        /// Always true; failed streams end with an error envelope instead
+        ok: bool,
+
+        /// Number of rows affected (for statements that return no rows)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        rows_affected: Option<u64>,
+
+        /// Row count, truncation and timing, as in the query success envelope
+        meta: Metadata,
+    },
 }
 
 #[cfg(test)]
@@ -295,4 +378,29 @@ mod tests {
         let meta = Metadata::new(0);
         assert_eq!(meta.contract_version, CONTRACT_VERSION);
     }
+
+    #[test]
+    fn test_ndjson_lines_are_tagged() {
+        let header = NdjsonLine::Header {
+            contract_version: CONTRACT_VERSION,
+            engine: "sqlite".to_string(),
+            command: "query".to_string(),
+            columns: vec![ResultColumn { name: "id".to_string(), type_name: None }],
+        };
+        let json = serde_json::to_string(&header).unwrap();
+        assert!(json.starts_with(r#"{"type":"header","contract_version":"1""#), "{json}");
+        assert!(json.contains(r#""columns":[{"name":"id","type":null}]"#), "{json}");
+
+        let row = NdjsonLine::Row { values: vec![serde_json::json!(1), serde_json::json!("a")] };
+        assert_eq!(serde_json::to_string(&row).unwrap(), r#"{"type":"row","values":[1,"a"]}"#);
+
+        let trailer = NdjsonLine::Trailer {
+            ok: true,
+            rows_affected: None,
+            meta: Metadata::with_query(5, 2, true, 0, None),
+        };
+        let json = serde_json::to_string(&trailer).unwrap();
+        assert!(json.starts_with(r#"{"type":"trailer","ok":true,"meta":{"#), "{json}");
+        assert!(json.contains(r#""next_offset":2"#), "{json}");
+    }
 }
@@ -0,0 +1,1001 @@
+//! Query parameters: positional values and engine-neutral `:name` placeholders
+//!
+//! Engines disagree on placeholder syntax (`$1` on `PostgreSQL`, `?` elsewhere), so
+//! callers may instead write `:name` in the SQL and pass `name=value` pairs. Before
+//! execution the named placeholders are rewritten to the engine's native syntax and
+//! the values laid out positionally, so binding still goes through each engine's
+//! `json_to_*_value` conversion. String literals, quoted identifiers, comments and
+//! `PostgreSQL` `::type` casts are never treated as placeholders.
+//!
+//! A value may also carry an explicit type as a `::type` suffix (`...::uuid`,
+//! `[1,2]::int[]`, `...::bytes`), written as `{"$type": "uuid", "value": "..."}` in
+//! JSON. Typed values are validated
+//! here and decoded by each engine into its native type via [`ParamValue`], so a
+//! UUID, timestamp, exact NUMERIC, binary value or array binds without a cast.
+
+use std::collections::BTreeMap;
+
+use base64::Engine as _;
+use serde_json::Value;
+
+use crate::capability::{skip_dollar_quoted, skip_quoted};
+use crate::engine::DatabaseType;
+use crate::error::{PlenumError, Result};
+
+/// Parameters supplied with a query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryParams {
+    /// Bound in order to the engine's native placeholders (`$1`, `?`)
+    Positional(Vec<Value>),
+    /// Bound by name to `:name` placeholders
+    Named(BTreeMap<String, Value>),
+}
+
+impl QueryParams {
+    /// Parse `--param` flags for `sql`.
+    ///
+    /// The flags bind by name when `sql` contains `:name` placeholders and every
+    /// flag is `name=value`; otherwise they are positional, so `SQLite`'s native
+    /// `:name` placeholders still bind in order. Values follow [`parse_value`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `INVALID_INPUT` for a positional flag alongside `:name` placeholders
+    /// on an engine without native named placeholders, or a name given twice.
+    pub fn from_cli(raw: &[String], sql: &str, engine: DatabaseType) -> Result<Self> {
+        if named_placeholders(sql, engine).is_empty() {
+            return Ok(Self::Positional(raw.iter().map(|s| parse_value(s)).collect()));
+        }
+        let mut named = BTreeMap::new();
+        for entry in raw {
+            let Some((name, value)) = entry.split_once('=').filter(|(n, _)| is_param_name(n))
+            else {
+                if engine == DatabaseType::SQLite {
+                    return Ok(Self::Positional(raw.iter().map(|s| parse_value(s)).collect()));
+                }
+                return Err(PlenumError::invalid_input(format!(
+                    "--param '{entry}' must be name=value when the SQL uses :name placeholders"
+                )));
+            };
+            if named.insert(name.to_string(), parse_value(value)).is_some() {
+                return Err(PlenumError::invalid_input(format!("Parameter '{name}' given twice")));
+            }
+        }
+        Ok(Self::Named(named))
+    }
+
+    /// Parse the MCP `params` argument: an array is positional, an object is named.
+    ///
+    /// # Errors
+    ///
+    /// Returns `INVALID_INPUT` for any other JSON type or an invalid parameter name.
+    pub fn from_json(value: Option<&Value>) -> Result<Self> {
+        match value {
+            None | Some(Value::Null) => Ok(Self::Positional(Vec::new())),
+            Some(Value::Array(values)) => Ok(Self::Positional(values.clone())),
+            Some(Value::Object(map)) => {
+                if let Some(bad) = map.keys().find(|k| !is_param_name(k)) {
+                    return Err(PlenumError::invalid_input(format!(
+                        "Invalid parameter name '{bad}': use letters, digits and underscores"
+                    )));
+                }
+                Ok(Self::Named(map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()))
+            }
+            Some(_) => Err(PlenumError::invalid_input(
+                "params must be an array (positional) or an object (named)",
+            )),
+        }
+    }
+
+    /// Rewrite `sql` for `engine` and lay the values out positionally.
+    ///
+    /// Positional parameters pass through unchanged. Named placeholders become
+    /// `$n` on `PostgreSQL` (a repeated name reuses its number) and `?` elsewhere
+    /// (the value is repeated per occurrence).
+    ///
+    /// # Errors
+    ///
+    /// Returns `INVALID_INPUT` when a placeholder has no value, a value has no
+    /// placeholder, or the SQL mixes `:name` with native positional placeholders.
+    pub fn bind(self, sql: &str, engine: DatabaseType) -> Result<(String, Vec<Value>)> {
+        let named = match self {
+            Self::Positional(values) => {
+                check_typed(values.iter().enumerate().map(|(n, v)| ((n + 1).to_string(), v)))?;
+                return Ok((sql.to_string(), values));
+            }
+            Self::Named(named) => named,
+        };
+        // Validate by name: once laid out, a value no longer knows its placeholder
+        check_typed(named.iter().map(|(name, v)| (format!(":{name}"), v)))?;
+        let chars: Vec<char> = sql.chars().collect();
+        let spans = scan(&chars, engine);
+        if spans.positional {
+            return Err(PlenumError::invalid_input(
+                "Cannot mix :name placeholders with positional placeholders ($1, ?)",
+            ));
+        }
+
+        let mut out = String::with_capacity(sql.len());
+        let mut values = Vec::new();
+        let mut numbers: Vec<&str> = Vec::new();
+        let mut last = 0;
+        for (start, end) in &spans.named {
+            let name: String = chars[start + 1..*end].iter().collect();
+            let value = named.get(&name).ok_or_else(|| {
+                PlenumError::invalid_input(format!("Missing value for parameter :{name}"))
+            })?;
+            out.extend(&chars[last..*start]);
+            if engine == DatabaseType::Postgres {
+                let key = named.get_key_value(&name).map_or("", |(k, _)| k.as_str());
+                let n = numbers.iter().position(|k| *k == key).unwrap_or_else(|| {
+                    numbers.push(key);
+                    values.push(value.clone());
+                    numbers.len() - 1
+                });
+                out.push('$');
+                out.push_str(&(n + 1).to_string());
+            } else {
+                out.push('?');
+                values.push(value.clone());
+            }
+            last = *end;
+        }
+        out.extend(&chars[last..]);
+
+        let used: Vec<String> =
+            spans.named.iter().map(|(s, e)| chars[s + 1..*e].iter().collect()).collect();
+        if let Some(unused) = named.keys().find(|k| !used.contains(k)) {
+            return Err(PlenumError::invalid_input(format!(
+                "Parameter '{unused}' is not used by any :{unused} placeholder in the SQL"
+            )));
+        }
+        Ok((out, values))
+    }
+}
+
+/// Reject malformed typed parameters before anything reaches an engine.
+///
+/// Each value comes with the label its error names: a position or a `:name`.
+fn check_typed<'a>(values: impl IntoIterator<Item = (String, &'a Value)>) -> Result<()> {
+    for (label, value) in values {
+        if let Some(Err(PlenumError::InvalidInput(msg))) = ParamValue::from_json(value) {
+            return Err(PlenumError::invalid_input(format!("Parameter {label}: {msg}")));
+        }
+    }
+    Ok(())
+}
+
+/// Parse one `--param` value.
+///
+/// `value::type` and `[json array]::type[]` (e.g. `...::uuid`, `[1,2,3]::int[]`)
+/// become typed parameters; see [`ParamType`] for the names, which are matched
+/// exactly. Otherwise JSON numbers, booleans, `null` and quoted strings keep their
+/// type (so `'"a::text"'` stays the literal string), and anything else binds as text.
+#[must_use]
+pub fn parse_value(raw: &str) -> Value {
+    if let Some((rest, suffix)) = raw.rsplit_once("::") {
+        let (name, array) = suffix.strip_suffix("[]").map_or((suffix, false), |name| (name, true));
+        if let Some(ty) = ParamType::from_name(name) {
+            let value = if rest == "null" && !matches!(ty, ParamType::Text | ParamType::Json) {
+                Value::Null
+            } else {
+                Value::String(rest.to_string())
+            };
+            let name = if array { format!("{}[]", ty.as_str()) } else { ty.as_str().to_string() };
+            return serde_json::json!({ "$type": name, "value": value });
+        }
+    }
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+/// Names of the `:name` placeholders in `sql`, in order of appearance.
+#[must_use]
+pub fn named_placeholders(sql: &str, engine: DatabaseType) -> Vec<String> {
+    let chars: Vec<char> = sql.chars().collect();
+    scan(&chars, engine).named.iter().map(|(s, e)| chars[s + 1..*e].iter().collect()).collect()
+}
+
+pub(crate) fn is_param_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Placeholders found outside literals and comments
+struct Placeholders {
+    /// `(start, end)` char spans of `:name` placeholders, colon included
+    named: Vec<(usize, usize)>,
+    /// Whether a native positional placeholder (`$1`, or `?` off `PostgreSQL`) occurs
+    positional: bool,
+}
+
+fn scan(chars: &[char], engine: DatabaseType) -> Placeholders {
+    let mut found = Placeholders { named: Vec::new(), positional: false };
+    let ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    let mut braces = 0usize;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let after_identifier = i > 0 && ident(chars[i - 1]);
+        // DuckDB struct and map literals: the `:` after a quoted key in `{'k':v}`
+        let after_key = braces > 0
+            && chars[..i]
+                .iter()
+                .rev()
+                .find(|ch| !ch.is_whitespace())
+                .is_some_and(|&ch| ch == '\'' || ch == '"');
+
+        if c == '\'' || (c == '"' && engine == DatabaseType::MySQL) {
+            i = skip_quoted(chars, i, engine == DatabaseType::MySQL);
+        } else if matches!(c, '"' | '`') {
+            i = skip_quoted(chars, i, false);
+        } else if c == '[' && engine == DatabaseType::SQLite {
+            i = chars[i..].iter().position(|&ch| ch == ']').map_or(chars.len(), |p| i + p + 1);
+        } else if (c == '-' && next == Some('-')) || (c == '#' && engine == DatabaseType::MySQL) {
+            i = chars[i..].iter().position(|&ch| ch == '\n').map_or(chars.len(), |p| i + p);
+        } else if c == '/' && next == Some('*') {
+            i = chars[i + 2..]
+                .windows(2)
+                .position(|w| w == ['*', '/'])
+                .map_or(chars.len(), |p| i + 2 + p + 2);
+        } else if c == '$' && !after_identifier {
+            if next.is_some_and(|n| n.is_ascii_digit()) {
+                found.positional |= engine != DatabaseType::MySQL;
+                i += 1;
+            } else if engine == DatabaseType::Postgres {
+                i = skip_dollar_quoted(chars, i).unwrap_or(i + 1);
+            } else {
+                i += 1;
+            }
+        } else if c == '?' {
+            found.positional |= engine != DatabaseType::Postgres;
+            i += 1;
+        } else if c == ':' && next == Some(':') {
+            // PostgreSQL / DuckDB `::type` cast; the type name is never a placeholder
+            i = chars[i..].iter().position(|&ch| ch != ':').map_or(chars.len(), |p| i + p);
+        } else if matches!(c, '{' | '}') && engine == DatabaseType::DuckDB {
+            braces = if c == '{' { braces + 1 } else { braces.saturating_sub(1) };
+            i += 1;
+        } else if c == ':'
+            && !after_identifier
+            && !after_key
+            && next.is_some_and(|n| n.is_ascii_alphabetic() || n == '_')
+        {
+            let end = chars[i + 1..]
+                .iter()
+                .position(|&ch| !(ch.is_ascii_alphanumeric() || ch == '_'))
+                .map_or(chars.len(), |p| i + 1 + p);
+            found.named.push((i, end));
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    found
+}
+
+// ============================================================================
+// Typed parameters
+// ============================================================================
+
+/// Declared type of a typed parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamType {
+    Bool,
+    Int,
+    Float,
+    /// Exact decimal, kept as text so no precision is lost
+    Decimal,
+    Text,
+    Uuid,
+    Date,
+    Time,
+    /// Date and time, with an optional UTC offset
+    Timestamp,
+    Json,
+    /// Binary data, written as base64
+    Bytes,
+}
+
+impl ParamType {
+    /// Accepted type names and aliases
+    const NAMES: &'static [(&'static str, Self)] = &[
+        ("bool", Self::Bool),
+        ("boolean", Self::Bool),
+        ("int", Self::Int),
+        ("integer", Self::Int),
+        ("bigint", Self::Int),
+        ("float", Self::Float),
+        ("double", Self::Float),
+        ("decimal", Self::Decimal),
+        ("numeric", Self::Decimal),
+        ("text", Self::Text),
+        ("uuid", Self::Uuid),
+        ("date", Self::Date),
+        ("time", Self::Time),
+        ("timestamp", Self::Timestamp),
+        ("timestamptz", Self::Timestamp),
+        ("json", Self::Json),
+        ("jsonb", Self::Json),
+        ("bytes", Self::Bytes),
+        ("bytea", Self::Bytes),
+    ];
+
+    /// Look up a type name or alias (exact, lowercase)
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES.iter().find(|(n, _)| *n == name).map(|(_, ty)| *ty)
+    }
+
+    /// Canonical type name
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Bool => "bool",
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::Decimal => "decimal",
+            Self::Text => "text",
+            Self::Uuid => "uuid",
+            Self::Date => "date",
+            Self::Time => "time",
+            Self::Timestamp => "timestamp",
+            Self::Json => "json",
+            Self::Bytes => "bytes",
+        }
+    }
+}
+
+/// Calendar date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Days since 1970-01-01 (negative before)
+    #[must_use]
+    pub const fn days_since_epoch(self) -> i64 {
+        // Howard Hinnant's days_from_civil
+        let y = if self.month <= 2 { self.year as i64 - 1 } else { self.year as i64 };
+        let era = if y >= 0 { y } else { y - 399 } / 400;
+        let yoe = y - era * 400;
+        let mp = (self.month as i64 + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// Date `days` after 1970-01-01
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub const fn from_days_since_epoch(days: i64) -> Self {
+        // Howard Hinnant's civil_from_days
+        let z = days + 719_468;
+        let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Self { year, month, day }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.splitn(3, '-');
+        let (y, m, d) = (parts.next()?, parts.next()?, parts.next()?);
+        if y.len() != 4 || m.len() != 2 || d.len() != 2 {
+            return None;
+        }
+        let date = Self { year: digits(y)?, month: digits(m)?, day: digits(d)? };
+        let leap = date.year % 4 == 0 && (date.year % 100 != 0 || date.year % 400 == 0);
+        let month_days = match date.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=month_days).contains(&date.day).then_some(date)
+    }
+}
+
+/// Time of day with microsecond precision
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Time {
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub micros: u32,
+}
+
+impl Time {
+    /// Microseconds since midnight
+    #[must_use]
+    pub fn micros_since_midnight(self) -> i64 {
+        (i64::from(self.hour) * 3600 + i64::from(self.minute) * 60 + i64::from(self.second))
+            * 1_000_000
+            + i64::from(self.micros)
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let (hms, fraction) = s.split_once('.').map_or((s, None), |(a, b)| (a, Some(b)));
+        let mut parts = hms.split(':');
+        let (h, m, sec) = (parts.next()?, parts.next()?, parts.next().unwrap_or("00"));
+        if parts.next().is_some() || h.len() != 2 || m.len() != 2 || sec.len() != 2 {
+            return None;
+        }
+        let micros = match fraction {
+            None => 0,
+            Some(f) if (1..=6).contains(&f.len()) => {
+                digits::<u32>(f)? * 10u32.pow(6 - f.len() as u32)
+            }
+            Some(_) => return None,
+        };
+        let time = Self { hour: digits(h)?, minute: digits(m)?, second: digits(sec)?, micros };
+        (time.hour < 24 && time.minute < 60 && time.second < 60).then_some(time)
+    }
+}
+
+/// Date and time, with the UTC offset when one was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    pub date: Date,
+    pub time: Time,
+    /// UTC offset in seconds (`Z` is 0); `None` for a local timestamp
+    pub offset_seconds: Option<i32>,
+}
+
+impl Timestamp {
+    /// Microseconds since 1970-01-01 00:00:00, shifted to UTC when an offset is set
+    #[must_use]
+    pub fn micros_since_epoch(self) -> i64 {
+        self.date.days_since_epoch() * 86_400_000_000 + self.time.micros_since_midnight()
+            - i64::from(self.offset_seconds.unwrap_or(0)) * 1_000_000
+    }
+
+    /// The same instant as a UTC timestamp without an offset
+    #[must_use]
+    pub fn to_utc(self) -> Self {
+        let micros = self.micros_since_epoch();
+        let days = micros.div_euclid(86_400_000_000);
+        let rem = micros.rem_euclid(86_400_000_000);
+        let secs = u32::try_from(rem / 1_000_000).unwrap_or(0);
+        Self {
+            date: Date::from_days_since_epoch(days),
+            time: Time {
+                hour: secs / 3600,
+                minute: secs / 60 % 60,
+                second: secs % 60,
+                micros: u32::try_from(rem % 1_000_000).unwrap_or(0),
+            },
+            offset_seconds: None,
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let date = Date::parse(s.get(..10)?)?;
+        let rest = s.get(10..)?;
+        let Some(rest) = rest.strip_prefix(['T', 't', ' ']) else {
+            return rest.is_empty().then_some(Self {
+                date,
+                time: Time::default(),
+                offset_seconds: None,
+            });
+        };
+        let (time, offset_seconds) = if let Some(t) = rest.strip_suffix(['Z', 'z']) {
+            (t, Some(0))
+        } else if let Some(pos) = rest.rfind(['+', '-']) {
+            let (t, offset) = rest.split_at(pos);
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let (h, m) = offset[1..].split_once(':').unwrap_or((&offset[1..], "00"));
+            if h.len() != 2 || m.len() != 2 {
+                return None;
+            }
+            let (h, m): (i32, i32) = (digits(h)?, digits(m)?);
+            (t, Some(sign * (h * 3600 + m * 60)))
+        } else {
+            (rest, None)
+        };
+        Some(Self { date, time: Time::parse(time)?, offset_seconds })
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl std::fmt::Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if self.micros > 0 {
+            write!(f, ".{:06}", self.micros)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.date, self.time)?;
+        if let Some(offset) = self.offset_seconds {
+            let sign = if offset < 0 { '-' } else { '+' };
+            let offset = offset.unsigned_abs();
+            write!(f, "{sign}{:02}:{:02}", offset / 3600, offset / 60 % 60)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse an unsigned run of ASCII digits.
+fn digits<T: std::str::FromStr>(s: &str) -> Option<T> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// A decoded typed parameter, ready for an engine to bind natively
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    /// Validated decimal text (`[-]digits[.digits]`)
+    Decimal(String),
+    Text(String),
+    Uuid(u128),
+    Date(Date),
+    Time(Time),
+    Timestamp(Timestamp),
+    Json(Value),
+    Bytes(Vec<u8>),
+    /// Elements are `Null` or values of the element type
+    Array(ParamType, Vec<Self>),
+}
+
+impl ParamValue {
+    /// Decode a typed parameter object (`{"$type": "uuid", "value": "..."}`).
+    ///
+    /// `None` for any other JSON value, which engines bind as before; the `$type`
+    /// key keeps ordinary objects such as `{"type": ..., "value": ...}` untyped. A string
+    /// `value` is parsed according to the type; for `json` a string holding valid
+    /// JSON is parsed, and arrays accept a JSON array or a string containing one.
+    #[must_use]
+    pub fn from_json(value: &Value) -> Option<Result<Self>> {
+        let obj = value.as_object().filter(|o| o.len() == 2)?;
+        let name = obj.get("$type")?.as_str()?;
+        let value = obj.get("value")?;
+        let (base, array) = name.strip_suffix("[]").map_or((name, false), |n| (n, true));
+        let Some(ty) = ParamType::from_name(base) else {
+            return Some(Err(PlenumError::invalid_input(format!(
+                "Unknown parameter type '{name}'; expected one of bool, int, float, decimal, \
+                 text, uuid, date, time, timestamp, json, bytes (append [] for an array)"
+            ))));
+        };
+        if !array {
+            return Some(Self::scalar(ty, value));
+        }
+        let parsed;
+        let items = match value {
+            Value::Null => return Some(Ok(Self::Null)),
+            Value::Array(items) => items,
+            Value::String(s) => match serde_json::from_str::<Value>(s) {
+                Ok(Value::Array(items)) => {
+                    parsed = items;
+                    &parsed
+                }
+                _ => {
+                    return Some(Err(PlenumError::invalid_input(format!(
+                        "{name} value must be a JSON array, got '{s}'"
+                    ))))
+                }
+            },
+            other => {
+                return Some(Err(PlenumError::invalid_input(format!(
+                    "{name} value must be a JSON array, got {other}"
+                ))))
+            }
+        };
+        Some(
+            items
+                .iter()
+                .map(|item| Self::scalar(ty, item))
+                .collect::<Result<_>>()
+                .map(|items| Self::Array(ty, items)),
+        )
+    }
+
+    fn scalar(ty: ParamType, value: &Value) -> Result<Self> {
+        let invalid =
+            || PlenumError::invalid_input(format!("invalid {} value: {value}", ty.as_str()));
+        let text = value.as_str();
+        Ok(match (ty, value) {
+            (ParamType::Json, _) => Self::Json(
+                text.and_then(|s| serde_json::from_str(s).ok()).unwrap_or_else(|| value.clone()),
+            ),
+            (_, Value::Null) => Self::Null,
+            (ParamType::Bool, Value::Bool(b)) => Self::Bool(*b),
+            (ParamType::Bool, _) => match text.map(str::to_ascii_lowercase).as_deref() {
+                Some("true" | "t") => Self::Bool(true),
+                Some("false" | "f") => Self::Bool(false),
+                _ => return Err(invalid()),
+            },
+            (ParamType::Int, _) => Self::Int(
+                value
+                    .as_i64()
+                    .or_else(|| text.and_then(|s| s.trim().parse().ok()))
+                    .ok_or_else(invalid)?,
+            ),
+            (ParamType::Float, _) => Self::Float(
+                value
+                    .as_f64()
+                    .or_else(|| text.and_then(|s| s.trim().parse().ok()))
+                    .filter(|f: &f64| f.is_finite())
+                    .ok_or_else(invalid)?,
+            ),
+            (ParamType::Decimal, Value::Number(n)) => {
+                Self::Decimal(Some(n.to_string()).filter(|s| is_decimal(s)).ok_or_else(invalid)?)
+            }
+            (ParamType::Decimal, _) => {
+                let s = text.map(str::trim).filter(|s| is_decimal(s)).ok_or_else(invalid)?;
+                Self::Decimal(s.strip_prefix('+').unwrap_or(s).to_string())
+            }
+            (ParamType::Text, _) => {
+                Self::Text(text.map_or_else(|| value.to_string(), String::from))
+            }
+            (ParamType::Uuid, _) => {
+                let hex: String = text.ok_or_else(invalid)?.chars().filter(|c| *c != '-').collect();
+                if hex.len() != 32 {
+                    return Err(invalid());
+                }
+                Self::Uuid(u128::from_str_radix(&hex, 16).map_err(|_| invalid())?)
+            }
+            (ParamType::Date, _) => Self::Date(text.and_then(Date::parse).ok_or_else(invalid)?),
+            (ParamType::Time, _) => Self::Time(text.and_then(Time::parse).ok_or_else(invalid)?),
+            (ParamType::Timestamp, _) => {
+                Self::Timestamp(text.and_then(Timestamp::parse).ok_or_else(invalid)?)
+            }
+            (ParamType::Bytes, _) => {
+                let s = text.ok_or_else(invalid)?;
+                let decoded = base64::engine::general_purpose::STANDARD
+                    .decode(s)
+                    .or_else(|_| base64::engine::general_purpose::STANDARD_NO_PAD.decode(s))
+                    .map_err(|_| {
+                        PlenumError::invalid_input(format!(
+                            "invalid bytes value: '{s}' is not base64"
+                        ))
+                    })?;
+                Self::Bytes(decoded)
+            }
+        })
+    }
+
+    /// Canonical text for engines without a native type (`None` for `Null` and `Bytes`)
+    #[must_use]
+    pub fn to_text(&self) -> Option<String> {
+        Some(match self {
+            Self::Null | Self::Bytes(_) => return None,
+            Self::Bool(b) => b.to_string(),
+            Self::Int(i) => i.to_string(),
+            Self::Float(f) => f.to_string(),
+            Self::Decimal(s) | Self::Text(s) => s.clone(),
+            Self::Uuid(u) => {
+                let hex = format!("{u:032x}");
+                format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..]
+                )
+            }
+            Self::Date(d) => d.to_string(),
+            Self::Time(t) => t.to_string(),
+            Self::Timestamp(ts) => ts.to_string(),
+            Self::Json(_) | Self::Array(..) => self.to_json().to_string(),
+        })
+    }
+
+    /// JSON form, used to pass arrays to engines that bind them as JSON text
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Null => Value::Null,
+            Self::Bool(b) => Value::Bool(*b),
+            Self::Int(i) => Value::from(*i),
+            Self::Float(f) => Value::from(*f),
+            Self::Json(v) => v.clone(),
+            Self::Bytes(b) => Value::String(base64::engine::general_purpose::STANDARD.encode(b)),
+            Self::Array(_, items) => Value::Array(items.iter().map(Self::to_json).collect()),
+            other => Value::String(other.to_text().unwrap_or_default()),
+        }
+    }
+}
+
+fn is_decimal(s: &str) -> bool {
+    let s = s.strip_prefix(['+', '-']).unwrap_or(s);
+    let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+    (!int.is_empty() || !frac.is_empty())
+        && int.bytes().all(|b| b.is_ascii_digit())
+        && frac.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn named(pairs: &[(&str, Value)]) -> QueryParams {
+        QueryParams::Named(pairs.iter().map(|(k, v)| ((*k).to_string(), v.clone())).collect())
+    }
+
+    #[test]
+    fn test_bind_postgres_numbers_distinct_names() {
+        let (sql, values) = named(&[("id", json!(7)), ("status", json!("open"))])
+            .bind(
+                "SELECT * FROM t WHERE id = :id AND status = :status OR parent = :id",
+                DatabaseType::Postgres,
+            )
+            .unwrap();
+        assert_eq!(sql, "SELECT * FROM t WHERE id = $1 AND status = $2 OR parent = $1");
+        assert_eq!(values, [json!(7), json!("open")]);
+    }
+
+    #[test]
+    fn test_bind_question_marks_repeat_values() {
+        for engine in [DatabaseType::MySQL, DatabaseType::SQLite, DatabaseType::DuckDB] {
+            let (sql, values) = named(&[("a", json!(1)), ("b", json!(2))])
+                .bind("SELECT :b, :a, :b", engine)
+                .unwrap();
+            assert_eq!(sql, "SELECT ?, ?, ?", "{engine:?}");
+            assert_eq!(values, [json!(2), json!(1), json!(2)]);
+        }
+    }
+
+    #[test]
+    fn test_placeholders_skip_literals_comments_and_casts() {
+        let sql = "SELECT ':skip', \"col:x\", created::date, $$ :body $$ -- :comment\n\
+                   /* :block */ FROM t WHERE a = :real";
+        assert_eq!(named_placeholders(sql, DatabaseType::Postgres), ["real"]);
+        assert_eq!(
+            named_placeholders("SELECT `a:b`, \":str\" # :c\n, :d", DatabaseType::MySQL),
+            ["d"]
+        );
+        assert_eq!(named_placeholders("SELECT [x:y], :z", DatabaseType::SQLite), ["z"]);
+        assert!(named_placeholders("SELECT arr[lo:hi] FROM t", DatabaseType::Postgres).is_empty());
+    }
+
+    #[test]
+    fn test_placeholders_skip_casts_next_to_identifiers() {
+        for engine in [DatabaseType::Postgres, DatabaseType::DuckDB] {
+            let sql = "SELECT col::text, (a)::int, 'x'::date, \"Q\"::int, arr[1]::int, \
+                       x ::int, x:: int, x:::int, y::int[]::text FROM t WHERE id = :id::uuid";
+            assert_eq!(named_placeholders(sql, engine), ["id"], "{engine:?}");
+        }
+    }
+
+    #[test]
+    fn test_placeholders_skip_duckdb_struct_keys() {
+        let sql = "SELECT {'k':v, \"q\":w, plain:x}, MAP {'a':b}, {'n': :n} WHERE s = {'k':'v'}";
+        assert_eq!(named_placeholders(sql, DatabaseType::DuckDB), ["n"]);
+        let (sql, values) = named(&[("n", json!(1))])
+            .bind("SELECT {'k':v, 'n':\n:n}", DatabaseType::DuckDB)
+            .unwrap();
+        assert_eq!(sql, "SELECT {'k':v, 'n':\n?}");
+        assert_eq!(values, [json!(1)]);
+        // Outside a literal a quoted value before `:name` is still followed by a placeholder
+        assert_eq!(named_placeholders("SELECT 'a' || :b", DatabaseType::DuckDB), ["b"]);
+    }
+
+    #[test]
+    fn test_bind_reports_missing_unused_and_mixed() {
+        let err = named(&[]).bind("SELECT :a", DatabaseType::SQLite).unwrap_err();
+        assert!(err.message().contains(":a"), "{}", err.message());
+        let err = named(&[("a", json!(1)), ("b", json!(2))])
+            .bind("SELECT :a", DatabaseType::SQLite)
+            .unwrap_err();
+        assert!(err.message().contains("'b'"), "{}", err.message());
+        assert!(named(&[("a", json!(1))]).bind("SELECT :a, $1", DatabaseType::Postgres).is_err());
+        assert!(named(&[("a", json!(1))]).bind("SELECT :a, ?", DatabaseType::MySQL).is_err());
+        // `?` is a jsonb operator on PostgreSQL, not a placeholder
+        assert!(named(&[("k", json!("x"))])
+            .bind("SELECT doc ? :k FROM t", DatabaseType::Postgres)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_from_cli_binds_by_name_only_for_name_value_flags() {
+        let raw = vec!["5".to_string(), "a=b".to_string()];
+        assert_eq!(
+            QueryParams::from_cli(&raw, "SELECT ?, ?", DatabaseType::SQLite).unwrap(),
+            QueryParams::Positional(vec![json!(5), json!("a=b")])
+        );
+        let raw = vec!["id=5".to_string(), "name=a=b".to_string()];
+        assert_eq!(
+            QueryParams::from_cli(&raw, "SELECT :id, :name", DatabaseType::SQLite).unwrap(),
+            named(&[("id", json!(5)), ("name", json!("a=b"))])
+        );
+        // SQLite binds its native :name placeholders positionally
+        let raw = vec!["5".to_string(), "x".to_string()];
+        assert_eq!(
+            QueryParams::from_cli(&raw, "SELECT :id, :name", DatabaseType::SQLite).unwrap(),
+            QueryParams::Positional(vec![json!(5), json!("x")])
+        );
+        let raw = vec!["id=5".to_string(), "7".to_string()];
+        assert_eq!(
+            QueryParams::from_cli(&raw, "SELECT :id, :n", DatabaseType::SQLite).unwrap(),
+            QueryParams::Positional(vec![json!("id=5"), json!(7)])
+        );
+        for engine in [DatabaseType::Postgres, DatabaseType::MySQL, DatabaseType::DuckDB] {
+            let raw = vec!["5".to_string()];
+            assert!(QueryParams::from_cli(&raw, "SELECT :id", engine).is_err(), "{engine:?}");
+        }
+        let raw = vec!["id=1".to_string(), "id=2".to_string()];
+        assert!(QueryParams::from_cli(&raw, "SELECT :id", DatabaseType::SQLite).is_err());
+    }
+
+    #[test]
+    fn test_from_json_array_or_object() {
+        assert_eq!(
+            QueryParams::from_json(Some(&json!([1, "x"]))).unwrap(),
+            QueryParams::Positional(vec![json!(1), json!("x")])
+        );
+        assert_eq!(
+            QueryParams::from_json(Some(&json!({"id": 1}))).unwrap(),
+            named(&[("id", json!(1))])
+        );
+        assert_eq!(QueryParams::from_json(None).unwrap(), QueryParams::Positional(Vec::new()));
+        assert!(QueryParams::from_json(Some(&json!("x"))).is_err());
+        assert!(QueryParams::from_json(Some(&json!({"bad-name": 1}))).is_err());
+    }
+
+    fn typed(value: &Value) -> Result<ParamValue> {
+        ParamValue::from_json(value).expect("typed parameter object")
+    }
+
+    #[test]
+    fn test_parse_value_type_suffixes() {
+        assert_eq!(
+            parse_value("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11::uuid"),
+            json!({"$type": "uuid", "value": "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11"})
+        );
+        assert_eq!(parse_value("[1,2,3]::int[]"), json!({"$type": "int[]", "value": "[1,2,3]"}));
+        assert_eq!(parse_value("1.50::numeric"), json!({"$type": "decimal", "value": "1.50"}));
+        assert_eq!(parse_value("null::uuid"), json!({"$type": "uuid", "value": null}));
+        assert_eq!(parse_value("null::text"), json!({"$type": "text", "value": "null"}));
+        assert_eq!(
+            parse_value("2024-01-01T01:30:00+02:00::timestamptz"),
+            json!({"$type": "timestamp", "value": "2024-01-01T01:30:00+02:00"})
+        );
+        // Values that merely look like `type:value` keep their existing meaning
+        for raw in ["text:hello", "date:2024-01-31", "TIME:12:30", "json:{}", "http://x", "12:30"] {
+            assert_eq!(parse_value(raw), json!(raw), "{raw}");
+        }
+        // Type names are exact, and a JSON string is never typed
+        assert_eq!(parse_value("x::UUID"), json!("x::UUID"));
+        assert_eq!(parse_value("x::varchar"), json!("x::varchar"));
+        assert_eq!(parse_value(r#""a::text""#), json!("a::text"));
+    }
+
+    #[test]
+    fn test_typed_scalars() {
+        assert_eq!(
+            typed(&json!({"$type": "uuid", "value": "A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11"}))
+                .unwrap()
+                .to_text()
+                .unwrap(),
+            "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11"
+        );
+        assert_eq!(
+            typed(&json!({"$type": "decimal", "value": "+12345.6700"})).unwrap(),
+            ParamValue::Decimal("12345.6700".to_string())
+        );
+        assert_eq!(typed(&json!({"$type": "int", "value": "42"})).unwrap(), ParamValue::Int(42));
+        assert_eq!(typed(&json!({"$type": "bool", "value": "t"})).unwrap(), ParamValue::Bool(true));
+        assert_eq!(
+            typed(&json!({"$type": "bytes", "value": "3q2+7w=="})).unwrap(),
+            ParamValue::Bytes(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(
+            typed(&json!({"$type": "json", "value": "{\"a\": 1}"})).unwrap(),
+            ParamValue::Json(json!({"a": 1}))
+        );
+        assert_eq!(typed(&json!({"$type": "date", "value": null})).unwrap(), ParamValue::Null);
+        for bad in [
+            json!({"$type": "uuid", "value": "not-a-uuid"}),
+            json!({"$type": "int", "value": "1.5"}),
+            json!({"$type": "decimal", "value": "1e5"}),
+            json!({"$type": "bytes", "value": "%%%"}),
+            json!({"$type": "date", "value": "2023-02-29"}),
+            json!({"$type": "time", "value": "24:00"}),
+            json!({"$type": "money", "value": "1"}),
+        ] {
+            assert!(typed(&bad).is_err(), "{bad}");
+        }
+        // Objects that are not exactly {$type, value} bind as before
+        assert!(ParamValue::from_json(&json!({"$type": "uuid"})).is_none());
+        assert!(ParamValue::from_json(&json!({"type": "uuid", "value": "x"})).is_none());
+        assert!(ParamValue::from_json(&json!({"$type": "UUID", "value": "x"})).unwrap().is_err());
+        assert!(ParamValue::from_json(&json!("uuid:x")).is_none());
+    }
+
+    #[test]
+    fn test_typed_dates_and_timestamps() {
+        let Ok(ParamValue::Date(leap)) = typed(&json!({"$type": "date", "value": "2024-02-29"}))
+        else {
+            panic!("expected date");
+        };
+        assert_eq!(leap.days_since_epoch(), 19_782);
+        assert_eq!(Date::from_days_since_epoch(19_782), leap);
+        assert_eq!(Date::from_days_since_epoch(-1).to_string(), "1969-12-31");
+
+        let Ok(ParamValue::Time(time)) = typed(&json!({"$type": "time", "value": "13:05:09.25"}))
+        else {
+            panic!("expected time");
+        };
+        assert_eq!(time.to_string(), "13:05:09.250000");
+
+        let Ok(ParamValue::Timestamp(ts)) =
+            typed(&json!({"$type": "timestamptz", "value": "2024-01-01T01:30:00+02:00"}))
+        else {
+            panic!("expected timestamp");
+        };
+        assert_eq!(ts.micros_since_epoch(), (19_723 * 86_400 - 1_800) * 1_000_000);
+        assert_eq!(ts.to_utc().to_string(), "2023-12-31 23:30:00");
+
+        let Ok(ParamValue::Timestamp(local)) =
+            typed(&json!({"$type": "timestamp", "value": "2024-01-01 00:00"}))
+        else {
+            panic!("expected timestamp");
+        };
+        assert_eq!(local.offset_seconds, None);
+        assert_eq!(local.micros_since_epoch(), 19_723 * 86_400 * 1_000_000);
+    }
+
+    #[test]
+    fn test_typed_arrays_and_bind_validation() {
+        assert_eq!(
+            typed(&json!({"$type": "int[]", "value": "[1, 2, null]"})).unwrap(),
+            ParamValue::Array(
+                ParamType::Int,
+                vec![ParamValue::Int(1), ParamValue::Int(2), ParamValue::Null]
+            )
+        );
+        assert_eq!(
+            typed(&json!({"$type": "text[]", "value": ["a", "b"]})).unwrap().to_text().unwrap(),
+            r#"["a","b"]"#
+        );
+        assert!(typed(&json!({"$type": "uuid[]", "value": "[\"x\"]"})).is_err());
+        assert!(typed(&json!({"$type": "int[]", "value": "1,2"})).is_err());
+
+        let err = QueryParams::Positional(vec![json!(1), json!({"$type": "uuid", "value": "x"})])
+            .bind("SELECT ?, ?", DatabaseType::SQLite)
+            .unwrap_err();
+        assert_eq!(err.error_code(), "INVALID_INPUT");
+        assert!(err.message().contains("Parameter 2: invalid uuid"), "{}", err.message());
+    }
+
+    #[test]
+    fn test_typed_errors_name_the_named_parameter() {
+        let sql = "SELECT * FROM t WHERE kind = :kind LIMIT :limit";
+        let flags = ["kind=login".to_string(), "limit=ten::int".to_string()];
+        for engine in [DatabaseType::Postgres, DatabaseType::SQLite] {
+            let err = QueryParams::from_cli(&flags, sql, engine)
+                .and_then(|params| params.bind(sql, engine))
+                .unwrap_err();
+            assert_eq!(err.error_code(), "INVALID_INPUT");
+            assert!(err.message().contains("Parameter :limit: invalid int"), "{}", err.message());
+        }
+    }
+}
@@ -0,0 +1,449 @@
+//! Plan baselines for saved queries (`.plenum/plans/*.json`)
+//!
+//! `plenum plan record` explains each saved query (see [`crate::saved`]) with its
+//! parameter defaults and writes the structured plan to `.plenum/plans/<name>.json`,
+//! a file meant to be committed next to the query. `plenum plan check` explains the
+//! queries again and compares each plan with its baseline:
+//!
+//! - the plan shape: nodes are compared top-down by operation, relation and index
+//!   (by engine label for nodes of operation `other`). A node that differs is reported
+//!   once, together with its subtree, as is a child that was added or removed;
+//! - the root's estimated cost, which counts as increased when it exceeds the baseline's
+//!   by more than the cost threshold. Engines without cost estimates only compare shapes.
+//!
+//! A changed shape is not necessarily worse (a new index turns a full scan into an
+//! index scan too); each change names both nodes so the reader can tell.
+//!
+//! Row estimates and inner costs are recorded but not compared, since every
+//! statistics update moves them. Queries with a required parameter that has no
+//! default cannot be explained and are reported as errors.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::cursor::outer_query;
+use crate::engine::{
+    Capabilities, ConnectionConfig, DatabaseEngine, DatabaseType, ExplainFormat, ExplainPlanNode,
+    PlanChange, PlanCheck, PlanOperation, PlanReport,
+};
+use crate::error::{PlenumError, Result};
+use crate::output::ErrorInfo;
+use crate::saved::SavedQuery;
+
+/// Default cost threshold: a root cost above 1.5 times the baseline's is an increase
+pub const DEFAULT_COST_THRESHOLD: f64 = 1.5;
+
+/// Status: the baseline was written
+pub const STATUS_RECORDED: &str = "recorded";
+
+/// Status: same shape and no cost increase past the threshold
+pub const STATUS_UNCHANGED: &str = "unchanged";
+
+/// Status: a plan node's operation, relation or index differs from the baseline
+pub const STATUS_SHAPE_CHANGED: &str = "shape_changed";
+
+/// Status: same shape, but the root cost rose past the threshold
+pub const STATUS_COST_INCREASED: &str = "cost_increased";
+
+/// Status: no baseline has been recorded for the query
+pub const STATUS_NO_BASELINE: &str = "no_baseline";
+
+/// Status: the query could not be explained, compared or recorded
+pub const STATUS_ERROR: &str = "error";
+
+/// Directory holding the project's plan baselines
+#[must_use]
+pub fn plans_dir(project_path: &Path) -> PathBuf {
+    project_path.join(".plenum").join("plans")
+}
+
+/// Baseline file of the saved query `name`, relative to the project root
+#[must_use]
+pub fn baseline_file(name: &str) -> PathBuf {
+    Path::new(".plenum").join("plans").join(format!("{name}.json"))
+}
+
+/// Contents of a baseline file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanBaseline {
+    /// Saved query name
+    pub name: String,
+    /// Engine the plan was recorded on
+    pub engine: DatabaseType,
+    /// Saved query SQL the plan was recorded for, header included
+    pub sql: String,
+    /// Structured plan
+    pub plan: ExplainPlanNode,
+}
+
+impl PlanBaseline {
+    /// Load the baseline of the saved query `name`; `None` when none was recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CONFIG_ERROR` for an unreadable or malformed baseline file.
+    pub fn load(project_path: &Path, name: &str) -> Result<Option<Self>> {
+        let path = project_path.join(baseline_file(name));
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(PlenumError::config_error(format!(
+                    "Could not read {}: {e}",
+                    path.display()
+                )))
+            }
+        };
+        serde_json::from_str(&text).map(Some).map_err(|e| {
+            PlenumError::config_error(format!("Invalid plan baseline {}: {e}", path.display()))
+        })
+    }
+
+    /// Write the baseline to `.plenum/plans/<name>.json` as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CONFIG_ERROR` when the directory or file cannot be written.
+    pub fn save(&self, project_path: &Path) -> Result<()> {
+        let dir = plans_dir(project_path);
+        std::fs::create_dir_all(&dir).map_err(|e| {
+            PlenumError::config_error(format!("Could not create {}: {e}", dir.display()))
+        })?;
+        let path = project_path.join(baseline_file(&self.name));
+        let mut text = serde_json::to_string_pretty(self).map_err(|e| {
+            PlenumError::config_error(format!("Could not serialize plan baseline: {e}"))
+        })?;
+        text.push('\n');
+        std::fs::write(&path, text).map_err(|e| {
+            PlenumError::config_error(format!("Could not write {}: {e}", path.display()))
+        })
+    }
+}
+
+/// Structured plan of a SELECT, as `--explain-format structured` returns it.
+///
+/// # Errors
+///
+/// `INVALID_INPUT` for anything but a SELECT, and whatever `E::execute` returns for the
+/// structured EXPLAIN.
+pub async fn explain_select<E: DatabaseEngine>(
+    config: &ConnectionConfig,
+    sql: &str,
+    params: &[Value],
+    timeout_ms: Option<u64>,
+) -> Result<ExplainPlanNode> {
+    let outer = outer_query(sql, config.engine);
+    if !outer.is_select {
+        return Err(PlenumError::invalid_input(
+            "Only a SELECT statement (without EXPLAIN) can be planned",
+        ));
+    }
+    let caps = Capabilities {
+        timeout_ms,
+        explain_format: Some(ExplainFormat::Structured),
+        ..Capabilities::default()
+    };
+    let result = E::execute(config, &format!("EXPLAIN {}", outer.body), params, &caps).await?;
+    result
+        .plan
+        .map(|plan| *plan)
+        .ok_or_else(|| PlenumError::query_failed("The engine returned no structured plan"))
+}
+
+/// Structured plan of a saved query, bound with its parameter defaults and run
+/// under its own `timeout_ms`.
+///
+/// # Errors
+///
+/// `INVALID_INPUT` for a required parameter without a default or a statement that is
+/// not a SELECT, and any error from [`explain_select`].
+pub async fn explain_saved<E: DatabaseEngine>(
+    config: &ConnectionConfig,
+    query: &SavedQuery,
+) -> Result<ExplainPlanNode> {
+    let (sql, params) = query.bind_values(BTreeMap::new())?.bind(&query.sql, config.engine)?;
+    explain_select::<E>(config, &sql, &params, query.timeout_ms).await
+}
+
+/// Write `plan` as the baseline of `query`.
+///
+/// # Errors
+///
+/// Returns `CONFIG_ERROR` when the baseline cannot be written.
+pub fn record(
+    project_path: &Path,
+    query: &SavedQuery,
+    engine: DatabaseType,
+    plan: ExplainPlanNode,
+) -> Result<PlanCheck> {
+    let current_cost = plan.estimated_cost;
+    let baseline = PlanBaseline { name: query.name.clone(), engine, sql: query.sql.clone(), plan };
+    baseline.save(project_path)?;
+    Ok(PlanCheck { current_cost, ..entry(&query.name, STATUS_RECORDED) })
+}
+
+/// Compare `plan` with the recorded baseline of `query`.
+///
+/// A root cost more than `cost_threshold` times the baseline's counts as an increase.
+///
+/// # Errors
+///
+/// Returns `CONFIG_ERROR` for an unreadable or malformed baseline file.
+pub fn check(
+    project_path: &Path,
+    query: &SavedQuery,
+    engine: DatabaseType,
+    plan: &ExplainPlanNode,
+    cost_threshold: f64,
+) -> Result<PlanCheck> {
+    let Some(baseline) = PlanBaseline::load(project_path, &query.name)? else {
+        return Ok(PlanCheck {
+            current_cost: plan.estimated_cost,
+            ..entry(&query.name, STATUS_NO_BASELINE)
+        });
+    };
+    if baseline.engine != engine {
+        return Ok(failed(
+            &query.name,
+            &PlenumError::invalid_input(format!(
+                "The baseline was recorded on {}, but the query now runs on {}; record it again",
+                baseline.engine.as_str(),
+                engine.as_str()
+            )),
+        ));
+    }
+
+    let mut changes = Vec::new();
+    diff_nodes(Some(&baseline.plan), Some(plan), "", &mut changes);
+    let baseline_cost = baseline.plan.estimated_cost;
+    let current_cost = plan.estimated_cost;
+    let cost_ratio = match (baseline_cost, current_cost) {
+        (Some(before), Some(after)) if before > 0.0 => Some(after / before),
+        _ => None,
+    };
+    let status = if !changes.is_empty() {
+        STATUS_SHAPE_CHANGED
+    } else if cost_ratio.is_some_and(|r| r > cost_threshold) {
+        STATUS_COST_INCREASED
+    } else {
+        STATUS_UNCHANGED
+    };
+    Ok(PlanCheck {
+        changes,
+        baseline_cost,
+        current_cost,
+        cost_ratio,
+        sql_changed: baseline.sql != query.sql,
+        ..entry(&query.name, status)
+    })
+}
+
+/// Entry of a saved query that could not be explained, compared or recorded
+#[must_use]
+pub fn failed(name: &str, err: &PlenumError) -> PlanCheck {
+    PlanCheck {
+        error: Some(ErrorInfo::new(err.error_code(), err.message())),
+        ..entry(name, STATUS_ERROR)
+    }
+}
+
+/// Collect per-query entries into a report with change and error counts
+#[must_use]
+pub fn report(queries: Vec<PlanCheck>) -> PlanReport {
+    let count = |statuses: &[&str]| {
+        queries.iter().filter(|q| statuses.contains(&q.status.as_str())).count()
+    };
+    let changed = count(&[STATUS_SHAPE_CHANGED, STATUS_COST_INCREASED]);
+    let errors = count(&[STATUS_ERROR]);
+    PlanReport { queries, changed, errors }
+}
+
+fn entry(name: &str, status: &str) -> PlanCheck {
+    PlanCheck {
+        name: name.to_string(),
+        status: status.to_string(),
+        baseline: baseline_file(name).to_string_lossy().into_owned(),
+        changes: Vec::new(),
+        baseline_cost: None,
+        current_cost: None,
+        cost_ratio: None,
+        sql_changed: false,
+        error: None,
+    }
+}
+
+/// Record nodes that differ between `baseline` and `current` at `path`; a differing
+/// node's children are not compared.
+fn diff_nodes(
+    baseline: Option<&ExplainPlanNode>,
+    current: Option<&ExplainPlanNode>,
+    path: &str,
+    changes: &mut Vec<PlanChange>,
+) {
+    let (before, after) = (baseline.map(describe), current.map(describe));
+    if before != after {
+        changes.push(PlanChange { path: path.to_string(), baseline: before, current: after });
+        return;
+    }
+    let (Some(baseline), Some(current)) = (baseline, current) else {
+        return;
+    };
+    for i in 0..baseline.children.len().max(current.children.len()) {
+        diff_nodes(
+            baseline.children.get(i),
+            current.children.get(i),
+            &format!("{path}/children/{i}"),
+            changes,
+        );
+    }
+}
+
+/// "operation on relation using index"; nodes of operation `other` go by their engine
+/// label, and a `DuckDB` catalog prefix is dropped so a moved database file compares equal.
+fn describe(node: &ExplainPlanNode) -> String {
+    let mut text = if node.operation == PlanOperation::Other {
+        node.node_type.clone()
+    } else {
+        serde_json::to_value(node.operation)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default()
+    };
+    if let Some(relation) = &node.relation {
+        let parts: Vec<&str> = relation.splitn(3, '.').collect();
+        let relation = if parts.len() == 3 { &relation[parts[0].len() + 1..] } else { relation };
+        text.push_str(" on ");
+        text.push_str(relation);
+    }
+    if let Some(index) = &node.index {
+        text.push_str(" using ");
+        text.push_str(index);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(
+        operation: PlanOperation,
+        relation: Option<&str>,
+        index: Option<&str>,
+        children: Vec<ExplainPlanNode>,
+    ) -> ExplainPlanNode {
+        ExplainPlanNode {
+            node_type: format!("{operation:?}"),
+            operation,
+            relation: relation.map(str::to_string),
+            index: index.map(str::to_string),
+            filter: None,
+            join_condition: None,
+            estimated_rows: Some(10.0),
+            estimated_cost: Some(100.0),
+            actual_rows: None,
+            actual_time_ms: None,
+            loops: None,
+            buffers: None,
+            children,
+        }
+    }
+
+    fn diff(baseline: &ExplainPlanNode, current: &ExplainPlanNode) -> Vec<PlanChange> {
+        let mut changes = Vec::new();
+        diff_nodes(Some(baseline), Some(current), "", &mut changes);
+        changes
+    }
+
+    #[test]
+    fn test_diff_nodes() {
+        let index_scan = node(PlanOperation::IndexScan, Some("orders"), Some("idx_status"), vec![]);
+        let full_scan = node(PlanOperation::FullScan, Some("orders"), None, vec![]);
+        let sorted = |child| node(PlanOperation::Sort, None, None, vec![child]);
+
+        assert!(diff(&sorted(index_scan.clone()), &sorted(index_scan.clone())).is_empty());
+
+        // Estimates drift without changing the shape
+        let mut drifted = index_scan.clone();
+        drifted.estimated_rows = Some(5000.0);
+        assert!(diff(&index_scan, &drifted).is_empty());
+
+        let changes = diff(&sorted(index_scan.clone()), &sorted(full_scan.clone()));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "/children/0");
+        assert_eq!(changes[0].baseline.as_deref(), Some("index_scan on orders using idx_status"));
+        assert_eq!(changes[0].current.as_deref(), Some("full_scan on orders"));
+
+        // A replaced root is reported once, not per descendant
+        let changes = diff(&sorted(index_scan.clone()), &index_scan);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "");
+
+        // Added and removed children
+        let join = |children| node(PlanOperation::HashJoin, None, None, children);
+        let changes = diff(&join(vec![full_scan.clone()]), &join(vec![full_scan, index_scan]));
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].path, "/children/1");
+        assert_eq!(changes[0].baseline, None);
+
+        // DuckDB catalogs name the database file
+        let moved = node(PlanOperation::FullScan, Some("old.main.t"), None, vec![]);
+        let here = node(PlanOperation::FullScan, Some("new.main.t"), None, vec![]);
+        assert!(diff(&moved, &here).is_empty());
+        assert_eq!(describe(&here), "full_scan on main.t");
+
+        // Uncategorized nodes compare by engine label
+        let mut gather = node(PlanOperation::Other, None, None, vec![]);
+        gather.node_type = "Gather".to_string();
+        let mut gather_merge = gather.clone();
+        gather_merge.node_type = "Gather Merge".to_string();
+        assert_eq!(diff(&gather, &gather_merge).len(), 1);
+    }
+
+    #[test]
+    fn test_check_against_baseline() {
+        let project = std::env::temp_dir().join(format!("plenum_plans_{}", std::process::id()));
+        let query_path = project.join("orders.sql");
+        let query = SavedQuery::parse("SELECT * FROM orders", &query_path).unwrap();
+        let scan = node(PlanOperation::FullScan, Some("orders"), None, vec![]);
+
+        let missing = check(&project, &query, DatabaseType::Postgres, &scan, 1.5).unwrap();
+        assert_eq!(missing.status, STATUS_NO_BASELINE);
+
+        record(&project, &query, DatabaseType::Postgres, scan.clone()).unwrap();
+        let mut pricier = scan.clone();
+        pricier.estimated_cost = Some(140.0);
+        let same = check(&project, &query, DatabaseType::Postgres, &pricier, 1.5).unwrap();
+        assert_eq!(same.status, STATUS_UNCHANGED);
+        assert_eq!(same.cost_ratio, Some(1.4));
+
+        pricier.estimated_cost = Some(200.0);
+        let edited = SavedQuery::parse("SELECT * FROM orders -- edited", &query_path).unwrap();
+        let increased = check(&project, &edited, DatabaseType::Postgres, &pricier, 1.5).unwrap();
+        assert_eq!(increased.status, STATUS_COST_INCREASED);
+        assert!(increased.sql_changed);
+
+        let other_engine = check(&project, &query, DatabaseType::MySQL, &scan, 1.5).unwrap();
+        assert_eq!(other_engine.status, STATUS_ERROR);
+
+        let _ = std::fs::remove_dir_all(&project);
+    }
+
+    #[test]
+    fn test_report_counts() {
+        let err = PlenumError::invalid_input("Saved query 'x' requires parameter 'y'");
+        let report = report(vec![
+            entry("a", STATUS_UNCHANGED),
+            entry("b", STATUS_SHAPE_CHANGED),
+            failed("c", &err),
+            entry("d", STATUS_NO_BASELINE),
+            entry("e", STATUS_COST_INCREASED),
+        ]);
+        assert_eq!((report.changed, report.errors), (2, 1));
+        assert_eq!(report.queries[2].error.as_ref().unwrap().code, "INVALID_INPUT");
+        assert_eq!(report.queries[0].baseline, baseline_file("a").to_string_lossy());
+    }
+}
@@ -0,0 +1,532 @@
+//! Project-level saved queries (`.plenum/queries/*.sql`)
+//!
+//! A saved query is a plain SQL file whose leading `--` comment lines declare how
+//! it is run, so the file stays valid SQL for any other client:
+//!
+//! ```sql
+//! -- name: orders_by_status
+//! -- description: Orders in one status, newest first
+//! -- connection: prod
+//! -- param: status text = open  # Order status to filter on
+//! -- param: since timestamp     # Only orders placed after this instant
+//! -- max_rows: 100
+//! -- timeout_ms: 5000
+//! SELECT id, total FROM orders WHERE status = :status AND placed_at > :since
+//! ```
+//!
+//! Recognized keys are `name` (defaults to the file stem), `description`,
+//! `connection`, `param`, `max_rows`, `max_bytes` and `timeout_ms`; other comment
+//! lines are ordinary comments. A `param` takes an optional type from
+//! [`ParamType`] (append `[]` for an array), an optional `= default` and an
+//! optional `# description`; a parameter without a default is required. The
+//! whole file, header included, is the SQL that runs.
+//!
+//! `plenum query --saved <name>` runs a saved query and the MCP server exposes
+//! each one as a `saved_<name>` tool (see [`SavedQuery::input_schema`]).
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::error::{PlenumError, Result};
+use crate::params::{is_param_name, parse_value, ParamType, ParamValue, QueryParams};
+
+/// Prefix of the MCP tool generated for each saved query
+pub const SAVED_TOOL_PREFIX: &str = "saved_";
+
+/// Arguments of a generated MCP tool that are not query parameters
+pub const RESERVED_ARGS: &[&str] = &["connection", "max_rows", "max_bytes", "timeout_ms"];
+
+/// MCP tool names are limited to 64 characters
+const MAX_NAME_LEN: usize = 64 - SAVED_TOOL_PREFIX.len();
+
+/// A saved query loaded from `.plenum/queries/`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SavedQuery {
+    /// Query name (letters, digits, `_` and `-`)
+    pub name: String,
+
+    /// What the query answers; shown as the MCP tool description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Saved connection to run against unless the caller names another
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<String>,
+
+    /// Declared `:name` parameters, in declaration order
+    pub params: Vec<SavedParam>,
+
+    /// Default `max_rows` when the caller passes none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_rows: Option<usize>,
+
+    /// Default `max_bytes` when the caller passes none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<usize>,
+
+    /// Default `timeout_ms` when the caller passes none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+
+    /// File contents, header comments included
+    pub sql: String,
+
+    /// File the query was loaded from
+    pub path: PathBuf,
+}
+
+/// A declared parameter of a saved query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SavedParam {
+    /// Placeholder name, without the colon
+    pub name: String,
+
+    /// Canonical type name (`int`, `uuid`, `text[]`, ...); untyped values bind as given
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+
+    /// Value used when the caller omits the parameter (text, as written in the header)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+
+    /// What the parameter means
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Directory holding a project's saved queries
+#[must_use]
+pub fn queries_dir(project_path: &Path) -> PathBuf {
+    project_path.join(".plenum").join("queries")
+}
+
+/// Load every `*.sql` file in the project's queries directory, sorted by name.
+///
+/// A missing directory yields no queries.
+///
+/// # Errors
+///
+/// Returns `CONFIG_ERROR` for an unreadable file, a malformed header or two files
+/// declaring the same name.
+pub fn load_saved_queries(project_path: &Path) -> Result<Vec<SavedQuery>> {
+    let (queries, mut skipped) = load_saved_queries_skipping_errors(project_path);
+    if skipped.is_empty() {
+        Ok(queries)
+    } else {
+        Err(skipped.swap_remove(0))
+    }
+}
+
+/// Load the project's saved queries, skipping the files that fail to load.
+///
+/// Returns the queries that loaded, sorted by name, and one `CONFIG_ERROR` per
+/// skipped file: an unreadable file, a malformed header, or a name already
+/// declared by another file (files are read in path order, so the first wins).
+#[must_use]
+pub fn load_saved_queries_skipping_errors(
+    project_path: &Path,
+) -> (Vec<SavedQuery>, Vec<PlenumError>) {
+    let dir = queries_dir(project_path);
+    if !dir.is_dir() {
+        return (Vec::new(), Vec::new());
+    }
+    let unreadable = |path: &Path, e: std::io::Error| {
+        PlenumError::config_error(format!("Could not read {}: {e}", path.display()))
+    };
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) => return (Vec::new(), vec![unreadable(&dir, e)]),
+    };
+
+    let mut queries: Vec<SavedQuery> = Vec::new();
+    let mut skipped = Vec::new();
+    let mut paths = Vec::new();
+    for entry in entries {
+        match entry {
+            Ok(entry) => paths.push(entry.path()),
+            Err(e) => skipped.push(unreadable(&dir, e)),
+        }
+    }
+    paths.retain(|path| path.extension().and_then(|e| e.to_str()) == Some("sql"));
+    paths.sort();
+    for path in paths {
+        let loaded = std::fs::read_to_string(&path)
+            .map_err(|e| unreadable(&path, e))
+            .and_then(|text| SavedQuery::parse(&text, &path));
+        match loaded {
+            Ok(query) => match queries.iter().find(|q| q.name == query.name) {
+                Some(other) => skipped.push(PlenumError::config_error(format!(
+                    "Saved query name '{}' is declared by both {} and {}",
+                    query.name,
+                    other.path.display(),
+                    path.display()
+                ))),
+                None => queries.push(query),
+            },
+            Err(e) => skipped.push(e),
+        }
+    }
+    queries.sort_by(|a, b| a.name.cmp(&b.name));
+    (queries, skipped)
+}
+
+/// Load the saved query called `name`; files that fail to load do not hide the others.
+///
+/// # Errors
+///
+/// Returns `INVALID_INPUT` listing the available names when there is no such query,
+/// or `CONFIG_ERROR` naming the skipped files when the query may be in one of them.
+pub fn find_saved_query(project_path: &Path, name: &str) -> Result<SavedQuery> {
+    let (queries, skipped) = load_saved_queries_skipping_errors(project_path);
+    let available: Vec<&str> = queries.iter().map(|q| q.name.as_str()).collect();
+    let reasons: Vec<String> = skipped.iter().map(PlenumError::message).collect();
+    let message = format!(
+        "Saved query '{name}' not found in {}. Available: {available:?}{}",
+        queries_dir(project_path).display(),
+        if reasons.is_empty() {
+            String::new()
+        } else {
+            format!(". Skipped files that failed to load: {}", reasons.join("; "))
+        }
+    );
+    match queries.into_iter().find(|q| q.name == name) {
+        Some(query) => Ok(query),
+        None if skipped.is_empty() => Err(PlenumError::invalid_input(message)),
+        None => Err(PlenumError::config_error(message)),
+    }
+}
+
+impl SavedQuery {
+    /// Parse a saved query file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CONFIG_ERROR` naming the file for an invalid name, parameter
+    /// declaration or cap value.
+    pub fn parse(text: &str, path: &Path) -> Result<Self> {
+        let bad = |msg: String| PlenumError::config_error(format!("{}: {msg}", path.display()));
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+        let mut query = Self {
+            name: stem.to_string(),
+            description: None,
+            connection: None,
+            params: Vec::new(),
+            max_rows: None,
+            max_bytes: None,
+            timeout_ms: None,
+            sql: text.to_string(),
+            path: path.to_path_buf(),
+        };
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+            let Some(comment) = line.strip_prefix("--") else {
+                break;
+            };
+            let Some((key, value)) = comment.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            let number = |key: &str| {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| bad(format!("{key} must be a number, got '{value}'")))
+            };
+            match key.trim().to_ascii_lowercase().as_str() {
+                "name" => value.clone_into(&mut query.name),
+                "description" => query.description = Some(value.to_string()),
+                "connection" => query.connection = Some(value.to_string()),
+                "param" => {
+                    let param = SavedParam::parse(value).map_err(bad)?;
+                    if RESERVED_ARGS.contains(&param.name.as_str()) {
+                        return Err(bad(format!(
+                            "parameter name '{}' is reserved for the MCP tool's own argument",
+                            param.name
+                        )));
+                    }
+                    if query.params.iter().any(|p| p.name == param.name) {
+                        return Err(bad(format!("parameter '{}' declared twice", param.name)));
+                    }
+                    query.params.push(param);
+                }
+                "max_rows" => query.max_rows = Some(number("max_rows")? as usize),
+                "max_bytes" => query.max_bytes = Some(number("max_bytes")? as usize),
+                "timeout_ms" => query.timeout_ms = Some(number("timeout_ms")?),
+                _ => {}
+            }
+        }
+
+        let valid_name = !query.name.is_empty()
+            && query.name.len() <= MAX_NAME_LEN
+            && query.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid_name {
+            return Err(bad(format!(
+                "invalid saved query name '{}': use up to {MAX_NAME_LEN} letters, digits, '_' or '-'",
+                query.name
+            )));
+        }
+        Ok(query)
+    }
+
+    /// Name of the MCP tool that runs this query
+    #[must_use]
+    pub fn tool_name(&self) -> String {
+        format!("{SAVED_TOOL_PREFIX}{}", self.name)
+    }
+
+    /// Bind `--param name=value` flags: declared types apply to the raw text, and
+    /// untyped parameters are parsed like any other `--param` value.
+    ///
+    /// # Errors
+    ///
+    /// See [`SavedQuery::bind_values`]; also `INVALID_INPUT` for a flag without `=`.
+    pub fn params_from_cli(&self, raw: &[String]) -> Result<QueryParams> {
+        let mut values = BTreeMap::new();
+        for entry in raw {
+            let (name, value) = entry.split_once('=').ok_or_else(|| {
+                PlenumError::invalid_input(format!(
+                    "--param '{entry}' must be name=value for saved query '{}'",
+                    self.name
+                ))
+            })?;
+            let typed = self.params.iter().any(|p| p.name == name && p.type_name.is_some());
+            let value = if typed { Value::String(value.to_string()) } else { parse_value(value) };
+            if values.insert(name.to_string(), value).is_some() {
+                return Err(PlenumError::invalid_input(format!("Parameter '{name}' given twice")));
+            }
+        }
+        self.bind_values(values)
+    }
+
+    /// Combine caller values with defaults into named parameters, tagging each value
+    /// with its declared type.
+    ///
+    /// # Errors
+    ///
+    /// Returns `INVALID_INPUT` for an undeclared parameter or a missing required one.
+    pub fn bind_values(&self, mut values: BTreeMap<String, Value>) -> Result<QueryParams> {
+        if let Some(unknown) = values.keys().find(|k| !self.params.iter().any(|p| &p.name == *k)) {
+            let declared: Vec<&str> = self.params.iter().map(|p| p.name.as_str()).collect();
+            return Err(PlenumError::invalid_input(format!(
+                "Saved query '{}' has no parameter '{unknown}'. Declared: {declared:?}",
+                self.name
+            )));
+        }
+
+        let mut named = BTreeMap::new();
+        for param in &self.params {
+            let value = match (values.remove(&param.name), &param.default) {
+                (Some(value), _) => value,
+                (None, Some(default)) if param.type_name.is_some() => {
+                    Value::String(default.clone())
+                }
+                (None, Some(default)) => parse_value(default),
+                (None, None) => {
+                    return Err(PlenumError::invalid_input(format!(
+                        "Saved query '{}' requires parameter '{}'",
+                        self.name, param.name
+                    )));
+                }
+            };
+            let value = match &param.type_name {
+                Some(ty) if ParamValue::from_json(&value).is_none() => {
+                    json!({ "$type": ty, "value": value })
+                }
+                _ => value,
+            };
+            named.insert(param.name.clone(), value);
+        }
+        Ok(QueryParams::Named(named))
+    }
+
+    /// JSON Schema for the generated MCP tool: one property per parameter plus
+    /// `connection`, `max_rows`, `max_bytes` and `timeout_ms` overrides.
+    #[must_use]
+    pub fn input_schema(&self) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for param in &self.params {
+            let mut schema = param.json_schema();
+            let mut description: Vec<String> = param.description.iter().cloned().collect();
+            match &param.default {
+                Some(default) => description.push(format!("Default: {default}.")),
+                None => required.push(Value::String(param.name.clone())),
+            }
+            if !description.is_empty() {
+                schema.insert("description".into(), Value::String(description.join(" ")));
+            }
+            properties.insert(param.name.clone(), Value::Object(schema));
+        }
+
+        let connection = self.connection.as_deref().map_or_else(
+            || "Optional: Saved connection to run against. Default: the project's default connection.".to_string(),
+            |c| format!("Optional: Saved connection to run against instead of '{c}'."),
+        );
+        properties
+            .insert("connection".into(), json!({ "type": "string", "description": connection }));
+        let caps = [
+            ("max_rows", self.max_rows.map(|n| n as u64), "Maximum rows to return"),
+            ("max_bytes", self.max_bytes.map(|n| n as u64), "Maximum serialized bytes of rows"),
+            ("timeout_ms", self.timeout_ms, "Query timeout in milliseconds"),
+        ];
+        for (key, default, what) in caps {
+            let description = default.map_or_else(
+                || format!("Optional: {what}."),
+                |n| format!("Optional: {what}. Default: {n}."),
+            );
+            properties.insert(key.into(), json!({ "type": "number", "description": description }));
+        }
+
+        json!({ "type": "object", "properties": properties, "required": required })
+    }
+}
+
+impl SavedParam {
+    /// Parse `name [type] [= default] [# description]`.
+    fn parse(spec: &str) -> std::result::Result<Self, String> {
+        let (spec, description) = match spec.split_once('#') {
+            Some((spec, description)) => (spec, Some(description.trim().to_string())),
+            None => (spec, None),
+        };
+        let (head, default) = match spec.split_once('=') {
+            Some((head, default)) => (head, Some(default.trim().to_string())),
+            None => (spec, None),
+        };
+        let mut words = head.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        if !is_param_name(name) {
+            return Err(format!("invalid parameter name '{name}' in 'param: {spec}'"));
+        }
+        let type_name = match words.next() {
+            None => None,
+            Some(ty) => {
+                let (base, array) = ty.strip_suffix("[]").map_or((ty, false), |b| (b, true));
+                let base = ParamType::from_name(base).ok_or_else(|| {
+                    format!(
+                        "unknown type '{ty}' for parameter '{name}'; expected one of bool, int, \
+                         float, decimal, text, uuid, date, time, timestamp, json, bytes"
+                    )
+                })?;
+                Some(if array { format!("{}[]", base.as_str()) } else { base.as_str().to_string() })
+            }
+        };
+        if let Some(extra) = words.next() {
+            return Err(format!("unexpected '{extra}' in declaration of parameter '{name}'"));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            type_name,
+            default,
+            description: description.filter(|d| !d.is_empty()),
+        })
+    }
+
+    /// JSON Schema for the parameter's value
+    fn json_schema(&self) -> Map<String, Value> {
+        let Some(type_name) = &self.type_name else {
+            return Map::new();
+        };
+        let (base, array) =
+            type_name.strip_suffix("[]").map_or((type_name.as_str(), false), |b| (b, true));
+        let scalar = match ParamType::from_name(base) {
+            Some(ParamType::Bool) => json!({ "type": "boolean" }),
+            Some(ParamType::Int) => json!({ "type": "integer" }),
+            Some(ParamType::Float) => json!({ "type": "number" }),
+            Some(ParamType::Decimal) => json!({ "type": ["string", "number"] }),
+            Some(ParamType::Uuid) => json!({ "type": "string", "format": "uuid" }),
+            Some(ParamType::Date) => json!({ "type": "string", "format": "date" }),
+            Some(ParamType::Time) => json!({ "type": "string", "format": "time" }),
+            Some(ParamType::Timestamp) => json!({ "type": "string", "format": "date-time" }),
+            Some(ParamType::Bytes) => json!({ "type": "string", "contentEncoding": "base64" }),
+            Some(ParamType::Json) => json!({}),
+            Some(ParamType::Text) | None => json!({ "type": "string" }),
+        };
+        let schema = if array { json!({ "type": "array", "items": scalar }) } else { scalar };
+        schema.as_object().cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDERS: &str = "-- description: Orders in one status\n\
+                          -- connection: prod\n\
+                          -- param: status text = open  # Order status\n\
+                          -- param: ids int[]\n\
+                          -- param: since\n\
+                          -- max_rows: 100\n\
+                          \n\
+                          -- Note: the header ends at the first SQL line\n\
+                          SELECT id FROM orders\n\
+                          -- max_rows: 5\n\
+                          WHERE status = :status AND id = ANY(:ids) AND placed_at > :since";
+
+    fn orders() -> SavedQuery {
+        SavedQuery::parse(ORDERS, Path::new("/p/.plenum/queries/orders_by_status.sql"))
+            .expect("parse")
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let query = orders();
+        assert_eq!(query.name, "orders_by_status", "name defaults to the file stem");
+        assert_eq!(query.description.as_deref(), Some("Orders in one status"));
+        assert_eq!(query.connection.as_deref(), Some("prod"));
+        assert_eq!(query.max_rows, Some(100), "keys after the first SQL line are ignored");
+        assert_eq!(
+            query.params[0],
+            SavedParam {
+                name: "status".into(),
+                type_name: Some("text".into()),
+                default: Some("open".into()),
+                description: Some("Order status".into()),
+            }
+        );
+        assert_eq!(query.params[1].type_name.as_deref(), Some("int[]"));
+        assert_eq!(query.params[2].type_name, None);
+        assert_eq!(query.sql, ORDERS);
+
+        let path = Path::new("q.sql");
+        for bad in ["-- param: x varchar", "-- param: 1x int", "-- max_rows: ten", "-- name: a b"] {
+            let err = SavedQuery::parse(bad, path).unwrap_err();
+            assert_eq!(err.error_code(), "CONFIG_ERROR", "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_bind_applies_types_and_defaults() {
+        let query = orders();
+        let raw = ["ids=[1,2]".to_string(), "since=42".to_string()];
+        let QueryParams::Named(named) = query.params_from_cli(&raw).expect("bind") else {
+            panic!("saved queries bind by name");
+        };
+        assert_eq!(named["status"], json!({ "$type": "text", "value": "open" }));
+        assert_eq!(named["ids"], json!({ "$type": "int[]", "value": "[1,2]" }));
+        assert_eq!(named["since"], json!(42), "untyped values parse like --param");
+
+        let missing = query.params_from_cli(&["since=1".to_string()]).unwrap_err();
+        assert!(missing.to_string().contains("requires parameter 'ids'"), "{missing}");
+        let unknown = query.bind_values(BTreeMap::from([("nope".to_string(), json!(1))]));
+        assert_eq!(unknown.unwrap_err().error_code(), "INVALID_INPUT");
+    }
+
+    #[test]
+    fn test_input_schema() {
+        let schema = orders().input_schema();
+        assert_eq!(schema["required"], json!(["ids", "since"]));
+        assert_eq!(schema["properties"]["status"]["type"], "string");
+        assert_eq!(schema["properties"]["status"]["description"], "Order status Default: open.");
+        assert_eq!(schema["properties"]["ids"]["items"]["type"], "integer");
+        assert_eq!(schema["properties"]["since"], json!({}));
+        assert_eq!(
+            schema["properties"]["max_rows"]["description"],
+            "Optional: Maximum rows to return. Default: 100."
+        );
+    }
+}
@@ -236,6 +236,8 @@ fn connect_test_reachable_sqlite_returns_connection_info() {
         "connected_database present"
     );
     assert!(data.get("user").and_then(Value::as_str).is_some(), "user present");
+    // SQLite has no credentials to audit, so no least-privilege warnings are emitted
+    assert!(data.get("warnings").is_none(), "no warnings for file-based engine: {data}");
 
     // --test must not write a config file
     assert!(!dir.join(".plenum").join("config.json").exists(), "no config saved in test mode");
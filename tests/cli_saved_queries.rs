@@ -0,0 +1,321 @@
+//! CLI and MCP coverage for saved queries (`.plenum/queries/*.sql`).
+//!
+//! Drives the compiled `plenum` binary in a scratch project with two saved
+//! `SQLite` connections and asserts that:
+//!   - `plenum query --saved <name>` runs on the header's connection with its
+//!     typed parameter defaults and caps, and flags override them,
+//!   - missing, undeclared and mistyped parameters fail before execution,
+//!   - `plenum mcp` lists each saved query as a `saved_<name>` tool with a
+//!     generated input schema, and calling it returns query rows; a malformed
+//!     file is skipped without hiding the others,
+//!   - `plenum plan record` / `check` store plan baselines and report a full scan
+//!     turning into an index scan once an index appears.
+
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+const EVENTS_BY_KIND: &str = "\
+-- description: Events of one kind above a severity
+-- connection: archive
+-- param: kind text            # Event kind
+-- param: min_severity int = 2 # Lowest severity to include
+-- max_rows: 2
+SELECT id, kind, severity FROM events
+WHERE kind = :kind AND severity >= :min_severity
+ORDER BY id
+";
+
+fn unique_tmp_dir(tag: &str) -> PathBuf {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+    let pid = std::process::id();
+    let dir = std::env::temp_dir().join(format!("plenum_saved_queries_{tag}_{pid}_{id}"));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("create scratch dir");
+    dir
+}
+
+/// Create `events` in a fresh `SQLite` file: ids 1..=`rows`, kinds alternating
+/// "login" / "logout", severity `id % 4`.
+fn create_events_db(path: &Path, rows: i64) {
+    let conn = rusqlite::Connection::open(path).expect("create sqlite db");
+    conn.execute_batch("CREATE TABLE events (id INTEGER PRIMARY KEY, kind TEXT, severity INTEGER)")
+        .expect("create events");
+    for id in 1..=rows {
+        let kind = if id % 2 == 0 { "logout" } else { "login" };
+        conn.execute("INSERT INTO events VALUES (?1, ?2, ?3)", rusqlite::params![id, kind, id % 4])
+            .expect("insert event");
+    }
+}
+
+/// Scratch project: `main` (default, 4 events) and `archive` (20 events)
+/// connections plus `.plenum/queries/events_by_kind.sql`.
+fn scratch_project(tag: &str) -> PathBuf {
+    let dir = unique_tmp_dir(tag);
+    create_events_db(&dir.join("main.db"), 4);
+    create_events_db(&dir.join("archive.db"), 20);
+    let config = json!({
+        "connections": {
+            "main": { "engine": "sqlite", "file": dir.join("main.db") },
+            "archive": { "engine": "sqlite", "file": dir.join("archive.db") }
+        },
+        "default": "main"
+    });
+    std::fs::create_dir_all(dir.join(".plenum").join("queries")).expect("create queries dir");
+    std::fs::write(dir.join(".plenum").join("config.json"), config.to_string())
+        .expect("write config");
+    std::fs::write(dir.join(".plenum").join("queries").join("events_by_kind.sql"), EVENTS_BY_KIND)
+        .expect("write saved query");
+    dir
+}
+
+fn plenum(cwd: &Path) -> Command {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_plenum"));
+    cmd.current_dir(cwd);
+    // Keep any global config lookups inside the scratch dir
+    cmd.env("HOME", cwd);
+    cmd.env("XDG_CONFIG_HOME", cwd);
+    cmd
+}
+
+/// Run `plenum query --saved ...`; returns (exit code, parsed envelope).
+fn run_saved(cwd: &Path, args: &[&str]) -> (i32, Value) {
+    let output = plenum(cwd).arg("query").arg("--saved").args(args).output().expect("spawn");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let envelope = serde_json::from_str(stdout.trim())
+        .unwrap_or_else(|e| panic!("non-JSON stdout {stdout:?}: {e}"));
+    (output.status.code().unwrap_or(-1), envelope)
+}
+
+/// Run `plenum plan ...`; returns (exit code, parsed envelope).
+fn run_plan(cwd: &Path, args: &[&str]) -> (i32, Value) {
+    let output = plenum(cwd).arg("plan").args(args).output().expect("spawn");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let envelope = serde_json::from_str(stdout.trim())
+        .unwrap_or_else(|e| panic!("non-JSON stdout {stdout:?}: {e}"));
+    (output.status.code().unwrap_or(-1), envelope)
+}
+
+fn ids(envelope: &Value) -> Vec<i64> {
+    envelope["data"]["rows"]
+        .as_array()
+        .expect("rows")
+        .iter()
+        .map(|row| row[0].as_i64().expect("id"))
+        .collect()
+}
+
+#[test]
+fn saved_query_runs_with_header_connection_defaults_and_caps() {
+    let dir = scratch_project("run");
+
+    // archive has login events 1, 3, 5, ...; severity >= 2 keeps 3, 7, 11, ...
+    let (code, envelope) = run_saved(&dir, &["events_by_kind", "--param", "kind=login"]);
+    assert_eq!(code, 0, "{envelope}");
+    assert_eq!(ids(&envelope), [3, 7], "header max_rows caps the page");
+    assert_eq!(envelope["meta"]["rows_truncated"], true);
+
+    // Flags override the header: another connection, parameter and cap
+    let (code, envelope) = run_saved(
+        &dir,
+        &[
+            "events_by_kind",
+            "--name",
+            "main",
+            "--param",
+            "kind=login",
+            "--param",
+            "min_severity=0",
+            "--max-rows",
+            "10",
+        ],
+    );
+    assert_eq!(code, 0, "{envelope}");
+    assert_eq!(ids(&envelope), [1, 3]);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn saved_query_rejects_bad_parameters_and_unknown_names() {
+    let dir = scratch_project("errors");
+    let cases: [&[&str]; 4] = [
+        &["events_by_kind"],
+        &["events_by_kind", "--param", "kind=login", "--param", "min_severity=high"],
+        &["events_by_kind", "--param", "kind=login", "--param", "limit=5"],
+        &["no_such_query"],
+    ];
+    for args in cases {
+        let (code, envelope) = run_saved(&dir, args);
+        assert_ne!(code, 0, "{args:?} must fail");
+        assert_eq!(envelope["error"]["code"], "INVALID_INPUT", "{args:?}: {envelope}");
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// Send `requests` to `plenum mcp`; returns the JSON-RPC responses and stderr.
+fn run_mcp(cwd: &Path, requests: &[Value]) -> (Vec<Value>, String) {
+    let mut child = plenum(cwd)
+        .arg("mcp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn plenum mcp");
+
+    let mut stdin = child.stdin.take().expect("stdin");
+    for request in requests {
+        writeln!(stdin, "{request}").unwrap();
+    }
+    drop(stdin);
+
+    let responses: Vec<Value> = BufReader::new(child.stdout.take().expect("stdout"))
+        .lines()
+        .map(|line| serde_json::from_str(&line.expect("read stdout")).expect("JSON-RPC line"))
+        .collect();
+    let output = child.wait_with_output().expect("wait for plenum mcp");
+    (responses, String::from_utf8_lossy(&output.stderr).into_owned())
+}
+
+fn call_events_by_kind(id: u64) -> Value {
+    json!({
+        "jsonrpc": "2.0", "id": id, "method": "tools/call",
+        "params": {
+            "name": "saved_events_by_kind",
+            "arguments": { "kind": "logout", "min_severity": 1, "max_rows": 3 }
+        }
+    })
+}
+
+fn tool_names(response: &Value) -> Vec<&str> {
+    response["result"]["tools"]
+        .as_array()
+        .expect("tools")
+        .iter()
+        .filter_map(|t| t["name"].as_str())
+        .collect()
+}
+
+#[test]
+fn mcp_exposes_saved_queries_as_tools() {
+    let dir = scratch_project("mcp");
+    let requests = [
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list", "params": {} }),
+        call_events_by_kind(2),
+    ];
+    let (responses, _) = run_mcp(&dir, &requests);
+
+    let tool = responses[0]["result"]["tools"]
+        .as_array()
+        .expect("tools")
+        .iter()
+        .find(|t| t["name"] == "saved_events_by_kind")
+        .expect("saved query tool listed");
+    let schema = &tool["inputSchema"];
+    assert_eq!(schema["required"], json!(["kind"]));
+    assert_eq!(schema["properties"]["min_severity"]["type"], "integer");
+    assert_eq!(
+        schema["properties"]["max_rows"]["description"],
+        "Optional: Maximum rows to return. Default: 2."
+    );
+
+    // archive logout events have even ids with severity 2 or 0; max_rows overrides
+    // the header's 2 and the integer argument binds as an integer
+    let text = responses[1]["result"]["content"][0]["text"].as_str().expect("tool result text");
+    let result: Value = serde_json::from_str(text).expect("query result JSON");
+    assert_eq!(result["columns"], json!(["id", "kind", "severity"]));
+    assert_eq!(result["rows"], json!([[2, "logout", 2], [6, "logout", 2], [10, "logout", 2]]));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn mcp_skips_only_malformed_saved_query_files() {
+    let dir = scratch_project("mcp_malformed");
+    let queries = dir.join(".plenum").join("queries");
+    std::fs::write(queries.join("broken.sql"), "-- max_rows: lots\nSELECT 1\n")
+        .expect("write malformed saved query");
+    std::fs::write(queries.join("all_events.sql"), "SELECT id FROM events\n")
+        .expect("write saved query");
+
+    let requests = [
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list", "params": {} }),
+        call_events_by_kind(2),
+    ];
+    let (responses, stderr) = run_mcp(&dir, &requests);
+
+    let names = tool_names(&responses[0]);
+    assert!(names.contains(&"query"), "built-in tools stay listed: {names:?}");
+    assert!(names.contains(&"saved_events_by_kind"), "{names:?}");
+    assert!(names.contains(&"saved_all_events"), "{names:?}");
+    assert!(!names.iter().any(|n| n.contains("broken")), "{names:?}");
+    assert!(stderr.contains("broken.sql"), "the skipped file is reported: {stderr:?}");
+
+    let text = responses[1]["result"]["content"][0]["text"].as_str().expect("tool result text");
+    let result: Value = serde_json::from_str(text).expect("query result JSON");
+    assert_eq!(result["rows"].as_array().map(Vec::len), Some(3), "{result}");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn plan_baselines_report_shape_changes() {
+    let dir = scratch_project("plan");
+    std::fs::write(
+        dir.join(".plenum").join("queries").join("logins.sql"),
+        "-- param: kind text = login\nSELECT id FROM events WHERE kind = :kind\n",
+    )
+    .expect("write saved query");
+
+    let (code, envelope) = run_plan(&dir, &["check", "--saved", "logins"]);
+    assert_eq!(code, 0, "{envelope}");
+    assert_eq!(envelope["data"]["queries"][0]["status"], "no_baseline");
+
+    // events_by_kind has a required parameter without a default, so it cannot be planned
+    let (code, envelope) = run_plan(&dir, &["record"]);
+    assert_eq!(code, 0, "{envelope}");
+    let queries = &envelope["data"]["queries"];
+    assert_eq!(queries[0]["name"], "events_by_kind");
+    assert_eq!(queries[0]["status"], "error");
+    assert_eq!(queries[0]["error"]["code"], "INVALID_INPUT");
+    assert_eq!(queries[1]["status"], "recorded");
+    assert_eq!(envelope["data"]["errors"], 1);
+    assert!(dir.join(".plenum").join("plans").join("logins.json").is_file());
+
+    let (_, envelope) = run_plan(&dir, &["check", "--saved", "logins"]);
+    assert_eq!(envelope["data"]["queries"][0]["status"], "unchanged", "{envelope}");
+    assert_eq!(envelope["data"]["changed"], 0);
+
+    rusqlite::Connection::open(dir.join("main.db"))
+        .expect("open sqlite db")
+        .execute_batch("CREATE INDEX idx_events_kind ON events (kind)")
+        .expect("create index");
+    let (code, envelope) = run_plan(&dir, &["check", "--saved", "logins"]);
+    assert_eq!(code, 1, "a changed plan fails the check: {envelope}");
+    assert_eq!(envelope["ok"], false);
+    let query = &envelope["data"]["queries"][0];
+    assert_eq!(query["status"], "shape_changed", "{envelope}");
+    assert_eq!(query["changes"][0]["path"], "/children/0");
+    assert_eq!(query["changes"][0]["baseline"], "full_scan on events");
+    assert!(
+        query["changes"][0]["current"].as_str().is_some_and(|c| c.contains("idx_events_kind")),
+        "{envelope}"
+    );
+    assert_eq!(envelope["data"]["changed"], 1);
+
+    // The archive connection still has no index: same plan as the baseline
+    let (code, envelope) = run_plan(&dir, &["check", "--saved", "logins", "--name", "archive"]);
+    assert_eq!(code, 0, "{envelope}");
+    assert_eq!(envelope["ok"], true);
+    assert_eq!(envelope["data"]["queries"][0]["status"], "unchanged", "{envelope}");
+
+    let (code, envelope) = run_plan(&dir, &["check", "--saved", "no_such_query"]);
+    assert_ne!(code, 0);
+    assert_eq!(envelope["error"]["code"], "INVALID_INPUT");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -13,20 +13,47 @@
 //!   introspect   — tables, columns + native types, PK, composite FK, indexes,
 //!                  views, schemas, comments, row estimates; stable JSON shape
 //!   query allowed — SELECT, EXPLAIN, EXPLAIN (FORMAT JSON), SHOW, DESCRIBE,
-//!                  SUMMARIZE, PRAGMA allowlist, transaction control
+//!                  SUMMARIZE, PRAGMA allowlist, transaction control; structured
+//!                  EXPLAIN operations, filters and join conditions; EXPLAIN
+//!                  ANALYZE reports actual rows and timings per operator
 //!   query denied  — INSERT / UPDATE / DELETE / CREATE / DROP / ALTER / COPY /
 //!                  ATTACH → `CAPABILITY_VIOLATION` before execution, then
 //!                  re-query to prove DB state unchanged
 //!   safety       — `max_rows` truncation + `rows_truncated` flag; `timeout_ms`
 //!                  (interrupt handle) tested for fast and long queries
+//!   large values — `--binary` applied inside LIST / STRUCT values;
+//!                  `--max-cell-bytes` on text, HUGEINT and serialized lists
+//!   streaming    — `execute_stream` header types, offset/`max_rows` window,
+//!                  sink refusal → `truncated_by=bytes`
+//!   params       — `:name` placeholders rewritten to `?` and bound per occurrence;
+//!                  typed values (date, timestamp, bytes, uuid, decimal, int[])
+//!   cursor       — keyset pages over a composite PK and a unique string index;
+//!                  no cursor for tables without a unique key
+//!   ordering     — `--deterministic` on a table without a primary key: wrapped
+//!                  ORDER BY for truncated pages, in-memory sort of LIST values
+//!   data diff    — composite-key rows added / changed; a `SQLite` copy diffed
+//!                  against `DuckDB` with named params bound per engine
+//!   advise       — equality filter on a large table → `CREATE INDEX`; ranges,
+//!                  existing indexes and small tables → none
+//!   summary      — `--summarize` types, bounds and examples; every row read
+//!   export       — `ExportWriter` files read back with their column types
 //!   envelope     — `QueryResult` / `IntrospectResult` serialize to valid JSON;
 //!                  deterministic with `execution_ms` excluded
 
 #![cfg(feature = "duckdb")]
 
+use plenum::cursor::execute_with_cursor;
+use plenum::diff::compute_data_diff;
 use plenum::engine::duckdb::DuckDbEngine;
-use plenum::engine::{IntrospectOperation, IntrospectResult, TableFields};
-use plenum::{Capabilities, ConnectionConfig, DatabaseEngine};
+use plenum::engine::{
+    summarize_query, IntrospectOperation, IntrospectResult, SizeOptions, TableFields,
+};
+use plenum::ordering::execute_with_ordering;
+use plenum::params::QueryParams;
+use plenum::{
+    limit_cells, BinaryFormat, Capabilities, ConnectionConfig, DatabaseEngine, ExplainFormat,
+    PlanOperation, ResultColumn, RowSink,
+};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -535,6 +562,47 @@ async fn parity_introspect_list_schemas_and_databases() {
     cleanup(&path);
 }
 
+#[tokio::test]
+async fn parity_introspect_sizes_ranked_by_row_estimate() {
+    // DuckDB reports a database size but no per-table bytes.
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let op = IntrospectOperation::Sizes { options: SizeOptions::default() };
+    let result = DuckDbEngine::introspect(&config, &op, None, None).await.expect("Sizes failed");
+    let IntrospectResult::Sizes { sizes } = result else { panic!("Expected Sizes") };
+
+    assert!(sizes.database_bytes.is_some_and(|b| b > 0), "{sizes:?}");
+    assert_eq!(sizes.total_tables, 5, "{:?}", sizes.tables);
+    assert_eq!(sizes.tables[0].name, "bulk_rows");
+    assert_eq!(sizes.tables[0].row_estimate, Some(1500));
+    assert!(sizes.tables.iter().all(|t| t.total_bytes.is_none() && t.table_bytes.is_none()));
+    assert!(!sizes.notes.is_empty());
+
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_introspect_settings_from_duckdb_settings() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let op = IntrospectOperation::Settings { pattern: Some("threads".to_string()) };
+    let result = DuckDbEngine::introspect(&config, &op, None, None).await.expect("Settings failed");
+    let IntrospectResult::Settings { settings } = result else { panic!("Expected Settings") };
+
+    assert_eq!(settings.len(), 1, "{settings:?}");
+    assert_eq!(settings[0].name, "threads");
+    assert!(settings[0].value.as_deref().is_some_and(|v| v.parse::<u32>().is_ok()));
+    assert_eq!(settings[0].session_changeable, Some(false), "threads is a GLOBAL setting");
+
+    let op = IntrospectOperation::Settings { pattern: None };
+    let result = DuckDbEngine::introspect(&config, &op, None, None).await.unwrap();
+    let IntrospectResult::Settings { settings } = result else { panic!("Expected Settings") };
+    assert!(settings.len() > 10, "{settings:?}");
+    assert!(settings.iter().any(|s| s.session_changeable == Some(true)), "LOCAL settings exist");
+
+    cleanup(&path);
+}
+
 #[tokio::test]
 async fn parity_introspect_stable_json_shape() {
     // Successive introspections must produce identical JSON (determinism).
@@ -670,6 +738,159 @@ async fn parity_query_structured_explain() {
     let plan = qr.plan.expect("structured explain must populate plan");
     assert_eq!(plan.node_type, "QUERY PLAN");
     assert!(!plan.children.is_empty(), "plan must have child nodes");
+
+    // Bound parameters reach the planner
+    let qr = DuckDbEngine::execute(
+        &config,
+        "EXPLAIN SELECT * FROM customers WHERE id = ?",
+        &[serde_json::json!(1)],
+        &caps,
+    )
+    .await
+    .expect("structured EXPLAIN with a bound parameter");
+    assert!(!qr.plan.expect("plan").children.is_empty());
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_query_structured_explain_operations() {
+    // Physical operators map onto the engine-independent operation taxonomy,
+    // with scan filters and join conditions carried over from extra_info.
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let caps = Capabilities {
+        explain_format: Some(plenum::ExplainFormat::Structured),
+        ..Capabilities::default()
+    };
+    let qr = DuckDbEngine::execute(
+        &config,
+        "EXPLAIN SELECT c.name, o.status FROM orders o JOIN customers c ON c.id = o.customer_id \
+         WHERE o.status <> 'cancelled' ORDER BY c.name",
+        &[],
+        &caps,
+    )
+    .await
+    .expect("structured EXPLAIN of a join");
+
+    fn flatten(node: &plenum::ExplainPlanNode) -> Vec<&plenum::ExplainPlanNode> {
+        let mut nodes = vec![node];
+        nodes.extend(node.children.iter().flat_map(flatten));
+        nodes
+    }
+    let plan = qr.plan.expect("plan");
+    let nodes = flatten(&plan);
+    let join =
+        nodes.iter().find(|n| n.operation == PlanOperation::HashJoin).expect("hash join node");
+    assert!(join.join_condition.as_deref().is_some_and(|c| c.contains('=')), "{join:?}");
+    let orders = nodes
+        .iter()
+        .find(|n| n.relation.as_deref().is_some_and(|r| r.ends_with("orders")))
+        .expect("orders scan");
+    assert_eq!(orders.operation, PlanOperation::FullScan);
+    assert!(orders.filter.as_deref().is_some_and(|f| f.contains("status")), "{orders:?}");
+    assert!(nodes.iter().any(|n| n.operation == PlanOperation::Sort));
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_advise_index_suggestions() {
+    // DuckDB only uses ART indexes for point lookups: equality filters get a
+    // suggestion, ranges and ORDER BY do not.
+    use plenum::advise::{advise, REASON_FULL_SCAN_FILTER};
+
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+
+    let advice = advise::<DuckDbEngine>(
+        &config,
+        "SELECT * FROM bulk_rows WHERE label = 'row-0042' ORDER BY n",
+        &[],
+        None,
+        1_000,
+    )
+    .await
+    .expect("advise on a filtered full scan");
+    assert_eq!(advice.suggestions.len(), 1, "{:?}", advice.suggestions);
+    let suggestion = &advice.suggestions[0];
+    assert_eq!(suggestion.reason, REASON_FULL_SCAN_FILTER);
+    assert_eq!(suggestion.table, "bulk_rows");
+    assert_eq!(suggestion.columns, vec!["label"]);
+    assert_eq!(
+        suggestion.statement,
+        "CREATE INDEX \"idx_bulk_rows_label\" ON \"main\".\"bulk_rows\" (\"label\");"
+    );
+    assert_eq!(suggestion.table_rows, Some(1500));
+
+    for sql in [
+        "SELECT * FROM bulk_rows WHERE n > 1400",
+        "SELECT * FROM customers WHERE email = 'ada@example.com'",
+    ] {
+        let advice = advise::<DuckDbEngine>(&config, sql, &[], None, 0).await.expect(sql);
+        assert!(advice.suggestions.is_empty(), "{sql}: {:?}", advice.suggestions);
+    }
+
+    // A table below min_rows is left alone
+    let advice = advise::<DuckDbEngine>(
+        &config,
+        "SELECT * FROM bulk_rows WHERE label = 'row-0042'",
+        &[],
+        None,
+        1_000_000,
+    )
+    .await
+    .expect("advise on a small table");
+    assert!(advice.suggestions.is_empty(), "{:?}", advice.suggestions);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_query_structured_explain_analyze() {
+    // EXPLAIN ANALYZE runs the query and reports each operator's actual
+    // cardinality and timing next to the estimate.
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let caps = Capabilities {
+        explain_format: Some(plenum::ExplainFormat::Structured),
+        timeout_ms: Some(10_000),
+        ..Capabilities::default()
+    };
+    let qr = DuckDbEngine::execute(&config, "EXPLAIN ANALYZE SELECT * FROM customers", &[], &caps)
+        .await
+        .expect("structured EXPLAIN ANALYZE");
+    let plan = qr.plan.expect("structured explain must populate plan");
+    assert!(plan.actual_time_ms.is_some(), "root carries the query latency");
+
+    fn find_scan(node: &plenum::ExplainPlanNode) -> Option<&plenum::ExplainPlanNode> {
+        if node.relation.as_deref().is_some_and(|r| r.ends_with("customers")) {
+            return Some(node);
+        }
+        node.children.iter().find_map(find_scan)
+    }
+    let scan = find_scan(&plan).expect("plan scans customers");
+    let count = DuckDbEngine::execute(
+        &config,
+        "SELECT count(*) FROM customers",
+        &[],
+        &Capabilities::default(),
+    )
+    .await
+    .expect("count customers");
+    assert_eq!(scan.actual_rows, count.rows[0][0].as_f64());
+    assert!(scan.actual_time_ms.is_some());
+    assert!(!plan.children.iter().any(|c| c.node_type == "EXPLAIN_ANALYZE"));
+
+    // Estimate-only plans carry no actuals
+    let qr = DuckDbEngine::execute(&config, "EXPLAIN SELECT * FROM customers", &[], &caps)
+        .await
+        .expect("structured EXPLAIN");
+    let plan = qr.plan.expect("plan");
+    assert_eq!(find_scan(&plan).expect("scan").actual_rows, None);
+
+    // ANALYZE executes the statement, so only SELECTs are accepted
+    let err = DuckDbEngine::execute(&config, "EXPLAIN ANALYZE SHOW TABLES", &[], &caps)
+        .await
+        .expect_err("EXPLAIN ANALYZE of SHOW must be rejected");
+    assert_eq!(err.error_code(), "INVALID_INPUT");
     cleanup(&path);
 }
 
@@ -1009,6 +1230,580 @@ async fn parity_safety_timeout_ms_interrupts_long_query() {
     );
 }
 
+// ============================================================================
+// Large values — --binary rendering and --max-cell-bytes truncation
+// ============================================================================
+
+#[tokio::test]
+async fn parity_binary_formats_apply_inside_nested_values() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let sql = "SELECT c_blob, [c_blob, c_blob] AS blobs, {'b': c_blob} AS s \
+               FROM type_matrix WHERE id = 1";
+
+    let caps = Capabilities { binary_format: Some(BinaryFormat::Hex), ..Capabilities::default() };
+    let qr = DuckDbEngine::execute(&config, sql, &[], &caps).await.expect("hex blobs");
+    assert_eq!(qr.rows[0][0], "deadbeef");
+    assert_eq!(qr.rows[0][1], serde_json::json!(["deadbeef", "deadbeef"]));
+    assert_eq!(qr.rows[0][2], serde_json::json!({"b": "deadbeef"}));
+
+    let caps =
+        Capabilities { binary_format: Some(BinaryFormat::LengthOnly), ..Capabilities::default() };
+    let qr = DuckDbEngine::execute(&config, sql, &[], &caps).await.expect("blob lengths");
+    assert_eq!(qr.rows[0][0], 4);
+    assert_eq!(qr.rows[0][1], serde_json::json!([4, 4]));
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_max_cell_bytes_truncates_text_and_nested_values() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let mut qr = DuckDbEngine::execute(
+        &config,
+        "SELECT c_hugeint, c_text, c_list, c_bool FROM type_matrix WHERE id = 1",
+        &[],
+        &Capabilities::default(),
+    )
+    .await
+    .expect("type_matrix row");
+    limit_cells(&mut qr.rows, 7);
+    let row = &qr.rows[0];
+    assert_eq!(row[0]["prefix"], "1701411", "HUGEINT renders as text and is truncated as text");
+    assert_eq!(row[1]["prefix"], "café r");
+    assert_eq!(row[2], serde_json::json!([1, 2, 3]), "lists within the limit are kept");
+    assert_eq!(row[3], true);
+
+    let mut qr = DuckDbEngine::execute(
+        &config,
+        "SELECT c_list FROM type_matrix WHERE id = 1",
+        &[],
+        &Capabilities::default(),
+    )
+    .await
+    .expect("list row");
+    limit_cells(&mut qr.rows, 4);
+    assert_eq!(qr.rows[0][0]["original_bytes"], 7, "lists are measured as serialized JSON");
+    assert_eq!(qr.rows[0][0]["prefix"], "[1,2");
+    cleanup(&path);
+}
+
+// ============================================================================
+// Streaming — execute_stream (--format ndjson)
+// ============================================================================
+
+/// Sink that records what `execute_stream` delivers, refusing rows past `accept`.
+#[derive(Default)]
+struct RecordingSink {
+    columns: Vec<ResultColumn>,
+    rows: Vec<Vec<serde_json::Value>>,
+    accept: Option<usize>,
+}
+
+impl RowSink for RecordingSink {
+    fn columns(&mut self, columns: &[ResultColumn]) -> plenum::Result<()> {
+        self.columns = columns.to_vec();
+        Ok(())
+    }
+
+    fn row(&mut self, row: Vec<serde_json::Value>) -> plenum::Result<bool> {
+        if self.accept.is_some_and(|max| self.rows.len() >= max) {
+            return Ok(false);
+        }
+        self.rows.push(row);
+        Ok(true)
+    }
+}
+
+#[tokio::test]
+async fn parity_stream_header_types_and_rows_match_execute() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let caps = Capabilities::default();
+    let sql = "SELECT id, name FROM customers ORDER BY id";
+
+    let mut sink = RecordingSink::default();
+    let summary = DuckDbEngine::execute_stream(&config, sql, &[], &caps, &mut sink)
+        .await
+        .expect("execute_stream");
+    let collected = DuckDbEngine::execute(&config, sql, &[], &caps).await.expect("execute");
+
+    let names: Vec<&str> = sink.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["id", "name"]);
+    assert_eq!(sink.columns[0].type_name.as_deref(), Some("INTEGER"));
+    assert_eq!(sink.columns[1].type_name.as_deref(), Some("VARCHAR"));
+    assert_eq!(sink.rows, collected.rows, "streamed rows must equal collected rows");
+    assert_eq!(summary.rows_returned, 3);
+    assert!(!summary.rows_truncated);
+    assert!(summary.rows_affected.is_none());
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_stream_offset_and_max_rows_truncate_bulk_table() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let caps = Capabilities { offset: Some(1000), ..Capabilities::new(Some(100), None) };
+
+    let mut sink = RecordingSink::default();
+    let summary = DuckDbEngine::execute_stream(
+        &config,
+        "SELECT n FROM bulk_rows ORDER BY n",
+        &[],
+        &caps,
+        &mut sink,
+    )
+    .await
+    .expect("execute_stream");
+
+    assert_eq!(sink.rows.len(), 100);
+    assert_eq!(sink.rows[0][0], serde_json::json!(1001));
+    assert_eq!(summary.rows_returned, 100);
+    assert!(summary.rows_truncated, "more rows remain after the window");
+    assert_eq!(summary.truncated_by, None);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_stream_sink_refusal_truncates_by_bytes() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let caps = Capabilities::default();
+
+    let mut sink = RecordingSink { accept: Some(5), ..RecordingSink::default() };
+    let summary = DuckDbEngine::execute_stream(
+        &config,
+        "SELECT n FROM bulk_rows ORDER BY n",
+        &[],
+        &caps,
+        &mut sink,
+    )
+    .await
+    .expect("execute_stream");
+
+    assert_eq!(sink.rows.len(), 5);
+    assert_eq!(summary.rows_returned, 5);
+    assert!(summary.rows_truncated);
+    assert_eq!(summary.truncated_by.as_deref(), Some("bytes"));
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_stream_rejects_structured_explain() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let caps =
+        Capabilities { explain_format: Some(ExplainFormat::Structured), ..Capabilities::default() };
+
+    let mut sink = RecordingSink::default();
+    let err = DuckDbEngine::execute_stream(
+        &config,
+        "EXPLAIN SELECT * FROM customers",
+        &[],
+        &caps,
+        &mut sink,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.error_code(), "INVALID_INPUT");
+    assert!(sink.columns.is_empty(), "nothing is streamed for a rejected query");
+    cleanup(&path);
+}
+
+// ============================================================================
+// Params — :name placeholders (--param name=value)
+// ============================================================================
+
+#[tokio::test]
+async fn parity_params_named_placeholders_bind_by_name() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let raw = vec!["lo=10".to_string(), "label=row-0012".to_string()];
+    let sql = "SELECT n FROM bulk_rows WHERE n > :lo AND n < :lo + 5 AND label <> :label \
+               AND label <> ':lo' ORDER BY n";
+    let (sql, params) = QueryParams::from_cli(&raw, sql, config.engine)
+        .and_then(|p| p.bind(sql, config.engine))
+        .expect("bind named params");
+    assert_eq!(params.len(), 3, ":lo is bound once per occurrence");
+
+    let qr = DuckDbEngine::execute(&config, &sql, &params, &Capabilities::default())
+        .await
+        .expect("named-param query");
+    let ns: Vec<i64> = qr.rows.iter().map(|r| r[0].as_i64().unwrap()).collect();
+    assert_eq!(ns, [11, 13, 14]);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_params_typed_values_bind_natively() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let raw: Vec<String> = [
+        "day=2024-02-29::date",
+        "at=2024-01-01T01:30:00+02:00::timestamptz",
+        "blob=3q2+7w==::bytes",
+        "id=A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11::uuid",
+        "price=12.50::decimal",
+        "ids=[1,2,3]::int[]",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+    let sql = "SELECT :day = DATE '2024-02-29', :at = TIMESTAMP '2023-12-31 23:30:00', \
+               :blob = '\\xDE\\xAD\\xBE\\xEF'::BLOB, \
+               :id::UUID = 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::UUID, \
+               :price::DECIMAL(10, 2) = 12.5, len(:ids::INTEGER[])";
+    let (sql, params) = QueryParams::from_cli(&raw, sql, config.engine)
+        .and_then(|p| p.bind(sql, config.engine))
+        .expect("bind typed params");
+
+    let qr = DuckDbEngine::execute(&config, &sql, &params, &Capabilities::default())
+        .await
+        .expect("typed-param query");
+    let t = serde_json::Value::Bool(true);
+    assert_eq!(qr.rows[0], [t.clone(), t.clone(), t.clone(), t.clone(), t, 3.into()]);
+
+    let bad = vec!["day=2023-02-29::date".to_string()];
+    let err = QueryParams::from_cli(&bad, "SELECT :day", config.engine)
+        .and_then(|p| p.bind("SELECT :day", config.engine))
+        .unwrap_err();
+    assert_eq!(err.error_code(), "INVALID_INPUT");
+    cleanup(&path);
+}
+
+// ============================================================================
+// Cursor — keyset pagination with next_cursor tokens (--cursor)
+// ============================================================================
+
+/// Page through `sql` with keyset cursors, returning every row.
+async fn collect_cursor_rows(
+    config: &ConnectionConfig,
+    sql: &str,
+    page: usize,
+) -> Vec<Vec<serde_json::Value>> {
+    let caps = Capabilities::new(Some(page), None);
+    let mut rows = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let (result, keyset) =
+            execute_with_cursor::<DuckDbEngine>(config, sql, &[], &caps, cursor.as_deref(), true)
+                .await
+                .expect("cursor page");
+        cursor = keyset.and_then(|k| k.next_cursor(&result));
+        rows.extend(result.rows);
+        if cursor.is_none() {
+            return rows;
+        }
+    }
+}
+
+#[tokio::test]
+async fn parity_cursor_pages_composite_primary_key() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let rows = collect_cursor_rows(&config, "SELECT order_no, customer_id FROM orders", 1).await;
+    let keys: Vec<(i64, i64)> =
+        rows.iter().map(|r| (r[1].as_i64().unwrap(), r[0].as_i64().unwrap())).collect();
+    assert_eq!(keys, [(1, 1), (1, 2), (2, 1)], "implicit ORDER BY customer_id, order_no");
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_cursor_pages_unique_string_index_descending() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let rows =
+        collect_cursor_rows(&config, "SELECT email, name FROM customers ORDER BY email DESC", 2)
+            .await;
+    let emails: Vec<&str> = rows.iter().map(|r| r[0].as_str().unwrap()).collect();
+    assert_eq!(emails, ["grace@example.com", "annie@example.com", "ada@example.com"]);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_cursor_not_issued_without_unique_key() {
+    // bulk_rows is created with CREATE TABLE AS: no primary key, no unique index.
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let caps = Capabilities::new(Some(100), None);
+    let (result, keyset) = execute_with_cursor::<DuckDbEngine>(
+        &config,
+        "SELECT n FROM bulk_rows ORDER BY n",
+        &[],
+        &caps,
+        None,
+        true,
+    )
+    .await
+    .expect("page");
+    assert!(result.rows_truncated);
+    assert!(keyset.is_none());
+    cleanup(&path);
+}
+
+// ============================================================================
+// Ordering — stable row order without ORDER BY (--deterministic)
+// ============================================================================
+
+#[tokio::test]
+async fn parity_deterministic_orders_table_without_primary_key() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let caps = Capabilities::new(Some(5), None);
+    let (page, keyset) = execute_with_ordering::<DuckDbEngine>(
+        &config,
+        "SELECT n % 4 AS bucket, n FROM bulk_rows WHERE n <= 12",
+        &[],
+        &caps,
+        None,
+        false,
+        true,
+    )
+    .await
+    .expect("wrapped page");
+    assert_eq!(
+        page.rows,
+        [[0, 4], [0, 8], [0, 12], [1, 1], [1, 5]].map(|r| r.map(serde_json::Value::from).to_vec())
+    );
+    assert!(page.rows_truncated);
+    assert!(keyset.is_none(), "no unique key to page by");
+    assert_eq!(page.ordering.as_deref(), Some("imposed"));
+
+    // LIST values are ordered by the database as well
+    let (lists, _) = execute_with_ordering::<DuckDbEngine>(
+        &config,
+        "SELECT [n % 2, n] AS pair FROM bulk_rows WHERE n <= 3",
+        &[],
+        &Capabilities::default(),
+        None,
+        false,
+        true,
+    )
+    .await
+    .expect("sorted page");
+    assert_eq!(
+        lists.rows.iter().map(|r| r[0].to_string()).collect::<Vec<_>>(),
+        ["[0,2]", "[1,1]", "[1,3]"]
+    );
+    assert_eq!(lists.ordering.as_deref(), Some("imposed"));
+    cleanup(&path);
+}
+
+// ============================================================================
+// Data diff — the same query on two connections, matched by key (--diff-against)
+// ============================================================================
+
+#[tokio::test]
+async fn parity_data_diff_composite_key() {
+    let base_path = build_parity_fixture();
+    // A copy of `orders` without the FK, with one status changed and one order added
+    let target_path = fixture_path("diff_target");
+    duckdb::Connection::open(&target_path)
+        .expect("open diff target")
+        .execute_batch(
+            "CREATE TABLE orders (customer_id INTEGER, order_no INTEGER, status VARCHAR, \
+                                  placed_at TIMESTAMP);
+             INSERT INTO orders VALUES
+                 (1, 1, 'shipped',   TIMESTAMP '2024-02-01 09:00:00'),
+                 (1, 2, 'shipped',   TIMESTAMP '2024-02-03 10:30:00'),
+                 (2, 1, 'cancelled', TIMESTAMP '2024-02-05 16:45:00'),
+                 (2, 2, 'pending',   TIMESTAMP '2024-03-01 08:00:00');",
+        )
+        .expect("seed diff target");
+    let base = ConnectionConfig::duckdb(base_path.clone());
+    let target = ConnectionConfig::duckdb(target_path.clone());
+
+    let diff = compute_data_diff(
+        &base,
+        &target,
+        "SELECT customer_id, order_no, status, placed_at FROM orders",
+        &QueryParams::Positional(Vec::new()),
+        &["customer_id".to_string(), "order_no".to_string()],
+        &Capabilities::default(),
+    )
+    .await
+    .expect("data diff");
+    assert_eq!(
+        (diff.counts.added, diff.counts.removed, diff.counts.changed, diff.counts.unchanged),
+        (1, 0, 1, 2)
+    );
+    assert_eq!(diff.rows_added[0]["order_no"], 2);
+    assert_eq!(diff.rows_added[0]["customer_id"], 2);
+    let change = &diff.rows_changed[0];
+    assert_eq!(
+        (change.key["customer_id"].as_i64(), change.key["order_no"].as_i64()),
+        (Some(1), Some(2))
+    );
+    assert_eq!(change.changes.len(), 1);
+    assert_eq!(change.changes[0].column, "status");
+
+    cleanup(&base_path);
+    cleanup(&target_path);
+}
+
+/// A `DuckDB` copy of a `SQLite` table diffs clean: integers compare across
+/// engines and `:name` parameters bind in each engine's syntax.
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn parity_data_diff_across_engines() {
+    let duckdb_path = build_parity_fixture();
+    let sqlite_path = fixture_path("diff_sqlite").with_extension("sqlite");
+    rusqlite::Connection::open(&sqlite_path)
+        .expect("open sqlite copy")
+        .execute_batch(
+            "CREATE TABLE bulk_rows (n INTEGER PRIMARY KEY, label TEXT NOT NULL);
+             WITH RECURSIVE s(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM s WHERE n < 1500)
+             INSERT INTO bulk_rows SELECT n, printf('row-%04d', n) FROM s;
+             UPDATE bulk_rows SET label = 'drift' WHERE n = 42;",
+        )
+        .expect("seed sqlite copy");
+
+    let diff = compute_data_diff(
+        &ConnectionConfig::duckdb(duckdb_path.clone()),
+        &ConnectionConfig::sqlite(sqlite_path.clone()),
+        "SELECT n, label FROM bulk_rows WHERE n BETWEEN :lo AND :hi",
+        &QueryParams::from_json(Some(&serde_json::json!({"lo": 1, "hi": 1000}))).unwrap(),
+        &["n".to_string()],
+        &Capabilities::default(),
+    )
+    .await
+    .expect("cross-engine data diff");
+    assert_eq!((diff.base_rows, diff.target_rows), (1000, 1000));
+    assert_eq!((diff.counts.changed, diff.counts.unchanged), (1, 999));
+    assert_eq!(diff.rows_changed[0].key["n"], 42);
+    assert_eq!(diff.rows_changed[0].changes[0].to, "drift");
+
+    cleanup(&duckdb_path);
+    cleanup(&sqlite_path);
+}
+
+// ============================================================================
+// Summary — per-column statistics over every streamed row (--summarize)
+// ============================================================================
+
+#[tokio::test]
+async fn parity_summary_types_and_bounds() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+
+    let summary = summarize_query::<DuckDbEngine>(
+        &config,
+        "SELECT customer_id, status, placed_at FROM orders ORDER BY customer_id, order_no",
+        &[],
+        None,
+    )
+    .await
+    .expect("orders summary");
+    assert_eq!(summary.row_count, 3);
+    let types: Vec<Option<&str>> = summary.columns.iter().map(|c| c.type_name.as_deref()).collect();
+    assert_eq!(types, [Some("INTEGER"), Some("VARCHAR"), Some("TIMESTAMP")]);
+
+    let customer = &summary.columns[0];
+    assert_eq!((customer.count, customer.distinct_count), (3, 2));
+    assert_eq!(customer.examples, [1, 2]);
+
+    let status = &summary.columns[1];
+    assert_eq!(status.min.as_ref().unwrap(), "cancelled");
+    assert_eq!(status.max.as_ref().unwrap(), "shipped");
+
+    let placed = &summary.columns[2];
+    assert!(placed.min.as_ref().unwrap().as_str().unwrap().starts_with("2024-02-01"));
+    assert!(placed.max.as_ref().unwrap().as_str().unwrap().starts_with("2024-02-05"));
+
+    // Every row is read: the summary ignores the caller's row cap entirely
+    let bulk = summarize_query::<DuckDbEngine>(&config, "SELECT n FROM bulk_rows", &[], None)
+        .await
+        .expect("bulk summary");
+    assert_eq!(bulk.row_count, 1500);
+    assert_eq!(bulk.columns[0].max, Some(1500.into()));
+    cleanup(&path);
+}
+
+// ============================================================================
+// Export — execute_stream into Parquet / Arrow IPC files (--output-file)
+// ============================================================================
+
+#[cfg(feature = "export")]
+#[tokio::test]
+async fn parity_export_arrow_ipc_type_matrix() {
+    use arrow::array::{
+        Array, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Int64Array, StringArray,
+        TimestampMicrosecondArray,
+    };
+    use arrow::datatypes::{DataType, TimeUnit};
+    use plenum::{BinaryFormat, ExportFormat, ExportWriter};
+
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::duckdb(path.clone());
+    let out = fixture_path("export").with_extension("arrow");
+
+    let mut writer =
+        ExportWriter::create(&out, ExportFormat::Arrow, config.engine, BinaryFormat::Base64, None)
+            .expect("create");
+    DuckDbEngine::execute_stream(
+        &config,
+        "SELECT c_integer, c_hugeint, c_decimal, c_bool, c_null_col, c_date, c_datetime, \
+         c_datetime::TIMESTAMPTZ AS c_tz, c_blob FROM type_matrix ORDER BY id",
+        &[],
+        &Capabilities::default(),
+        &mut writer,
+    )
+    .await
+    .expect("execute_stream");
+    let export = writer.finish().expect("finish");
+    assert_eq!(export.columns[0].source_type.as_deref(), Some("BIGINT"));
+
+    let file = std::fs::File::open(&out).expect("open arrow file");
+    let reader = arrow::ipc::reader::FileReader::try_new(file, None).expect("ipc reader");
+    let schema = reader.schema();
+    let types: Vec<&DataType> = schema.fields().iter().map(|f| f.data_type()).collect();
+    assert_eq!(
+        types,
+        [
+            &DataType::Int64,
+            &DataType::Utf8,
+            &DataType::Decimal128(38, 4),
+            &DataType::Boolean,
+            &DataType::Utf8,
+            &DataType::Date32,
+            &DataType::Timestamp(TimeUnit::Microsecond, None),
+            &DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into())),
+            &DataType::Binary,
+        ],
+        "HUGEINT is written losslessly as text"
+    );
+    let batches: Vec<_> = reader.collect::<Result<_, _>>().expect("read batches");
+    let batch = &batches[0];
+    assert_eq!(batch.num_rows(), export.rows_written);
+    let ints = batch.column(0).as_any().downcast_ref::<Int64Array>().expect("Int64");
+    assert_eq!(ints.value(0), i64::MAX, "BIGINT boundary value survives");
+    let huge = batch.column(1).as_any().downcast_ref::<StringArray>().expect("Utf8");
+    assert!(!huge.value(0).is_empty());
+    let decimals = batch.column(2).as_any().downcast_ref::<Decimal128Array>().expect("Decimal");
+    assert_eq!(decimals.value_as_string(0), "12345678.9999");
+    assert_eq!(decimals.value_as_string(1), "-0.0001");
+    let bools = batch.column(3).as_any().downcast_ref::<BooleanArray>().expect("Boolean");
+    assert_eq!(bools.len(), export.rows_written);
+    assert!(batch.column(4).is_null(0), "NULLs stay NULL");
+    let dates = batch.column(5).as_any().downcast_ref::<Date32Array>().expect("Date32");
+    assert_eq!(dates.value_as_date(0).map(|d| d.to_string()).as_deref(), Some("2024-01-15"));
+    for idx in [6, 7] {
+        let stamps = batch
+            .column(idx)
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .expect("Timestamp");
+        assert_eq!(stamps.value(1), 946_684_799_000_000, "1999-12-31 23:59:59");
+        assert!(stamps.is_null(2));
+    }
+    let blobs = batch.column(8).as_any().downcast_ref::<BinaryArray>().expect("Binary");
+    assert_eq!(blobs.value(0), [0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(blobs.value(1), [0, 1, 2, 3]);
+
+    let _ = std::fs::remove_file(&out);
+    cleanup(&path);
+}
+
 // ============================================================================
 // Envelope — JSON shape and determinism
 // ============================================================================
@@ -95,6 +95,9 @@ async fn test_large_result_set_with_max_rows() {
         offset: None,
         max_bytes: None,
         explain_format: None,
+        max_cell_bytes: None,
+        binary_format: None,
+        control: None,
     };
 
     let result = SqliteEngine::execute(&config, "SELECT * FROM large_table", &[], &caps).await;
@@ -474,6 +477,9 @@ async fn test_timeout_capability() {
         offset: None,
         max_bytes: None,
         explain_format: None,
+        max_cell_bytes: None,
+        binary_format: None,
+        control: None,
     };
 
     // Simple query should complete within timeout
@@ -249,6 +249,9 @@ async fn test_cross_engine_max_rows_enforcement() {
         offset: None,
         max_bytes: None,
         explain_format: None,
+        max_cell_bytes: None,
+        binary_format: None,
+        control: None,
     };
 
     let result =
@@ -68,6 +68,8 @@ CREATE TABLE order_items (
     unit_price  DECIMAL(10, 2) NOT NULL,
     PRIMARY KEY (customer_id, order_no, line_no),
     KEY idx_order_items_sku (sku),
+    -- Redundant with the primary key prefix; exercised by --index-health.
+    KEY idx_order_items_customer (customer_id),
     CONSTRAINT fk_order_items_order
         FOREIGN KEY (customer_id, order_no)
         REFERENCES orders (customer_id, order_no)
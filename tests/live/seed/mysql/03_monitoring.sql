@@ -0,0 +1,10 @@
+-- Plenum live-test monitoring grants — MySQL dialect.
+-- Runs as root via /docker-entrypoint-initdb.d/ after the schema and data.
+--
+-- `plenum inspect` reads performance_schema (threads, lock waits, statement
+-- digests) and `introspect --index-health` reads the sys schema views. A
+-- monitoring role needs these read-only grants; none of them allow writes.
+
+GRANT PROCESS ON *.* TO 'plenum'@'%';
+GRANT SELECT ON performance_schema.* TO 'plenum'@'%';
+GRANT SELECT, EXECUTE ON sys.* TO 'plenum'@'%';
@@ -69,6 +69,9 @@ CREATE TABLE order_items (
 
 CREATE INDEX idx_order_items_sku ON order_items (sku);
 
+-- Redundant with the primary key prefix; exercised by --index-health.
+CREATE INDEX idx_order_items_customer ON order_items (customer_id);
+
 CREATE VIEW v_order_totals AS
 SELECT o.customer_id,
        o.order_no,
@@ -96,3 +99,11 @@ CREATE TABLE analytics.page_views (
     viewed_at timestamptz NOT NULL,
     meta      jsonb
 );
+
+-- Row-level security for privilege introspection. The owner (plenum) bypasses
+-- non-forced RLS, so this does not change what the other suites read.
+ALTER TABLE analytics.page_views ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY public_paths ON analytics.page_views
+    FOR SELECT TO PUBLIC
+    USING (path NOT LIKE '/admin/%');
@@ -0,0 +1,8 @@
+-- Plenum live-test monitoring setup — PostgreSQL dialect.
+-- Runs via /docker-entrypoint-initdb.d/ after the schema and data.
+--
+-- `plenum inspect --top-queries` reads pg_stat_statements. The library is
+-- preloaded by the compose `command`; the extension makes its view visible
+-- in plenum_test.
+
+CREATE EXTENSION IF NOT EXISTS pg_stat_statements;
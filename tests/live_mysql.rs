@@ -22,9 +22,11 @@
 //! When run with `--include-ignored` and a DSN var is missing, tests fail
 //! fast with a clear message. They never silently skip or pass.
 
-use serde_json::Value;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
 const MYSQL80_DSN_VAR: &str = "PLENUM_TEST_MYSQL_DSN";
 const MYSQL84_DSN_VAR: &str = "PLENUM_TEST_MYSQL84_DSN";
@@ -630,6 +632,162 @@ mysql_matrix!(
     introspect_list_indexes
 );
 
+/// `--index-health <table>` reports the seeded `customer_id` index as redundant
+/// with the primary key (via `sys.schema_redundant_indexes`).
+fn introspect_index_health(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let (code, stdout) =
+        run_plenum(&home, &["introspect", "--dsn", &dsn, "--index-health", "order_items"]);
+    assert_eq!(code, 0, "introspect --index-health failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "introspect");
+    assert_eq!(
+        envelope.pointer("/data/type").and_then(Value::as_str),
+        Some("index_health"),
+        "unexpected introspect result type: {envelope}"
+    );
+    let findings = envelope
+        .pointer("/data/health/findings")
+        .and_then(Value::as_array)
+        .expect("index_health carries findings");
+    let redundant = findings
+        .iter()
+        .find(|f| f.get("index").and_then(Value::as_str) == Some("idx_order_items_customer"))
+        .unwrap_or_else(|| panic!("idx_order_items_customer not reported: {envelope}"));
+    assert_eq!(redundant.get("reason").and_then(Value::as_str), Some("redundant"));
+    assert!(
+        redundant.get("detail").and_then(Value::as_str).unwrap_or_default().contains("PRIMARY"),
+        "dominant index should be the primary key: {redundant}"
+    );
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(
+    mysql80_introspect_index_health,
+    mysql84_introspect_index_health,
+    introspect_index_health
+);
+
+/// `--sizes` reports data and index bytes from `information_schema.TABLES`,
+/// largest first. `InnoDB` statistics may lag the seed, so only the order is checked.
+fn introspect_sizes(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let (code, stdout) = run_plenum(&home, &["introspect", "--dsn", &dsn, "--sizes"]);
+    assert_eq!(code, 0, "introspect --sizes failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "introspect");
+    assert_eq!(envelope.pointer("/data/type").and_then(Value::as_str), Some("sizes"));
+    let tables = envelope
+        .pointer("/data/sizes/tables")
+        .and_then(Value::as_array)
+        .expect("sizes carries tables");
+    assert!(
+        tables.iter().any(|t| t.get("name").and_then(Value::as_str) == Some("bulk_rows")),
+        "seeded tables listed: {envelope}"
+    );
+    let totals: Vec<u64> =
+        tables.iter().filter_map(|t| t.get("total_bytes").and_then(Value::as_u64)).collect();
+    assert!(totals.windows(2).all(|w| w[0] >= w[1]), "largest first: {totals:?}");
+    for table in tables {
+        let part = |key: &str| table.get(key).and_then(Value::as_u64).unwrap_or(0);
+        assert_eq!(part("total_bytes"), part("table_bytes") + part("index_bytes"), "{table}");
+    }
+    assert!(envelope.pointer("/data/sizes/database_bytes").and_then(Value::as_u64).is_some());
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(mysql80_introspect_sizes, mysql84_introspect_sizes, introspect_sizes);
+
+/// `--settings` lists session variables filtered by a glob; the scope-derived
+/// `session_changeable` flag is null only when `performance_schema` hides it.
+fn introspect_settings(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let (code, stdout) =
+        run_plenum(&home, &["introspect", "--dsn", &dsn, "--settings", "*_buffer_pool_size"]);
+    assert_eq!(code, 0, "introspect --settings failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "introspect");
+    assert_eq!(envelope.pointer("/data/type").and_then(Value::as_str), Some("settings"));
+    let settings =
+        envelope.pointer("/data/settings").and_then(Value::as_array).expect("settings array");
+    let names: Vec<&str> =
+        settings.iter().filter_map(|s| s.get("name").and_then(Value::as_str)).collect();
+    assert_eq!(names, ["innodb_buffer_pool_size"], "{envelope}");
+    assert!(settings[0].get("value").and_then(Value::as_str).is_some());
+
+    let (code, stdout) =
+        run_plenum(&home, &["introspect", "--dsn", &dsn, "--settings", "sql_mode"]);
+    assert_eq!(code, 0, "introspect --settings sql_mode failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "introspect");
+    let changeable = envelope.pointer("/data/settings/0/session_changeable");
+    assert!(
+        matches!(changeable, Some(Value::Bool(true) | Value::Null)),
+        "sql_mode has session scope: {envelope}"
+    );
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(mysql80_introspect_settings, mysql84_introspect_settings, introspect_settings);
+
+/// `inspect --activity` reads `performance_schema` threads and lock waits into
+/// the standard envelope.
+fn inspect_activity(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let (code, stdout) = run_plenum(&home, &["inspect", "--dsn", &dsn, "--activity"]);
+    assert_eq!(code, 0, "inspect --activity failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "inspect");
+    assert_eq!(
+        envelope.pointer("/data/type").and_then(Value::as_str),
+        Some("activity"),
+        "unexpected inspect result type: {envelope}"
+    );
+    assert!(envelope.pointer("/data/activity/sessions").and_then(Value::as_array).is_some());
+    assert!(envelope.pointer("/data/activity/lock_waits").and_then(Value::as_array).is_some());
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(mysql80_inspect_activity, mysql84_inspect_activity, inspect_activity);
+
+/// `inspect --top-queries` ranks `performance_schema` digests; a statement just
+/// run through `plenum query` shows up with normalized text.
+fn inspect_top_queries(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    query_ok(&home, &dsn, "SELECT name FROM customers WHERE id = 1");
+    let (code, stdout) = run_plenum(
+        &home,
+        &["inspect", "--dsn", &dsn, "--top-queries", "--order-by", "calls", "--limit", "1000"],
+    );
+    assert_eq!(code, 0, "inspect --top-queries failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "inspect");
+    assert_eq!(
+        envelope.pointer("/data/top_queries/order_by").and_then(Value::as_str),
+        Some("calls")
+    );
+    let statements = envelope
+        .pointer("/data/top_queries/statements")
+        .and_then(Value::as_array)
+        .expect("top_queries carries statements");
+    let customers = statements
+        .iter()
+        .find(|s| {
+            s.get("query")
+                .and_then(Value::as_str)
+                .is_some_and(|q| q.contains("`customers`") && q.contains('?'))
+        })
+        .unwrap_or_else(|| panic!("customers lookup digest not reported: {envelope}"));
+    assert!(customers.get("calls").and_then(Value::as_u64).unwrap_or(0) >= 1, "{customers}");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(mysql80_inspect_top_queries, mysql84_inspect_top_queries, inspect_top_queries);
+
 /// `--list-views` + `--view`: the seeded view is listed and its details carry
 /// a definition and columns.
 fn introspect_views(dsn_var: &str, tag: &str) {
@@ -998,6 +1156,436 @@ mysql_matrix!(
     safety_max_rows_truncation
 );
 
+/// `--format ndjson`: header with `MySQL` column types, one line per row as it is
+/// read off the wire, and a trailer carrying the pagination metadata.
+fn query_ndjson_stream(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n, label FROM bulk_rows ORDER BY n",
+            "--format",
+            "ndjson",
+            "--max-rows",
+            "100",
+            "--offset",
+            "10",
+        ],
+    );
+    assert_eq!(code, 0, "ndjson query failed, stdout={stdout}");
+    let lines: Vec<Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap_or_else(|e| panic!("bad line {l:?}: {e}")))
+        .collect();
+    assert_eq!(lines.len(), 102, "header + 100 rows + trailer");
+    assert_eq!(lines[0]["type"], "header");
+    assert_eq!(lines[0].pointer("/columns/0/name").and_then(Value::as_str), Some("n"));
+    assert_eq!(lines[0].pointer("/columns/0/type").and_then(Value::as_str), Some("long"));
+    assert_eq!(lines[1]["type"], "row");
+    assert_eq!(lines[1].pointer("/values/1").and_then(Value::as_str), Some("row-0011"));
+    let trailer = &lines[101];
+    assert_eq!(trailer["type"], "trailer");
+    assert_eq!(trailer.pointer("/meta/rows_returned").and_then(Value::as_u64), Some(100));
+    assert_eq!(trailer.pointer("/meta/rows_truncated").and_then(Value::as_bool), Some(true));
+    assert_eq!(trailer.pointer("/meta/next_offset").and_then(Value::as_u64), Some(110));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(mysql80_query_ndjson_stream, mysql84_query_ndjson_stream, query_ndjson_stream);
+
+/// `--param name=value` binds `:name` placeholders as `?`, once per occurrence.
+fn query_named_params(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT label FROM bulk_rows WHERE n BETWEEN :lo AND :lo + 1 AND label <> ':lo' \
+             ORDER BY n",
+            "--param",
+            "lo=41",
+        ],
+    );
+    assert_eq!(code, 0, "named-param query failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    let rows = envelope.pointer("/data/rows").and_then(Value::as_array).expect("rows");
+    let labels: Vec<&str> = rows.iter().map(|r| r[0].as_str().expect("label")).collect();
+    assert_eq!(labels, ["row-0041", "row-0042"]);
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(mysql80_query_named_params, mysql84_query_named_params, query_named_params);
+
+/// Typed `--param` values bind natively: dates and offset timestamps as `MySQL`
+/// temporal values (converted to UTC), `bytes` as binary, `decimal` as exact text.
+fn query_typed_params(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT :day = DATE '2024-02-29' AS day_eq, \
+             :at = TIMESTAMP '2023-12-31 23:30:00' AS at_eq, HEX(:blob) AS blob, \
+             CAST(:price AS CHAR) AS price, JSON_LENGTH(:ids) AS ids",
+            "--param",
+            "day=2024-02-29::date",
+            "--param",
+            "at=2024-01-01T01:30:00+02:00::timestamp",
+            "--param",
+            "blob=3q2+7w==::bytes",
+            "--param",
+            "price=12345678901234567890.123456789::decimal",
+            "--param",
+            "ids=[1,2,3]::int[]",
+        ],
+    );
+    assert_eq!(code, 0, "typed-param query failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    let row = envelope.pointer("/data/rows/0").and_then(Value::as_array).expect("row");
+    assert_eq!(row[0].as_i64(), Some(1));
+    assert_eq!(row[1].as_i64(), Some(1));
+    assert_eq!(row[2], "DEADBEEF");
+    assert_eq!(row[3], "12345678901234567890.123456789");
+    assert_eq!(row[4].as_i64(), Some(3));
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &["query", "--dsn", &dsn, "--sql", "SELECT :d", "--param", "d=2023-02-29::date"],
+    );
+    assert_ne!(code, 0, "invalid date must fail, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, false, "query");
+    assert_eq!(error_code(&envelope), "INVALID_INPUT", "envelope: {envelope}");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(mysql80_query_typed_params, mysql84_query_typed_params, query_typed_params);
+
+/// `--binary` applies to every value of a binary column, including binary strings
+/// that are valid UTF-8, and `--max-cell-bytes` truncates long text with a marker.
+fn query_large_values(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+    let sql = "SELECT UNHEX('DEADBEEF') AS b, BINARY 'abc' AS t, REPEAT('x', 500) AS wide, \
+               42 AS n";
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &["query", "--dsn", &dsn, "--sql", sql, "--binary", "hex", "--max-cell-bytes", "100"],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    let row = envelope.pointer("/data/rows/0").and_then(Value::as_array).expect("row");
+    assert_eq!(row[0], "deadbeef");
+    assert_eq!(row[1], "616263");
+    assert_eq!(row[2]["truncated"], true);
+    assert_eq!(row[2]["original_bytes"], 500);
+    assert_eq!(row[2]["prefix"].as_str().map(str::len), Some(100));
+    assert_eq!(row[3], "42", "numeric columns are not binary (text protocol)");
+
+    let (code, stdout) =
+        run_plenum(&home, &["query", "--dsn", &dsn, "--sql", sql, "--binary", "length-only"]);
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    let row = envelope.pointer("/data/rows/0").and_then(Value::as_array).expect("row");
+    assert_eq!((row[0].as_u64(), row[1].as_u64()), (Some(4), Some(3)));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(mysql80_query_large_values, mysql84_query_large_values, query_large_values);
+
+/// `--summarize` folds every row of the result into per-column statistics and
+/// returns no rows.
+fn query_summarize(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n, label, NULLIF(n % 2, 0) AS odd FROM bulk_rows WHERE n > :lo",
+            "--param",
+            "lo=500",
+            "--summarize",
+        ],
+    );
+    assert_eq!(code, 0, "summarize failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    let data = envelope.get("data").expect("data");
+    assert_eq!(data["row_count"], 1000);
+    assert!(data.get("rows").is_none(), "a summary must not return rows");
+
+    let n = &data["columns"][0];
+    assert!(n["type"].is_string(), "MySQL declares every column type: {n}");
+    assert_eq!(n["distinct_count"], 1000);
+    assert_eq!(n["min"], 501);
+    assert_eq!(n["max"], 1500);
+    assert_eq!(data["columns"][1]["max"], "row-1500");
+    let odd = &data["columns"][2];
+    assert_eq!((odd["count"].as_u64(), odd["null_count"].as_u64()), (Some(500), Some(500)));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(mysql80_query_summarize, mysql84_query_summarize, query_summarize);
+
+/// `--deterministic` orders a truncated UNION ALL by every column (the derived
+/// table needs its alias on `MySQL`), keeps the query's own ORDER BY, and runs a
+/// join with duplicate column names unordered.
+fn query_deterministic(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+    let sql = "SELECT label FROM bulk_rows WHERE n > 1498 \
+               UNION ALL SELECT label FROM bulk_rows WHERE n <= 2";
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &["query", "--dsn", &dsn, "--sql", sql, "--max-rows", "3", "--deterministic"],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_eq!(
+        envelope.pointer("/data/rows"),
+        Some(&serde_json::json!([["row-0001"], ["row-0002"], ["row-1499"]]))
+    );
+    assert_eq!(envelope.pointer("/meta/ordering").and_then(Value::as_str), Some("imposed"));
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT label FROM bulk_rows ORDER BY n DESC",
+            "--max-rows",
+            "1",
+            "--deterministic",
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_eq!(envelope.pointer("/data/rows/0/0").and_then(Value::as_str), Some("row-1500"));
+    assert_eq!(envelope.pointer("/meta/ordering").and_then(Value::as_str), Some("engine"));
+
+    // Duplicate column names cannot be selected from a derived table: run as written
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT * FROM customers c JOIN orders o ON o.customer_id = c.id",
+            "--max-rows",
+            "1",
+            "--deterministic",
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_eq!(envelope.pointer("/meta/ordering").and_then(Value::as_str), Some("unordered"));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(mysql80_query_deterministic, mysql84_query_deterministic, query_deterministic);
+
+/// `advise` turns a filesort over a filtered full scan into one online
+/// `ALTER TABLE ... ADD INDEX`; an indexed lookup gets no suggestion.
+fn advise_index_suggestions(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+    let advise = |sql: &str| {
+        let (code, stdout) =
+            run_plenum(&home, &["advise", "--dsn", &dsn, "--sql", sql, "--min-rows", "0"]);
+        assert_eq!(code, 0, "stdout={stdout}");
+        assert_envelope(&stdout, true, "advise")
+    };
+
+    let envelope = advise("SELECT * FROM orders WHERE status = 'shipped' ORDER BY placed_at DESC");
+    let suggestions =
+        envelope.pointer("/data/suggestions").and_then(Value::as_array).expect("suggestions");
+    assert_eq!(suggestions.len(), 1, "{envelope}");
+    assert_eq!(suggestions[0].pointer("/reason").and_then(Value::as_str), Some("sort"));
+    assert_eq!(
+        suggestions[0].pointer("/columns"),
+        Some(&serde_json::json!(["status", "placed_at"]))
+    );
+    let statement = suggestions[0].pointer("/statement").and_then(Value::as_str).unwrap_or("");
+    assert!(statement.starts_with("ALTER TABLE "), "{statement}");
+    assert!(
+        statement.ends_with(
+            "ADD INDEX `idx_orders_status_placed_at` (`status`, `placed_at` DESC), \
+             ALGORITHM=INPLACE, LOCK=NONE;"
+        ),
+        "{statement}"
+    );
+
+    let envelope = advise("SELECT * FROM bulk_rows WHERE label = 'row-0042'");
+    assert_eq!(envelope.pointer("/data/suggestions"), Some(&serde_json::json!([])));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(
+    mysql80_advise_index_suggestions,
+    mysql84_advise_index_suggestions,
+    advise_index_suggestions
+);
+
+/// Structured `EXPLAIN ANALYZE` parses `MySQL`'s TREE output into plan nodes
+/// carrying actual rows, time and loops, and the shared operation taxonomy.
+fn query_structured_explain_analyze(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "EXPLAIN ANALYZE SELECT n FROM bulk_rows WHERE label <> ''",
+            "--explain-format",
+            "structured",
+            "--timeout-ms",
+            "10000",
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    let plan = envelope.pointer("/data/plan").expect("plan");
+    assert_eq!(plan.pointer("/actual_rows").and_then(Value::as_f64), Some(1500.0), "{plan}");
+    assert_eq!(plan.pointer("/loops").and_then(Value::as_u64), Some(1), "{plan}");
+    assert!(plan.pointer("/actual_time_ms").is_some_and(Value::is_number), "{plan}");
+    let scan = plan.pointer("/children/0").expect("filter wraps a table scan");
+    assert_eq!(scan.pointer("/relation").and_then(Value::as_str), Some("bulk_rows"));
+    assert_eq!(plan.pointer("/operation").and_then(Value::as_str), Some("filter"));
+    assert!(plan.pointer("/filter").and_then(Value::as_str).is_some_and(|f| f.contains("label")));
+    assert_eq!(scan.pointer("/operation").and_then(Value::as_str), Some("full_scan"));
+    assert_eq!(scan.pointer("/actual_rows").and_then(Value::as_f64), Some(1500.0));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(
+    mysql80_query_structured_explain_analyze,
+    mysql84_query_structured_explain_analyze,
+    query_structured_explain_analyze
+);
+
+/// Structured EXPLAIN binds `--param` values through a prepared statement.
+fn query_structured_explain_binds_params(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "EXPLAIN SELECT n FROM bulk_rows WHERE label = :label",
+            "--param",
+            "label=row-0042",
+            "--explain-format",
+            "structured",
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    let plan = envelope.pointer("/data/plan").expect("plan");
+    let scan = plan.pointer("/children/0").expect("query block wraps the table");
+    assert_eq!(scan.pointer("/relation").and_then(Value::as_str), Some("bulk_rows"), "{plan}");
+    assert_eq!(scan.pointer("/index").and_then(Value::as_str), Some("idx_bulk_rows_label"));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(
+    mysql80_query_structured_explain_binds_params,
+    mysql84_query_structured_explain_binds_params,
+    query_structured_explain_binds_params
+);
+
+/// `--paginate` / `--cursor` keyset pages: the implicit primary-key order covers `bulk_rows`
+/// exactly once, and no cursor is issued when the key column is not selected.
+fn query_cursor_pagination(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let mut labels = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut args = vec![
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n, label FROM bulk_rows",
+            "--max-rows",
+            "600",
+            "--paginate",
+        ];
+        if let Some(c) = cursor.as_deref() {
+            args.extend(["--cursor", c]);
+        }
+        let (code, stdout) = run_plenum(&home, &args);
+        assert_eq!(code, 0, "cursor page failed, stdout={stdout}");
+        let envelope = assert_envelope(&stdout, true, "query");
+        let rows = envelope.pointer("/data/rows").and_then(Value::as_array).expect("rows");
+        labels.extend(rows.iter().map(|r| r[1].as_str().expect("label").to_string()));
+        cursor = envelope.pointer("/meta/next_cursor").and_then(Value::as_str).map(String::from);
+        if cursor.is_none() {
+            break;
+        }
+    }
+    let expected: Vec<String> = (1..=1500).map(|n| format!("row-{n:04}")).collect();
+    assert_eq!(labels, expected);
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT label FROM bulk_rows",
+            "--max-rows",
+            "600",
+            "--paginate",
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_eq!(envelope.pointer("/meta/has_more").and_then(Value::as_bool), Some(true));
+    assert!(envelope.pointer("/meta/next_cursor").is_none(), "key column n is not selected");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(
+    mysql80_query_cursor_pagination,
+    mysql84_query_cursor_pagination,
+    query_cursor_pagination
+);
+
 /// `--timeout-ms` exceeded via `SLEEP()`: structured `QUERY_TIMEOUT` error that
 /// names the configured budget (locks in the REF-258 timeout-as-error fix).
 /// No wall-clock assertions — only the structured outcome is checked.
@@ -1024,6 +1612,71 @@ mysql_matrix!(
     safety_timeout_structured_error
 );
 
+/// MCP `notifications/cancelled` stops a running `SLEEP()` with `KILL QUERY`,
+/// and the server moves on to the next request.
+fn safety_mcp_cancel_stops_sleep(dsn_var: &str, tag: &str) {
+    let dsn = require_dsn(dsn_var);
+    let home = scratch_home(tag);
+
+    let bin = env!("CARGO_BIN_EXE_plenum");
+    let mut child = Command::new(bin)
+        .arg("mcp")
+        .current_dir(&home)
+        .env("HOME", &home)
+        .env("XDG_CONFIG_HOME", &home)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn plenum mcp");
+    let mut stdin = child.stdin.take().expect("stdin");
+
+    let started = Instant::now();
+    let sleep = json!({
+        "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+        "params": { "name": "query", "arguments": { "dsn": dsn, "sql": "SELECT SLEEP(30)" } }
+    });
+    writeln!(stdin, "{sleep}").unwrap();
+    stdin.flush().unwrap();
+    std::thread::sleep(Duration::from_secs(1));
+
+    // Queued behind the sleep: only answered once the sleep has stopped
+    let cancelled = json!({
+        "jsonrpc": "2.0", "method": "notifications/cancelled", "params": { "requestId": 1 }
+    });
+    let still_running = json!({
+        "jsonrpc": "2.0", "id": 2, "method": "tools/call",
+        "params": { "name": "query", "arguments": {
+            "dsn": dsn,
+            "sql": "SELECT COUNT(*) AS sleeping FROM information_schema.processlist \
+                    WHERE info = 'SELECT SLEEP(30)'"
+        } }
+    });
+    writeln!(stdin, "{cancelled}").unwrap();
+    writeln!(stdin, "{still_running}").unwrap();
+    drop(stdin);
+
+    let responses: Vec<Value> = BufReader::new(child.stdout.take().expect("stdout"))
+        .lines()
+        .map(|line| serde_json::from_str(&line.expect("read stdout")).expect("JSON-RPC line"))
+        .collect();
+    let _ = child.wait();
+
+    assert!(started.elapsed() < Duration::from_secs(15), "cancel did not stop SLEEP");
+    assert_eq!(responses.len(), 1, "the cancelled call gets no response: {responses:#?}");
+    assert_eq!(responses[0]["id"], json!(2));
+    let text = responses[0]["result"]["content"][0]["text"].as_str().expect("tool result text");
+    let result: Value = serde_json::from_str(text).expect("query result JSON");
+    assert_eq!(result["rows"], json!([[0]]), "SLEEP still running on the server");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+mysql_matrix!(
+    mysql80_safety_mcp_cancel_stops_sleep,
+    mysql84_safety_mcp_cancel_stops_sleep,
+    safety_mcp_cancel_stops_sleep
+);
+
 // ============================================================================
 // envelope contract
 // ============================================================================
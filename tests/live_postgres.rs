@@ -13,7 +13,7 @@
 //! - query denied: writes/DDL → `CAPABILITY_VIOLATION` with DB state proven
 //!   unchanged afterwards
 //! - safety: `max_rows` truncation on the >1,000-row table, `timeout_ms`
-//!   via `pg_sleep()`
+//!   via `pg_sleep()`, MCP `notifications/cancelled` stopping a `pg_sleep()`
 //! - envelope: required fields from `schemas/*.json`, deterministic output
 //!   with `execution_ms` redacted, JSON-only stdout
 //!
@@ -32,9 +32,11 @@
 //! When run with `--include-ignored` and the DSN var is missing, tests fail
 //! fast with a clear message. They never silently skip or pass.
 
-use serde_json::Value;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
 
 const POSTGRES_DSN_VAR: &str = "PLENUM_TEST_POSTGRES_DSN";
 
@@ -249,7 +251,10 @@ fn assert_matches_schema(envelope: &Value, schema_file: &str) {
         );
     }
 
-    if let Some(error_required) = schema["definitions"]["ErrorInfo"]["required"].as_array() {
+    let is_error_schema = required.iter().any(|key| key == "error");
+    if let Some(error_required) =
+        schema["definitions"]["ErrorInfo"]["required"].as_array().filter(|_| is_error_schema)
+    {
         for key in error_required {
             let key = key.as_str().expect("required entries are strings");
             assert!(
@@ -373,6 +378,61 @@ fn postgres16_connect_test_reports_server_metadata() {
     let _ = std::fs::remove_dir_all(&home);
 }
 
+/// `connect --test` flags the seed user as not least-privilege: it owns every
+/// seeded table and can create objects in its database.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_connect_test_reports_least_privilege_warnings() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let parts = parse_dsn(&dsn);
+    let home = scratch_home("connect_audit");
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "connect",
+            "--engine",
+            "postgres",
+            "--host",
+            &parts.host,
+            "--port",
+            &parts.port,
+            "--user",
+            &parts.user,
+            "--password",
+            &parts.password,
+            "--database",
+            &parts.database,
+            "--test",
+        ],
+    );
+    assert_eq!(code, 0, "connect --test failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "connect");
+    assert_matches_schema(&envelope, "connect_success.json");
+
+    let warnings = envelope.pointer("/data/warnings").and_then(Value::as_array).cloned();
+    let warnings = warnings.unwrap_or_else(|| panic!("expected warnings: {envelope}"));
+    let ownership = warnings
+        .iter()
+        .find(|w| w["code"].as_str() == Some("TABLE_OWNERSHIP"))
+        .unwrap_or_else(|| panic!("expected TABLE_OWNERSHIP: {warnings:?}"));
+    assert_eq!(ownership["severity"].as_str(), Some("warning"));
+    assert!(
+        string_vec(&ownership["objects"]).contains(&"public.orders".to_string()),
+        "owned tables: {ownership}"
+    );
+    assert!(
+        warnings.iter().any(|w| w["code"].as_str() == Some("DDL_PRIVILEGE")),
+        "database owner can create schemas: {warnings:?}"
+    );
+    assert!(
+        !warnings.iter().any(|w| w["code"].as_str() == Some("READ_REPLICA")),
+        "primary must not be reported as a replica: {warnings:?}"
+    );
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
 /// A wrong password yields a normalized `CONNECTION_FAILED` error envelope
 /// that never echoes the credential back.
 #[test]
@@ -769,6 +829,222 @@ fn postgres16_introspect_multiple_schemas() {
     let _ = std::fs::remove_dir_all(&home);
 }
 
+/// `--list-privileges` reports table grants and RLS policies for the
+/// connected user, scoped by `--schema`.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_introspect_privileges_and_rls() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("privileges");
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &["introspect", "--dsn", &dsn, "--schema", "analytics", "--list-privileges", ""],
+    );
+    assert_eq!(code, 0, "introspect --list-privileges failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "introspect");
+    assert_matches_schema(&envelope, "introspect_success.json");
+
+    let report = &envelope["data"]["privileges"];
+    assert_eq!(report["user"].as_str(), Some("plenum"), "report: {report}");
+    assert_eq!(report["scope"].as_str(), Some("database"));
+
+    let tables = report["tables"].as_array().expect("tables array");
+    assert_eq!(tables.len(), 1, "analytics tables: {tables:?}");
+    let page_views = &tables[0];
+    assert_eq!(page_views["table"].as_str(), Some("page_views"));
+    assert!(
+        string_vec(&page_views["privileges"]).contains(&"SELECT".to_string()),
+        "owner should hold SELECT: {page_views}"
+    );
+    assert_eq!(page_views["rls_enabled"].as_bool(), Some(true));
+    assert_eq!(page_views["rls_forced"].as_bool(), Some(false));
+
+    let policy = &page_views["policies"][0];
+    assert_eq!(policy["name"].as_str(), Some("public_paths"), "policy: {policy}");
+    assert_eq!(policy["command"].as_str(), Some("SELECT"));
+    assert_eq!(policy["applies_to_user"].as_bool(), Some(true));
+    assert!(policy["using"].as_str().unwrap_or_default().contains("/admin/"));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `--index-health <table>` reports the seeded `customer_id` index as redundant
+/// with the primary key and notes when usage counters were last reset.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_introspect_index_health() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("index_health");
+
+    let (code, stdout) =
+        run_plenum(&home, &["introspect", "--dsn", &dsn, "--index-health", "order_items"]);
+    assert_eq!(code, 0, "introspect --index-health failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "introspect");
+    assert_matches_schema(&envelope, "introspect_success.json");
+
+    let health = &envelope["data"]["health"];
+    let findings = health["findings"].as_array().expect("findings array");
+    assert!(findings.iter().all(|f| f["table"].as_str() == Some("order_items")), "{health}");
+    let redundant = findings
+        .iter()
+        .find(|f| {
+            f["index"].as_str() == Some("idx_order_items_customer")
+                && f["reason"].as_str() == Some("redundant")
+        })
+        .unwrap_or_else(|| panic!("idx_order_items_customer not reported redundant: {health}"));
+    assert_eq!(redundant["schema"].as_str(), Some("public"));
+    assert_eq!(redundant["metric"].as_str(), Some("index_bytes"));
+    assert!(redundant["detail"].as_str().unwrap_or_default().contains("order_items_pkey"));
+    assert!(
+        !findings.iter().any(|f| f["index"].as_str() == Some("order_items_pkey")),
+        "primary keys are never reported: {health}"
+    );
+    assert!(!health["notes"].as_array().expect("notes array").is_empty());
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `--sizes` reports bytes per table that add up to `pg_total_relation_size`,
+/// largest first, and pages with `--limit` / `--offset`.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_introspect_sizes() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("sizes");
+
+    let (code, stdout) = run_plenum(&home, &["introspect", "--dsn", &dsn, "--sizes"]);
+    assert_eq!(code, 0, "introspect --sizes failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "introspect");
+    assert_matches_schema(&envelope, "introspect_success.json");
+
+    let sizes = &envelope["data"]["sizes"];
+    let tables = sizes["tables"].as_array().expect("tables array");
+    assert_eq!(tables[0]["name"].as_str(), Some("bulk_rows"), "largest first: {sizes}");
+    for table in tables {
+        let part = |key: &str| table[key].as_u64().unwrap_or_else(|| panic!("{key}: {table}"));
+        assert_eq!(
+            part("total_bytes"),
+            part("table_bytes") + part("index_bytes") + part("toast_bytes"),
+            "{table}"
+        );
+    }
+    let database_bytes = sizes["database_bytes"].as_u64().expect("database_bytes");
+    assert!(database_bytes >= tables[0]["total_bytes"].as_u64().unwrap_or(0));
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &["introspect", "--dsn", &dsn, "--sizes", "--limit", "1", "--offset", "1"],
+    );
+    assert_eq!(code, 0, "introspect --sizes --limit failed, stdout={stdout}");
+    let page = assert_envelope(&stdout, true, "introspect");
+    let page = &page["data"]["sizes"];
+    assert_eq!(page["tables"].as_array().map(Vec::len), Some(1));
+    assert_eq!(page["tables"][0]["name"], tables[1]["name"]);
+    assert_eq!(page["offset"].as_u64(), Some(1));
+    assert_eq!(page["has_more"].as_bool(), Some(true));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `--settings` reads `pg_settings` with a glob filter; `max_connections` needs a
+/// restart while `TimeZone` can be set per session.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_introspect_settings() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("settings");
+
+    let (code, stdout) =
+        run_plenum(&home, &["introspect", "--dsn", &dsn, "--settings", "max_conn*"]);
+    assert_eq!(code, 0, "introspect --settings failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "introspect");
+    assert_matches_schema(&envelope, "introspect_success.json");
+    let settings = envelope["data"]["settings"].as_array().expect("settings array");
+    assert_eq!(settings.len(), 1, "{envelope}");
+    assert_eq!(settings[0]["name"].as_str(), Some("max_connections"));
+    assert_eq!(settings[0]["session_changeable"].as_bool(), Some(false));
+    assert!(settings[0]["source"].is_string(), "{envelope}");
+
+    let (code, stdout) = run_plenum(&home, &["introspect", "--dsn", &dsn, "--settings"]);
+    assert_eq!(code, 0, "introspect --settings (all) failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "introspect");
+    let settings = envelope["data"]["settings"].as_array().expect("settings array");
+    let by_name = |name: &str| {
+        settings.iter().find(|s| s["name"] == name).unwrap_or_else(|| panic!("{name} listed"))
+    };
+    assert_eq!(by_name("TimeZone")["session_changeable"].as_bool(), Some(true));
+    assert_eq!(by_name("work_mem")["unit"].as_str(), Some("kB"));
+    let expected = scalar_query(&home, &dsn, "SELECT count(*)::text FROM pg_settings");
+    assert_eq!(expected.as_str(), Some(settings.len().to_string().as_str()));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+// ===== inspect =====
+
+/// `inspect --activity` returns the session and lock-wait arrays in the
+/// standard envelope; plenum's own backend is never listed.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_inspect_activity() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("inspect_activity");
+
+    let (code, stdout) =
+        run_plenum(&home, &["inspect", "--dsn", &dsn, "--activity", "--redact-queries"]);
+    assert_eq!(code, 0, "inspect --activity failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "inspect");
+    assert_matches_schema(&envelope, "inspect_success.json");
+
+    let activity = &envelope["data"]["activity"];
+    assert_eq!(envelope["data"]["type"].as_str(), Some("activity"));
+    let sessions = activity["sessions"].as_array().expect("sessions array");
+    assert!(activity["lock_waits"].as_array().is_some(), "{activity}");
+    assert!(
+        !sessions
+            .iter()
+            .any(|s| s["query"].as_str().is_some_and(|q| q.contains("pg_stat_activity"))),
+        "plenum's own activity query leaked into sessions: {activity}"
+    );
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `inspect --top-queries` ranks `pg_stat_statements` entries; a statement just
+/// run through `plenum query` shows up normalized, with its calls counted.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_inspect_top_queries() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("inspect_top_queries");
+
+    for id in ["1", "2"] {
+        scalar_query(&home, &dsn, &format!("SELECT name FROM customers WHERE id = {id}"));
+    }
+    let (code, stdout) = run_plenum(
+        &home,
+        &["inspect", "--dsn", &dsn, "--top-queries", "--order-by", "calls", "--limit", "1000"],
+    );
+    assert_eq!(code, 0, "inspect --top-queries failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "inspect");
+    assert_matches_schema(&envelope, "inspect_success.json");
+
+    let report = &envelope["data"]["top_queries"];
+    assert_eq!(report["order_by"].as_str(), Some("calls"));
+    let statements = report["statements"].as_array().expect("statements array");
+    let lookup = statements
+        .iter()
+        .find(|s| s["query"].as_str() == Some("SELECT name FROM customers WHERE id = $1"))
+        .unwrap_or_else(|| panic!("customers lookup not reported: {report}"));
+    assert!(lookup["calls"].as_u64().unwrap_or(0) >= 2, "{lookup}");
+    assert_eq!(lookup["database"].as_str(), Some("plenum_test"));
+    let calls: Vec<u64> = statements.iter().filter_map(|s| s["calls"].as_u64()).collect();
+    assert!(calls.windows(2).all(|w| w[0] >= w[1]), "not ranked by calls: {calls:?}");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
 // ===== query: allowed operations =====
 
 /// SELECT round-trips seeded values — unicode/emoji strings, booleans, JSONB,
@@ -1000,58 +1276,958 @@ fn postgres16_query_max_rows_truncation() {
     let _ = std::fs::remove_dir_all(&home);
 }
 
-/// `timeout_ms` exceeded via `pg_sleep()` surfaces a structured
-/// `QUERY_TIMEOUT` error, not a hang or a driver panic.
+/// `--format ndjson` streams a header with `PostgreSQL` type names, one line per
+/// row and a trailer; a result larger than one portal fetch batch arrives intact.
 #[test]
 #[ignore = "requires live DB (scripts/test-live.sh)"]
-fn postgres16_query_timeout_via_pg_sleep() {
+fn postgres16_query_ndjson_stream() {
     let dsn = require_dsn(POSTGRES_DSN_VAR);
-    let home = scratch_home("timeout");
+    let home = scratch_home("ndjson");
+
+    let parse_lines = |stdout: &str| -> Vec<Value> {
+        stdout
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap_or_else(|e| panic!("bad line {l:?}: {e}")))
+            .collect()
+    };
 
     let (code, stdout) = run_plenum(
         &home,
-        &["query", "--dsn", &dsn, "--sql", "SELECT pg_sleep(5)", "--timeout-ms", "300"],
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT id, name FROM customers ORDER BY id",
+            "--format",
+            "ndjson",
+            "--max-rows",
+            "1",
+        ],
     );
-    assert_ne!(code, 0, "pg_sleep past the timeout must fail, stdout={stdout}");
+    assert_eq!(code, 0, "ndjson query failed, stdout={stdout}");
+    let lines = parse_lines(&stdout);
+    assert_eq!(lines.len(), 3, "header, one row, trailer: {stdout}");
+    assert_eq!(lines[0]["type"], "header");
+    assert_eq!(lines[0]["engine"], "postgres");
+    assert_eq!(lines[0].pointer("/columns/0/name").and_then(Value::as_str), Some("id"));
+    assert_eq!(lines[0].pointer("/columns/0/type").and_then(Value::as_str), Some("int4"));
+    assert_eq!(lines[0].pointer("/columns/1/type").and_then(Value::as_str), Some("varchar"));
+    assert_eq!(lines[1]["type"], "row");
+    assert_eq!(lines[1].pointer("/values/0").and_then(Value::as_i64), Some(1));
+    assert_eq!(lines[2]["type"], "trailer");
+    assert_eq!(lines[2].pointer("/meta/rows_returned").and_then(Value::as_u64), Some(1));
+    assert_eq!(lines[2].pointer("/meta/rows_truncated").and_then(Value::as_bool), Some(true));
+    assert_eq!(lines[2].pointer("/meta/next_offset").and_then(Value::as_u64), Some(1));
+
+    // bulk_rows (1,500 rows) spans more than one portal fetch.
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n FROM bulk_rows ORDER BY n",
+            "--format",
+            "ndjson",
+        ],
+    );
+    assert_eq!(code, 0, "ndjson bulk query failed");
+    let lines = parse_lines(&stdout);
+    assert_eq!(lines.len(), 1502, "header + 1500 rows + trailer");
+    assert_eq!(lines[1500].pointer("/values/0").and_then(Value::as_i64), Some(1500));
+    assert_eq!(lines[1501].pointer("/meta/rows_returned").and_then(Value::as_u64), Some(1500));
+    assert_eq!(lines[1501].pointer("/meta/rows_truncated").and_then(Value::as_bool), Some(false));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `--param name=value` binds `:name` placeholders: a repeated name reuses one
+/// `$n`, and `::type` casts and string literals are left alone. (JSON integers
+/// bind as `int8`, hence the casts against the `integer` column.)
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_query_named_params() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("named_params");
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n, ':lo' AS literal, n::text AS n_text FROM bulk_rows \
+             WHERE n BETWEEN :lo::bigint AND :lo::bigint + :span::bigint ORDER BY n",
+            "--param",
+            "span=2",
+            "--param",
+            "lo=100",
+        ],
+    );
+    assert_eq!(code, 0, "named-param query failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_matches_schema(&envelope, "query_success.json");
+    let rows = envelope.pointer("/data/rows").and_then(Value::as_array).expect("rows");
+    let ns: Vec<i64> = rows.iter().map(|r| r[0].as_i64().expect("n")).collect();
+    assert_eq!(ns, [100, 101, 102]);
+    assert_eq!(rows[0][1], ":lo");
+    assert_eq!(rows[0][2], "100");
+
+    let (code, stdout) =
+        run_plenum(&home, &["query", "--dsn", &dsn, "--sql", "SELECT :a::int", "--param", "b=1"]);
+    assert_ne!(code, 0, "missing :a must fail, stdout={stdout}");
     let envelope = assert_envelope(&stdout, false, "query");
-    assert_matches_schema(&envelope, "error_envelope.json");
-    assert_error_code(&envelope, "QUERY_TIMEOUT");
+    assert_error_code(&envelope, "INVALID_INPUT");
 
     let _ = std::fs::remove_dir_all(&home);
 }
 
-// ===== envelope contract =====
+/// Typed `--param` values bind as the native type the statement expects:
+/// `int` fits an `integer` column without a cast, `int[]` binds an `integer[]`
+/// for `= ANY(...)`, and `decimal` round-trips through `numeric` exactly. A
+/// typed value that cannot represent the inferred type fails the query.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_query_typed_params() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("typed_params");
 
-/// Identical inputs produce identical outputs once `execution_ms` is
-/// redacted, for both query and introspect, and stdout parses as exactly
-/// one JSON document (no logs, no diagnostics).
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n, :id = 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::uuid AS uuid_eq, \
+             :big::numeric::text AS big, :small::numeric::text AS small, \
+             encode(:blob, 'hex') AS blob, \
+             :at = '2023-12-31 23:30:00+00'::timestamptz AS at_eq, \
+             :day::date::text AS day, (:doc::jsonb)->>'a' AS doc_a \
+             FROM bulk_rows WHERE n = ANY(:ids) AND n <> :skip ORDER BY n",
+            "--param",
+            "id=A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11::uuid",
+            "--param",
+            "big=12345678901234567890.123456789::decimal",
+            "--param",
+            "small=-0.000120::decimal",
+            "--param",
+            "blob=3q2+7w==::bytes",
+            "--param",
+            "at=2024-01-01T01:30:00+02:00::timestamptz",
+            "--param",
+            "day=2024-02-29::date",
+            "--param",
+            r#"doc={"a": "x"}::json"#,
+            "--param",
+            "ids=[3,1,2]::int[]",
+            "--param",
+            "skip=2::int",
+        ],
+    );
+    assert_eq!(code, 0, "typed-param query failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_matches_schema(&envelope, "query_success.json");
+    let rows = envelope.pointer("/data/rows").and_then(Value::as_array).expect("rows");
+    let ns: Vec<i64> = rows.iter().map(|r| r[0].as_i64().expect("n")).collect();
+    assert_eq!(ns, [1, 3]);
+    assert_eq!(rows[0][1], true);
+    assert_eq!(rows[0][2], "12345678901234567890.123456789");
+    assert_eq!(rows[0][3], "-0.000120");
+    assert_eq!(rows[0][4], "deadbeef");
+    assert_eq!(rows[0][5], true);
+    assert_eq!(rows[0][6], "2024-02-29");
+    assert_eq!(rows[0][7], "x");
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n FROM bulk_rows WHERE n = :n",
+            "--param",
+            "n=a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11::uuid",
+        ],
+    );
+    assert_ne!(code, 0, "uuid against integer must fail, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, false, "query");
+    assert_error_code(&envelope, "QUERY_FAILED");
+    let message = envelope.pointer("/error/message").and_then(Value::as_str).expect("message");
+    assert!(message.contains("uuid parameter cannot bind to int4"), "{message}");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `--diff-against` runs the same SQL on a saved `SQLite` copy of `bulk_rows`
+/// and reports the drift by `--key`. The typed named parameter binds as an
+/// `integer` `$1` on `PostgreSQL` and as `?` on `SQLite`, so the SQL needs no cast.
 #[test]
 #[ignore = "requires live DB (scripts/test-live.sh)"]
-fn postgres16_envelope_determinism_and_json_only_stdout() {
+fn postgres16_query_data_diff() {
     let dsn = require_dsn(POSTGRES_DSN_VAR);
-    let home = scratch_home("determinism");
+    let home = scratch_home("data_diff");
+    let mirror = home.join("mirror.sqlite");
+    rusqlite::Connection::open(&mirror)
+        .expect("open mirror")
+        .execute_batch(
+            "CREATE TABLE bulk_rows (n INTEGER PRIMARY KEY, label TEXT NOT NULL);
+             WITH RECURSIVE s(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM s WHERE n < 20)
+             INSERT INTO bulk_rows SELECT n, printf('row-%04d', n) FROM s;
+             UPDATE bulk_rows SET label = 'drift' WHERE n = 3;
+             DELETE FROM bulk_rows WHERE n = 4;",
+        )
+        .expect("seed mirror");
+    let mirror = mirror.to_string_lossy().into_owned();
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "connect", "--name", "mirror", "--engine", "sqlite", "--file", &mirror, "--save",
+            "local",
+        ],
+    );
+    assert_eq!(code, 0, "saving the mirror connection failed, stdout={stdout}");
 
-    let query_args =
-        ["query", "--dsn", &dsn, "--sql", "SELECT id, name, email FROM customers ORDER BY id"];
-    let introspect_args = ["introspect", "--dsn", &dsn, "--table", "orders"];
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n, label FROM bulk_rows WHERE n <= :hi",
+            "--param",
+            "hi=20::int",
+            "--diff-against",
+            "mirror",
+            "--key",
+            "n",
+        ],
+    );
+    assert_eq!(code, 0, "data diff failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_matches_schema(&envelope, "query_diff_success.json");
+    let data = envelope.get("data").expect("data");
+    assert_eq!(data["counts"]["changed"], 1);
+    assert_eq!(data["counts"]["removed"], 1);
+    assert_eq!(data["counts"]["unchanged"], 18);
+    assert_eq!(data["rows_removed"][0]["n"], 4);
+    assert_eq!(data["rows_changed"][0]["key"]["n"], 3);
+    assert_eq!(data["rows_changed"][0]["changes"][0]["from"], "row-0003");
+    assert_eq!(data["rows_changed"][0]["changes"][0]["to"], "drift");
 
-    for args in [&query_args[..], &introspect_args[..]] {
-        let (code_a, stdout_a) = run_plenum(&home, args);
-        let (code_b, stdout_b) = run_plenum(&home, args);
-        assert_eq!(code_a, 0, "first run failed, stdout={stdout_a}");
-        assert_eq!(code_b, 0, "second run failed, stdout={stdout_b}");
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT label FROM bulk_rows",
+            "--diff-against",
+            "mirror",
+            "--key",
+            "n",
+        ],
+    );
+    assert_ne!(code, 0, "a key missing from the result must fail, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, false, "query");
+    assert_error_code(&envelope, "INVALID_INPUT");
 
-        // Stdout is a single JSON document: parsing the entire stream as one
-        // value succeeds only when nothing else is interleaved.
-        let envelope_a: Value = serde_json::from_str(stdout_a.trim()).expect("stdout is JSON only");
-        let envelope_b: Value = serde_json::from_str(stdout_b.trim()).expect("stdout is JSON only");
+    let _ = std::fs::remove_dir_all(&home);
+}
 
-        assert_eq!(
-            redact_execution_ms(envelope_a),
-            redact_execution_ms(envelope_b),
-            "identical inputs must produce identical outputs (execution_ms redacted) for {args:?}"
-        );
-    }
+/// `--binary` renders BYTEA as hex or its length, and `--max-cell-bytes` replaces a
+/// large JSONB document with a marker so the byte budget still fits the row.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_query_large_values() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("large_values");
+    let sql = "SELECT decode('deadbeef', 'hex') AS b, \
+               (SELECT jsonb_agg(n) FROM generate_series(1, 2000) AS n) AS doc";
+
+    let (code, stdout) =
+        run_plenum(&home, &["query", "--dsn", &dsn, "--sql", sql, "--max-bytes", "1000"]);
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_eq!(envelope.pointer("/data/rows").and_then(Value::as_array).map(Vec::len), Some(0));
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            sql,
+            "--max-bytes",
+            "1000",
+            "--max-cell-bytes",
+            "32",
+            "--binary",
+            "hex",
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_matches_schema(&envelope, "query_success.json");
+    let row = envelope.pointer("/data/rows/0").and_then(Value::as_array).expect("row");
+    assert_eq!(row[0], "deadbeef");
+    assert_eq!(row[1]["truncated"], true);
+    assert_eq!(row[1]["prefix"], "[1,2,3,4,5,6,7,8,9,10,11,12,13,1");
+    assert_eq!(row[1]["original_bytes"], 8894, "measured as compact serialized JSON");
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &["query", "--dsn", &dsn, "--sql", sql, "--binary", "length-only", "--max-cell-bytes", "4"],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_eq!(envelope.pointer("/data/rows/0/0"), Some(&Value::from(4)));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `--deterministic` runs an unordered query under ORDER BY so the page is a fixed
+/// prefix, and reports who fixed the order in `metadata.ordering`.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_query_deterministic() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("deterministic");
+    let sql = "SELECT n % 3 AS bucket, n FROM bulk_rows WHERE n <= 9 UNION ALL SELECT 0, 0";
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &["query", "--dsn", &dsn, "--sql", sql, "--max-rows", "4", "--deterministic"],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_matches_schema(&envelope, "query_success.json");
+    assert_eq!(envelope["data"]["rows"], serde_json::json!([[0, 0], [0, 3], [0, 6], [0, 9]]));
+    assert_eq!(envelope["meta"]["ordering"], "imposed");
+    assert_eq!(envelope["meta"]["has_more"], true);
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n FROM bulk_rows ORDER BY n DESC",
+            "--max-rows",
+            "2",
+            "--deterministic",
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_eq!(envelope["data"]["rows"], serde_json::json!([[1500], [1499]]));
+    assert_eq!(envelope["meta"]["ordering"], "engine");
+
+    // json has no ordering operator: the query runs as written
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n, '{}'::json AS doc FROM bulk_rows WHERE n <= 3",
+            "--max-rows",
+            "2",
+            "--deterministic",
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_eq!(envelope["data"]["rows"].as_array().map(Vec::len), Some(2));
+    assert_eq!(envelope["meta"]["ordering"], "unordered");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// Structured EXPLAIN maps `PostgreSQL` node types onto the shared operation
+/// taxonomy and carries index names, filters and join conditions.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_query_structured_explain_operations() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("explain_operations");
+    let explain = |sql: &str| {
+        let (code, stdout) = run_plenum(
+            &home,
+            &["query", "--dsn", &dsn, "--sql", sql, "--explain-format", "structured"],
+        );
+        assert_eq!(code, 0, "stdout={stdout}");
+        let envelope = assert_envelope(&stdout, true, "query");
+        assert_matches_schema(&envelope, "query_success.json");
+        envelope["data"]["plan"].clone()
+    };
+
+    let plan = explain("EXPLAIN SELECT * FROM bulk_rows WHERE n = 7");
+    assert_eq!(plan["operation"], "index_scan", "{plan}");
+    assert_eq!(plan["index"], "bulk_rows_pkey");
+    assert_eq!(plan["relation"], "bulk_rows");
+
+    let plan = explain("EXPLAIN SELECT n FROM bulk_rows WHERE n < 5");
+    assert_eq!(plan["operation"], "index_only_scan", "{plan}");
+
+    // Tiny tables: a nested loop with the join condition as its join filter
+    let plan = explain(
+        "EXPLAIN SELECT c.name, o.status FROM orders o JOIN customers c ON c.id = o.customer_id \
+         WHERE o.status <> 'cancelled'",
+    );
+    assert!(
+        ["nested_loop", "hash_join", "merge_join"].contains(&plan["operation"].as_str().unwrap()),
+        "{plan}"
+    );
+    assert!(plan["join_condition"].as_str().is_some_and(|c| c.contains("customer_id")), "{plan}");
+    let orders = plan["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|child| child["relation"] == "orders")
+        .expect("orders scan");
+    assert_eq!(orders["operation"], "full_scan");
+    assert!(orders["filter"].as_str().is_some_and(|f| f.contains("status")), "{orders}");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `advise` suggests vendor DDL from the plan and the table's indexes; nothing is run.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_advise_index_suggestions() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("advise");
+    let advise = |sql: &str, min_rows: &str| {
+        let (code, stdout) =
+            run_plenum(&home, &["advise", "--dsn", &dsn, "--sql", sql, "--min-rows", min_rows]);
+        assert_eq!(code, 0, "stdout={stdout}");
+        let envelope = assert_envelope(&stdout, true, "advise");
+        assert_matches_schema(&envelope, "advise_success.json");
+        envelope["data"].clone()
+    };
+
+    // Sort over a filtered seq scan: one index serving the filter and the order
+    let sorted = "SELECT * FROM orders WHERE status = 'shipped' ORDER BY placed_at DESC";
+    let data = advise(sorted, "0");
+    let suggestions = data["suggestions"].as_array().expect("suggestions");
+    assert_eq!(suggestions.len(), 1, "{data}");
+    assert_eq!(suggestions[0]["reason"], "sort");
+    assert_eq!(suggestions[0]["columns"], serde_json::json!(["status", "placed_at"]));
+    assert_eq!(
+        suggestions[0]["statement"],
+        "CREATE INDEX CONCURRENTLY \"idx_orders_status_placed_at\" ON \"public\".\"orders\" \
+         (\"status\", \"placed_at\" DESC);"
+    );
+    assert_eq!(data["plan"]["operation"], "sort", "{data}");
+
+    // Below the default min_rows, and served by idx_bulk_rows_label: nothing
+    assert_eq!(advise(sorted, "10000")["suggestions"], serde_json::json!([]));
+    assert_eq!(
+        advise("SELECT * FROM bulk_rows WHERE label = 'row-0042'", "0")["suggestions"],
+        serde_json::json!([])
+    );
+
+    let (code, stdout) =
+        run_plenum(&home, &["advise", "--dsn", &dsn, "--sql", "DELETE FROM orders"]);
+    assert_ne!(code, 0);
+    let envelope = assert_envelope(&stdout, false, "advise");
+    assert_eq!(envelope["error"]["code"], "INVALID_INPUT");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// Structured `EXPLAIN ANALYZE` runs the query and adds actual rows, time, loops
+/// and buffer counts to the plan; writes stay rejected.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_query_structured_explain_analyze() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("explain_analyze");
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "EXPLAIN ANALYZE SELECT n FROM bulk_rows WHERE n <= 100",
+            "--explain-format",
+            "structured",
+            "--timeout-ms",
+            "10000",
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_matches_schema(&envelope, "query_success.json");
+    let plan = &envelope["data"]["plan"];
+    assert_eq!(plan["relation"], "bulk_rows", "{plan}");
+    assert_eq!(plan["actual_rows"], 100.0);
+    assert_eq!(plan["loops"], 1);
+    assert!(plan["actual_time_ms"].is_number(), "{plan}");
+    assert!(plan["estimated_rows"].is_number(), "{plan}");
+    assert!(plan["buffers"]["shared_hit"].is_u64(), "{plan}");
+
+    // Without ANALYZE the plan stays estimate-only
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "EXPLAIN SELECT n FROM bulk_rows",
+            "--explain-format",
+            "structured",
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert!(envelope["data"]["plan"].get("actual_rows").is_none());
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "EXPLAIN ANALYZE DELETE FROM bulk_rows",
+            "--explain-format",
+            "structured",
+        ],
+    );
+    assert_ne!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, false, "query");
+    assert_eq!(envelope["error"]["code"], "CAPABILITY_VIOLATION");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `--summarize` reads every row of the result, even past `bulk_rows`'s 1500, and
+/// reports per-column statistics with the server's type names instead of rows.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_query_summarize() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("summarize");
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n, label, NULLIF(n % 2, 0) AS odd FROM bulk_rows WHERE n > :lo",
+            "--param",
+            "lo=500::int",
+            "--summarize",
+        ],
+    );
+    assert_eq!(code, 0, "summarize failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_matches_schema(&envelope, "query_summary_success.json");
+    let data = envelope.get("data").expect("data");
+    assert_eq!(data["row_count"], 1000);
+    assert!(data.get("rows").is_none(), "a summary must not return rows");
+
+    let n = &data["columns"][0];
+    assert_eq!(n["type"], "int4");
+    assert_eq!(n["distinct_count"], 1000);
+    assert_eq!(n["distinct_exact"], true);
+    assert_eq!(n["min"], 501);
+    assert_eq!(n["max"], 1500);
+    assert_eq!(data["columns"][1]["min"], "row-0501");
+    let odd = &data["columns"][2];
+    assert_eq!((odd["count"].as_u64(), odd["null_count"].as_u64()), (Some(500), Some(500)));
+    assert_eq!(odd["distinct_count"], 1);
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT n FROM bulk_rows",
+            "--summarize",
+            "--max-rows",
+            "5",
+        ],
+    );
+    assert_ne!(code, 0, "--summarize with --max-rows must be rejected, stdout={stdout}");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `--paginate` / `--cursor` page a filtered, ordered scan through keyset predicates: pages
+/// neither skip nor repeat rows, the last page carries no `next_cursor`, and a
+/// cursor replayed against different SQL is rejected.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_query_cursor_pagination() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("cursor");
+    let sql = "SELECT n, label FROM bulk_rows WHERE n <= 25 ORDER BY n";
+
+    let mut seen = Vec::new();
+    let mut cursor: Option<String> = None;
+    let mut pages = 0;
+    loop {
+        let mut args = vec!["query", "--dsn", &dsn, "--sql", sql, "--max-rows", "10", "--paginate"];
+        if let Some(c) = cursor.as_deref() {
+            args.extend(["--cursor", c]);
+        }
+        let (code, stdout) = run_plenum(&home, &args);
+        assert_eq!(code, 0, "cursor page failed, stdout={stdout}");
+        let envelope = assert_envelope(&stdout, true, "query");
+        assert_matches_schema(&envelope, "query_success.json");
+        let rows = envelope.pointer("/data/rows").and_then(Value::as_array).expect("rows");
+        seen.extend(rows.iter().map(|r| r[0].as_i64().expect("n")));
+        if pages > 0 {
+            assert!(envelope.pointer("/meta/next_offset").is_none(), "cursor pages have no offset");
+        }
+        pages += 1;
+        cursor = envelope.pointer("/meta/next_cursor").and_then(Value::as_str).map(String::from);
+        if cursor.is_none() {
+            break;
+        }
+        assert_eq!(envelope.pointer("/data/next_cursor"), envelope.pointer("/meta/next_cursor"));
+    }
+    assert_eq!(pages, 3);
+    assert_eq!(seen, (1..=25).collect::<Vec<i64>>());
+
+    // Unique string key: the value is bound as a parameter cast to varchar.
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT email FROM customers ORDER BY email",
+            "--max-rows",
+            "1",
+            "--paginate",
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let first = assert_envelope(&stdout, true, "query");
+    let next = first.pointer("/meta/next_cursor").and_then(Value::as_str).expect("next_cursor");
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT email FROM customers ORDER BY email",
+            "--max-rows",
+            "1",
+            "--cursor",
+            next,
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let second = assert_envelope(&stdout, true, "query");
+    assert!(
+        second.pointer("/data/rows/0/0").and_then(Value::as_str)
+            > first.pointer("/data/rows/0/0").and_then(Value::as_str)
+    );
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT email FROM customers ORDER BY email DESC",
+            "--max-rows",
+            "1",
+            "--cursor",
+            next,
+        ],
+    );
+    assert_ne!(code, 0, "cursor for other SQL must fail, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, false, "query");
+    assert_error_code(&envelope, "INVALID_INPUT");
+
+    // Without --paginate the query runs as written and no cursor is issued.
+    let (code, stdout) = run_plenum(
+        &home,
+        &["query", "--dsn", &dsn, "--sql", "SELECT n FROM bulk_rows", "--max-rows", "10"],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_eq!(envelope.pointer("/meta/has_more"), Some(&Value::Bool(true)));
+    assert!(envelope.pointer("/meta/next_cursor").is_none());
+
+    // Composite integer primary key, resumed with a parameterized filter
+    let sql = "SELECT customer_id, order_no FROM orders WHERE customer_id >= $1::bigint";
+    let (code, stdout) = run_plenum(
+        &home,
+        &["query", "--dsn", &dsn, "--sql", sql, "--param", "1", "--max-rows", "1", "--paginate"],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let first = assert_envelope(&stdout, true, "query");
+    let next = first.pointer("/meta/next_cursor").and_then(Value::as_str).expect("next_cursor");
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            sql,
+            "--param",
+            "1",
+            "--max-rows",
+            "1",
+            "--cursor",
+            next,
+        ],
+    );
+    assert_eq!(code, 0, "stdout={stdout}");
+    let second = assert_envelope(&stdout, true, "query");
+    assert_ne!(first.pointer("/data/rows/0"), second.pointer("/data/rows/0"));
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `--output-file` writes the rows locally and reports the path, row count and
+/// file schema (`PostgreSQL` type name → export type) in the stdout envelope.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_query_output_file_csv() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("export");
+    let out = home.join("customers.csv");
+    let out_str = out.to_str().expect("utf-8 temp path");
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &[
+            "query",
+            "--dsn",
+            &dsn,
+            "--sql",
+            "SELECT id, name, id * 1.5::float8 AS score FROM customers ORDER BY id",
+            "--output-file",
+            out_str,
+            "--max-rows",
+            "2",
+        ],
+    );
+    assert_eq!(code, 0, "export query failed, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, true, "query");
+    assert_matches_schema(&envelope, "query_export_success.json");
+    assert_eq!(envelope.pointer("/data/format").and_then(Value::as_str), Some("csv"));
+    assert_eq!(envelope.pointer("/data/rows_written").and_then(Value::as_u64), Some(2));
+    assert_eq!(
+        envelope.pointer("/data/columns/0/source_type").and_then(Value::as_str),
+        Some("int4")
+    );
+    assert_eq!(envelope.pointer("/data/columns/0/type").and_then(Value::as_str), Some("int64"));
+    assert_eq!(envelope.pointer("/data/columns/2/type").and_then(Value::as_str), Some("float64"));
+    assert_eq!(envelope.pointer("/meta/rows_truncated").and_then(Value::as_bool), Some(true));
+
+    let csv = std::fs::read_to_string(&out).expect("read exported csv");
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("id,name,score"));
+    assert!(lines.next().is_some_and(|l| l.starts_with("1,")), "{csv}");
+    assert_eq!(csv.lines().count(), 3, "header + 2 rows: {csv}");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// `timeout_ms` exceeded via `pg_sleep()` surfaces a structured
+/// `QUERY_TIMEOUT` error, not a hang or a driver panic.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_query_timeout_via_pg_sleep() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("timeout");
+
+    let (code, stdout) = run_plenum(
+        &home,
+        &["query", "--dsn", &dsn, "--sql", "SELECT pg_sleep(5)", "--timeout-ms", "300"],
+    );
+    assert_ne!(code, 0, "pg_sleep past the timeout must fail, stdout={stdout}");
+    let envelope = assert_envelope(&stdout, false, "query");
+    assert_matches_schema(&envelope, "error_envelope.json");
+    assert_error_code(&envelope, "QUERY_TIMEOUT");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+/// MCP `notifications/cancelled` stops a running `pg_sleep()` on the server via
+/// the session's cancel token, and the server moves on to the next request.
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_mcp_cancel_stops_pg_sleep() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("mcp-cancel");
+
+    let bin = env!("CARGO_BIN_EXE_plenum");
+    let mut child = Command::new(bin)
+        .arg("mcp")
+        .current_dir(&home)
+        .env("HOME", &home)
+        .env("XDG_CONFIG_HOME", &home)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn plenum mcp");
+    let mut stdin = child.stdin.take().expect("stdin");
+
+    let started = Instant::now();
+    let sleep = json!({
+        "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+        "params": { "name": "query", "arguments": { "dsn": dsn, "sql": "SELECT pg_sleep(30)" } }
+    });
+    writeln!(stdin, "{sleep}").unwrap();
+    stdin.flush().unwrap();
+    std::thread::sleep(Duration::from_secs(1));
+
+    // Queued behind the sleep: only answered once the sleep has stopped
+    let cancelled = json!({
+        "jsonrpc": "2.0", "method": "notifications/cancelled", "params": { "requestId": 1 }
+    });
+    let still_running = json!({
+        "jsonrpc": "2.0", "id": 2, "method": "tools/call",
+        "params": { "name": "query", "arguments": {
+            "dsn": dsn,
+            "sql": "SELECT count(*) AS sleeping FROM pg_stat_activity \
+                    WHERE state = 'active' AND query = 'SELECT pg_sleep(30)'"
+        } }
+    });
+    writeln!(stdin, "{cancelled}").unwrap();
+    writeln!(stdin, "{still_running}").unwrap();
+    drop(stdin);
+
+    let responses: Vec<Value> = BufReader::new(child.stdout.take().expect("stdout"))
+        .lines()
+        .map(|line| serde_json::from_str(&line.expect("read stdout")).expect("JSON-RPC line"))
+        .collect();
+    let _ = child.wait();
+
+    assert!(started.elapsed() < Duration::from_secs(15), "cancel did not stop pg_sleep");
+    assert_eq!(responses.len(), 1, "the cancelled call gets no response: {responses:#?}");
+    assert_eq!(responses[0]["id"], json!(2));
+    let text = responses[0]["result"]["content"][0]["text"].as_str().expect("tool result text");
+    let result: Value = serde_json::from_str(text).expect("query result JSON");
+    assert_eq!(result["rows"], json!([[0]]), "pg_sleep still active on the server");
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+// ===== envelope contract =====
+
+/// Identical inputs produce identical outputs once `execution_ms` is
+/// redacted, for both query and introspect, and stdout parses as exactly
+/// one JSON document (no logs, no diagnostics).
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_envelope_determinism_and_json_only_stdout() {
+    let dsn = require_dsn(POSTGRES_DSN_VAR);
+    let home = scratch_home("determinism");
+
+    let query_args =
+        ["query", "--dsn", &dsn, "--sql", "SELECT id, name, email FROM customers ORDER BY id"];
+    let introspect_args = ["introspect", "--dsn", &dsn, "--table", "orders"];
+
+    for args in [&query_args[..], &introspect_args[..]] {
+        let (code_a, stdout_a) = run_plenum(&home, args);
+        let (code_b, stdout_b) = run_plenum(&home, args);
+        assert_eq!(code_a, 0, "first run failed, stdout={stdout_a}");
+        assert_eq!(code_b, 0, "second run failed, stdout={stdout_b}");
+
+        // Stdout is a single JSON document: parsing the entire stream as one
+        // value succeeds only when nothing else is interleaved.
+        let envelope_a: Value = serde_json::from_str(stdout_a.trim()).expect("stdout is JSON only");
+        let envelope_b: Value = serde_json::from_str(stdout_b.trim()).expect("stdout is JSON only");
+
+        assert_eq!(
+            redact_execution_ms(envelope_a),
+            redact_execution_ms(envelope_b),
+            "identical inputs must produce identical outputs (execution_ms redacted) for {args:?}"
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(&home);
+}
+
+#[test]
+#[ignore = "requires live DB (scripts/test-live.sh)"]
+fn postgres16_plan_baselines() {
+    let parts = parse_dsn(&require_dsn(POSTGRES_DSN_VAR));
+    let home = scratch_home("plan");
+    let config = serde_json::json!({
+        "connections": {
+            "pg": {
+                "engine": "postgres",
+                "host": parts.host,
+                "port": parts.port.parse::<u16>().expect("port"),
+                "user": parts.user,
+                "password": parts.password,
+                "database": parts.database
+            }
+        },
+        "default": "pg"
+    });
+    let queries = home.join(".plenum").join("queries");
+    std::fs::create_dir_all(&queries).expect("create queries dir");
+    std::fs::write(home.join(".plenum").join("config.json"), config.to_string())
+        .expect("write config");
+    let write_query = |condition: &str| {
+        let sql =
+            format!("-- param: label text = row-0042\nSELECT n FROM bulk_rows WHERE {condition}\n");
+        std::fs::write(queries.join("bulk_by_label.sql"), sql).expect("write saved query");
+    };
+    let plan = |args: &[&str]| {
+        let (code, stdout) = run_plenum(&home, &[&["plan"], args].concat());
+        assert_eq!(code, 0, "stdout={stdout}");
+        let envelope = assert_envelope(&stdout, true, "plan");
+        assert_matches_schema(&envelope, "plan_success.json");
+        envelope["data"].clone()
+    };
+
+    // Served by idx_bulk_rows_label
+    write_query("label = :label");
+    let data = plan(&["record"]);
+    assert_eq!(data["queries"][0]["status"], "recorded", "{data}");
+    assert!(data["queries"][0]["current_cost"].as_f64().is_some(), "{data}");
+
+    let data = plan(&["check"]);
+    assert_eq!(data["queries"][0]["status"], "unchanged", "{data}");
+    assert_eq!(data["queries"][0]["cost_ratio"], 1.0);
+
+    // The index cannot serve a suffix match: the scan turns into a full scan
+    write_query("label LIKE '%' || :label");
+    let data = plan(&["check"]);
+    let query = &data["queries"][0];
+    assert_eq!(query["status"], "shape_changed", "{data}");
+    assert_eq!(query["sql_changed"], true);
+    assert!(
+        query["changes"][0]["baseline"].as_str().is_some_and(|b| b.contains("idx_bulk_rows_label")),
+        "{data}"
+    );
+    assert_eq!(query["changes"][0]["current"], "full_scan on bulk_rows", "{data}");
+    assert_eq!(data["changed"], 1);
 
     let _ = std::fs::remove_dir_all(&home);
 }
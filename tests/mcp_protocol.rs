@@ -1,8 +1,9 @@
 //! JSON-RPC 2.0 envelope compliance for `plenum mcp`.
 //!
 //! Drives the compiled `plenum mcp` binary over stdio and asserts that every
-//! emitted line is a valid JSON-RPC 2.0 Response — never a Notification, never
-//! `id: null`, never with extra top-level keys. Regression coverage for the
+//! emitted line is a valid JSON-RPC 2.0 Response — never `id: null`, never with
+//! extra top-level keys, and never a Notification unless the client asked for
+//! progress. Also covers `notifications/cancelled` for a running query. Regression coverage for the
 //! Claude Code Zod-strict validation incident where replying to
 //! `notifications/initialized` dropped the stdio connection before
 //! `tools/list` ever ran.
@@ -141,6 +142,8 @@ fn mcp_handshake_emits_only_valid_responses() {
         .collect();
     assert!(names.contains(&"introspect"), "tools list missing introspect: {names:?}");
     assert!(names.contains(&"query"), "tools list missing query: {names:?}");
+    assert!(names.contains(&"inspect"), "tools list missing inspect: {names:?}");
+    assert!(names.contains(&"advise"), "tools list missing advise: {names:?}");
 }
 
 #[test]
@@ -185,3 +188,85 @@ fn mcp_does_not_respond_to_unparseable_input() {
     let _ = child.kill();
     let _ = child.wait();
 }
+
+/// Read the next stdout line as JSON, failing the test if none arrives.
+fn read_message(reader: &mut impl BufRead) -> Value {
+    let mut buf = String::new();
+    let read = reader.read_line(&mut buf).expect("read stdout");
+    assert!(read > 0, "plenum mcp closed stdout early");
+    serde_json::from_str(buf.trim())
+        .unwrap_or_else(|e| panic!("non-JSON output on stdout: {e}: {buf:?}"))
+}
+
+#[test]
+fn mcp_cancels_running_query_and_reports_progress() {
+    let bin = env!("CARGO_BIN_EXE_plenum");
+
+    let mut child = Command::new(bin)
+        .arg("mcp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn plenum mcp");
+
+    let mut stdin = child.stdin.take().expect("stdin");
+    let mut reader = BufReader::new(child.stdout.take().expect("stdout"));
+
+    // Runs far longer than the test: only cancellation can end it
+    let call = json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "tools/call",
+        "params": {
+            "name": "query",
+            "arguments": {
+                "engine": "sqlite",
+                "file": ":memory:",
+                "sql": "WITH RECURSIVE c(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM c \
+                        WHERE n < 1000000000) SELECT count(*) FROM c"
+            },
+            "_meta": { "progressToken": "tok-7" }
+        }
+    });
+    writeln!(stdin, "{call}").unwrap();
+    stdin.flush().unwrap();
+
+    // Progress notifications carry the client's token and no id
+    let progress = read_message(&mut reader);
+    assert_eq!(progress["method"], json!("notifications/progress"), "{progress}");
+    assert!(progress.get("id").is_none(), "notifications must not carry an id: {progress}");
+    assert_eq!(progress["params"]["progressToken"], json!("tok-7"));
+    assert!(progress["params"]["progress"].as_u64().is_some_and(|p| p >= 1), "{progress}");
+
+    let cancelled = json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/cancelled",
+        "params": { "requestId": 7, "reason": "user aborted" }
+    });
+    let list_tools = json!({ "jsonrpc": "2.0", "id": 8, "method": "tools/list", "params": {} });
+    writeln!(stdin, "{cancelled}").unwrap();
+    writeln!(stdin, "{list_tools}").unwrap();
+    stdin.flush().unwrap();
+    drop(stdin);
+
+    // The cancelled call gets no response; the next request is still served
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut responses = Vec::new();
+    while Instant::now() < deadline {
+        let mut buf = String::new();
+        if reader.read_line(&mut buf).expect("read stdout") == 0 {
+            break;
+        }
+        let value: Value = serde_json::from_str(buf.trim()).expect("JSON-RPC line");
+        if value.get("method").is_none() {
+            assert_valid_response(&value);
+            responses.push(value);
+        }
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+
+    let ids: Vec<&Value> = responses.iter().map(|r| &r["id"]).collect();
+    assert_eq!(ids, vec![&json!(8)], "only tools/list should be answered: {responses:#?}");
+}
@@ -127,6 +127,8 @@ fn test_query_result_serializes_to_pure_json() {
         rows_truncated: false,
         truncated_by: None,
         plan: None,
+        next_cursor: None,
+        ordering: None,
     };
 
     let json_str = serde_json::to_string(&result).expect("Should serialize");
@@ -420,6 +422,9 @@ async fn test_truncated_result_signals_rows_truncated_true() {
         timeout_ms: None,
         offset: None,
         explain_format: None,
+        max_cell_bytes: None,
+        binary_format: None,
+        control: None,
     };
 
     let result =
@@ -479,6 +484,9 @@ async fn test_pagination_with_offset_returns_disjoint_pages() {
         timeout_ms: None,
         offset: None,
         explain_format: None,
+        max_cell_bytes: None,
+        binary_format: None,
+        control: None,
     };
     let r1 = SqliteEngine::execute(&config, "SELECT id FROM products ORDER BY id", &[], &caps_p1)
         .await
@@ -492,6 +500,9 @@ async fn test_pagination_with_offset_returns_disjoint_pages() {
         timeout_ms: None,
         offset: Some(1),
         explain_format: None,
+        max_cell_bytes: None,
+        binary_format: None,
+        control: None,
     };
     let r2 = SqliteEngine::execute(&config, "SELECT id FROM products ORDER BY id", &[], &caps_p2)
         .await
@@ -556,6 +567,8 @@ async fn test_max_bytes_truncates_at_row_boundary() {
         rows_truncated: false,
         truncated_by: None,
         plan: None,
+        next_cursor: None,
+        ordering: None,
     };
 
     // Budget tight enough for 2 rows but not 3
@@ -579,6 +592,8 @@ async fn test_max_bytes_unflagged_when_under_budget() {
         rows_truncated: false,
         truncated_by: None,
         plan: None,
+        next_cursor: None,
+        ordering: None,
     };
 
     apply_byte_budget(&mut result, 1_000_000);
@@ -602,6 +617,8 @@ async fn test_max_bytes_signals_in_metadata() {
         rows_truncated: false,
         truncated_by: None,
         plan: None,
+        next_cursor: None,
+        ordering: None,
     };
     apply_byte_budget(&mut result, 30);
 
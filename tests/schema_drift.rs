@@ -3,7 +3,10 @@
 //!
 //! When this test fails, run `cargo run --bin generate-schemas` to regenerate.
 
-use plenum::{ConnectionInfo, ErrorEnvelope, IntrospectResult, QueryResult, SuccessEnvelope};
+use plenum::{
+    ConnectionInfo, DataDiff, ErrorEnvelope, ExportResult, IndexAdvice, InspectResult,
+    IntrospectResult, NdjsonLine, PlanReport, QueryResult, ResultSummary, SuccessEnvelope,
+};
 use schemars::schema_for;
 
 fn expected_schema(schema: &schemars::schema::RootSchema) -> String {
@@ -47,6 +50,36 @@ fn introspect_success_schema_not_stale() {
     );
 }
 
+#[test]
+fn advise_success_schema_not_stale() {
+    let generated = expected_schema(&schema_for!(SuccessEnvelope<IndexAdvice>));
+    let on_disk = on_disk("advise_success.json");
+    assert_eq!(
+        on_disk, generated,
+        "schemas/advise_success.json is stale — run: cargo run --bin generate-schemas"
+    );
+}
+
+#[test]
+fn plan_success_schema_not_stale() {
+    let generated = expected_schema(&schema_for!(SuccessEnvelope<PlanReport>));
+    let on_disk = on_disk("plan_success.json");
+    assert_eq!(
+        on_disk, generated,
+        "schemas/plan_success.json is stale — run: cargo run --bin generate-schemas"
+    );
+}
+
+#[test]
+fn inspect_success_schema_not_stale() {
+    let generated = expected_schema(&schema_for!(SuccessEnvelope<InspectResult>));
+    let on_disk = on_disk("inspect_success.json");
+    assert_eq!(
+        on_disk, generated,
+        "schemas/inspect_success.json is stale — run: cargo run --bin generate-schemas"
+    );
+}
+
 #[test]
 fn query_success_schema_not_stale() {
     let generated = expected_schema(&schema_for!(SuccessEnvelope<QueryResult>));
@@ -56,3 +89,43 @@ fn query_success_schema_not_stale() {
         "schemas/query_success.json is stale — run: cargo run --bin generate-schemas"
     );
 }
+
+#[test]
+fn query_ndjson_line_schema_not_stale() {
+    let generated = expected_schema(&schema_for!(NdjsonLine));
+    let on_disk = on_disk("query_ndjson_line.json");
+    assert_eq!(
+        on_disk, generated,
+        "schemas/query_ndjson_line.json is stale — run: cargo run --bin generate-schemas"
+    );
+}
+
+#[test]
+fn query_export_success_schema_not_stale() {
+    let generated = expected_schema(&schema_for!(SuccessEnvelope<ExportResult>));
+    let on_disk = on_disk("query_export_success.json");
+    assert_eq!(
+        on_disk, generated,
+        "schemas/query_export_success.json is stale — run: cargo run --bin generate-schemas"
+    );
+}
+
+#[test]
+fn query_diff_success_schema_not_stale() {
+    let generated = expected_schema(&schema_for!(SuccessEnvelope<DataDiff>));
+    let on_disk = on_disk("query_diff_success.json");
+    assert_eq!(
+        on_disk, generated,
+        "schemas/query_diff_success.json is stale — run: cargo run --bin generate-schemas"
+    );
+}
+
+#[test]
+fn query_summary_success_schema_not_stale() {
+    let generated = expected_schema(&schema_for!(SuccessEnvelope<ResultSummary>));
+    let on_disk = on_disk("query_summary_success.json");
+    assert_eq!(
+        on_disk, generated,
+        "schemas/query_summary_success.json is stale — run: cargo run --bin generate-schemas"
+    );
+}
@@ -13,20 +13,51 @@
 //!   introspect   — tables, columns + declared types/affinity, PK, composite FK,
 //!                  indexes, views; stable deterministic JSON shape
 //!   query allowed — SELECT, EXPLAIN, EXPLAIN QUERY PLAN, PRAGMA, transaction
-//!                  control (BEGIN, ROLLBACK, SAVEPOINT / RELEASE)
+//!                  control (BEGIN, ROLLBACK, SAVEPOINT / RELEASE); structured
+//!                  EXPLAIN operations, tables and indexes; EXPLAIN ANALYZE →
+//!                  `INVALID_INPUT`
 //!   query denied  — INSERT / UPDATE / DELETE / CREATE / DROP / ALTER →
 //!                  `CAPABILITY_VIOLATION` before execution, then re-query to
 //!                  prove DB state unchanged
 //!   safety       — `max_rows` truncation + `rows_truncated` flag; `timeout_ms`
 //!                  (`busy_timeout` + interrupt) documented and tested
+//!   large values — `--binary` hex / length-only / omit for BLOBs;
+//!                  `--max-cell-bytes` markers keep wide rows within `max_bytes`
+//!   streaming    — `execute_stream` header types, offset/`max_rows` window,
+//!                  sink refusal → `truncated_by=bytes`
+//!   params       — `:name` placeholders rewritten to `?` and bound per occurrence;
+//!                  typed values (bytes → BLOB, uuid/timestamp → canonical text)
+//!   cursor       — keyset pages cover the table exactly once; implicit PK order;
+//!                  cursor replayed against other SQL → `INVALID_INPUT`
+//!   ordering     — `--deterministic` sorts complete pages, wraps truncated ones in
+//!                  ORDER BY, keeps the query's own ORDER BY ("engine")
+//!   data diff    — added / removed / changed rows by key; identical results
+//!                  by digest alone; large results re-fetched per differing chunk
+//!   advise       — full-scan filter and sort suggestions from the WHERE clause;
+//!                  columns an index already leads with → none; non-SELECT →
+//!                  `INVALID_INPUT`
+//!   summary      — `--summarize` statistics cover all rows regardless of
+//!                  `max_rows`; empty results; writes rejected
+//!   export       — `ExportWriter` files read back with their column types
 //!   envelope     — `QueryResult` / `IntrospectResult` serialize to valid JSON;
 //!                  deterministic with `execution_ms` excluded
 
 #![cfg(feature = "sqlite")]
 
+use plenum::cursor::execute_with_cursor;
+use plenum::diff::compute_data_diff;
 use plenum::engine::sqlite::SqliteEngine;
-use plenum::engine::{IntrospectOperation, IntrospectResult, TableFields};
-use plenum::{Capabilities, ConnectionConfig, DatabaseEngine};
+use plenum::engine::{
+    summarize_query, InspectOperation, IntrospectOperation, IntrospectResult, ProfileOptions,
+    QueryRanking, SampleOptions, SizeOptions, TableFields,
+};
+use plenum::ordering::execute_with_ordering;
+use plenum::params::QueryParams;
+use plenum::{
+    apply_byte_budget, apply_byte_budget_with_cell_limit, limit_cells, BinaryFormat, Capabilities,
+    ConnectionConfig, DatabaseEngine, ExplainFormat, PlanOperation, ResultColumn, RowSink,
+};
+use serde_json::{json, Value};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -185,7 +216,8 @@ fn build_parity_fixture() -> PathBuf {
     .expect("insert orders");
 
     // ------------------------------------------------------------------
-    // order_items — 3-col composite PK, composite FK → orders, index on sku
+    // order_items — 3-col composite PK, composite FK → orders, index on sku,
+    // and an index on customer_id made redundant by the primary key prefix
     // ------------------------------------------------------------------
     conn.execute_batch(
         "CREATE TABLE order_items (
@@ -199,7 +231,8 @@ fn build_parity_fixture() -> PathBuf {
             FOREIGN KEY (customer_id, order_no)
                 REFERENCES orders(customer_id, order_no)
         );
-        CREATE INDEX idx_order_items_sku ON order_items(sku)",
+        CREATE INDEX idx_order_items_sku ON order_items(sku);
+        CREATE INDEX idx_order_items_customer ON order_items(customer_id)",
     )
     .expect("create order_items");
 
@@ -551,6 +584,428 @@ async fn parity_introspect_list_indexes_for_table() {
     cleanup(&path);
 }
 
+#[tokio::test]
+async fn parity_introspect_profile_orders() {
+    // A table smaller than the sample cap is read in full, so counts are exact.
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let result = SqliteEngine::introspect(
+        &config,
+        &IntrospectOperation::ProfileTable {
+            name: "orders".to_string(),
+            options: ProfileOptions::default(),
+        },
+        None,
+        None,
+    )
+    .await;
+    assert!(result.is_ok(), "ProfileTable(orders) failed: {:?}", result.err());
+    let IntrospectResult::TableProfile { profile } = result.unwrap() else {
+        panic!("Expected TableProfile variant");
+    };
+    assert_eq!(profile.source, "sample");
+    assert_eq!(profile.row_count, 3);
+    assert!(profile.exhaustive);
+
+    let names: Vec<&str> = profile.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["customer_id", "order_no", "status", "placed_at"]);
+
+    let customer_id = &profile.columns[0];
+    assert_eq!(customer_id.min, Some(serde_json::json!(1)));
+    assert_eq!(customer_id.max, Some(serde_json::json!(2)));
+    assert_eq!(customer_id.distinct_count, 2);
+    assert_eq!(customer_id.top_values[0].value, serde_json::json!(1));
+    assert_eq!(customer_id.top_values[0].count, 2);
+
+    let status = &profile.columns[2];
+    assert!(status.null_fraction.abs() < f64::EPSILON);
+    assert_eq!(status.distinct_count, 3);
+    assert_eq!(status.min, Some(serde_json::json!("cancelled")));
+    assert_eq!(status.max, Some(serde_json::json!("shipped")));
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_introspect_profile_respects_sample_cap() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let options = ProfileOptions { sample_rows: 100, top_n: 2, timeout_ms: Some(5_000) };
+    let result = SqliteEngine::introspect(
+        &config,
+        &IntrospectOperation::ProfileTable { name: "bulk_rows".to_string(), options },
+        None,
+        None,
+    )
+    .await
+    .expect("ProfileTable(bulk_rows) failed");
+    let IntrospectResult::TableProfile { profile } = result else {
+        panic!("Expected TableProfile variant");
+    };
+    assert_eq!(profile.row_count, 100, "sample must stop at the cap");
+    assert!(!profile.exhaustive, "1500-row table sampled at 100 is not exhaustive");
+    assert!(profile.columns.iter().all(|c| c.top_values.len() == 2));
+
+    // The profile describes the seeded sample, not the first 100 rows
+    let sample =
+        sample_bulk_rows(&config, SampleOptions { size: 100, seed: 0, timeout_ms: None }).await;
+    let ns: Vec<i64> = sample.rows.iter().map(|row| row[0].as_i64().unwrap()).collect();
+    let n = &profile.columns[0];
+    assert_eq!(n.min, ns.iter().min().map(|v| serde_json::json!(v)));
+    assert_eq!(n.max, ns.iter().max().map(|v| serde_json::json!(v)));
+    assert!(ns.iter().any(|&v| v > 100), "not a prefix: {ns:?}");
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_introspect_profile_missing_table_errors() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let result = SqliteEngine::introspect(
+        &config,
+        &IntrospectOperation::ProfileTable {
+            name: "no_such_table".to_string(),
+            options: ProfileOptions::default(),
+        },
+        None,
+        None,
+    )
+    .await;
+    assert!(result.is_err(), "profiling a missing table must fail");
+    cleanup(&path);
+}
+
+async fn sample_bulk_rows(
+    config: &ConnectionConfig,
+    options: SampleOptions,
+) -> plenum::QueryResult {
+    let result = SqliteEngine::introspect(
+        config,
+        &IntrospectOperation::SampleTable { name: "bulk_rows".to_string(), options },
+        None,
+        None,
+    )
+    .await
+    .expect("SampleTable(bulk_rows) failed");
+    let IntrospectResult::TableSample { sample } = result else {
+        panic!("Expected TableSample variant");
+    };
+    sample
+}
+
+#[tokio::test]
+async fn parity_introspect_sample_is_reproducible() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let options = SampleOptions { size: 25, seed: 42, timeout_ms: None };
+
+    let first = sample_bulk_rows(&config, options.clone()).await;
+    let second = sample_bulk_rows(&config, options.clone()).await;
+    assert_eq!(first.rows.len(), 25, "sample must return exactly --size rows");
+    assert_eq!(first.columns, second.columns);
+    assert_eq!(first.rows, second.rows, "same seed must return the same rows");
+
+    let head = SqliteEngine::execute(
+        &config,
+        "SELECT * FROM bulk_rows LIMIT 25",
+        &[],
+        &Capabilities::default(),
+    )
+    .await
+    .expect("head query failed");
+    assert_ne!(first.rows, head.rows, "sample must not just be the first rows");
+
+    let reseeded = sample_bulk_rows(&config, SampleOptions { seed: 43, ..options }).await;
+    assert_ne!(first.rows, reseeded.rows, "a different seed should draw a different sample");
+    cleanup(&path);
+}
+
+/// Adjacent seeds draw unrelated samples, not the previous one shifted by a row.
+#[tokio::test]
+async fn parity_introspect_sample_adjacent_seeds_are_not_shifted() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let ids = |sample: &plenum::QueryResult| -> Vec<i64> {
+        let mut ids: Vec<i64> = sample.rows.iter().map(|row| row[0].as_i64().unwrap()).collect();
+        ids.sort_unstable();
+        ids
+    };
+
+    let mut samples = Vec::new();
+    for seed in 1..=3 {
+        let options = SampleOptions { size: 25, seed, timeout_ms: None };
+        samples.push(ids(&sample_bulk_rows(&config, options).await));
+    }
+    for pair in samples.windows(2) {
+        let shifted: Vec<i64> = pair[0].iter().map(|n| n - 1).collect();
+        assert_ne!(pair[1], shifted, "seed + 1 must not shift the sample");
+        let shared = pair[1].iter().filter(|n| pair[0].contains(n)).count();
+        assert!(shared < 5, "adjacent seeds share {shared} of 25 rows: {pair:?}");
+    }
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_introspect_sample_small_table_returns_all_rows() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let result = SqliteEngine::introspect(
+        &config,
+        &IntrospectOperation::SampleTable {
+            name: "orders".to_string(),
+            options: SampleOptions::default(),
+        },
+        None,
+        None,
+    )
+    .await
+    .expect("SampleTable(orders) failed");
+    let IntrospectResult::TableSample { sample } = result else {
+        panic!("Expected TableSample variant");
+    };
+    assert_eq!(sample.columns, vec!["customer_id", "order_no", "status", "placed_at"]);
+    assert_eq!(sample.rows.len(), 3);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_introspect_sample_missing_table_errors() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let result = SqliteEngine::introspect(
+        &config,
+        &IntrospectOperation::SampleTable {
+            name: "no_such_table".to_string(),
+            options: SampleOptions::default(),
+        },
+        None,
+        None,
+    )
+    .await;
+    assert!(result.is_err(), "sampling a missing table must fail");
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_introspect_privileges_file_level() {
+    // SQLite has no users or grants: the report is file-level only.
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let result = SqliteEngine::introspect(
+        &config,
+        &IntrospectOperation::ListPrivileges { table: None },
+        None,
+        None,
+    )
+    .await
+    .expect("ListPrivileges failed");
+    let IntrospectResult::Privileges { privileges } = result else {
+        panic!("Expected Privileges variant");
+    };
+    assert_eq!(privileges.scope, "file");
+    assert!(privileges.roles.is_empty() && privileges.tables.is_empty());
+    assert!(privileges.grants.is_empty());
+    let file = privileges.file.expect("file access must be reported");
+    assert_eq!(file.path, path.to_str().unwrap());
+    assert!(file.writable, "fixture is created writable");
+    cleanup(&path);
+}
+
+async fn index_health(config: &ConnectionConfig) -> plenum::IndexHealthReport {
+    let result = SqliteEngine::introspect(
+        config,
+        &IntrospectOperation::IndexHealth { table: None },
+        None,
+        None,
+    )
+    .await
+    .expect("IndexHealth failed");
+    let IntrospectResult::IndexHealth { health } = result else {
+        panic!("Expected IndexHealth variant");
+    };
+    health
+}
+
+#[tokio::test]
+async fn parity_introspect_index_health_flags_redundant_index() {
+    // idx_order_items_customer (customer_id) is a prefix of the primary key index.
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let health = index_health(&config).await;
+
+    assert_eq!(health.findings.len(), 1, "only the redundant index: {:?}", health.findings);
+    let finding = &health.findings[0];
+    assert_eq!(finding.table, "order_items");
+    assert_eq!(finding.index.as_deref(), Some("idx_order_items_customer"));
+    assert_eq!(finding.reason, "redundant");
+    assert_eq!(finding.metric, "key_columns");
+    assert!((finding.value - 1.0).abs() < f64::EPSILON);
+    assert!(finding.detail.contains("sqlite_autoindex_order_items_1"), "{}", finding.detail);
+    assert!(
+        health.notes.iter().any(|n| n.contains("ANALYZE")),
+        "missing sqlite_stat1 must be noted: {:?}",
+        health.notes
+    );
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_introspect_index_health_duplicate_and_unselective() {
+    let path = build_parity_fixture();
+    {
+        let conn = rusqlite::Connection::open(&path).expect("open fixture");
+        conn.execute_batch(
+            "ALTER TABLE bulk_rows ADD COLUMN bucket INTEGER;
+             UPDATE bulk_rows SET bucket = n % 3;
+             CREATE INDEX idx_bulk_rows_bucket ON bulk_rows(bucket);
+             CREATE INDEX idx_bulk_rows_bucket_copy ON bulk_rows(bucket);
+             ANALYZE;",
+        )
+        .expect("add low-cardinality indexes");
+    }
+    let config = ConnectionConfig::sqlite(path.clone());
+    let health = index_health(&config).await;
+
+    let find = |index: &str, reason: &str| {
+        health
+            .findings
+            .iter()
+            .find(|f| f.index.as_deref() == Some(index) && f.reason == reason)
+            .unwrap_or_else(|| panic!("{index} not reported as {reason}: {:?}", health.findings))
+    };
+    let duplicate = find("idx_bulk_rows_bucket_copy", "duplicate");
+    assert!(duplicate.detail.contains("idx_bulk_rows_bucket"), "{}", duplicate.detail);
+    let unselective = find("idx_bulk_rows_bucket", "unselective");
+    assert_eq!(unselective.metric, "avg_rows_per_key");
+    assert!(unselective.value >= 500.0, "3 buckets over 1500 rows: {}", unselective.value);
+    assert!(
+        !health
+            .findings
+            .iter()
+            .any(|f| f.index.as_deref() == Some("idx_bulk_rows_bucket") && f.reason == "duplicate"),
+        "only the later of two identical indexes is the duplicate"
+    );
+    assert!(!health.notes.iter().any(|n| n.contains("ANALYZE")), "{:?}", health.notes);
+
+    let filtered = SqliteEngine::introspect(
+        &config,
+        &IntrospectOperation::IndexHealth { table: Some("order_items".to_string()) },
+        None,
+        None,
+    )
+    .await
+    .expect("IndexHealth(order_items) failed");
+    let IntrospectResult::IndexHealth { health } = filtered else {
+        panic!("Expected IndexHealth variant");
+    };
+    assert!(health.findings.iter().all(|f| f.table == "order_items"), "{:?}", health.findings);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_introspect_sizes_from_dbstat() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let op = IntrospectOperation::Sizes { options: SizeOptions::default() };
+    let IntrospectResult::Sizes { sizes } =
+        SqliteEngine::introspect(&config, &op, None, None).await.expect("Sizes failed")
+    else {
+        panic!("Expected Sizes variant");
+    };
+
+    assert!(sizes.notes.is_empty(), "bundled SQLite has dbstat: {:?}", sizes.notes);
+    assert_eq!(sizes.total_tables, 5, "{:?}", sizes.tables);
+    assert_eq!(sizes.tables[0].name, "bulk_rows", "largest table first: {:?}", sizes.tables);
+    let mut table_total = 0;
+    for table in &sizes.tables {
+        let (data, index) = (table.table_bytes.unwrap(), table.index_bytes.unwrap());
+        assert_eq!(table.total_bytes, Some(data + index), "{table:?}");
+        assert!(table.toast_bytes.is_none());
+        table_total += data + index;
+    }
+    let order_items = sizes.tables.iter().find(|t| t.name == "order_items").unwrap();
+    assert!(order_items.index_bytes.unwrap() > 0, "autoindex and idx_order_items_customer");
+    assert!(sizes.database_bytes.unwrap() >= table_total);
+
+    let op = IntrospectOperation::Sizes { options: SizeOptions { limit: 2, offset: 2 } };
+    let IntrospectResult::Sizes { sizes: page } =
+        SqliteEngine::introspect(&config, &op, None, None).await.unwrap()
+    else {
+        panic!("Expected Sizes variant");
+    };
+    assert_eq!(page.tables.len(), 2);
+    assert_eq!(page.tables[0].name, sizes.tables[2].name);
+    assert!(page.has_more);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_introspect_settings_from_pragmas() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let op = IntrospectOperation::Settings { pattern: None };
+    let IntrospectResult::Settings { settings } =
+        SqliteEngine::introspect(&config, &op, None, None).await.expect("Settings failed")
+    else {
+        panic!("Expected Settings variant");
+    };
+
+    let names: Vec<&str> = settings.iter().map(|s| s.name.as_str()).collect();
+    assert!(names.windows(2).all(|w| w[0] < w[1]), "sorted by name: {names:?}");
+    let page_size = settings.iter().find(|s| s.name == "page_size").expect("page_size listed");
+    assert_eq!(page_size.unit.as_deref(), Some("bytes"));
+    assert_eq!(page_size.session_changeable, Some(false));
+    assert!(page_size.value.as_deref().is_some_and(|v| v.parse::<u32>().is_ok()));
+    let foreign_keys = settings.iter().find(|s| s.name == "foreign_keys").unwrap();
+    assert_eq!(foreign_keys.session_changeable, Some(true));
+    assert!(settings.iter().all(|s| s.source.is_none()));
+
+    let op = IntrospectOperation::Settings { pattern: Some("*PAGE_*".to_string()) };
+    let IntrospectResult::Settings { settings: filtered } =
+        SqliteEngine::introspect(&config, &op, None, None).await.unwrap()
+    else {
+        panic!("Expected Settings variant");
+    };
+    let names: Vec<&str> = filtered.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["max_page_count", "page_count", "page_size"]);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_introspect_duckdb_catalog_operations_rejected() {
+    // Sequences, macros, user types, constraints and attached databases are
+    // DuckDB catalog operations; SQLite must reject them as INVALID_INPUT.
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    for op in [
+        IntrospectOperation::ListSequences,
+        IntrospectOperation::ListMacros,
+        IntrospectOperation::ListTypes,
+        IntrospectOperation::ListConstraints { table: None },
+        IntrospectOperation::ListAttachedDatabases,
+    ] {
+        let err = SqliteEngine::introspect(&config, &op, None, None).await.unwrap_err();
+        assert_eq!(err.error_code(), "INVALID_INPUT", "{} must be rejected", op.name());
+        assert!(err.message().contains(op.name()), "message should name the operation: {err}");
+    }
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_inspect_operations_rejected() {
+    // A SQLite file has no server sessions or statement statistics to report.
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    for op in [
+        InspectOperation::Activity,
+        InspectOperation::TopQueries { order_by: QueryRanking::TotalTime, limit: 10 },
+    ] {
+        let err = SqliteEngine::inspect(&config, &op).await.unwrap_err();
+        assert_eq!(err.error_code(), "INVALID_INPUT", "{} must be rejected", op.name());
+        assert!(err.message().contains(op.name()), "message should name the operation: {err}");
+    }
+    cleanup(&path);
+}
+
 #[tokio::test]
 async fn parity_introspect_stable_json_shape() {
     // Successive introspections must produce identical JSON (determinism).
@@ -675,6 +1130,158 @@ async fn parity_query_explain_query_plan_allowed() {
     cleanup(&path);
 }
 
+#[tokio::test]
+async fn parity_query_structured_explain_operations() {
+    // EXPLAIN QUERY PLAN details map onto the engine-independent operation
+    // taxonomy with the table and index each node reads.
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let caps =
+        Capabilities { explain_format: Some(ExplainFormat::Structured), ..Capabilities::default() };
+
+    fn flatten(node: &plenum::ExplainPlanNode) -> Vec<(PlanOperation, Option<&str>, Option<&str>)> {
+        let mut nodes = vec![(node.operation, node.relation.as_deref(), node.index.as_deref())];
+        nodes.extend(node.children.iter().flat_map(flatten));
+        nodes
+    }
+
+    let qr = SqliteEngine::execute(
+        &config,
+        "EXPLAIN QUERY PLAN SELECT customers.name, orders.status FROM orders \
+         JOIN customers ON customers.id = orders.customer_id ORDER BY customers.name",
+        &[],
+        &caps,
+    )
+    .await
+    .expect("structured EXPLAIN of a join");
+    let nodes = flatten(qr.plan.as_ref().expect("plan"));
+    assert!(nodes.contains(&(PlanOperation::FullScan, Some("orders"), None)), "{nodes:?}");
+    assert!(
+        nodes.contains(&(PlanOperation::IndexScan, Some("customers"), Some("PRIMARY KEY"))),
+        "{nodes:?}"
+    );
+    assert!(nodes.iter().any(|n| n.0 == PlanOperation::Sort), "{nodes:?}");
+
+    let qr = SqliteEngine::execute(
+        &config,
+        "EXPLAIN QUERY PLAN SELECT sku FROM order_items WHERE sku = 'SKU-1'",
+        &[],
+        &caps,
+    )
+    .await
+    .expect("structured EXPLAIN of an index lookup");
+    let nodes = flatten(qr.plan.as_ref().expect("plan"));
+    assert!(
+        nodes.contains(&(
+            PlanOperation::IndexOnlyScan,
+            Some("order_items"),
+            Some("idx_order_items_sku")
+        )),
+        "{nodes:?}"
+    );
+
+    // Bound parameters reach the planner
+    let qr = SqliteEngine::execute(
+        &config,
+        "EXPLAIN QUERY PLAN SELECT sku FROM order_items WHERE sku = ?",
+        &[json!("SKU-1")],
+        &caps,
+    )
+    .await
+    .expect("structured EXPLAIN with a bound parameter");
+    assert_eq!(flatten(qr.plan.as_ref().expect("plan")), nodes);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_advise_index_suggestions() {
+    // SQLite plans carry no filter text: advice reads the WHERE clause of a
+    // single-table plan. Without ANALYZE there is no row estimate, so min_rows
+    // does not rule the tables out.
+    use plenum::advise::{advise, REASON_FULL_SCAN_FILTER, REASON_SORT};
+
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+
+    let advice = advise::<SqliteEngine>(
+        &config,
+        "SELECT * FROM orders WHERE status = 'shipped'",
+        &[],
+        None,
+        1_000_000,
+    )
+    .await
+    .expect("advise on a filtered full scan");
+    assert_eq!(advice.suggestions.len(), 1, "{:?}", advice.suggestions);
+    let suggestion = &advice.suggestions[0];
+    assert_eq!(suggestion.reason, REASON_FULL_SCAN_FILTER);
+    assert_eq!(suggestion.table, "orders");
+    assert_eq!(suggestion.columns, vec!["status"]);
+    assert_eq!(
+        suggestion.statement,
+        "CREATE INDEX IF NOT EXISTS \"idx_orders_status\" ON \"orders\" (\"status\");"
+    );
+    assert!(suggestion.rationale.contains("\"SCAN orders\""), "{}", suggestion.rationale);
+    assert_eq!(suggestion.table_rows, None);
+
+    // The sort suggestion leads with the filter column and replaces the filter one
+    let advice = advise::<SqliteEngine>(
+        &config,
+        "SELECT * FROM orders WHERE status = 'shipped' ORDER BY placed_at DESC LIMIT 5",
+        &[],
+        None,
+        0,
+    )
+    .await
+    .expect("advise on a sorted scan");
+    assert_eq!(advice.suggestions.len(), 1, "{:?}", advice.suggestions);
+    assert_eq!(advice.suggestions[0].reason, REASON_SORT);
+    assert_eq!(advice.suggestions[0].columns, vec!["status", "placed_at"]);
+    assert!(
+        advice.suggestions[0].statement.ends_with("(\"status\", \"placed_at\" DESC);"),
+        "{}",
+        advice.suggestions[0].statement
+    );
+
+    // Served by uq_customers_email / idx_order_items_sku: nothing to suggest
+    for sql in [
+        "SELECT * FROM customers WHERE email = 'a@example.com'",
+        "SELECT * FROM order_items WHERE sku = 'SKU-1'",
+        "SELECT * FROM customers WHERE lower(name) = 'x'",
+    ] {
+        let advice = advise::<SqliteEngine>(&config, sql, &[], None, 0).await.expect(sql);
+        assert!(advice.suggestions.is_empty(), "{sql}: {:?}", advice.suggestions);
+    }
+
+    let err = advise::<SqliteEngine>(&config, "DELETE FROM orders", &[], None, 0)
+        .await
+        .expect_err("advise on a write");
+    assert_eq!(err.error_code(), "INVALID_INPUT");
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_query_structured_explain_analyze_rejected() {
+    // SQLite has no EXPLAIN ANALYZE: structured explain keeps the estimated plan
+    // only and refuses to pretend it measured anything.
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let caps =
+        Capabilities { explain_format: Some(ExplainFormat::Structured), ..Capabilities::default() };
+    let err = SqliteEngine::execute(&config, "EXPLAIN ANALYZE SELECT * FROM customers", &[], &caps)
+        .await
+        .expect_err("EXPLAIN ANALYZE must be rejected on SQLite");
+    assert_eq!(err.error_code(), "INVALID_INPUT");
+
+    let qr = SqliteEngine::execute(&config, "EXPLAIN SELECT * FROM customers", &[], &caps)
+        .await
+        .expect("structured EXPLAIN");
+    let plan = qr.plan.expect("structured explain must populate plan");
+    assert_eq!(plan.actual_rows, None);
+    assert!(plan.children.iter().all(|c| c.actual_rows.is_none() && c.loops.is_none()));
+    cleanup(&path);
+}
+
 #[tokio::test]
 async fn parity_query_pragma_table_info_allowed() {
     let path = build_parity_fixture();
@@ -958,6 +1565,888 @@ async fn parity_safety_timeout_ms_interrupts_long_query() {
     );
 }
 
+// ============================================================================
+// Large values — --binary rendering and --max-cell-bytes truncation
+// ============================================================================
+
+#[tokio::test]
+async fn parity_binary_formats_render_blobs() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let sql = "SELECT c_blob FROM type_matrix ORDER BY id";
+
+    let rendered = |binary_format| {
+        let config = config.clone();
+        async move {
+            let caps = Capabilities { binary_format, ..Capabilities::default() };
+            let qr = SqliteEngine::execute(&config, sql, &[], &caps).await.expect("blob query");
+            qr.rows.into_iter().map(|row| row[0].clone()).collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(rendered(None).await, [json!("3q2+7w=="), json!("AAECAw=="), Value::Null]);
+    assert_eq!(
+        rendered(Some(BinaryFormat::Hex)).await,
+        [json!("deadbeef"), json!("00010203"), Value::Null]
+    );
+    assert_eq!(rendered(Some(BinaryFormat::LengthOnly)).await, [json!(4), json!(4), Value::Null]);
+    assert_eq!(rendered(Some(BinaryFormat::Omit)).await, [Value::Null, Value::Null, Value::Null]);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_max_cell_bytes_truncates_long_values() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let mut qr = SqliteEngine::execute(
+        &config,
+        "SELECT c_integer, c_text, c_blob FROM type_matrix WHERE id = 1",
+        &[],
+        &Capabilities::default(),
+    )
+    .await
+    .expect("type_matrix row");
+    limit_cells(&mut qr.rows, 5);
+    let row = &qr.rows[0];
+    assert_eq!(row[0], json!(i64::MAX), "numbers are never truncated");
+    assert_eq!(row[1]["truncated"], true);
+    assert_eq!(row[1]["prefix"], "café");
+    assert_eq!(row[1]["original_bytes"], "café résumé 🚀".len());
+    assert_eq!(row[1]["sha256"].as_str().map(str::len), Some(64));
+    assert_eq!(row[2]["original_bytes"], 8, "binary is measured as rendered (Base64)");
+
+    // One wide cell per row no longer exhausts the byte budget on its own
+    let sql = "SELECT n, substr(hex(zeroblob(2500)), 1, 5000) AS wide FROM bulk_rows \
+               ORDER BY n LIMIT 20";
+    let mut full =
+        SqliteEngine::execute(&config, sql, &[], &Capabilities::default()).await.expect("wide");
+    apply_byte_budget(&mut full, 4000);
+    assert!(full.rows.is_empty(), "a single 5000-byte cell exceeds the budget");
+
+    let mut limited =
+        SqliteEngine::execute(&config, sql, &[], &Capabilities::default()).await.expect("wide");
+    apply_byte_budget_with_cell_limit(&mut limited, 4000, Some(16));
+    assert_eq!(limited.rows.len(), 20);
+    assert_eq!(limited.rows[0][1].as_str().map(str::len), Some(5000), "kept rows hold real values");
+    limit_cells(&mut limited.rows, 16);
+    assert_eq!(limited.rows[0][1]["original_bytes"], 5000);
+    assert_eq!(limited.rows[0][1]["sha256"], limited.rows[19][1]["sha256"]);
+    cleanup(&path);
+}
+
+// ============================================================================
+// Streaming — execute_stream (--format ndjson)
+// ============================================================================
+
+/// Sink that records what `execute_stream` delivers, refusing rows past `accept`.
+#[derive(Default)]
+struct RecordingSink {
+    columns: Vec<ResultColumn>,
+    rows: Vec<Vec<serde_json::Value>>,
+    accept: Option<usize>,
+}
+
+impl RowSink for RecordingSink {
+    fn columns(&mut self, columns: &[ResultColumn]) -> plenum::Result<()> {
+        self.columns = columns.to_vec();
+        Ok(())
+    }
+
+    fn row(&mut self, row: Vec<serde_json::Value>) -> plenum::Result<bool> {
+        if self.accept.is_some_and(|max| self.rows.len() >= max) {
+            return Ok(false);
+        }
+        self.rows.push(row);
+        Ok(true)
+    }
+}
+
+#[tokio::test]
+async fn parity_stream_header_types_and_rows_match_execute() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let caps = Capabilities::default();
+    let sql = "SELECT id, name FROM customers ORDER BY id";
+
+    let mut sink = RecordingSink::default();
+    let summary = SqliteEngine::execute_stream(&config, sql, &[], &caps, &mut sink)
+        .await
+        .expect("execute_stream");
+    let collected = SqliteEngine::execute(&config, sql, &[], &caps).await.expect("execute");
+
+    let names: Vec<&str> = sink.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["id", "name"]);
+    assert_eq!(sink.columns[0].type_name.as_deref(), Some("INTEGER"));
+    assert_eq!(sink.columns[1].type_name.as_deref(), Some("TEXT"));
+    assert_eq!(sink.rows, collected.rows, "streamed rows must equal collected rows");
+    assert_eq!(summary.rows_returned, 3);
+    assert!(!summary.rows_truncated);
+    assert!(summary.rows_affected.is_none());
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_stream_offset_and_max_rows_truncate_bulk_table() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let caps = Capabilities { offset: Some(1000), ..Capabilities::new(Some(100), None) };
+
+    let mut sink = RecordingSink::default();
+    let summary = SqliteEngine::execute_stream(
+        &config,
+        "SELECT n FROM bulk_rows ORDER BY n",
+        &[],
+        &caps,
+        &mut sink,
+    )
+    .await
+    .expect("execute_stream");
+
+    assert_eq!(sink.rows.len(), 100);
+    assert_eq!(sink.rows[0][0], serde_json::json!(1001));
+    assert_eq!(summary.rows_returned, 100);
+    assert!(summary.rows_truncated, "more rows remain after the window");
+    assert_eq!(summary.truncated_by, None);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_stream_sink_refusal_truncates_by_bytes() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let caps = Capabilities::default();
+
+    let mut sink = RecordingSink { accept: Some(5), ..RecordingSink::default() };
+    let summary = SqliteEngine::execute_stream(
+        &config,
+        "SELECT n FROM bulk_rows ORDER BY n",
+        &[],
+        &caps,
+        &mut sink,
+    )
+    .await
+    .expect("execute_stream");
+
+    assert_eq!(sink.rows.len(), 5);
+    assert_eq!(summary.rows_returned, 5);
+    assert!(summary.rows_truncated);
+    assert_eq!(summary.truncated_by.as_deref(), Some("bytes"));
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_stream_rejects_structured_explain() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let caps =
+        Capabilities { explain_format: Some(ExplainFormat::Structured), ..Capabilities::default() };
+
+    let mut sink = RecordingSink::default();
+    let err = SqliteEngine::execute_stream(
+        &config,
+        "EXPLAIN SELECT * FROM customers",
+        &[],
+        &caps,
+        &mut sink,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.error_code(), "INVALID_INPUT");
+    assert!(sink.columns.is_empty(), "nothing is streamed for a rejected query");
+    cleanup(&path);
+}
+
+// ============================================================================
+// Params — :name placeholders (--param name=value)
+// ============================================================================
+
+#[tokio::test]
+async fn parity_params_named_placeholders_bind_by_name() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let raw = vec!["lo=10".to_string(), "label=row-0012".to_string()];
+    let sql = "SELECT n FROM bulk_rows WHERE n > :lo AND n < :lo + 5 AND label <> :label \
+               AND label <> ':lo' ORDER BY n";
+    let (sql, params) = QueryParams::from_cli(&raw, sql, config.engine)
+        .and_then(|p| p.bind(sql, config.engine))
+        .expect("bind named params");
+    assert_eq!(params.len(), 3, ":lo is bound once per occurrence");
+
+    let qr = SqliteEngine::execute(&config, &sql, &params, &Capabilities::default())
+        .await
+        .expect("named-param query");
+    let ns: Vec<i64> = qr.rows.iter().map(|r| r[0].as_i64().unwrap()).collect();
+    assert_eq!(ns, [11, 13, 14]);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_params_native_named_placeholders_bind_positionally() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let raw = vec!["10".to_string(), "14".to_string()];
+    let sql = "SELECT n FROM bulk_rows WHERE n > :lo AND n < :hi ORDER BY n";
+    let (sql, params) = QueryParams::from_cli(&raw, sql, config.engine)
+        .and_then(|p| p.bind(sql, config.engine))
+        .expect("bind positional params");
+    assert!(sql.contains(":lo"), "SQLite keeps its native placeholders: {sql}");
+
+    let qr = SqliteEngine::execute(&config, &sql, &params, &Capabilities::default())
+        .await
+        .expect("native named-param query");
+    let ns: Vec<i64> = qr.rows.iter().map(|r| r[0].as_i64().unwrap()).collect();
+    assert_eq!(ns, [11, 12, 13]);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_params_typed_values_bind_natively() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let raw: Vec<String> = [
+        "blob=3q2+7w==::bytes",
+        "id=A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11::uuid",
+        "at=2024-01-01T01:30:00+02:00::timestamp",
+        "n=42::int",
+        "ids=[1,2,3]::int[]",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+    let sql = "SELECT typeof(:blob), :blob = X'DEADBEEF', :id, :at, typeof(:n), \
+               json_array_length(:ids)";
+    let (sql, params) = QueryParams::from_cli(&raw, sql, config.engine)
+        .and_then(|p| p.bind(sql, config.engine))
+        .expect("bind typed params");
+
+    let qr = SqliteEngine::execute(&config, &sql, &params, &Capabilities::default())
+        .await
+        .expect("typed-param query");
+    assert_eq!(
+        qr.rows[0],
+        serde_json::json!([
+            "blob",
+            1,
+            "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11",
+            "2024-01-01 01:30:00+02:00",
+            "integer",
+            3
+        ])
+        .as_array()
+        .unwrap()
+        .as_slice()
+    );
+    cleanup(&path);
+}
+
+// ============================================================================
+// Cursor — keyset pagination with next_cursor tokens (--cursor)
+// ============================================================================
+
+/// Page through `sql` with keyset cursors, returning every page's first column.
+async fn collect_cursor_pages(config: &ConnectionConfig, sql: &str, page: usize) -> Vec<Vec<i64>> {
+    let caps = Capabilities::new(Some(page), None);
+    let mut pages = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let (result, keyset) =
+            execute_with_cursor::<SqliteEngine>(config, sql, &[], &caps, cursor.as_deref(), true)
+                .await
+                .expect("cursor page");
+        pages.push(result.rows.iter().map(|r| r[0].as_i64().expect("integer key")).collect());
+        cursor = keyset.and_then(|k| k.next_cursor(&result));
+        if cursor.is_none() {
+            return pages;
+        }
+    }
+}
+
+#[tokio::test]
+async fn parity_cursor_pages_cover_filtered_table_once() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let pages = collect_cursor_pages(
+        &config,
+        "SELECT n, label FROM bulk_rows WHERE n % 3 = 0 OR n > 1400 ORDER BY n DESC",
+        200,
+    )
+    .await;
+
+    let all: Vec<i64> = pages.concat();
+    let expected: Vec<i64> = (1..=1500).rev().filter(|n| n % 3 == 0 || *n > 1400).collect();
+    assert_eq!(all, expected, "pages must neither skip nor repeat rows");
+    assert_eq!(pages.len(), expected.len().div_ceil(200));
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_cursor_orders_by_primary_key_without_order_by() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let pages = collect_cursor_pages(&config, "SELECT n, label FROM bulk_rows", 700).await;
+    assert_eq!(pages.iter().map(Vec::len).collect::<Vec<_>>(), [700, 700, 100]);
+    assert_eq!(pages.concat(), (1..=1500).collect::<Vec<i64>>(), "implicit ORDER BY n");
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_cursor_requires_opt_in() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let caps = Capabilities::new(Some(700), None);
+    for sql in ["SELECT n, label FROM bulk_rows", "SELECT n FROM bulk_rows ORDER BY n"] {
+        let (result, keyset) =
+            execute_with_cursor::<SqliteEngine>(&config, sql, &[], &caps, None, false)
+                .await
+                .expect("plain page");
+        assert!(result.rows_truncated);
+        assert!(keyset.is_none(), "no keyset without --paginate: {sql}");
+    }
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_cursor_round_trips_quoted_text_keys() {
+    let path = build_parity_fixture();
+    rusqlite::Connection::open(&path)
+        .expect("open fixture")
+        .execute_batch(
+            "CREATE TABLE tags (name TEXT PRIMARY KEY, n INTEGER);
+             INSERT INTO tags VALUES ('a''b', 1), ('a\\b', 2), ('a'' OR ''1''=''1', 3),
+                                     ('a\\''--', 4), ('b', 5);",
+        )
+        .expect("create tags");
+    let config = ConnectionConfig::sqlite(path.clone());
+    let caps = Capabilities::new(Some(1), None);
+    let sql = "SELECT name FROM tags WHERE n > ? ORDER BY name";
+    let params = [json!(0)];
+    let mut names = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let (result, keyset) = execute_with_cursor::<SqliteEngine>(
+            &config,
+            sql,
+            &params,
+            &caps,
+            cursor.as_deref(),
+            true,
+        )
+        .await
+        .expect("cursor page");
+        names.extend(result.rows.iter().map(|r| r[0].as_str().expect("name").to_string()));
+        cursor = keyset.and_then(|k| k.next_cursor(&result));
+        if cursor.is_none() {
+            break;
+        }
+    }
+    let mut expected = ["a'b", "a\\b", "a' OR '1'='1", "a\\'--", "b"].map(String::from);
+    expected.sort();
+    assert_eq!(names, expected);
+    cleanup(&path);
+}
+
+#[tokio::test]
+async fn parity_cursor_rejects_other_query_and_ineligible_shapes() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let caps = Capabilities::new(Some(10), None);
+    let sql = "SELECT n FROM bulk_rows ORDER BY n";
+    let (result, keyset) =
+        execute_with_cursor::<SqliteEngine>(&config, sql, &[], &caps, None, true)
+            .await
+            .expect("first page");
+    let cursor = keyset.and_then(|k| k.next_cursor(&result)).expect("next_cursor");
+
+    let err = execute_with_cursor::<SqliteEngine>(
+        &config,
+        "SELECT n FROM bulk_rows WHERE n > 5 ORDER BY n",
+        &[],
+        &caps,
+        Some(&cursor),
+        true,
+    )
+    .await
+    .expect_err("cursor bound to another query");
+    assert_eq!(err.error_code(), "INVALID_INPUT");
+
+    // label is NOT NULL but not unique: no cursor rather than a page that could skip rows.
+    let sql = "SELECT n, label FROM bulk_rows ORDER BY label";
+    let (result, keyset) =
+        execute_with_cursor::<SqliteEngine>(&config, sql, &[], &caps, None, true)
+            .await
+            .expect("label page");
+    assert!(result.rows_truncated);
+    assert!(keyset.is_none());
+    cleanup(&path);
+}
+
+// ============================================================================
+// Ordering — stable row order without ORDER BY (--deterministic)
+// ============================================================================
+
+#[tokio::test]
+async fn parity_deterministic_orders_unordered_queries() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let sql = "SELECT label FROM bulk_rows WHERE n > 1497 \
+               UNION ALL SELECT label FROM bulk_rows WHERE n <= 2;";
+    let labels = |rows: &[Vec<Value>]| -> Vec<String> {
+        rows.iter().map(|r| r[0].as_str().expect("label").to_string()).collect()
+    };
+
+    let (plain, _) = execute_with_ordering::<SqliteEngine>(
+        &config,
+        sql,
+        &[],
+        &Capabilities::default(),
+        None,
+        false,
+        false,
+    )
+    .await
+    .expect("plain query");
+    assert_eq!(labels(&plain.rows)[0], "row-1498", "UNION ALL keeps branch order");
+    assert_eq!(plain.ordering, None);
+
+    // Complete page: ordered by the database under the wrapped ORDER BY
+    let (sorted, _) = execute_with_ordering::<SqliteEngine>(
+        &config,
+        sql,
+        &[],
+        &Capabilities::default(),
+        None,
+        false,
+        true,
+    )
+    .await
+    .expect("sorted page");
+    assert_eq!(labels(&sorted.rows), ["row-0001", "row-0002", "row-1498", "row-1499", "row-1500"]);
+    assert_eq!(sorted.ordering.as_deref(), Some("imposed"));
+
+    // Truncated page: the wrapped ORDER BY makes max_rows cut a fixed prefix
+    let caps = Capabilities::new(Some(3), None);
+    let (page, keyset) =
+        execute_with_ordering::<SqliteEngine>(&config, sql, &[], &caps, None, false, true)
+            .await
+            .expect("wrapped page");
+    assert_eq!(labels(&page.rows), ["row-0001", "row-0002", "row-1498"]);
+    assert!(page.rows_truncated);
+    assert!(keyset.is_none());
+    assert_eq!(page.ordering.as_deref(), Some("imposed"));
+
+    // The query's own ORDER BY is kept
+    let (own, _) = execute_with_ordering::<SqliteEngine>(
+        &config,
+        "SELECT n FROM bulk_rows ORDER BY n DESC",
+        &[],
+        &caps,
+        None,
+        false,
+        true,
+    )
+    .await
+    .expect("ordered query");
+    assert_eq!(own.rows[0][0], 1500);
+    assert_eq!(own.ordering.as_deref(), Some("engine"));
+
+    // A paginated single-table SELECT is ordered by its primary key
+    let (pk, keyset) = execute_with_ordering::<SqliteEngine>(
+        &config,
+        "SELECT n, label FROM bulk_rows",
+        &[],
+        &caps,
+        None,
+        true,
+        true,
+    )
+    .await
+    .expect("primary-key page");
+    assert_eq!(pk.rows.iter().map(|r| r[0].clone()).collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(pk.ordering.as_deref(), Some("imposed"));
+    assert!(keyset.and_then(|k| k.next_cursor(&pk)).is_some());
+
+    // A statement that cannot be wrapped runs as written
+    let (pragma, _) = execute_with_ordering::<SqliteEngine>(
+        &config,
+        "PRAGMA table_info(bulk_rows)",
+        &[],
+        &caps,
+        None,
+        false,
+        true,
+    )
+    .await
+    .expect("pragma");
+    assert_eq!(pragma.rows.len(), 2);
+    assert_eq!(pragma.ordering.as_deref(), Some("unordered"));
+    cleanup(&path);
+}
+
+// ============================================================================
+// Data diff — the same query on two connections, matched by key (--diff-against)
+// ============================================================================
+
+/// A second fixture whose `bulk_rows` differ from the first: n=5 relabelled,
+/// n=7 deleted, n=2000 added.
+fn build_drifted_fixture() -> PathBuf {
+    let path = build_parity_fixture();
+    let conn = rusqlite::Connection::open(&path).expect("open drifted fixture");
+    conn.execute_batch(
+        "UPDATE bulk_rows SET label = 'changed' WHERE n = 5;
+         DELETE FROM bulk_rows WHERE n = 7;
+         INSERT INTO bulk_rows (n, label) VALUES (2000, 'row-2000');",
+    )
+    .expect("drift bulk_rows");
+    path
+}
+
+#[tokio::test]
+async fn parity_data_diff_reports_added_removed_changed() {
+    let base_path = build_parity_fixture();
+    let target_path = build_drifted_fixture();
+    let base = ConnectionConfig::sqlite(base_path.clone());
+    let target = ConnectionConfig::sqlite(target_path.clone());
+    let key = vec!["n".to_string()];
+
+    let diff = compute_data_diff(
+        &base,
+        &target,
+        "SELECT n, label FROM bulk_rows",
+        &QueryParams::Positional(Vec::new()),
+        &key,
+        &Capabilities::default(),
+    )
+    .await
+    .expect("data diff");
+    assert_eq!((diff.base_rows, diff.target_rows), (1500, 1500));
+    assert_eq!(
+        (diff.counts.added, diff.counts.removed, diff.counts.changed, diff.counts.unchanged),
+        (1, 1, 1, 1498)
+    );
+    assert_eq!(diff.rows_added[0]["n"], 2000);
+    assert_eq!(diff.rows_removed[0]["n"], 7);
+    assert_eq!(diff.rows_changed[0].key["n"], 5);
+    assert_eq!(diff.rows_changed[0].changes[0].column, "label");
+    assert_eq!(diff.rows_changed[0].changes[0].from, "row-0005");
+    assert_eq!(diff.rows_changed[0].changes[0].to, "changed");
+    assert!((1..=3).contains(&diff.chunks_differing), "{}", diff.chunks_differing);
+
+    // Identical results compare by digest alone
+    let same = compute_data_diff(
+        &base,
+        &base,
+        "SELECT n, label FROM bulk_rows WHERE n <= :hi",
+        &QueryParams::from_json(Some(&serde_json::json!({"hi": 100}))).unwrap(),
+        &key,
+        &Capabilities::default(),
+    )
+    .await
+    .expect("identical data diff");
+    assert_eq!(same.counts.unchanged, 100);
+    assert_eq!(same.chunks_differing, 0);
+    assert!(same.rows_added.is_empty() && same.rows_removed.is_empty());
+
+    cleanup(&base_path);
+    cleanup(&target_path);
+}
+
+/// Results larger than the in-memory limit are digested first and fetched a
+/// second time for the differing chunks only.
+#[tokio::test]
+async fn parity_data_diff_large_result_refetches_differing_chunks() {
+    let base_path = build_parity_fixture();
+    let target_path = build_drifted_fixture();
+    let base = ConnectionConfig::sqlite(base_path.clone());
+    let target = ConnectionConfig::sqlite(target_path.clone());
+    let sql = "WITH RECURSIVE s(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM s WHERE n < 12000) \
+               SELECT s.n, b.label FROM s LEFT JOIN bulk_rows b ON b.n = s.n";
+
+    let diff = compute_data_diff(
+        &base,
+        &target,
+        sql,
+        &QueryParams::Positional(Vec::new()),
+        &["n".to_string()],
+        &Capabilities::new(Some(1), None),
+    )
+    .await
+    .expect("large data diff");
+    assert_eq!((diff.base_rows, diff.target_rows), (12000, 12000));
+    assert_eq!((diff.counts.changed, diff.counts.unchanged), (3, 11997));
+    let keys: Vec<i64> = diff.rows_changed.iter().map(|c| c.key["n"].as_i64().unwrap()).collect();
+    assert_eq!(keys, [5], "max_rows caps the reported rows");
+    assert!(diff.truncated);
+
+    cleanup(&base_path);
+    cleanup(&target_path);
+}
+
+#[tokio::test]
+async fn parity_data_diff_rejects_missing_key_and_writes() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let none = QueryParams::Positional(Vec::new());
+    let caps = Capabilities::default();
+
+    let err = compute_data_diff(
+        &config,
+        &config,
+        "SELECT label FROM bulk_rows",
+        &none,
+        &["n".to_string()],
+        &caps,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.error_code(), "INVALID_INPUT");
+    assert!(err.message().contains("'n'"), "{}", err.message());
+
+    let err = compute_data_diff(
+        &config,
+        &config,
+        "DELETE FROM bulk_rows",
+        &none,
+        &["n".to_string()],
+        &caps,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.error_code(), "CAPABILITY_VIOLATION");
+
+    // A duplicate key is rejected even when both results are identical
+    let err = compute_data_diff(
+        &config,
+        &config,
+        "SELECT n % 10 AS n, label FROM bulk_rows",
+        &none,
+        &["n".to_string()],
+        &caps,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.error_code(), "INVALID_INPUT");
+    assert!(err.message().contains("not unique"), "{}", err.message());
+    cleanup(&path);
+}
+
+// ============================================================================
+// Summary — per-column statistics over every streamed row (--summarize)
+// ============================================================================
+
+#[tokio::test]
+async fn parity_summary_covers_every_row() {
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let sql = "SELECT n, label, CASE WHEN n % 3 = 0 THEN NULL ELSE n % 10 END AS d \
+               FROM bulk_rows ORDER BY n";
+
+    let summary = summarize_query::<SqliteEngine>(&config, sql, &[], None).await.expect("summary");
+    assert_eq!(summary.row_count, 1500, "max_rows does not apply to a summary");
+
+    let n = &summary.columns[0];
+    assert_eq!((n.count, n.null_count, n.distinct_count), (1500, 0, 1500));
+    assert!(n.distinct_exact);
+    assert_eq!((n.min.clone(), n.max.clone()), (Some(1.into()), Some(1500.into())));
+    assert_eq!(n.examples, [1, 2, 3]);
+
+    let label = &summary.columns[1];
+    assert_eq!(label.min.as_ref().unwrap(), "row-0001");
+    assert_eq!(label.max.as_ref().unwrap(), "row-1500");
+
+    let d = &summary.columns[2];
+    assert_eq!((d.count, d.null_count, d.distinct_count), (1000, 500, 10));
+    assert_eq!((d.min.clone(), d.max.clone()), (Some(0.into()), Some(9.into())));
+    assert_eq!(d.examples, [1, 2, 4]);
+
+    // An empty result still reports its columns
+    let empty = summarize_query::<SqliteEngine>(
+        &config,
+        "SELECT n FROM bulk_rows WHERE n > ?",
+        &[serde_json::json!(5000)],
+        None,
+    )
+    .await
+    .expect("empty summary");
+    assert_eq!(empty.row_count, 0);
+    assert_eq!(empty.columns[0].name, "n");
+    assert_eq!(empty.columns[0].min, None);
+
+    let err = summarize_query::<SqliteEngine>(&config, "DELETE FROM bulk_rows", &[], None)
+        .await
+        .unwrap_err();
+    assert_eq!(err.error_code(), "CAPABILITY_VIOLATION");
+    cleanup(&path);
+}
+
+// ============================================================================
+// Export — execute_stream into Parquet / Arrow IPC files (--output-file)
+// ============================================================================
+
+#[cfg(feature = "export")]
+#[tokio::test]
+async fn parity_export_parquet_round_trip_keeps_types() {
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use plenum::{BinaryFormat, ExportFormat, ExportType, ExportWriter};
+
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let caps = Capabilities { offset: Some(10), ..Capabilities::new(Some(1000), None) };
+    let out = fixture_path("export").with_extension("parquet");
+
+    let mut writer = ExportWriter::create(
+        &out,
+        ExportFormat::Parquet,
+        config.engine,
+        BinaryFormat::Base64,
+        None,
+    )
+    .expect("create");
+    let summary = SqliteEngine::execute_stream(
+        &config,
+        "SELECT n, label, n * 0.5 AS half FROM bulk_rows ORDER BY n",
+        &[],
+        &caps,
+        &mut writer,
+    )
+    .await
+    .expect("execute_stream");
+    let export = writer.finish().expect("finish");
+
+    assert_eq!(summary.rows_returned, 1000);
+    assert!(summary.rows_truncated);
+    assert_eq!(export.rows_written, 1000);
+    let types: Vec<ExportType> = export.columns.iter().map(|c| c.export_type).collect();
+    assert_eq!(types, [ExportType::Int64, ExportType::Utf8, ExportType::Float64]);
+    assert_eq!(export.columns[2].source_type, None, "expression columns are inferred");
+
+    let file = std::fs::File::open(&out).expect("open parquet");
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file).expect("parquet reader");
+    let schema = reader.schema().clone();
+    assert_eq!(schema.field(0).data_type(), &arrow::datatypes::DataType::Int64);
+    assert_eq!(schema.field(2).data_type(), &arrow::datatypes::DataType::Float64);
+    let batches: Vec<_> = reader.build().expect("build").collect::<Result<_, _>>().expect("read");
+    assert_eq!(batches.iter().map(arrow::array::RecordBatch::num_rows).sum::<usize>(), 1000);
+    let first = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<arrow::array::Int64Array>()
+        .expect("Int64 column");
+    assert_eq!(first.value(0), 11, "offset 10 skips n=1..10");
+
+    let _ = std::fs::remove_file(&out);
+    cleanup(&path);
+}
+
+/// `--max-cell-bytes` only shapes the JSON output: an export writes the real values.
+#[cfg(feature = "export")]
+#[tokio::test]
+async fn parity_export_ignores_cell_limit_markers() {
+    let path = build_parity_fixture();
+    let out = fixture_path("export_cells").with_extension("csv");
+    let sql = "SELECT n, substr(hex(zeroblob(50)), 1, 100) AS wide FROM bulk_rows \
+               ORDER BY n LIMIT 2";
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_plenum"))
+        .args(["query", "--engine", "sqlite", "--file"])
+        .arg(&path)
+        .args(["--sql", sql, "--max-cell-bytes", "8", "--format", "csv", "--output-file"])
+        .arg(&out)
+        .output()
+        .expect("spawn plenum");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+
+    let csv = std::fs::read_to_string(&out).expect("read csv");
+    let wide = "0".repeat(100);
+    assert_eq!(csv, format!("n,wide\n1,{wide}\n2,{wide}\n"));
+    assert!(!csv.contains("truncated"), "no marker objects in exported cells: {csv}");
+
+    let _ = std::fs::remove_file(&out);
+    cleanup(&path);
+}
+
+/// BLOB columns are decoded from their `--binary` rendering into Arrow binary.
+#[cfg(feature = "export")]
+#[tokio::test]
+async fn parity_export_blob_column_is_binary() {
+    use arrow::array::{Array, BinaryArray};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let path = build_parity_fixture();
+    let out = fixture_path("export_blob").with_extension("parquet");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_plenum"))
+        .args(["query", "--engine", "sqlite", "--file"])
+        .arg(&path)
+        .args(["--sql", "SELECT c_blob FROM type_matrix ORDER BY id", "--binary", "hex"])
+        .arg("--output-file")
+        .arg(&out)
+        .output()
+        .expect("spawn plenum");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    let envelope: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json");
+    assert_eq!(envelope["data"]["columns"][0]["type"], "binary");
+
+    let file = std::fs::File::open(&out).expect("open parquet");
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file).expect("parquet reader");
+    assert_eq!(reader.schema().field(0).data_type(), &arrow::datatypes::DataType::Binary);
+    let batches: Vec<_> = reader.build().expect("build").collect::<Result<_, _>>().expect("read");
+    let blobs = batches[0].column(0).as_any().downcast_ref::<BinaryArray>().expect("Binary");
+    assert_eq!(blobs.value(0), [0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(blobs.value(1), [0, 1, 2, 3]);
+
+    let _ = std::fs::remove_file(&out);
+    cleanup(&path);
+}
+
+/// A failed query leaves an existing output file as it was.
+#[cfg(feature = "export")]
+#[tokio::test]
+async fn parity_export_failure_keeps_existing_file() {
+    let path = build_parity_fixture();
+    let out = fixture_path("export_keep").with_extension("csv");
+    std::fs::write(&out, "n\n1\n").expect("seed previous export");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_plenum"))
+        .args(["query", "--engine", "sqlite", "--file"])
+        .arg(&path)
+        .args(["--sql", "SELECT * FROM nosuch", "--output-file"])
+        .arg(&out)
+        .output()
+        .expect("spawn plenum");
+    assert!(!output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    assert_eq!(std::fs::read_to_string(&out).expect("read csv"), "n\n1\n");
+
+    let _ = std::fs::remove_file(&out);
+    cleanup(&path);
+}
+
+#[cfg(feature = "export")]
+#[tokio::test]
+async fn parity_export_type_mismatch_removes_partial_file() {
+    use plenum::{BinaryFormat, ExportFormat, ExportWriter};
+
+    let path = build_parity_fixture();
+    let config = ConnectionConfig::sqlite(path.clone());
+    let out = fixture_path("export_mismatch").with_extension("arrow");
+
+    // SQLite lets an INTEGER column hold text; the export refuses rather than guessing.
+    let mut writer =
+        ExportWriter::create(&out, ExportFormat::Arrow, config.engine, BinaryFormat::Base64, None)
+            .expect("create");
+    SqliteEngine::execute_stream(
+        &config,
+        "SELECT n FROM bulk_rows UNION ALL SELECT 'oops'",
+        &[],
+        &Capabilities::default(),
+        &mut writer,
+    )
+    .await
+    .expect("execute_stream");
+    let err = writer.finish().expect_err("text in an integer column must fail");
+    assert_eq!(err.error_code(), "QUERY_FAILED");
+    assert!(!out.exists(), "partial export file must be removed");
+    cleanup(&path);
+}
+
 // ============================================================================
 // Envelope — JSON shape and determinism
 // ============================================================================